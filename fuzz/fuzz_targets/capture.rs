@@ -0,0 +1,26 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use macaddress::capture::Capture;
+use macaddress::frame;
+
+fuzz_target!(|data: &[u8]| {
+    let capture = match Capture::new(data) {
+        Ok(capture) => capture,
+        Err(_) => return,
+    };
+
+    // Stop at the first error, as a caller would, since a reader
+    // that has lost its place in the file cannot recover.
+    for result in capture.take(1024) {
+        match result {
+            Ok(frame) => {
+                if let Ok((destination, source)) = frame::addresses(&frame.data) {
+                    assert_eq!(destination.to_octets(), frame.data[..6]);
+                    assert_eq!(source.to_octets(), frame.data[6..12]);
+                }
+            }
+            Err(_) => break,
+        }
+    }
+});
@@ -0,0 +1,30 @@
+#![no_main]
+
+use libfuzzer_sys::arbitrary::{self, Arbitrary};
+use libfuzzer_sys::fuzz_target;
+use macaddress::format::{self, Notation, MAX_LEN};
+
+#[derive(Arbitrary, Debug)]
+struct Input {
+    octets: [u8; 6],
+    notation: u8,
+    buffer_len: u8,
+}
+
+fuzz_target!(|input: Input| {
+    let notation = Notation::ALL[usize::from(input.notation) % Notation::ALL.len()];
+    let formatted = format::format(&input.octets, notation);
+
+    assert!(formatted.len() <= notation.max_len());
+    assert_eq!(format::parse(&formatted, notation).unwrap(), input.octets);
+
+    let mut buffer = vec![0; usize::from(input.buffer_len) % (MAX_LEN + 1)];
+
+    match format::format_into(&input.octets, notation, &mut buffer) {
+        Ok(written) => assert_eq!(written, formatted),
+        Err(error) => {
+            assert_eq!(error.required, formatted.len());
+            assert!(buffer.len() < error.required);
+        }
+    }
+});
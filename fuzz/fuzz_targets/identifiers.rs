@@ -0,0 +1,28 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use macaddress::evpn::EthernetSegmentId;
+use macaddress::lacp::LacpSystemId;
+use macaddress::stp::BridgeId;
+
+fuzz_target!(|data: &[u8]| {
+    let digits = match std::str::from_utf8(data) {
+        Ok(digits) => digits,
+        Err(_) => return,
+    };
+
+    if let Ok(id) = digits.parse::<LacpSystemId>() {
+        assert_eq!(id.to_string().parse::<LacpSystemId>().unwrap(), id);
+        assert_eq!(LacpSystemId::from_bytes(&id.to_bytes()).unwrap(), id);
+    }
+
+    if let Ok(id) = digits.parse::<BridgeId>() {
+        assert_eq!(id.to_string().parse::<BridgeId>().unwrap(), id);
+        assert_eq!(BridgeId::from_bytes(&id.to_bytes()).unwrap(), id);
+    }
+
+    if let Ok(esi) = digits.parse::<EthernetSegmentId>() {
+        assert_eq!(esi.to_string().parse::<EthernetSegmentId>().unwrap(), esi);
+        assert_eq!(EthernetSegmentId::from_bytes(&esi.to_bytes()).unwrap(), esi);
+    }
+});
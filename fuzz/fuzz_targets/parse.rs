@@ -0,0 +1,51 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use macaddress::format::Notation;
+use macaddress::utils;
+use macaddress::MediaAccessControlAddress;
+
+fuzz_target!(|data: &[u8]| {
+    let digits = match std::str::from_utf8(data) {
+        Ok(digits) => digits,
+        Err(_) => return,
+    };
+
+    let validated = utils::validate(digits);
+    let mac = match MediaAccessControlAddress::new(digits) {
+        Ok(mac) => mac,
+        Err(error) => {
+            // `validate`, `new`, and `diagnose` must reject the same
+            // strings for the same reason.
+            assert_eq!(validated.unwrap_err(), error);
+
+            let diagnosis = utils::diagnose(digits).unwrap_err();
+            assert_eq!(diagnosis.kind(), error);
+
+            if let Some(position) = diagnosis.position() {
+                assert!(digits.is_char_boundary(position));
+            }
+            let _ = diagnosis.to_string();
+            return;
+        }
+    };
+
+    let notation = validated.unwrap();
+    assert_eq!(utils::diagnose(digits).unwrap(), notation);
+    let plain = mac.to_plain_notation();
+    assert_eq!(plain.len(), 12);
+
+    // Whatever notation the input was in, it must parse as that
+    // notation, too.
+    let octets = MediaAccessControlAddress::from_notation(digits, notation)
+        .unwrap()
+        .to_octets();
+    assert_eq!(octets, mac.to_octets());
+
+    // Every notation must round-trip.
+    for notation in Notation::ALL.iter() {
+        let formatted = mac.to_notation(*notation);
+        let reparsed = MediaAccessControlAddress::new(&formatted).unwrap();
+        assert_eq!(reparsed.to_plain_notation(), plain);
+    }
+});
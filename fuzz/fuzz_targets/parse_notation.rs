@@ -0,0 +1,32 @@
+#![no_main]
+
+use libfuzzer_sys::arbitrary::{self, Arbitrary};
+use libfuzzer_sys::fuzz_target;
+use macaddress::format::{self, Notation};
+use macaddress::MediaAccessControlAddress;
+
+#[derive(Arbitrary, Debug)]
+struct Input {
+    digits: String,
+    notation: u8,
+}
+
+fuzz_target!(|input: Input| {
+    let notation = Notation::ALL[usize::from(input.notation) % Notation::ALL.len()];
+
+    match format::parse(&input.digits, notation) {
+        Ok(octets) => {
+            // Anything one notation accepts, `new` accepts, too.
+            let mac = MediaAccessControlAddress::new(&input.digits).unwrap();
+            assert_eq!(mac.to_octets(), octets);
+        }
+        Err(error) => {
+            // A string that is a MAC address in some other notation
+            // must say which one.
+            if MediaAccessControlAddress::new(&input.digits).is_ok() {
+                assert!(error.notation().is_some());
+                assert_ne!(error.notation(), Some(notation));
+            }
+        }
+    }
+});
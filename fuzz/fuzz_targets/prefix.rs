@@ -0,0 +1,43 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use macaddress::prefix::MacPrefix;
+use macaddress::range::MacRange;
+
+fuzz_target!(|data: &[u8]| {
+    let text = match std::str::from_utf8(data) {
+        Ok(text) => text,
+        Err(_) => return,
+    };
+
+    if let Ok(prefix) = text.parse::<MacPrefix>() {
+        assert_eq!(prefix.to_string().parse::<MacPrefix>().unwrap(), prefix);
+        assert_eq!(prefix.size(), 1 << (48 - u32::from(prefix.length())));
+        assert!(prefix.first() <= prefix.last());
+        assert!(prefix.contains(&prefix.first()));
+        assert!(prefix.contains(&prefix.last()));
+
+        // A prefix is a range that is its own only prefix.
+        let range = MacRange::from(prefix);
+        assert_eq!(range.to_prefixes(), vec![prefix]);
+    }
+
+    if let Ok(range) = text.parse::<MacRange>() {
+        assert_eq!(range.to_string().parse::<MacRange>().unwrap(), range);
+
+        // The prefixes that cover the range do so exactly, in order
+        // and without gaps.
+        let prefixes = range.to_prefixes();
+        assert_eq!(prefixes.first().unwrap().first(), range.first());
+        assert_eq!(prefixes.last().unwrap().last(), range.last());
+        assert_eq!(
+            prefixes.iter().map(MacPrefix::size).sum::<u64>(),
+            range.size()
+        );
+
+        for pair in prefixes.windows(2) {
+            let next = pair[0].last().to_decimal_representation() + 1;
+            assert_eq!(pair[1].first().to_decimal_representation(), next);
+        }
+    }
+});
@@ -0,0 +1,254 @@
+//! `macaddress db`, which manages the local vendor database.
+
+use super::output::{self, Field, Output};
+use macaddress::vendor::VendorDatabase;
+use macaddress::Error;
+use std::fs;
+use std::path::Path;
+use std::time::SystemTime;
+
+/// The registries that `db update` downloads when no source is
+/// given, from its mirrors or the IEEE.
+const REGISTRIES: [&str; 3] = ["MA-L", "MA-M", "MA-S"];
+
+/// Where `db update` writes the database when no path is given.
+const DEFAULT_OUT: &str = "vendors.csv";
+
+/// Runs `macaddress db <subcommand>`.
+pub fn run(args: &[String], output: Output) -> Result<(), String> {
+    match args.first().map(String::as_str) {
+        Some("update") => update(&args[1..], output),
+        _ => Err(String::from("Pass in a db subcommand (update).")),
+    }
+}
+
+/// How `db update` downloads, as set by its options.
+#[derive(Default)]
+struct Network<'a> {
+    proxy: Option<&'a str>,
+    mirrors: Vec<&'a str>,
+    timeout: Option<u64>,
+    retries: Option<u32>,
+}
+
+/// Reads a registry CSV from a registry (MA-L, MA-M, or MA-S) or
+/// URL (with the `net` feature), or from a file.
+///
+/// Plain HTTP is refused unless the source has a digest to check,
+/// since nothing else would stop tampering on the way.  HTTPS
+/// downloads always verify the server's certificate.
+fn fetch(source: &str, digest: Option<&str>, network: &Network) -> Result<String, String> {
+    if source.starts_with("http://") && digest.is_none() {
+        Err(format!(
+            "Pass in an https:// URL, or a --sha256 digest for {}.",
+            source
+        ))
+    } else if is_remote(source) {
+        download(source, network)
+    } else {
+        fs::read_to_string(source).map_err(|error| format!("{}: {}", source, error))
+    }
+}
+
+/// Returns whether `source` is downloaded rather than read from a
+/// file.
+fn is_remote(source: &str) -> bool {
+    REGISTRIES.contains(&source) || source.starts_with("https://") || source.starts_with("http://")
+}
+
+#[cfg(feature = "net")]
+fn download(source: &str, network: &Network) -> Result<String, String> {
+    use macaddress::vendor::RegistryClient;
+    use std::time::Duration;
+
+    let mut client = RegistryClient::new();
+
+    if let Some(proxy) = network.proxy {
+        client = client.with_proxy(proxy)?;
+    }
+
+    for mirror in network.mirrors.iter() {
+        client = client.with_mirror(mirror)?;
+    }
+
+    if let Some(timeout) = network.timeout {
+        client = client.with_timeout(Duration::from_secs(timeout));
+    }
+
+    if let Some(retries) = network.retries {
+        client = client.with_retries(retries, Duration::from_secs(1));
+    }
+
+    let body = if REGISTRIES.contains(&source) {
+        client.fetch_registry(source)
+    } else {
+        client.fetch(source)
+    };
+
+    body.map_err(String::from)
+}
+
+#[cfg(not(feature = "net"))]
+fn download(source: &str, _: &Network) -> Result<String, String> {
+    Err(format!(
+        "Build with the net feature to download {}, or pass in a file.",
+        source
+    ))
+}
+
+/// Runs `macaddress db update`, which builds the database from
+/// every source, writes it, and reads it back to verify it.
+fn update(args: &[String], output: Output) -> Result<(), String> {
+    let mut sources = Vec::new();
+    let mut out = DEFAULT_OUT;
+    let mut expect = None;
+    let mut network = Network::default();
+    let mut args = args.iter();
+
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--source" => sources.push((super::value(arg, &mut args)?, None)),
+            "--sha256" => {
+                let digest = super::value(arg, &mut args)?;
+
+                if digest.len() != 64 || !digest.chars().all(|c| c.is_ascii_hexdigit()) {
+                    return Err(String::from(
+                        "Pass in a SHA-256 digest of 64 hexadecimal digits.",
+                    ));
+                }
+
+                match sources.last_mut() {
+                    Some((_, expected @ None)) => *expected = Some(digest),
+                    _ => {
+                        return Err(String::from(
+                            "Pass in --sha256 once, right after the --source that it checks.",
+                        ))
+                    }
+                }
+            }
+            "--proxy" => network.proxy = Some(super::value(arg, &mut args)?),
+            "--mirror" => network.mirrors.push(super::value(arg, &mut args)?),
+            "--timeout" => {
+                let timeout = super::value(arg, &mut args)?;
+                network.timeout = Some(
+                    timeout
+                        .parse()
+                        .map_err(|_| String::from("Pass in a timeout in whole seconds."))?,
+                );
+            }
+            "--retries" => {
+                let retries = super::value(arg, &mut args)?;
+                network.retries = Some(retries.parse().map_err(|_| {
+                    String::from("Pass in a number of retries between 0 and 4294967295.")
+                })?);
+            }
+            "--out" => out = super::value(arg, &mut args)?,
+            "--expect" => {
+                let checksum = super::value(arg, &mut args)?;
+                let checksum = u32::from_str_radix(checksum, 16)
+                    .map_err(|_| String::from("Pass in a checksum of 8 hexadecimal digits."))?;
+                expect = Some(checksum);
+            }
+            _ => return Err(format!("Pass in a known option (not {}).", arg)),
+        }
+    }
+
+    if sources.is_empty() {
+        sources.extend(REGISTRIES.iter().map(|registry| (*registry, None)));
+    }
+
+    let mut database = VendorDatabase::new();
+
+    for (source, digest) in sources.iter() {
+        eprintln!("Fetching {}...", source);
+        let csv = fetch(source, *digest, &network)?;
+
+        // HTTPS only proves where the registry came from, not that
+        // it is what its publisher meant to publish.
+        if digest.is_none() && is_remote(source) {
+            eprintln!(
+                "Warning: {} was not verified, since no --sha256 digest was passed in for it.",
+                source
+            );
+        }
+
+        let mut part = match digest {
+            Some(digest) => VendorDatabase::from_csv_with_sha256(&csv, digest),
+            None => VendorDatabase::from_csv(&csv),
+        }
+        .map_err(|error| format!("{}: {}", source, error))?;
+
+        // The registry CSVs carry no version, so the checksum of
+        // what was fetched stands in for one.
+        let version = format!("{:08x}", part.checksum());
+
+        for registry in ["MA-L", "MA-M", "MA-S"].iter() {
+            if part.count(registry) > 0 {
+                part.set_source(registry, &version)?;
+            }
+        }
+
+        eprintln!("Parsed {} assignments from {}.", part.len(), source);
+        database.merge(part);
+    }
+
+    database.set_built(SystemTime::now());
+    let checksum = database.checksum();
+
+    if let Some(expected) = expect {
+        if expected != checksum {
+            return Err(format!(
+                "The database's checksum is {:08x}, not {:08x}, so it was not written.",
+                checksum, expected
+            ));
+        }
+    }
+
+    // Write beside the destination and rename, so that a failed
+    // update never leaves a truncated database behind.
+    let out = Path::new(out);
+    let temporary = out.with_extension("tmp");
+    fs::write(&temporary, database.to_csv())
+        .map_err(|error| format!("{}: {}", temporary.display(), error))?;
+
+    let written = fs::read_to_string(&temporary)
+        .map_err(Error::from)
+        .and_then(|csv| VendorDatabase::from_csv(&csv));
+
+    if written.map(|database| database.checksum()).ok() != Some(checksum) {
+        let _ = fs::remove_file(&temporary);
+        return Err(format!(
+            "{} did not read back with checksum {:08x}.",
+            temporary.display(),
+            checksum
+        ));
+    }
+
+    fs::rename(&temporary, out).map_err(|error| format!("{}: {}", out.display(), error))?;
+
+    eprintln!(
+        "Wrote {} assignments (MA-L {}, MA-M {}, MA-S {}) to {}.",
+        database.len(),
+        database.count("MA-L"),
+        database.count("MA-M"),
+        database.count("MA-S"),
+        out.display()
+    );
+
+    if output == Output::Text {
+        println!("{:08x}", checksum);
+        return Ok(());
+    }
+
+    output::print_record(
+        output,
+        &[
+            ("out", Field::Text(out.display().to_string())),
+            ("assignments", Field::Number(database.len() as u64)),
+            ("ma_l", Field::Number(database.count("MA-L") as u64)),
+            ("ma_m", Field::Number(database.count("MA-M") as u64)),
+            ("ma_s", Field::Number(database.count("MA-S") as u64)),
+            ("checksum", Field::Text(format!("{:08x}", checksum))),
+        ],
+    )
+}
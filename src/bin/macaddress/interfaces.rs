@@ -0,0 +1,79 @@
+//! `macaddress interfaces`, which lists the host's network
+//! interfaces with their addresses, classification, and vendors.
+
+use super::output::{self, Field, Output};
+use macaddress::fingerprint::{self, Origin};
+use macaddress::format::{self, Notation};
+use macaddress::system::{self, AddressSource};
+use macaddress::vendor::VendorDatabase;
+
+/// Runs `macaddress interfaces`.
+pub fn run(args: &[String], output: Output) -> Result<(), String> {
+    let mut notation = Notation::Colon;
+    let mut database = VendorDatabase::new();
+    let mut args = args.iter();
+
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--notation" => notation = super::value(arg, &mut args)?.parse()?,
+            "--db" => {
+                database = VendorDatabase::load(super::value(arg, &mut args)?)?;
+            }
+            _ => return Err(format!("Pass in a known option (not {}).", arg)),
+        }
+    }
+
+    let mut records = Vec::new();
+
+    for interface in system::interfaces()?.iter() {
+        let report = fingerprint::fingerprint(&interface.address, &database)?;
+
+        // The operating system knows better than the heuristic
+        // whether it made the address up.
+        let origin = match (interface.source, report.origin) {
+            (Some(AddressSource::Random), Some(_)) => Some(Origin::Randomized),
+            (_, origin) => origin,
+        };
+
+        records.push(vec![
+            ("name", Field::Text(interface.name.clone())),
+            (
+                "address",
+                Field::Text(format::format(&interface.address.to_octets(), notation)),
+            ),
+            (
+                "administration",
+                Field::Text(String::from(if interface.address.is_uaa() {
+                    "uaa"
+                } else {
+                    "laa"
+                })),
+            ),
+            (
+                "origin",
+                Field::Text(String::from(origin.map(Origin::as_str).unwrap_or_default())),
+            ),
+            (
+                "source",
+                Field::Text(String::from(
+                    interface
+                        .source
+                        .map(AddressSource::as_str)
+                        .unwrap_or_default(),
+                )),
+            ),
+            (
+                "vendor",
+                Field::Text(
+                    report
+                        .platform
+                        .map(String::from)
+                        .or(report.vendor)
+                        .unwrap_or_default(),
+                ),
+            ),
+        ]);
+    }
+
+    output::print_records(output, "interfaces", &records)
+}
@@ -0,0 +1,111 @@
+//! The `macaddress` command-line tool, which puts the library's
+//! parsing, classification, and vendor lookups at your fingertips.
+
+use output::Output;
+use std::env;
+use std::process;
+
+mod db;
+mod interfaces;
+mod neighbors;
+mod output;
+mod range;
+mod sort;
+
+#[cfg(feature = "serve")]
+mod serve;
+
+/// The usage message that `macaddress help` prints.
+const USAGE: &str = "Usage: macaddress <command> [options] [--json | --csv | --yaml | --toml]
+
+Commands:
+    db update [--source <url|file> [--sha256 <digest>]]... [--out <path>] [--expect <checksum>]
+              [--proxy <url>] [--mirror <url>]... [--timeout <seconds>] [--retries <count>]
+        Builds the local vendor database from the IEEE registries.
+    sort [--unique] [--notation <notation>] [--buffer <addresses>] [--db <path>] [file]...
+        Normalizes and numerically sorts addresses, one per line.
+    dedup [--notation <notation>] [--buffer <addresses>] [--db <path>] [file]...
+        Normalizes, numerically sorts, and deduplicates addresses.
+    interfaces [--notation <notation>] [--db <path>]
+        Lists the host's network interfaces with their classification and vendor.
+    neighbors [--interface <name>] [--notation <notation>] [--db <path>]
+        Prints the host's ARP and NDP table with vendor names.
+    range expand <prefix|range> [--limit <addresses>] [--notation <notation>] [--db <path>]
+        Lists the addresses in a prefix or range (65536 by default).
+    range summarize [--ranges] [file]...
+        Collapses addresses, one per line, into the fewest prefixes or ranges.
+    serve [--listen <address:port>] [--db <path>]
+        Answers /normalize, /classify, and /vendor lookups over HTTP.
+    help
+        Prints this message.
+
+Every command writes JSON with --json, CSV with --csv, YAML with --yaml, or TOML
+with --toml instead of text.";
+
+/// Returns the value that follows an option (for example, the
+/// path after `--out`).
+pub fn value<'a>(
+    option: &str,
+    values: &mut impl Iterator<Item = &'a String>,
+) -> Result<&'a str, String> {
+    values
+        .next()
+        .map(String::as_str)
+        .ok_or_else(|| format!("Pass in a value after {}.", option))
+}
+
+/// Reports how many input lines were skipped because they were not
+/// addresses, if any were.
+pub fn report_skipped(invalid: usize) {
+    match invalid {
+        0 => {}
+        1 => eprintln!("Skipped 1 invalid line."),
+        _ => eprintln!("Skipped {} invalid lines.", invalid),
+    }
+}
+
+/// Runs `macaddress serve`, if the tool was built with the `serve`
+/// feature.
+#[cfg(feature = "serve")]
+fn serve(args: &[String]) -> Result<(), String> {
+    serve::run(args)
+}
+
+#[cfg(not(feature = "serve"))]
+fn serve(_: &[String]) -> Result<(), String> {
+    Err(String::from(
+        "Build with the serve feature to run macaddress serve.",
+    ))
+}
+
+/// Runs the subcommand named by the first argument.
+fn run(args: &[String]) -> Result<(), String> {
+    let (output, args) = Output::take(args)?;
+
+    match args.first().map(String::as_str) {
+        Some("db") => db::run(&args[1..], output),
+        Some("sort") => sort::run(&args[1..], false, output),
+        Some("dedup") => sort::run(&args[1..], true, output),
+        Some("interfaces") => interfaces::run(&args[1..], output),
+        Some("neighbors") => neighbors::run(&args[1..], output),
+        Some("range") => range::run(&args[1..], output),
+        Some("serve") => serve(&args[1..]),
+        None | Some("help") | Some("--help") | Some("-h") => {
+            println!("{}", USAGE);
+            Ok(())
+        }
+        Some(command) => Err(format!(
+            "Pass in a known command (not {}); run `macaddress help` for a list.",
+            command
+        )),
+    }
+}
+
+fn main() {
+    let args: Vec<String> = env::args().skip(1).collect();
+
+    if let Err(error) = run(&args) {
+        eprintln!("macaddress: {}", error);
+        process::exit(1);
+    }
+}
@@ -0,0 +1,52 @@
+//! `macaddress neighbors`, which prints the host's ARP and NDP
+//! table with normalized addresses and vendor names.
+
+use super::output::{self, Field, Output};
+use macaddress::format::{self, Notation};
+use macaddress::system;
+use macaddress::vendor::{VendorDatabase, VendorResolver};
+
+/// Runs `macaddress neighbors`.
+pub fn run(args: &[String], output: Output) -> Result<(), String> {
+    let mut notation = Notation::Colon;
+    let mut database = VendorDatabase::new();
+    let mut interface = None;
+    let mut args = args.iter();
+
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--notation" => notation = super::value(arg, &mut args)?.parse()?,
+            "--db" => {
+                database = VendorDatabase::load(super::value(arg, &mut args)?)?;
+            }
+            "--interface" | "-i" => interface = Some(super::value(arg, &mut args)?),
+            _ => return Err(format!("Pass in a known option (not {}).", arg)),
+        }
+    }
+
+    let mut neighbors = system::neighbors()?;
+    neighbors.retain(|neighbor| interface.is_none_or(|name| neighbor.interface == name));
+    neighbors.sort_by(|a, b| (&a.interface, a.ip).cmp(&(&b.interface, b.ip)));
+
+    let mut records = Vec::new();
+
+    for neighbor in neighbors.iter() {
+        let vendor = database.resolve(&neighbor.address)?.unwrap_or_default();
+
+        records.push(vec![
+            ("ip", Field::Text(neighbor.ip.to_string())),
+            (
+                "address",
+                Field::Text(format::format(&neighbor.address.to_octets(), notation)),
+            ),
+            ("interface", Field::Text(neighbor.interface.clone())),
+            (
+                "state",
+                Field::Text(neighbor.state.clone().unwrap_or_default()),
+            ),
+            ("vendor", Field::Text(vendor)),
+        ]);
+    }
+
+    output::print_records(output, "neighbors", &records)
+}
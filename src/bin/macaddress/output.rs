@@ -0,0 +1,238 @@
+//! The machine-readable output modes that every subcommand offers,
+//! so that scripts need not parse text.
+
+use macaddress::export::{escape_json, Encoding};
+use std::io::{self, Write};
+
+/// How a subcommand writes its results.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Output {
+    /// Text for people.
+    Text,
+    /// JSON (`--json`).
+    Json,
+    /// CSV with a header row (`--csv`).
+    Csv,
+    /// YAML (`--yaml`).
+    Yaml,
+    /// TOML (`--toml`).
+    Toml,
+}
+
+impl Output {
+    /// Removes `--json`, `--csv`, `--yaml`, or `--toml` from anywhere
+    /// in the arguments
+    /// and returns the output mode that it chose, along with the
+    /// other arguments.
+    pub fn take(args: &[String]) -> Result<(Self, Vec<String>), String> {
+        let mut output = Output::Text;
+        let mut rest = Vec::new();
+
+        for arg in args.iter() {
+            let chosen = match arg.as_str() {
+                "--json" => Output::Json,
+                "--csv" => Output::Csv,
+                "--yaml" => Output::Yaml,
+                "--toml" => Output::Toml,
+                _ => {
+                    rest.push(arg.clone());
+                    continue;
+                }
+            };
+
+            if output != Output::Text && output != chosen {
+                return Err(String::from(
+                    "Pass in only one of --json, --csv, --yaml, or --toml.",
+                ));
+            }
+
+            output = chosen;
+        }
+
+        Ok((output, rest))
+    }
+
+    /// Returns the encoding with which `Exporter` writes addresses
+    /// in this mode, or `None` for text.
+    pub fn encoding(self) -> Option<Encoding> {
+        match self {
+            Output::Text => None,
+            Output::Json => Some(Encoding::Json),
+            Output::Csv => Some(Encoding::Csv),
+            Output::Yaml => Some(Encoding::Yaml),
+            Output::Toml => Some(Encoding::Toml),
+        }
+    }
+}
+
+/// One value in a record.
+pub enum Field {
+    Text(String),
+    Number(u64),
+}
+
+/// Quotes a CSV cell if it needs it.
+fn escape_csv(text: &str) -> String {
+    if text.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", text.replace('"', "\"\""))
+    } else {
+        text.to_string()
+    }
+}
+
+/// Renders a field as text.
+fn text(field: &Field) -> String {
+    match field {
+        Field::Text(text) => text.clone(),
+        Field::Number(number) => number.to_string(),
+    }
+}
+
+/// Renders a record as a JSON object.
+pub fn json_object(fields: &[(&str, Field)]) -> String {
+    let members: Vec<String> = fields
+        .iter()
+        .map(|(name, field)| match field {
+            Field::Text(text) => format!("\"{}\":{}", name, escape_json(text)),
+            Field::Number(number) => format!("\"{}\":{}", name, number),
+        })
+        .collect();
+    format!("{{{}}}", members.join(","))
+}
+
+/// Renders a field as a JSON value, which is also a YAML and a
+/// TOML value.
+fn value(field: &Field) -> String {
+    match field {
+        Field::Text(text) => escape_json(text),
+        Field::Number(number) => number.to_string(),
+    }
+}
+
+/// Renders a record as a YAML mapping, with each line after the
+/// first indented by `indent`.
+fn yaml_mapping(fields: &[(&str, Field)], indent: &str) -> String {
+    if fields.is_empty() {
+        return String::from("{}\n");
+    }
+
+    let lines: Vec<String> = fields
+        .iter()
+        .map(|(name, field)| format!("{}: {}\n", name, value(field)))
+        .collect();
+    lines.join(indent)
+}
+
+/// Renders a record as TOML key/value pairs.
+fn toml_pairs(fields: &[(&str, Field)]) -> String {
+    fields
+        .iter()
+        .map(|(name, field)| format!("{} = {}\n", name, value(field)))
+        .collect()
+}
+
+/// Renders a record as a CSV row.
+fn csv_row(fields: &[(&str, Field)]) -> String {
+    let cells: Vec<String> = fields
+        .iter()
+        .map(|(_, field)| escape_csv(&text(field)))
+        .collect();
+    cells.join(",")
+}
+
+/// Renders the names of a record's fields as a CSV header row.
+fn csv_header(fields: &[(&str, Field)]) -> String {
+    let names: Vec<&str> = fields.iter().map(|(name, _)| *name).collect();
+    names.join(",")
+}
+
+/// Writes one record to standard output as a JSON object, as CSV
+/// with a header row, as a YAML mapping, or as a TOML table, or as
+/// `name: value` lines for text.
+pub fn print_record(output: Output, fields: &[(&str, Field)]) -> Result<(), String> {
+    let record = match output {
+        Output::Text => fields
+            .iter()
+            .map(|(name, field)| format!("{}: {}\n", name, text(field)))
+            .collect(),
+        Output::Json => format!("{}\n", json_object(fields)),
+        Output::Csv => format!("{}\n{}\n", csv_header(fields), csv_row(fields)),
+        Output::Yaml => yaml_mapping(fields, ""),
+        Output::Toml => toml_pairs(fields),
+    };
+
+    io::stdout()
+        .write_all(record.as_bytes())
+        .map_err(|error| error.to_string())
+}
+
+/// Writes records with the same fields to standard output as a
+/// JSON array, as CSV with one header row, as a YAML sequence, or as
+/// a TOML array of tables called `name`, or as aligned columns for
+/// text.
+pub fn print_records(
+    output: Output,
+    name: &str,
+    records: &[Vec<(&str, Field)>],
+) -> Result<(), String> {
+    let rendered = match output {
+        Output::Text => {
+            let rows: Vec<Vec<String>> = records
+                .iter()
+                .map(|fields| fields.iter().map(|(_, field)| text(field)).collect())
+                .collect();
+            let mut widths = Vec::new();
+
+            for row in rows.iter() {
+                widths.resize(row.len(), 0);
+
+                for (width, cell) in widths.iter_mut().zip(row.iter()) {
+                    *width = cell.chars().count().max(*width);
+                }
+            }
+
+            let mut rendered = String::new();
+
+            for row in rows.iter() {
+                let cells: Vec<String> = row
+                    .iter()
+                    .zip(widths.iter())
+                    .map(|(cell, width)| format!("{:width$}", cell, width = width))
+                    .collect();
+                rendered.push_str(cells.join("  ").trim_end());
+                rendered.push('\n');
+            }
+
+            rendered
+        }
+        Output::Json => {
+            let objects: Vec<String> = records.iter().map(|fields| json_object(fields)).collect();
+            format!("[{}]\n", objects.join(","))
+        }
+        Output::Csv => {
+            let mut rows: Vec<String> = records
+                .first()
+                .map(|fields| csv_header(fields))
+                .into_iter()
+                .collect();
+            rows.extend(records.iter().map(|fields| csv_row(fields)));
+            rows.iter().map(|row| format!("{}\n", row)).collect()
+        }
+        Output::Yaml if records.is_empty() => String::from("[]\n"),
+        Output::Yaml => records
+            .iter()
+            .map(|fields| format!("- {}", yaml_mapping(fields, "  ")))
+            .collect(),
+        Output::Toml => {
+            let tables: Vec<String> = records
+                .iter()
+                .map(|fields| format!("[[{}]]\n{}", name, toml_pairs(fields)))
+                .collect();
+            tables.join("\n")
+        }
+    };
+
+    io::stdout()
+        .write_all(rendered.as_bytes())
+        .map_err(|error| error.to_string())
+}
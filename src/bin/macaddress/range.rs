@@ -0,0 +1,203 @@
+//! `macaddress range`, which expands prefixes and ranges into
+//! addresses and summarizes lists of addresses into prefixes, for
+//! building and checking filter lists.
+
+use super::output::{self, Field, Output};
+use macaddress::export::Exporter;
+use macaddress::format::{self, Notation};
+use macaddress::prefix::MacPrefix;
+use macaddress::range::{self, MacRange};
+use macaddress::vendor::VendorDatabase;
+use macaddress::MediaAccessControlAddress;
+use std::fs::File;
+use std::io::{self, BufRead, BufReader, BufWriter, Write};
+
+/// The number of addresses that `range expand` writes when no limit
+/// is given, so that expanding an OUI by mistake does not write
+/// sixteen million lines.
+const DEFAULT_LIMIT: u64 = 65536;
+
+/// Runs `macaddress range <subcommand>`.
+pub fn run(args: &[String], output: Output) -> Result<(), String> {
+    match args.first().map(String::as_str) {
+        Some("expand") => expand(&args[1..], output),
+        Some("summarize") => summarize(&args[1..], output),
+        _ => Err(String::from(
+            "Pass in a range subcommand (expand or summarize).",
+        )),
+    }
+}
+
+/// Parses a prefix (`a0:b1:c2:00:00:00/24`) or a range
+/// (`a0:b1:c2:00:00:00-a0:b1:c2:00:00:ff`).
+fn parse(text: &str) -> Result<MacRange, String> {
+    let range = if text.contains('/') {
+        text.parse::<MacPrefix>().map(MacRange::from)
+    } else {
+        text.parse()
+    };
+
+    Ok(range?)
+}
+
+/// Runs `macaddress range expand`, which lists the addresses in a
+/// prefix or range, up to a limit.
+fn expand(args: &[String], output: Output) -> Result<(), String> {
+    let mut target = None;
+    let mut limit = DEFAULT_LIMIT;
+    let mut notation = Notation::Colon;
+    let mut database = VendorDatabase::new();
+    let mut args = args.iter();
+
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--limit" => {
+                limit = super::value(arg, &mut args)?
+                    .parse()
+                    .map_err(|_| String::from("Pass in a limit of 0 or more addresses."))?;
+            }
+            "--notation" => notation = super::value(arg, &mut args)?.parse()?,
+            "--db" => {
+                database = VendorDatabase::load(super::value(arg, &mut args)?)?;
+            }
+            text if target.is_none() && !text.starts_with('-') => target = Some(parse(text)?),
+            _ => return Err(format!("Pass in a known option (not {}).", arg)),
+        }
+    }
+
+    let range = target.ok_or_else(|| String::from("Pass in a prefix or range to expand."))?;
+    let addresses = range.iter().take(limit.min(range.size()) as usize);
+    let stdout = io::stdout();
+    let mut writer = BufWriter::new(stdout.lock());
+
+    match output.encoding() {
+        None => {
+            for mac in addresses {
+                writeln!(writer, "{}", format::format(&mac.to_octets(), notation))
+                    .map_err(|error| error.to_string())?;
+            }
+        }
+        Some(encoding) => {
+            Exporter::new(&database, encoding)
+                .with_notation(notation)
+                .write(addresses, &mut writer)?;
+        }
+    }
+
+    writer.flush().map_err(|error| error.to_string())?;
+
+    if range.size() > limit {
+        eprintln!(
+            "Stopped after {} of {} addresses; pass in a larger --limit for more.",
+            limit,
+            range.size()
+        );
+    }
+
+    Ok(())
+}
+
+/// Runs `macaddress range summarize`, which collapses a list of
+/// addresses into the fewest prefixes (or, with `--ranges`, ranges)
+/// that cover them exactly.
+fn summarize(args: &[String], output: Output) -> Result<(), String> {
+    let mut ranges = false;
+    let mut paths = Vec::new();
+
+    for arg in args.iter() {
+        match arg.as_str() {
+            "--ranges" => ranges = true,
+            path if !path.starts_with('-') || path == "-" => paths.push(path),
+            _ => return Err(format!("Pass in a known option (not {}).", arg)),
+        }
+    }
+
+    if paths.is_empty() {
+        paths.push("-");
+    }
+
+    let mut addresses = Vec::new();
+    let mut invalid = 0;
+
+    for path in paths.iter() {
+        let reader: Box<dyn BufRead> = if *path == "-" {
+            Box::new(BufReader::new(io::stdin()))
+        } else {
+            Box::new(BufReader::new(
+                File::open(path).map_err(|error| format!("{}: {}", path, error))?,
+            ))
+        };
+
+        for (number, line) in reader.lines().enumerate() {
+            let line = line.map_err(|error| format!("{}: {}", path, error))?;
+            let digits = line.trim();
+
+            if digits.is_empty() {
+                continue;
+            }
+
+            match MediaAccessControlAddress::new_with_diagnostics(digits) {
+                Ok(mac) => addresses.push(mac),
+                Err(error) => {
+                    eprintln!("{}:{}: {}", path, number + 1, error);
+                    invalid += 1;
+                }
+            }
+        }
+    }
+
+    let summary = range::summarize(&addresses);
+
+    super::report_skipped(invalid);
+
+    if ranges {
+        let records: Vec<Vec<(&str, Field)>> = summary
+            .iter()
+            .map(|range| {
+                vec![
+                    ("range", Field::Text(range.to_string())),
+                    ("first", Field::Text(range.first().to_colon_notation())),
+                    ("last", Field::Text(range.last().to_colon_notation())),
+                    ("size", Field::Number(range.size())),
+                ]
+            })
+            .collect();
+
+        return print(output, &records, summary.iter().map(MacRange::to_string));
+    }
+
+    let prefixes: Vec<MacPrefix> = summary.iter().flat_map(MacRange::to_prefixes).collect();
+    let records: Vec<Vec<(&str, Field)>> = prefixes
+        .iter()
+        .map(|prefix| {
+            vec![
+                ("prefix", Field::Text(prefix.to_string())),
+                ("first", Field::Text(prefix.first().to_colon_notation())),
+                ("last", Field::Text(prefix.last().to_colon_notation())),
+                ("size", Field::Number(prefix.size())),
+            ]
+        })
+        .collect();
+
+    print(output, &records, prefixes.iter().map(MacPrefix::to_string))
+}
+
+/// Writes one line per item for text, or the records otherwise.
+fn print(
+    output: Output,
+    records: &[Vec<(&str, Field)>],
+    lines: impl Iterator<Item = String>,
+) -> Result<(), String> {
+    if output != Output::Text {
+        return output::print_records(output, "ranges", records);
+    }
+
+    let stdout = io::stdout();
+    let mut writer = BufWriter::new(stdout.lock());
+
+    for line in lines {
+        writeln!(writer, "{}", line).map_err(|error| error.to_string())?;
+    }
+
+    writer.flush().map_err(|error| error.to_string())
+}
@@ -0,0 +1,363 @@
+//! `macaddress serve`, which answers normalization, classification,
+//! and vendor lookups over HTTP, so that services in any language
+//! can use the library.
+
+use super::output::{self, Field};
+use macaddress::fingerprint;
+use macaddress::format::Notation;
+use macaddress::vendor::{VendorDatabase, VendorResolver};
+use macaddress::MediaAccessControlAddress;
+use std::io::{self, BufRead, BufReader, Read, Write};
+use std::net::{TcpListener, TcpStream};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::thread;
+use std::time::{Duration, Instant};
+
+/// The address that the server listens on when none is given.
+const DEFAULT_LISTEN: &str = "127.0.0.1:8080";
+
+/// How long a client may take to send its whole request, and to
+/// accept each write of the response.
+const TIMEOUT: Duration = Duration::from_secs(10);
+
+/// The longest request line or header line that the server reads.
+const MAX_LINE: usize = 8192;
+
+/// The most header lines that the server reads.
+const MAX_HEADERS: usize = 64;
+
+/// The most bytes of headers that the server reads.
+const MAX_HEADER_BYTES: usize = 16384;
+
+/// The most connections that the server handles at once.  Clients
+/// beyond it are turned away with `503 Service Unavailable`, on a
+/// best-effort basis, and then disconnected.
+const MAX_CONNECTIONS: usize = 64;
+
+/// Runs `macaddress serve`.
+pub fn run(args: &[String]) -> Result<(), String> {
+    let mut listen = DEFAULT_LISTEN;
+    let mut database = VendorDatabase::new();
+    let mut args = args.iter();
+
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--listen" => listen = super::value(arg, &mut args)?,
+            "--db" => {
+                database = VendorDatabase::load(super::value(arg, &mut args)?)?;
+            }
+            _ => return Err(format!("Pass in a known option (not {}).", arg)),
+        }
+    }
+
+    let listener = TcpListener::bind(listen).map_err(|error| format!("{}: {}", listen, error))?;
+    let database = Arc::new(database);
+    let connections = Arc::new(AtomicUsize::new(0));
+    eprintln!(
+        "Listening on http://{} with {} assignments.",
+        listener.local_addr().map_err(|error| error.to_string())?,
+        database.len()
+    );
+
+    for stream in listener.incoming() {
+        let stream = match stream {
+            Ok(stream) => stream,
+            Err(error) => {
+                eprintln!("macaddress: {}", error);
+                continue;
+            }
+        };
+        if connections.fetch_add(1, Ordering::SeqCst) >= MAX_CONNECTIONS {
+            connections.fetch_sub(1, Ordering::SeqCst);
+            // The response fits in the socket's send buffer, so a
+            // non-blocking write turns a client away without letting
+            // one that reads nothing stall the loop.
+            let _ = stream.set_nonblocking(true);
+            let _ = respond(
+                &stream,
+                "503 Service Unavailable",
+                &error(String::from("Try again once other requests finish.")),
+            );
+            continue;
+        }
+
+        let database = Arc::clone(&database);
+        let connection = Connection(Arc::clone(&connections));
+
+        thread::spawn(move || {
+            if let Err(error) = handle(stream, &database) {
+                eprintln!("macaddress: {}", error);
+            }
+
+            drop(connection);
+        });
+    }
+
+    Ok(())
+}
+
+/// One connection that is being handled, which it stops counting
+/// against `MAX_CONNECTIONS` when dropped.
+struct Connection(Arc<AtomicUsize>);
+
+impl Drop for Connection {
+    fn drop(&mut self) {
+        self.0.fetch_sub(1, Ordering::SeqCst);
+    }
+}
+
+/// A client's stream that fails to read once `deadline` passes, so
+/// that a client that trickles its request in cannot hold a thread
+/// for longer than `TIMEOUT`.
+struct Deadline<'a> {
+    stream: &'a TcpStream,
+    deadline: Instant,
+}
+
+impl Read for Deadline<'_> {
+    fn read(&mut self, buffer: &mut [u8]) -> io::Result<usize> {
+        let remaining = self
+            .deadline
+            .checked_duration_since(Instant::now())
+            .filter(|remaining| !remaining.is_zero())
+            .ok_or_else(|| {
+                io::Error::new(
+                    io::ErrorKind::TimedOut,
+                    "The client took too long to send its request.",
+                )
+            })?;
+
+        let mut stream = self.stream;
+        stream.set_read_timeout(Some(remaining))?;
+        stream.read(buffer)
+    }
+}
+
+/// Reads one request from a client and writes the response.
+fn handle(stream: TcpStream, database: &VendorDatabase) -> Result<(), String> {
+    stream
+        .set_write_timeout(Some(TIMEOUT))
+        .map_err(|error| error.to_string())?;
+    let mut reader = BufReader::new(Deadline {
+        stream: &stream,
+        deadline: Instant::now() + TIMEOUT,
+    });
+    let request = read_line(&mut reader)?;
+
+    // The headers are read, but none of them matter.
+    let mut headers = 0;
+    let mut header_bytes = 0;
+
+    loop {
+        let header = read_line(&mut reader)?;
+
+        if header.is_empty() {
+            break;
+        }
+
+        headers += 1;
+        header_bytes += header.len();
+
+        if headers > MAX_HEADERS || header_bytes > MAX_HEADER_BYTES {
+            respond(
+                &stream,
+                "431 Request Header Fields Too Large",
+                &error(String::from("Pass in fewer or shorter headers.")),
+            )?;
+            return Err(String::from("The client sent too many headers."));
+        }
+    }
+
+    let (status, body) = match request.split(' ').collect::<Vec<&str>>().as_slice() {
+        ["GET", target, version] if version.starts_with("HTTP/") => route(target, database),
+        [_, _, version] if version.starts_with("HTTP/") => (
+            "405 Method Not Allowed",
+            error(String::from("Pass in a GET request.")),
+        ),
+        _ => (
+            "400 Bad Request",
+            error(String::from("Pass in an HTTP request.")),
+        ),
+    };
+
+    respond(&stream, status, &body)
+}
+
+/// Writes a response with a status and a JSON body.
+fn respond(mut stream: &TcpStream, status: &str, body: &str) -> Result<(), String> {
+    let response = format!(
+        "HTTP/1.1 {}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}\n",
+        status,
+        body.len() + 1,
+        body
+    );
+
+    stream
+        .write_all(response.as_bytes())
+        .map_err(|error| error.to_string())
+}
+
+/// Reads one line of the request, without its line ending.
+fn read_line(reader: &mut impl BufRead) -> Result<String, String> {
+    let mut line = Vec::new();
+    let read = reader
+        .by_ref()
+        .take(MAX_LINE as u64)
+        .read_until(b'\n', &mut line)
+        .map_err(|error| error.to_string())?;
+
+    if read == 0 {
+        return Err(String::from("The client closed the connection early."));
+    }
+
+    if line.last() != Some(&b'\n') {
+        return Err(String::from("The client sent a line that was too long."));
+    }
+
+    let line = String::from_utf8(line).map_err(|error| error.to_string())?;
+    Ok(line.trim_end_matches(['\r', '\n']).to_string())
+}
+
+/// Answers a request for `target`, such as
+/// `/classify?address=a0:b1:c2:d3:e4:f5`, with a status and a JSON
+/// body.
+fn route(target: &str, database: &VendorDatabase) -> (&'static str, String) {
+    let (path, query) = target.split_once('?').unwrap_or((target, ""));
+    let parameter = |name: &str| {
+        query
+            .split('&')
+            .filter_map(|pair| pair.split_once('='))
+            .find(|(key, _)| *key == name)
+            .map(|(_, value)| decode(value))
+    };
+
+    if !matches!(path, "/normalize" | "/classify" | "/vendor") {
+        return (
+            "404 Not Found",
+            error(format!(
+                "Pass in /normalize, /classify, or /vendor (not {}).",
+                path
+            )),
+        );
+    }
+
+    let answer = parameter("address")
+        .ok_or_else(|| String::from("Pass in an address parameter."))
+        .and_then(|address| {
+            MediaAccessControlAddress::new_with_diagnostics(address).map_err(String::from)
+        })
+        .and_then(|mac| {
+            let notation = match parameter("notation") {
+                Some(notation) => notation.parse()?,
+                None => Notation::Colon,
+            };
+
+            answer(path, &mac, notation, database)
+        });
+
+    match answer {
+        Ok(body) => ("200 OK", body),
+        Err(message) => ("400 Bad Request", error(message)),
+    }
+}
+
+/// Returns the JSON body for a valid address.
+fn answer(
+    path: &str,
+    mac: &MediaAccessControlAddress,
+    notation: Notation,
+    database: &VendorDatabase,
+) -> Result<String, String> {
+    let address = ("address", Field::Text(mac.to_notation(notation)));
+
+    let fields = match path {
+        "/normalize" => vec![address],
+        "/vendor" => vec![
+            address,
+            ("oui", Field::Text(mac.to_fragments().0.to_string())),
+            (
+                "vendor",
+                Field::Text(database.resolve(mac)?.unwrap_or_default()),
+            ),
+        ],
+        _ => {
+            let report = fingerprint::fingerprint(mac, database)?;
+            vec![
+                address,
+                ("kind", Field::Text(report.kind.to_string())),
+                ("cast", Field::Text(report.cast.to_string())),
+                // Only unicast addresses are administered universally
+                // or locally, as `is_uaa` and `is_laa` judge.
+                (
+                    "administration",
+                    Field::Text(String::from(if mac.is_uaa() {
+                        "uaa"
+                    } else if mac.is_laa() {
+                        "laa"
+                    } else {
+                        ""
+                    })),
+                ),
+                (
+                    "origin",
+                    Field::Text(
+                        report
+                            .origin
+                            .map(|origin| origin.to_string())
+                            .unwrap_or_default(),
+                    ),
+                ),
+                (
+                    "platform",
+                    Field::Text(String::from(report.platform.unwrap_or_default())),
+                ),
+                (
+                    "role",
+                    Field::Text(report.role.map(|role| role.to_string()).unwrap_or_default()),
+                ),
+                ("vendor", Field::Text(report.vendor.unwrap_or_default())),
+            ]
+        }
+    };
+
+    Ok(output::json_object(&fields))
+}
+
+/// Returns a JSON body for an error.
+fn error(message: String) -> String {
+    output::json_object(&[("error", Field::Text(message))])
+}
+
+/// Decodes a percent-encoded query value, in which `+` is a space.
+fn decode(value: &str) -> String {
+    let bytes = value.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut index = 0;
+
+    while index < bytes.len() {
+        // `from_str_radix` would accept a sign, as in `%+1`.
+        let hex = bytes
+            .get(index + 1..index + 3)
+            .filter(|hex| hex.iter().all(u8::is_ascii_hexdigit))
+            .and_then(|hex| std::str::from_utf8(hex).ok())
+            .and_then(|hex| u8::from_str_radix(hex, 16).ok());
+
+        match (bytes[index], hex) {
+            (b'%', Some(byte)) => {
+                decoded.push(byte);
+                index += 3;
+            }
+            (b'+', _) => {
+                decoded.push(b' ');
+                index += 1;
+            }
+            (byte, _) => {
+                decoded.push(byte);
+                index += 1;
+            }
+        }
+    }
+
+    String::from_utf8_lossy(&decoded).into_owned()
+}
@@ -0,0 +1,332 @@
+//! `macaddress sort` and `macaddress dedup`, which normalize,
+//! numerically sort, and optionally deduplicate lists of MAC
+//! addresses of any size.
+
+use super::output::Output;
+use macaddress::export::Exporter;
+use macaddress::format::{self, Notation};
+use macaddress::vendor::VendorDatabase;
+use macaddress::MediaAccessControlAddress;
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+use std::env;
+use std::fs::{self, DirBuilder, File, OpenOptions};
+use std::io::{self, BufRead, BufReader, BufWriter, Read, Write};
+use std::iter::Peekable;
+use std::path::PathBuf;
+use std::process;
+use std::time::{SystemTime, UNIX_EPOCH};
+use std::vec;
+
+#[cfg(unix)]
+use std::os::unix::fs::DirBuilderExt;
+
+/// The number of addresses that are sorted in memory before they
+/// spill to a temporary file, which bounds memory use to about
+/// 96 MiB by default (and as much again while a buffer is
+/// radix-sorted).
+const DEFAULT_BUFFER: usize = 16 * 1024 * 1024;
+
+/// How many names `Runs::directory` tries before it gives up on
+/// creating a private directory.
+const MAX_ATTEMPTS: u32 = 100;
+
+/// Sorted runs of addresses, each in a temporary file of raw
+/// 6-octet records, which are removed when the runs are dropped.
+///
+/// The files are created in a private directory, which is created
+/// with the first run, so that other users can neither read them
+/// nor plant files or links in their place.
+struct Runs {
+    directory: Option<PathBuf>,
+    paths: Vec<PathBuf>,
+}
+
+impl Runs {
+    /// Instantiates `Runs` without any runs.
+    fn new() -> Self {
+        Runs {
+            directory: None,
+            paths: Vec::new(),
+        }
+    }
+
+    /// Returns the private directory for the runs, creating it in
+    /// the temporary directory if need be.
+    fn directory(&mut self) -> Result<PathBuf, String> {
+        if let Some(directory) = &self.directory {
+            return Ok(directory.clone());
+        }
+
+        let nanos = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|elapsed| elapsed.subsec_nanos())
+            .unwrap_or_default();
+
+        for attempt in 0..MAX_ATTEMPTS {
+            let directory = env::temp_dir().join(format!(
+                "macaddress-sort-{}-{:08x}-{}",
+                process::id(),
+                nanos,
+                attempt
+            ));
+            let mut builder = DirBuilder::new();
+            #[cfg(unix)]
+            builder.mode(0o700);
+
+            match builder.create(&directory) {
+                Ok(()) => {
+                    self.directory = Some(directory.clone());
+                    return Ok(directory);
+                }
+                Err(error) if error.kind() == io::ErrorKind::AlreadyExists => continue,
+                Err(error) => return Err(format!("{}: {}", directory.display(), error)),
+            }
+        }
+
+        Err(String::from(
+            "Pass in a temporary directory in which a private directory can be created.",
+        ))
+    }
+
+    /// Sorts `buffer`, writes it to a new run, and empties it.
+    fn spill(&mut self, buffer: &mut Vec<[u8; 6]>, unique: bool) -> Result<(), String> {
+        sort(buffer, unique);
+
+        let path = self.directory()?.join(self.paths.len().to_string());
+        let file = OpenOptions::new()
+            .write(true)
+            .create_new(true)
+            .open(&path)
+            .map_err(|error| format!("{}: {}", path.display(), error))?;
+        let mut writer = BufWriter::new(file);
+        self.paths.push(path);
+
+        for octets in buffer.iter() {
+            writer
+                .write_all(octets)
+                .map_err(|error| error.to_string())?;
+        }
+
+        writer.flush().map_err(|error| error.to_string())?;
+        buffer.clear();
+        Ok(())
+    }
+}
+
+impl Drop for Runs {
+    fn drop(&mut self) {
+        for path in self.paths.iter() {
+            let _ = fs::remove_file(path);
+        }
+
+        if let Some(directory) = &self.directory {
+            let _ = fs::remove_dir(directory);
+        }
+    }
+}
+
+/// Sorts addresses in place, removing duplicates if `unique`.
+fn sort(buffer: &mut Vec<[u8; 6]>, unique: bool) {
+    macaddress::sort::sort_octets(buffer);
+
+    if unique {
+        buffer.dedup();
+    }
+}
+
+/// Reads the next 6-octet record from a run.
+fn next_record(reader: &mut impl Read) -> Result<Option<[u8; 6]>, String> {
+    let mut octets = [0; 6];
+
+    match reader.read_exact(&mut octets) {
+        Ok(()) => Ok(Some(octets)),
+        Err(error) if error.kind() == io::ErrorKind::UnexpectedEof => Ok(None),
+        Err(error) => Err(error.to_string()),
+    }
+}
+
+/// Merges sorted runs with the sorted addresses left in memory,
+/// always yielding the smallest address at the head of any of
+/// them.
+///
+/// If a run cannot be read, it stops early and keeps the error.
+struct Merge {
+    readers: Vec<BufReader<File>>,
+    heap: BinaryHeap<Reverse<([u8; 6], usize)>>,
+    remaining: Peekable<vec::IntoIter<[u8; 6]>>,
+    unique: bool,
+    previous: Option<[u8; 6]>,
+    error: Option<String>,
+}
+
+impl Merge {
+    /// Instantiates `Merge` with the runs and the sorted buffer.
+    fn new(runs: &Runs, buffer: Vec<[u8; 6]>, unique: bool) -> Result<Self, String> {
+        let mut readers = Vec::new();
+        let mut heap = BinaryHeap::new();
+
+        for path in runs.paths.iter() {
+            let file =
+                File::open(path).map_err(|error| format!("{}: {}", path.display(), error))?;
+            let mut reader = BufReader::new(file);
+
+            if let Some(octets) = next_record(&mut reader)? {
+                heap.push(Reverse((octets, readers.len())));
+            }
+
+            readers.push(reader);
+        }
+
+        Ok(Self {
+            readers,
+            heap,
+            remaining: buffer.into_iter().peekable(),
+            unique,
+            previous: None,
+            error: None,
+        })
+    }
+
+    /// Returns the next address, duplicates included.
+    fn next_any(&mut self) -> Result<Option<[u8; 6]>, String> {
+        let from_heap = match (self.heap.peek(), self.remaining.peek()) {
+            (None, None) => return Ok(None),
+            (Some(Reverse((octets, _))), Some(next)) => octets <= next,
+            (Some(_), None) => true,
+            (None, Some(_)) => false,
+        };
+
+        if !from_heap {
+            return Ok(self.remaining.next());
+        }
+
+        let Reverse((octets, index)) = self.heap.pop().unwrap();
+
+        if let Some(next) = next_record(&mut self.readers[index])? {
+            self.heap.push(Reverse((next, index)));
+        }
+
+        Ok(Some(octets))
+    }
+}
+
+impl Iterator for Merge {
+    type Item = [u8; 6];
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            match self.next_any() {
+                Ok(Some(octets)) if self.unique && self.previous == Some(octets) => {}
+                Ok(Some(octets)) => {
+                    self.previous = Some(octets);
+                    return Some(octets);
+                }
+                Ok(None) => return None,
+                Err(error) => {
+                    self.error = Some(error);
+                    return None;
+                }
+            }
+        }
+    }
+}
+
+/// Runs `macaddress sort` (or, with `unique`, `macaddress dedup`).
+pub fn run(args: &[String], unique: bool, output: Output) -> Result<(), String> {
+    let mut unique = unique;
+    let mut notation = Notation::Colon;
+    let mut capacity = DEFAULT_BUFFER;
+    let mut database = VendorDatabase::new();
+    let mut paths = Vec::new();
+    let mut args = args.iter();
+
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--unique" | "-u" => unique = true,
+            "--notation" => notation = super::value(arg, &mut args)?.parse()?,
+            "--buffer" => {
+                capacity = super::value(arg, &mut args)?
+                    .parse()
+                    .ok()
+                    .filter(|capacity| *capacity > 0)
+                    .ok_or_else(|| String::from("Pass in a buffer of at least 1 address."))?;
+            }
+            "--db" => {
+                database = VendorDatabase::load(super::value(arg, &mut args)?)?;
+            }
+            path if !path.starts_with('-') || path == "-" => paths.push(path),
+            _ => return Err(format!("Pass in a known option (not {}).", arg)),
+        }
+    }
+
+    if paths.is_empty() {
+        paths.push("-");
+    }
+
+    let mut buffer = Vec::with_capacity(capacity.min(DEFAULT_BUFFER));
+    let mut runs = Runs::new();
+    let mut invalid = 0;
+
+    for path in paths.iter() {
+        let reader: Box<dyn BufRead> = if *path == "-" {
+            Box::new(BufReader::new(io::stdin()))
+        } else {
+            Box::new(BufReader::new(
+                File::open(path).map_err(|error| format!("{}: {}", path, error))?,
+            ))
+        };
+
+        for (number, line) in reader.lines().enumerate() {
+            let line = line.map_err(|error| format!("{}: {}", path, error))?;
+            let digits = line.trim();
+
+            if digits.is_empty() {
+                continue;
+            }
+
+            match MediaAccessControlAddress::new_with_diagnostics(digits) {
+                Ok(mac) => buffer.push(mac.to_octets()),
+                Err(error) => {
+                    eprintln!("{}:{}: {}", path, number + 1, error);
+                    invalid += 1;
+                }
+            }
+
+            if buffer.len() == capacity {
+                runs.spill(&mut buffer, unique)?;
+            }
+        }
+    }
+
+    sort(&mut buffer, unique);
+
+    let mut merge = Merge::new(&runs, buffer, unique)?;
+    let stdout = io::stdout();
+    let mut writer = BufWriter::new(stdout.lock());
+
+    match output.encoding() {
+        None => {
+            for octets in merge.by_ref() {
+                writeln!(writer, "{}", format::format(&octets, notation))
+                    .map_err(|error| error.to_string())?;
+            }
+        }
+        Some(encoding) => {
+            let addresses = merge.by_ref().map(MediaAccessControlAddress::from);
+
+            Exporter::new(&database, encoding)
+                .with_notation(notation)
+                .write(addresses, &mut writer)?;
+        }
+    }
+
+    if let Some(error) = merge.error {
+        return Err(error);
+    }
+
+    writer.flush().map_err(|error| error.to_string())?;
+
+    super::report_skipped(invalid);
+    Ok(())
+}
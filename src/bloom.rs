@@ -0,0 +1,191 @@
+use super::error::Error;
+use super::hash::mix;
+use super::macaddress::MediaAccessControlAddress;
+
+/// The four octets that begin a serialized `MacBloom`.
+const MAGIC: [u8; 4] = *b"MBF1";
+
+/// The length of a serialized `MacBloom`'s header (the magic, the
+/// number of hash functions, the number of bits, and the number of
+/// insertions).
+const HEADER_LEN: usize = 24;
+
+/// `MacBloom` is a Bloom filter of MAC addresses, which answers
+/// whether it holds an address in constant space.
+///
+/// It never misses an address that was inserted, but it may claim
+/// to hold one that was not, at the false-positive rate chosen
+/// when it was created.  At a 1% rate, it needs about 1.2 bytes
+/// per address, so it suits deny lists and seen lists too large to
+/// hold exactly.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct MacBloom {
+    words: Vec<u64>,
+    bits: u64,
+    hashes: u32,
+    count: u64,
+}
+
+impl MacBloom {
+    /// Instantiates an empty `MacBloom` sized to hold `capacity`
+    /// addresses with the given false-positive rate (greater than
+    /// 0 and less than 1).
+    pub fn new(capacity: u64, false_positive_rate: f64) -> Result<Self, Error> {
+        if capacity == 0 {
+            return Err(Error::Invalid(String::from(
+                "Pass in a capacity greater than 0.",
+            )));
+        }
+
+        if !(false_positive_rate > 0.0 && false_positive_rate < 1.0) {
+            return Err(Error::Invalid(String::from(
+                "Pass in a false-positive rate greater than 0 and less than 1.",
+            )));
+        }
+
+        let ln2 = std::f64::consts::LN_2;
+        let bits = (-(capacity as f64) * false_positive_rate.ln() / (ln2 * ln2)).ceil() as u64;
+        let hashes = ((bits as f64 / capacity as f64) * ln2).round().max(1.0) as u32;
+
+        Self::with_parameters(bits, hashes)
+    }
+
+    /// Instantiates an empty `MacBloom` with `bits` bits and
+    /// `hashes` hash functions, for callers who sized it themselves.
+    pub fn with_parameters(bits: u64, hashes: u32) -> Result<Self, Error> {
+        if bits == 0 || hashes == 0 {
+            return Err(Error::Invalid(String::from(
+                "Pass in at least 1 bit and at least 1 hash function.",
+            )));
+        }
+
+        Ok(Self {
+            words: vec![0; bits.div_ceil(64) as usize],
+            bits,
+            hashes,
+            count: 0,
+        })
+    }
+
+    /// Returns the bits that represent the MAC address, by double
+    /// hashing its 48-bit value.
+    fn indexes(
+        address: &MediaAccessControlAddress,
+        bits: u64,
+        hashes: u32,
+    ) -> impl Iterator<Item = u64> {
+        let first = mix(address.to_decimal_representation());
+        let second = mix(first) | 1;
+
+        (0..u64::from(hashes))
+            .map(move |index| first.wrapping_add(index.wrapping_mul(second)) % bits)
+    }
+
+    /// Inserts the MAC address.
+    pub fn insert(&mut self, address: &MediaAccessControlAddress) {
+        for index in Self::indexes(address, self.bits, self.hashes) {
+            self.words[(index / 64) as usize] |= 1 << (index % 64);
+        }
+
+        self.count += 1;
+    }
+
+    /// Whether the filter probably holds the MAC address.
+    ///
+    /// `false` is always correct; `true` is wrong at roughly the
+    /// false-positive rate.
+    pub fn contains(&self, address: &MediaAccessControlAddress) -> bool {
+        Self::indexes(address, self.bits, self.hashes)
+            .all(|index| self.words[(index / 64) as usize] & (1 << (index % 64)) != 0)
+    }
+
+    /// Returns the number of insertions, counting repeats.
+    pub fn len(&self) -> u64 {
+        self.count
+    }
+
+    /// Whether nothing has been inserted.
+    pub fn is_empty(&self) -> bool {
+        self.count == 0
+    }
+
+    /// Returns the number of bits in the filter.
+    pub fn bits(&self) -> u64 {
+        self.bits
+    }
+
+    /// Returns the number of hash functions.
+    pub fn hashes(&self) -> u32 {
+        self.hashes
+    }
+
+    /// Returns the false-positive rate expected after the
+    /// insertions so far.
+    pub fn false_positive_rate(&self) -> f64 {
+        let k = f64::from(self.hashes);
+        let exponent = -k * self.count as f64 / self.bits as f64;
+        (1.0 - exponent.exp()).powf(k)
+    }
+
+    /// Removes every address.
+    pub fn clear(&mut self) {
+        self.words.iter_mut().for_each(|word| *word = 0);
+        self.count = 0;
+    }
+
+    /// Returns the filter's portable encoding: a 24-octet header
+    /// (`MBF1`, then the number of hash functions, bits, and
+    /// insertions in network byte order), then the bits in 64-bit
+    /// words in network byte order.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = Vec::with_capacity(HEADER_LEN + self.words.len() * 8);
+        bytes.extend_from_slice(&MAGIC);
+        bytes.extend_from_slice(&self.hashes.to_be_bytes());
+        bytes.extend_from_slice(&self.bits.to_be_bytes());
+        bytes.extend_from_slice(&self.count.to_be_bytes());
+
+        for word in self.words.iter() {
+            bytes.extend_from_slice(&word.to_be_bytes());
+        }
+
+        bytes
+    }
+
+    /// Instantiates `MacBloom` with the encoding that `to_bytes`
+    /// returns.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, Error> {
+        if bytes.len() < HEADER_LEN || bytes[..4] != MAGIC {
+            return Err(Error::Invalid(String::from(
+                "Pass in bytes that begin with a MacBloom header.",
+            )));
+        }
+
+        let mut hashes = [0; 4];
+        let mut bits = [0; 8];
+        let mut count = [0; 8];
+        hashes.copy_from_slice(&bytes[4..8]);
+        bits.copy_from_slice(&bytes[8..16]);
+        count.copy_from_slice(&bytes[16..24]);
+
+        let bits = u64::from_be_bytes(bits);
+        let body = &bytes[HEADER_LEN..];
+
+        if body.len() as u64 != bits.div_ceil(64) * 8 {
+            return Err(Error::Invalid(format!(
+                "Pass in {} octets of bits after the header.",
+                bits.div_ceil(64) * 8
+            )));
+        }
+
+        let mut bloom = Self::with_parameters(bits, u32::from_be_bytes(hashes))?;
+
+        for (word, chunk) in bloom.words.iter_mut().zip(body.chunks_exact(8)) {
+            let mut octets = [0; 8];
+            octets.copy_from_slice(chunk);
+            *word = u64::from_be_bytes(octets);
+        }
+
+        bloom.count = u64::from_be_bytes(count);
+        Ok(bloom)
+    }
+}
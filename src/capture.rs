@@ -0,0 +1,460 @@
+use super::error::Error;
+use super::frame;
+use super::macaddress::MediaAccessControlAddress;
+use std::collections::BTreeMap;
+use std::io::{self, Read};
+use std::sync::Mutex;
+
+#[cfg(target_os = "linux")]
+mod live;
+
+#[cfg(target_os = "linux")]
+pub use self::live::LiveCapture;
+
+/// The link type of Ethernet frames (`LINKTYPE_ETHERNET`).
+pub const LINKTYPE_ETHERNET: u32 = 1;
+
+/// The largest frame or block that `Capture` will read, which
+/// keeps a corrupt length field from exhausting memory.
+const MAX_LEN: usize = 0x0400_0000;
+
+/// The pcapng block types that `Capture` understands.
+const SECTION_HEADER: u32 = 0x0a0d_0d0a;
+const INTERFACE_DESCRIPTION: u32 = 0x0000_0001;
+const SIMPLE_PACKET: u32 = 0x0000_0003;
+const ENHANCED_PACKET: u32 = 0x0000_0006;
+
+/// One frame read from a capture file.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Frame {
+    /// The link type of the interface that captured the frame
+    /// (see `LINKTYPE_ETHERNET`).
+    pub link_type: u32,
+    /// The captured bytes, which may be fewer than were sent if
+    /// the capture was truncated to a snapshot length.
+    pub data: Vec<u8>,
+    /// The number of bytes that were sent.
+    pub length: usize,
+}
+
+/// The layout of the file that `Capture` is reading.
+#[derive(Debug)]
+enum Format {
+    /// A classic pcap file with a single link type.
+    Pcap { link_type: u32 },
+    /// A pcapng file with one link type per interface in the
+    /// current section.
+    Pcapng { interfaces: Vec<u32> },
+}
+
+/// A reader that walks a classic pcap or a pcapng file and yields
+/// its frames.  Either byte order is accepted.
+///
+/// It is also an iterator over `Result<Frame, Error>`.
+#[derive(Debug)]
+pub struct Capture<R> {
+    reader: R,
+    format: Format,
+    big_endian: bool,
+}
+
+impl<R: Read> Capture<R> {
+    /// Instantiates `Capture` by reading the file header from
+    /// `reader`, which should be buffered.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(level = "debug", skip_all, err)
+    )]
+    pub fn new(mut reader: R) -> Result<Self, Error> {
+        let mut magic = [0; 4];
+        reader.read_exact(&mut magic).map_err(describe)?;
+
+        match magic {
+            [0xd4, 0xc3, 0xb2, 0xa1] | [0x4d, 0x3c, 0xb2, 0xa1] => Self::pcap(reader, false),
+            [0xa1, 0xb2, 0xc3, 0xd4] | [0xa1, 0xb2, 0x3c, 0x4d] => Self::pcap(reader, true),
+            [0x0a, 0x0d, 0x0d, 0x0a] => {
+                let mut capture = Self {
+                    reader,
+                    format: Format::Pcapng {
+                        interfaces: Vec::new(),
+                    },
+                    big_endian: false,
+                };
+                let mut length = [0; 4];
+                capture.reader.read_exact(&mut length).map_err(describe)?;
+                capture.section(length)?;
+                Ok(capture)
+            }
+            _ => Err(Error::Invalid(String::from(
+                "Pass in a pcap or pcapng file.",
+            ))),
+        }
+    }
+
+    /// Finishes reading a classic pcap file header.
+    fn pcap(mut reader: R, big_endian: bool) -> Result<Self, Error> {
+        let mut header = [0; 20];
+        reader.read_exact(&mut header).map_err(describe)?;
+
+        let mut capture = Self {
+            reader,
+            format: Format::Pcap { link_type: 0 },
+            big_endian,
+        };
+        // The upper bits may describe the frame check sequence.
+        let link_type = capture.u32_at(&header, 16) & 0xffff;
+        capture.format = Format::Pcap { link_type };
+
+        #[cfg(feature = "tracing")]
+        tracing::debug!(link_type, big_endian, "reading a pcap file");
+
+        Ok(capture)
+    }
+
+    /// Reads the rest of a pcapng section header block, whose
+    /// block type and `length` have already been read.  The length
+    /// is in the section's byte order, which only the byte-order
+    /// magic that follows it reveals.
+    fn section(&mut self, length: [u8; 4]) -> Result<(), Error> {
+        let mut magic = [0; 4];
+        self.reader.read_exact(&mut magic).map_err(describe)?;
+
+        self.big_endian = match magic {
+            [0x1a, 0x2b, 0x3c, 0x4d] => true,
+            [0x4d, 0x3c, 0x2b, 0x1a] => false,
+            _ => {
+                return Err(Error::Invalid(String::from(
+                    "Pass in a pcapng file with a valid byte-order magic.",
+                )))
+            }
+        };
+
+        let length = self.block_length(self.u32_at(&length, 0))?;
+        self.body(length - 12)?;
+
+        #[cfg(feature = "tracing")]
+        tracing::debug!(big_endian = self.big_endian, "reading a pcapng section");
+
+        self.format = Format::Pcapng {
+            interfaces: Vec::new(),
+        };
+        Ok(())
+    }
+
+    /// Returns the next frame, or `None` at the end of the file.
+    pub fn next_frame(&mut self) -> Result<Option<Frame>, Error> {
+        match self.format {
+            Format::Pcap { link_type } => self.next_record(link_type),
+            Format::Pcapng { .. } => self.next_block(),
+        }
+    }
+
+    /// Reads the next record of a classic pcap file.
+    fn next_record(&mut self, link_type: u32) -> Result<Option<Frame>, Error> {
+        let mut header = [0; 16];
+
+        if !self.read_or_end(&mut header)? {
+            return Ok(None);
+        }
+
+        let captured = self.u32_at(&header, 8) as usize;
+        let length = self.u32_at(&header, 12) as usize;
+
+        if captured > MAX_LEN {
+            return Err(Error::Invalid(format!(
+                "Pass in a record shorter than {} bytes.",
+                MAX_LEN
+            )));
+        }
+
+        let data = self.body(captured)?;
+        Ok(Some(Frame {
+            link_type,
+            data,
+            length,
+        }))
+    }
+
+    /// Reads pcapng blocks until one of them holds a frame.
+    fn next_block(&mut self) -> Result<Option<Frame>, Error> {
+        loop {
+            let mut fields = [0; 8];
+
+            if !self.read_or_end(&mut fields)? {
+                return Ok(None);
+            }
+
+            if self.u32_at(&fields, 0) == SECTION_HEADER {
+                self.section([fields[4], fields[5], fields[6], fields[7]])?;
+                continue;
+            }
+
+            let block_type = self.u32_at(&fields, 0);
+            let length = self.block_length(self.u32_at(&fields, 4))?;
+            let body = self.body(length - 12)?;
+            self.body(4)?;
+
+            match block_type {
+                INTERFACE_DESCRIPTION if body.len() >= 2 => {
+                    let link_type = if self.big_endian {
+                        u16::from_be_bytes([body[0], body[1]])
+                    } else {
+                        u16::from_le_bytes([body[0], body[1]])
+                    };
+
+                    if let Format::Pcapng { interfaces } = &mut self.format {
+                        #[cfg(feature = "tracing")]
+                        tracing::trace!(
+                            interface = interfaces.len(),
+                            link_type,
+                            "described interface"
+                        );
+
+                        interfaces.push(u32::from(link_type));
+                    }
+                }
+                ENHANCED_PACKET if body.len() >= 20 => {
+                    let interface = self.u32_at(&body, 0) as usize;
+                    let captured = self.u32_at(&body, 12) as usize;
+                    let length = self.u32_at(&body, 16) as usize;
+                    let link_type = self.link_type(interface)?;
+
+                    if 20 + captured > body.len() {
+                        return Err(Error::Invalid(String::from(
+                            "Pass in a pcapng file without truncated packets.",
+                        )));
+                    }
+
+                    let data = body[20..20 + captured].to_vec();
+                    return Ok(Some(Frame {
+                        link_type,
+                        data,
+                        length,
+                    }));
+                }
+                SIMPLE_PACKET if body.len() >= 4 => {
+                    let length = self.u32_at(&body, 0) as usize;
+                    let captured = length.min(body.len() - 4);
+                    let link_type = self.link_type(0)?;
+
+                    let data = body[4..4 + captured].to_vec();
+                    return Ok(Some(Frame {
+                        link_type,
+                        data,
+                        length,
+                    }));
+                }
+                _ => {}
+            }
+        }
+    }
+
+    /// Returns the link type of a pcapng interface.
+    fn link_type(&self, interface: usize) -> Result<u32, Error> {
+        match &self.format {
+            Format::Pcapng { interfaces } => interfaces.get(interface).copied().ok_or_else(|| {
+                Error::Invalid(format!(
+                    "Pass in a pcapng file that describes interface {}.",
+                    interface
+                ))
+            }),
+            Format::Pcap { link_type } => Ok(*link_type),
+        }
+    }
+
+    /// Checks the total length of a pcapng block.
+    fn block_length(&self, length: u32) -> Result<usize, Error> {
+        let length = length as usize;
+
+        if length < 12 || length & 3 != 0 || length > MAX_LEN {
+            return Err(Error::Invalid(format!(
+                "Pass in a pcapng file with valid block lengths (not {}).",
+                length
+            )));
+        }
+
+        Ok(length)
+    }
+
+    /// Reads exactly `length` bytes.
+    fn body(&mut self, length: usize) -> Result<Vec<u8>, Error> {
+        let mut body = vec![0; length];
+        self.reader.read_exact(&mut body).map_err(describe)?;
+        Ok(body)
+    }
+
+    /// Fills `buffer`, or returns `false` if the file ends before
+    /// the first byte.
+    fn read_or_end(&mut self, buffer: &mut [u8]) -> Result<bool, Error> {
+        let mut filled = 0;
+
+        while filled < buffer.len() {
+            match self.reader.read(&mut buffer[filled..]) {
+                Ok(0) if filled == 0 => return Ok(false),
+                Ok(0) => {
+                    return Err(Error::Invalid(String::from(
+                        "Pass in a capture file that is not truncated.",
+                    )))
+                }
+                Ok(count) => filled += count,
+                Err(error) if error.kind() == io::ErrorKind::Interrupted => {}
+                Err(error) => return Err(describe(error)),
+            }
+        }
+
+        Ok(true)
+    }
+
+    /// Reads a `u32` at `offset` in the file's byte order.
+    fn u32_at(&self, bytes: &[u8], offset: usize) -> u32 {
+        let mut field = [0; 4];
+        field.copy_from_slice(&bytes[offset..offset + 4]);
+
+        if self.big_endian {
+            u32::from_be_bytes(field)
+        } else {
+            u32::from_le_bytes(field)
+        }
+    }
+}
+
+impl<R: Read> Iterator for Capture<R> {
+    type Item = Result<Frame, Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.next_frame().transpose()
+    }
+}
+
+/// Converts an I/O error into this crate's error, reporting a
+/// file that ends early as invalid.
+fn describe(error: io::Error) -> Error {
+    match error.kind() {
+        io::ErrorKind::UnexpectedEof => Error::Invalid(String::from(
+            "Pass in a capture file that is not truncated.",
+        )),
+        _ => Error::Io(error),
+    }
+}
+
+/// One MAC address seen in a capture, along with the number of
+/// frames and bytes that it sent and received.
+#[derive(Debug)]
+pub struct Observation {
+    /// The MAC address.
+    pub address: MediaAccessControlAddress,
+    /// The number of frames whose source was the address.
+    pub source_frames: u64,
+    /// The number of bytes in frames whose source was the address.
+    pub source_bytes: u64,
+    /// The number of frames whose destination was the address.
+    pub destination_frames: u64,
+    /// The number of bytes in frames whose destination was the
+    /// address.
+    pub destination_bytes: u64,
+}
+
+/// The counters that `Monitor` keeps for one address.
+#[derive(Clone, Copy, Debug, Default)]
+struct Counters {
+    source_frames: u64,
+    source_bytes: u64,
+    destination_frames: u64,
+    destination_bytes: u64,
+}
+
+/// A live set of the MAC addresses seen in Ethernet frames, with
+/// frame and byte counters for each.
+///
+/// It is safe to share between the thread that records frames
+/// (for example, from a `LiveCapture`) and the threads that read
+/// snapshots of it (for example, to export metrics).
+#[derive(Debug, Default)]
+pub struct Monitor {
+    counters: Mutex<BTreeMap<[u8; 6], Counters>>,
+}
+
+impl Monitor {
+    /// Instantiates an empty `Monitor`.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Counts the frame's source and destination addresses.
+    ///
+    /// Frames on link types other than Ethernet, and Ethernet
+    /// frames too short to hold a header, are skipped.
+    pub fn record(&self, frame: &Frame) {
+        if frame.link_type != LINKTYPE_ETHERNET {
+            return;
+        }
+
+        if let Ok((destination, source)) = frame::addresses(&frame.data) {
+            let length = frame.length as u64;
+            let mut counters = self.counters.lock().unwrap();
+
+            let source = counters.entry(source.to_octets()).or_default();
+            source.source_frames += 1;
+            source.source_bytes += length;
+
+            let destination = counters.entry(destination.to_octets()).or_default();
+            destination.destination_frames += 1;
+            destination.destination_bytes += length;
+        }
+    }
+
+    /// Returns the number of addresses seen so far.
+    pub fn len(&self) -> usize {
+        self.counters.lock().unwrap().len()
+    }
+
+    /// Whether no addresses have been seen so far.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns every address seen so far, sorted, with its
+    /// counters.
+    pub fn snapshot(&self) -> Vec<Observation> {
+        self.counters
+            .lock()
+            .unwrap()
+            .iter()
+            .map(|(octets, counters)| Observation {
+                address: MediaAccessControlAddress::from(*octets),
+                source_frames: counters.source_frames,
+                source_bytes: counters.source_bytes,
+                destination_frames: counters.destination_frames,
+                destination_bytes: counters.destination_bytes,
+            })
+            .collect()
+    }
+
+    /// Forgets every address seen so far.
+    pub fn clear(&self) {
+        self.counters.lock().unwrap().clear();
+    }
+}
+
+/// Reads a pcap or pcapng file and returns every unique MAC
+/// address in it, sorted, with frame and byte counts.
+///
+/// Only Ethernet frames are considered.  Frames on other link
+/// types, and Ethernet frames too short to hold a header, are
+/// skipped.
+#[cfg_attr(
+    feature = "tracing",
+    tracing::instrument(level = "debug", skip_all, err)
+)]
+pub fn observe<R: Read>(reader: R) -> Result<Vec<Observation>, Error> {
+    let monitor = Monitor::new();
+
+    for frame in Capture::new(reader)? {
+        monitor.record(&frame?);
+    }
+
+    #[cfg(feature = "tracing")]
+    tracing::debug!(addresses = monitor.len(), "observed capture");
+
+    Ok(monitor.snapshot())
+}
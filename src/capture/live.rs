@@ -0,0 +1,206 @@
+use super::{Frame, Monitor, LINKTYPE_ETHERNET};
+use crate::error::Error;
+use std::ffi::CString;
+use std::io;
+use std::mem;
+use std::os::unix::io::{AsRawFd, FromRawFd, OwnedFd};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::Duration;
+
+/// The largest frame that `LiveCapture` will read, which covers
+/// jumbo frames and the segments that offloading hands up.
+const SNAPLEN: usize = 65_536;
+
+/// Returns the error that the last system call set, as
+/// `Error::System`.
+fn last_os_error() -> Error {
+    Error::System(io::Error::last_os_error().to_string())
+}
+
+/// A Linux packet socket that captures every frame sent or
+/// received on one interface.
+///
+/// Opening it requires the `CAP_NET_RAW` capability (for example,
+/// running as root).  Its methods fail with `Error::System` when
+/// the operating system refuses a request.
+#[derive(Debug)]
+pub struct LiveCapture {
+    socket: OwnedFd,
+    buffer: Vec<u8>,
+}
+
+impl LiveCapture {
+    /// Instantiates `LiveCapture` on the interface with the given
+    /// name (for example, `eth0`).
+    #[cfg_attr(feature = "tracing", tracing::instrument(level = "debug", err))]
+    pub fn open(interface: &str) -> Result<Self, Error> {
+        let name = CString::new(interface).map_err(|_| {
+            Error::Invalid(String::from("Pass in an interface name without NUL bytes."))
+        })?;
+        let index = unsafe { libc::if_nametoindex(name.as_ptr()) };
+
+        if index == 0 {
+            return Err(Error::System(format!(
+                "Pass in the name of an existing interface (not {}).",
+                interface
+            )));
+        }
+
+        let protocol = (libc::ETH_P_ALL as u16).to_be();
+        let descriptor = unsafe {
+            libc::socket(
+                libc::AF_PACKET,
+                libc::SOCK_RAW | libc::SOCK_CLOEXEC,
+                i32::from(protocol),
+            )
+        };
+
+        if descriptor < 0 {
+            return Err(last_os_error());
+        }
+
+        let socket = unsafe { OwnedFd::from_raw_fd(descriptor) };
+
+        let mut address: libc::sockaddr_ll = unsafe { mem::zeroed() };
+        address.sll_family = libc::AF_PACKET as u16;
+        address.sll_protocol = protocol;
+        address.sll_ifindex = index as i32;
+
+        let result = unsafe {
+            libc::bind(
+                socket.as_raw_fd(),
+                &address as *const libc::sockaddr_ll as *const libc::sockaddr,
+                mem::size_of::<libc::sockaddr_ll>() as libc::socklen_t,
+            )
+        };
+
+        if result < 0 {
+            return Err(last_os_error());
+        }
+
+        // Every frame is reported as Ethernet, so refuse interfaces
+        // with other link layers (loopback frames have Ethernet
+        // headers too).
+        let mut bound: libc::sockaddr_ll = unsafe { mem::zeroed() };
+        let mut length = mem::size_of::<libc::sockaddr_ll>() as libc::socklen_t;
+        let result = unsafe {
+            libc::getsockname(
+                socket.as_raw_fd(),
+                &mut bound as *mut libc::sockaddr_ll as *mut libc::sockaddr,
+                &mut length,
+            )
+        };
+
+        if result < 0 {
+            return Err(last_os_error());
+        }
+
+        if bound.sll_hatype != libc::ARPHRD_ETHER && bound.sll_hatype != libc::ARPHRD_LOOPBACK {
+            return Err(Error::System(format!(
+                "Pass in an Ethernet interface (not {}, whose hardware type is {}).",
+                interface, bound.sll_hatype
+            )));
+        }
+
+        Ok(Self {
+            socket,
+            buffer: vec![0; SNAPLEN],
+        })
+    }
+
+    /// Makes `next_frame` give up after `timeout`, so that a caller
+    /// can check whether to stop, or wait forever if `None`.
+    ///
+    /// A timeout shorter than a microsecond is rounded up to one,
+    /// since the socket would take zero to mean waiting forever.
+    pub fn set_timeout(&self, timeout: Option<Duration>) -> Result<(), Error> {
+        let timeout = timeout.map_or(Duration::ZERO, |timeout| {
+            timeout.max(Duration::from_micros(1))
+        });
+        let value = libc::timeval {
+            tv_sec: timeout.as_secs() as libc::time_t,
+            tv_usec: libc::suseconds_t::from(timeout.subsec_micros() as i32),
+        };
+
+        let result = unsafe {
+            libc::setsockopt(
+                self.socket.as_raw_fd(),
+                libc::SOL_SOCKET,
+                libc::SO_RCVTIMEO,
+                &value as *const libc::timeval as *const libc::c_void,
+                mem::size_of::<libc::timeval>() as libc::socklen_t,
+            )
+        };
+
+        if result < 0 {
+            return Err(last_os_error());
+        }
+
+        Ok(())
+    }
+
+    /// Waits for the next frame, or returns `None` if the timeout
+    /// (see `set_timeout`) elapses first.
+    pub fn next_frame(&mut self) -> Result<Option<Frame>, Error> {
+        loop {
+            // `MSG_TRUNC` makes the packet socket report the frame's
+            // full length, even if the buffer holds only part of it.
+            let length = unsafe {
+                libc::recv(
+                    self.socket.as_raw_fd(),
+                    self.buffer.as_mut_ptr() as *mut libc::c_void,
+                    self.buffer.len(),
+                    libc::MSG_TRUNC,
+                )
+            };
+
+            if length >= 0 {
+                let length = length as usize;
+                let data = self.buffer[..length.min(SNAPLEN)].to_vec();
+
+                return Ok(Some(Frame {
+                    link_type: LINKTYPE_ETHERNET,
+                    data,
+                    length,
+                }));
+            }
+
+            let error = io::Error::last_os_error();
+
+            #[cfg(feature = "tracing")]
+            tracing::trace!(%error, "packet socket receive failed");
+
+            match error.kind() {
+                io::ErrorKind::Interrupted => {}
+                io::ErrorKind::WouldBlock | io::ErrorKind::TimedOut => return Ok(None),
+                _ => return Err(Error::System(error.to_string())),
+            }
+        }
+    }
+
+    /// Records every frame in `monitor` until `stop` is set, checking
+    /// it at least once per `interval` (at least a microsecond).
+    ///
+    /// Run it on its own thread and read snapshots of `monitor` from
+    /// others.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(level = "debug", skip_all, err)
+    )]
+    pub fn run(
+        &mut self,
+        monitor: &Monitor,
+        stop: &AtomicBool,
+        interval: Duration,
+    ) -> Result<(), Error> {
+        self.set_timeout(Some(interval))?;
+
+        while !stop.load(Ordering::Relaxed) {
+            if let Some(frame) = self.next_frame()? {
+                monitor.record(&frame);
+            }
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,106 @@
+use super::error::Error;
+use std::fmt;
+use std::str::FromStr;
+
+/// The kinds of extended identifier, as determined by the
+/// two or four least-significant bits of the first octet.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Kind {
+    /// An extended unique identifier (EUI), which has an OUI.
+    Unique,
+    /// An extended local identifier (ELI), which has a CID.
+    Local,
+    /// Neither an EUI nor an ELI.
+    Unknown,
+}
+
+impl Kind {
+    /// Returns the kind's stable name (`unique`, `local`, or
+    /// `unknown`).
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Kind::Unique => "unique",
+            Kind::Local => "local",
+            Kind::Unknown => "unknown",
+        }
+    }
+}
+
+impl fmt::Display for Kind {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.pad(self.as_str())
+    }
+}
+
+/// Parses a kind from its stable name, ignoring ASCII case.
+impl FromStr for Kind {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        [Kind::Unique, Kind::Local, Kind::Unknown]
+            .iter()
+            .find(|kind| kind.as_str().eq_ignore_ascii_case(s.trim()))
+            .copied()
+            .ok_or_else(|| Error::Invalid(String::from("Pass in unique, local, or unknown.")))
+    }
+}
+
+/// Compares a kind with its stable name, so that code written
+/// when `kind` returned a `String` keeps working.
+impl PartialEq<str> for Kind {
+    fn eq(&self, other: &str) -> bool {
+        self.as_str() == other
+    }
+}
+
+impl PartialEq<&str> for Kind {
+    fn eq(&self, other: &&str) -> bool {
+        self.as_str() == *other
+    }
+}
+
+/// The ways in which a frame sent to an address is delivered,
+/// as determined by the individual/group bit (and, for broadcast,
+/// by every other bit, too).
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Cast {
+    /// Delivered to one interface.
+    Unicast,
+    /// Delivered to a group of interfaces.
+    Multicast,
+    /// Delivered to every interface.
+    Broadcast,
+}
+
+impl Cast {
+    /// Returns the cast's stable name (`unicast`, `multicast`, or
+    /// `broadcast`).
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Cast::Unicast => "unicast",
+            Cast::Multicast => "multicast",
+            Cast::Broadcast => "broadcast",
+        }
+    }
+}
+
+impl fmt::Display for Cast {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.pad(self.as_str())
+    }
+}
+
+/// Parses a cast from its stable name, ignoring ASCII case.
+impl FromStr for Cast {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        [Cast::Unicast, Cast::Multicast, Cast::Broadcast]
+            .iter()
+            .find(|cast| cast.as_str().eq_ignore_ascii_case(s.trim()))
+            .copied()
+            .ok_or_else(|| {
+                Error::Invalid(String::from("Pass in unicast, multicast, or broadcast."))
+            })
+    }
+}
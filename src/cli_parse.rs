@@ -0,0 +1,172 @@
+use super::macaddress::MediaAccessControlAddress;
+
+/// The network operating systems whose `show mac address-table`
+/// (or `show ethernet-switching table`) output `parse_as` reads.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Platform {
+    /// Cisco IOS and IOS XE.
+    Ios,
+    /// Cisco NX-OS.
+    NxOs,
+    /// Arista EOS.
+    Eos,
+    /// Juniper Junos, with or without ELS.
+    Junos,
+}
+
+impl Platform {
+    /// Guesses the platform that printed `text` from its headers,
+    /// or returns `None` if it has no telltale header.
+    pub fn detect(text: &str) -> Option<Self> {
+        if text.contains("MAC flags")
+            || text.contains("Ethernet switching table")
+            || text.contains("Ethernet-switching table")
+        {
+            Some(Platform::Junos)
+        } else if text.contains("Last Move") {
+            Some(Platform::Eos)
+        } else if text.contains("NTFY") || text.contains("Gateway MAC") {
+            Some(Platform::NxOs)
+        } else if text.contains("Mac Address Table") || text.contains("Mac Address ") {
+            Some(Platform::Ios)
+        } else {
+            None
+        }
+    }
+}
+
+/// How a switch came to hold an entry in its MAC address table.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub enum EntryKind {
+    /// Learned from traffic.
+    Dynamic,
+    /// Configured, or held by the switch for itself.
+    Static,
+    /// Anything else, as printed (for example, `secure` or `igmp`).
+    Other(String),
+}
+
+/// One entry in a switch's MAC address table.
+#[derive(Debug)]
+pub struct MacTableEntry {
+    /// The VLAN, as printed: its ID (for example, `10`), its name
+    /// on Junos (for example, `default`), or a placeholder such as
+    /// `All` or `-` for entries in no one VLAN.
+    pub vlan: String,
+    /// The MAC address.
+    pub address: MediaAccessControlAddress,
+    /// The interface or interfaces, as printed (for example,
+    /// `Gi1/0/1` or `ge-0/0/1.0`).
+    pub interface: String,
+    /// How the switch came to hold the entry.
+    pub kind: EntryKind,
+}
+
+impl MacTableEntry {
+    /// Returns the VLAN ID, if the VLAN was printed as one.
+    pub fn vlan_id(&self) -> Option<u16> {
+        self.vlan.parse().ok().filter(|vlan| *vlan < 4096)
+    }
+}
+
+/// Returns the kind that Cisco and Arista print in the `Type`
+/// column.
+fn type_column(column: &str) -> EntryKind {
+    match column.to_ascii_lowercase().as_str() {
+        "dynamic" => EntryKind::Dynamic,
+        "static" => EntryKind::Static,
+        _ => EntryKind::Other(column.to_string()),
+    }
+}
+
+/// Returns the kind that Junos prints in the `Type` column (without
+/// ELS) or the `MAC flags` column (with ELS).
+fn junos_column(column: &str) -> EntryKind {
+    match column.to_ascii_lowercase().as_str() {
+        "learn" | "learned" => EntryKind::Dynamic,
+        "static" => EntryKind::Static,
+        _ if column.starts_with('D') => EntryKind::Dynamic,
+        _ if column.starts_with('S') || column.starts_with('P') => EntryKind::Static,
+        _ => EntryKind::Other(column.to_string()),
+    }
+}
+
+/// Parses the output of `show mac address-table` (or, on Junos,
+/// `show ethernet-switching table`), guessing the platform with
+/// `Platform::detect` and falling back to Cisco IOS.
+pub fn parse(text: &str) -> Vec<MacTableEntry> {
+    parse_as(text, Platform::detect(text).unwrap_or(Platform::Ios))
+}
+
+/// Parses the output of `show mac address-table` (or, on Junos,
+/// `show ethernet-switching table`) from the given platform.
+///
+/// The parser is tolerant of screen-scraped text: any line without
+/// a VLAN and a MAC address in its columns, such as a banner, a
+/// legend, a total, a prompt, or a pager's `--More--`, is skipped
+/// rather than rejected.
+pub fn parse_as(text: &str, platform: Platform) -> Vec<MacTableEntry> {
+    let mut entries = Vec::new();
+
+    for line in text.lines() {
+        // Pagers sometimes leave their prompt, and the backspaces
+        // that erased it, at the start of a line.
+        let line = line.replace('\u{8}', "");
+        let line = line.trim().trim_start_matches("--More--");
+        let columns: Vec<&str> = line.split_whitespace().collect();
+
+        // The address is the first column that parses as one, and the
+        // VLAN comes right before it.
+        let index = match columns
+            .iter()
+            .position(|column| column.len() >= 12 && MediaAccessControlAddress::new(column).is_ok())
+        {
+            Some(index) if index > 0 => index,
+            _ => continue,
+        };
+
+        let rest = &columns[index + 1..];
+
+        let (kind, interface) = match platform {
+            // Vlan, Mac Address, Type, [Learn, Age,] Ports.
+            Platform::Ios => match rest {
+                [kind, .., interface] => (type_column(kind), interface.to_string()),
+                _ => continue,
+            },
+            // VLAN, MAC Address, Type, age, Secure, NTFY, Ports, where
+            // the ports may hold spaces (for example, `vPC Peer-Link`).
+            Platform::NxOs => match rest {
+                [kind, _, _, _, interface @ ..] if !interface.is_empty() => {
+                    (type_column(kind), interface.join(" "))
+                }
+                _ => continue,
+            },
+            // Vlan, Mac Address, Type, Ports, Moves, Last Move.
+            Platform::Eos => match rest {
+                [kind, interface, ..] => (type_column(kind), interface.to_string()),
+                _ => continue,
+            },
+            // VLAN, MAC address, Type or flags, [Age,] Interface, and
+            // more, where the age is a number or `-`.
+            Platform::Junos => match rest {
+                [kind, more @ ..] => match more
+                    .iter()
+                    .find(|column| column.chars().any(|c| c.is_ascii_alphabetic()))
+                {
+                    Some(interface) => (junos_column(kind), interface.to_string()),
+                    None => continue,
+                },
+                _ => continue,
+            },
+        };
+
+        entries.push(MacTableEntry {
+            vlan: columns[index - 1].to_string(),
+            address: MediaAccessControlAddress::new(columns[index]).unwrap(),
+            interface,
+            kind,
+        });
+    }
+
+    entries
+}
@@ -0,0 +1,257 @@
+use super::error::Error;
+use super::format::Notation;
+use super::macaddress::MediaAccessControlAddress;
+use std::io::{BufRead, Write};
+
+/// Where the MAC addresses are in each row.
+#[derive(Clone, Debug, PartialEq, Eq)]
+enum Column {
+    Named(String),
+    At(usize),
+}
+
+/// `MacColumn` reads and rewrites the MAC address column of a CSV
+/// file, such as an asset inventory exported from a spreadsheet, in
+/// which the addresses may be in any mix of notations and casing.
+///
+/// The first row is a header.  Fields may be quoted, with doubled
+/// double quotes inside them, and quoted fields may span lines.
+/// Empty cells in the column are allowed and left empty.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct MacColumn {
+    column: Column,
+    notation: Notation,
+    delimiter: char,
+}
+
+impl MacColumn {
+    /// Instantiates `MacColumn` for the column whose header is
+    /// `name`, ignoring ASCII case and surrounding whitespace, which
+    /// writes addresses in colon notation and expects commas
+    /// between fields.
+    pub fn named(name: &str) -> Self {
+        Self {
+            column: Column::Named(name.trim().to_string()),
+            notation: Notation::Colon,
+            delimiter: ',',
+        }
+    }
+
+    /// Instantiates `MacColumn` for the column at `index`, counting
+    /// from 0, whatever its header.
+    pub fn at(index: usize) -> Self {
+        Self {
+            column: Column::At(index),
+            notation: Notation::Colon,
+            delimiter: ',',
+        }
+    }
+
+    /// Writes addresses in `notation` instead.
+    pub fn with_notation(mut self, notation: Notation) -> Self {
+        self.notation = notation;
+        self
+    }
+
+    /// Expects `delimiter` between fields instead, such as the `;`
+    /// that spreadsheets write in locales with decimal commas.
+    pub fn with_delimiter(mut self, delimiter: char) -> Self {
+        self.delimiter = delimiter;
+        self
+    }
+
+    /// Reads the addresses in the column, in order, skipping empty
+    /// cells.
+    ///
+    /// If a cell is not a MAC address, then the error names its
+    /// line.
+    pub fn read<R: BufRead>(&self, reader: R) -> Result<Vec<MediaAccessControlAddress>, Error> {
+        let mut addresses = Vec::new();
+
+        self.for_each_row(reader, |_, cell| {
+            if let Some(address) = cell {
+                addresses.push(address);
+            }
+            Ok(())
+        })?;
+
+        Ok(addresses)
+    }
+
+    /// Copies the CSV file from `reader` to `writer`, rewriting each
+    /// address in the column in the chosen notation and leaving
+    /// every other field as it is, and returns the number of
+    /// addresses.
+    ///
+    /// If a cell is not a MAC address, then the error names its
+    /// line, and the output is incomplete.
+    pub fn normalize<R: BufRead, W: Write>(
+        &self,
+        reader: R,
+        mut writer: W,
+    ) -> Result<usize, Error> {
+        let mut count = 0;
+
+        self.for_each_row(reader, |row, cell| {
+            if let Some(address) = cell {
+                row.fields[row.index] = address.to_notation(self.notation);
+                count += 1;
+            }
+
+            let fields: Vec<String> = row.fields.iter().map(|field| self.quote(field)).collect();
+            writer.write_all(fields.join(&self.delimiter.to_string()).as_bytes())?;
+            writer.write_all(row.ending.as_bytes())?;
+            Ok(())
+        })?;
+
+        writer.flush()?;
+        Ok(count)
+    }
+
+    /// Reads each row, finds the column in the header, and calls
+    /// `visit` with every row, including the header, and the address
+    /// in the column, if the row is not the header and the cell is
+    /// not empty.
+    fn for_each_row<R, F>(&self, mut reader: R, mut visit: F) -> Result<(), Error>
+    where
+        R: BufRead,
+        F: FnMut(&mut Row, Option<MediaAccessControlAddress>) -> Result<(), Error>,
+    {
+        let mut number = 0;
+        let mut index = None;
+
+        while let Some((mut row, lines)) = self.read_row(&mut reader)? {
+            number += 1;
+            let line = number;
+            number += lines - 1;
+
+            let column = match index {
+                Some(column) => column,
+                None => {
+                    let column = self.find(&row.fields)?;
+                    index = Some(column);
+                    row.index = column;
+                    visit(&mut row, None)?;
+                    continue;
+                }
+            };
+
+            row.index = column;
+            let cell = row.fields.get(column).map_or("", |cell| cell.trim());
+
+            if cell.is_empty() {
+                visit(&mut row, None)?;
+                continue;
+            }
+
+            let address = MediaAccessControlAddress::new(cell).map_err(|error| {
+                Error::Other(format!(
+                    "{} on line {}.",
+                    error.to_string().trim_end_matches('.'),
+                    line
+                ))
+            })?;
+
+            // A short row gets empty fields up to the column.
+            if row.fields.len() <= column {
+                row.fields.resize(column + 1, String::new());
+            }
+
+            visit(&mut row, Some(address))?;
+        }
+
+        Ok(())
+    }
+
+    /// Returns the index of the column, given the header.
+    fn find(&self, header: &[String]) -> Result<usize, Error> {
+        match &self.column {
+            Column::Named(name) => header
+                .iter()
+                .position(|field| field.trim().eq_ignore_ascii_case(name))
+                .ok_or_else(|| Error::Other(format!("Pass in a CSV file with a {} column.", name))),
+            Column::At(index) => Ok(*index),
+        }
+    }
+
+    /// Reads one row, which spans more than one line if a quoted
+    /// field holds a line break, and returns it with the number of
+    /// lines that it spans, or `None` at the end of the file.
+    fn read_row<R: BufRead>(&self, reader: &mut R) -> Result<Option<(Row, usize)>, Error> {
+        let mut text = String::new();
+        let mut lines = 0;
+
+        loop {
+            if reader.read_line(&mut text)? == 0 {
+                break;
+            }
+
+            lines += 1;
+
+            if text.matches('"').count().is_multiple_of(2) {
+                break;
+            }
+        }
+
+        if lines == 0 {
+            return Ok(None);
+        }
+
+        let ending = if text.ends_with("\r\n") {
+            "\r\n"
+        } else if text.ends_with('\n') {
+            "\n"
+        } else {
+            ""
+        };
+        text.truncate(text.len() - ending.len());
+
+        Ok(Some((
+            Row {
+                fields: self.split(&text),
+                index: 0,
+                ending,
+            },
+            lines,
+        )))
+    }
+
+    /// Splits a row into fields, honoring double quotes and doubled
+    /// double quotes within them.
+    fn split(&self, text: &str) -> Vec<String> {
+        let mut fields = vec![String::new()];
+        let mut quoted = false;
+        let mut characters = text.chars().peekable();
+
+        while let Some(character) = characters.next() {
+            match character {
+                '"' if quoted && characters.peek() == Some(&'"') => {
+                    characters.next();
+                    fields.last_mut().unwrap().push('"');
+                }
+                '"' => quoted = !quoted,
+                c if c == self.delimiter && !quoted => fields.push(String::new()),
+                c => fields.last_mut().unwrap().push(c),
+            }
+        }
+
+        fields
+    }
+
+    /// Quotes a field if it needs it.
+    fn quote(&self, field: &str) -> String {
+        if field.contains([self.delimiter, '"', '\n', '\r']) {
+            format!("\"{}\"", field.replace('"', "\"\""))
+        } else {
+            field.to_string()
+        }
+    }
+}
+
+/// One row of a CSV file, with the index of the address column and
+/// the line ending that followed it.
+struct Row {
+    fields: Vec<String>,
+    index: usize,
+    ending: &'static str,
+}
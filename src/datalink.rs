@@ -0,0 +1,114 @@
+use super::classification::{Cast, Kind};
+use super::eui64::Eui64;
+use super::macaddress::MediaAccessControlAddress;
+use super::utils::MacParseError;
+use std::fmt;
+
+/// Any of the link-layer addresses that an inventory might hold,
+/// so that they can be stored in one collection.
+///
+/// Every variant is an IEEE extended identifier underneath, so they
+/// share the same octet-level methods.  They differ in how they
+/// are conventionally displayed.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum DataLinkAddress {
+    /// A 48-bit MAC address, displayed in colon notation
+    /// (for example, `a0:b1:c2:d3:e4:f5`).
+    Mac48(MediaAccessControlAddress),
+    /// A 64-bit extended unique identifier, displayed in colon
+    /// notation (for example, `a0:b1:c2:ff:fe:d3:e4:f5`).
+    Eui64(Eui64),
+    /// A 64-bit InfiniBand node or port GUID, displayed the way
+    /// `ibv_devinfo` does (for example, `0002:c903:00a1:b2c3`).
+    InfinibandGuid(Eui64),
+    /// A 48-bit Bluetooth device address (`BD_ADDR`), displayed
+    /// the way BlueZ does (for example, `A0:B1:C2:D3:E4:F5`).
+    Bluetooth(MediaAccessControlAddress),
+}
+
+impl DataLinkAddress {
+    /// Returns the address's octets, with the most-significant
+    /// octet first.
+    ///
+    /// Bluetooth sends `BD_ADDR` least-significant octet first,
+    /// so reverse these octets before writing them to an HCI
+    /// packet.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        match self {
+            DataLinkAddress::Mac48(mac) | DataLinkAddress::Bluetooth(mac) => {
+                mac.to_octets().to_vec()
+            }
+            DataLinkAddress::Eui64(eui) | DataLinkAddress::InfinibandGuid(eui) => {
+                eui.to_octets().to_vec()
+            }
+        }
+    }
+
+    /// Returns the address's kind (see `Identifier::kind`).
+    pub fn kind(&self) -> Kind {
+        match self {
+            DataLinkAddress::Mac48(mac) | DataLinkAddress::Bluetooth(mac) => mac.kind(),
+            DataLinkAddress::Eui64(eui) | DataLinkAddress::InfinibandGuid(eui) => eui.kind(),
+        }
+    }
+
+    /// Returns whether the address is a unicast, multicast, or
+    /// broadcast address (see `Identifier::cast`).
+    pub fn cast(&self) -> Cast {
+        match self {
+            DataLinkAddress::Mac48(mac) | DataLinkAddress::Bluetooth(mac) => mac.cast(),
+            DataLinkAddress::Eui64(eui) | DataLinkAddress::InfinibandGuid(eui) => eui.cast(),
+        }
+    }
+}
+
+/// Parses either a MAC address or an EUI-64, depending on how many
+/// hexadecimal digits `digits` holds: more than 12 makes it an
+/// EUI-64 (`DataLinkAddress::Eui64`), and otherwise it is a MAC
+/// address (`DataLinkAddress::Mac48`), so exports that mix the two
+/// need not be sorted by length first.
+///
+/// Each is parsed the way its `new` method parses it, so a MAC
+/// address may be in Solaris notation, too.  If `digits` is in
+/// neither, then the error is the one for the width it came closest
+/// to.
+pub fn parse_any(digits: &str) -> Result<DataLinkAddress, MacParseError> {
+    let count = digits.chars().filter(char::is_ascii_hexdigit).count();
+
+    if count > 12 {
+        Ok(DataLinkAddress::Eui64(Eui64::new(digits)?))
+    } else {
+        Ok(DataLinkAddress::Mac48(MediaAccessControlAddress::new(
+            digits,
+        )?))
+    }
+}
+
+impl fmt::Display for DataLinkAddress {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            DataLinkAddress::Mac48(mac) => f.pad(&mac.to_colon_notation()),
+            DataLinkAddress::Eui64(eui) => f.pad(&eui.to_colon_notation()),
+            DataLinkAddress::InfinibandGuid(guid) => {
+                let digits = guid.to_plain_notation();
+                let groups: Vec<&str> = (0..4)
+                    .map(|index| &digits[index * 4..index * 4 + 4])
+                    .collect();
+                f.pad(&groups.join(":"))
+            }
+            DataLinkAddress::Bluetooth(mac) => f.pad(&mac.to_colon_notation().to_uppercase()),
+        }
+    }
+}
+
+impl From<MediaAccessControlAddress> for DataLinkAddress {
+    fn from(mac: MediaAccessControlAddress) -> Self {
+        DataLinkAddress::Mac48(mac)
+    }
+}
+
+impl From<Eui64> for DataLinkAddress {
+    fn from(eui: Eui64) -> Self {
+        DataLinkAddress::Eui64(eui)
+    }
+}
@@ -0,0 +1,270 @@
+use super::error::Error;
+use super::macaddress::MediaAccessControlAddress;
+use std::net::IpAddr;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+/// One lease from a DHCP server's lease file: the MAC address that
+/// holds it, the IP address, and, if known, the client's hostname
+/// and when the lease expires.
+#[derive(Debug)]
+pub struct Lease {
+    /// The MAC address of the client.
+    pub address: MediaAccessControlAddress,
+    /// The IP address that the client leased.
+    pub ip: IpAddr,
+    /// The hostname that the client sent, if any.
+    pub hostname: Option<String>,
+    /// When the lease expires, or `None` if it never does.
+    pub expiry: Option<SystemTime>,
+}
+
+/// Parses a dnsmasq lease file (for example,
+/// `/var/lib/misc/dnsmasq.leases`).
+///
+/// Each line holds an expiry in seconds from the UNIX epoch (0 for
+/// an infinite lease), a MAC address, an IP address, a hostname (`*`
+/// if the client sent none), and a client ID.  DHCPv6 leases, which
+/// are keyed by DUID and IAID instead of a MAC address, are
+/// skipped.
+pub fn parse_dnsmasq(text: &str) -> Result<Vec<Lease>, Error> {
+    let mut leases = Vec::new();
+
+    for line in text.lines() {
+        let columns: Vec<&str> = line.split_whitespace().collect();
+
+        if columns.is_empty() || columns[0] == "duid" {
+            continue;
+        }
+
+        if columns.len() < 4 {
+            return Err(Error::Invalid(format!(
+                "Pass in dnsmasq leases with an expiry, a MAC address, an IP address, and a hostname (not {}).",
+                line.trim()
+            )));
+        }
+
+        let address = match MediaAccessControlAddress::new(columns[1]) {
+            Ok(address) => address,
+            Err(_) if columns[2].contains(':') => continue,
+            Err(error) => return Err(error.into()),
+        };
+
+        let seconds: u64 = columns[0].parse().map_err(|_| {
+            Error::Invalid(format!(
+                "Pass in dnsmasq leases with expiries in seconds (not {}).",
+                columns[0]
+            ))
+        })?;
+
+        leases.push(Lease {
+            address,
+            ip: parse_ip(columns[2])?,
+            hostname: Some(columns[3])
+                .filter(|hostname| *hostname != "*")
+                .map(str::to_string),
+            expiry: Some(seconds)
+                .filter(|seconds| *seconds != 0)
+                .map(|seconds| UNIX_EPOCH + Duration::from_secs(seconds)),
+        });
+    }
+
+    Ok(leases)
+}
+
+/// Parses an ISC dhcpd lease file (for example,
+/// `/var/lib/dhcp/dhcpd.leases`).
+///
+/// dhcpd appends a new `lease` block each time that a lease changes,
+/// so only the last block for each IP address is kept, in the order
+/// that the addresses first appear.  Blocks without a `hardware
+/// ethernet` statement, and DHCPv6 blocks, are skipped.  Times may
+/// be in dhcpd's default UTC format or in its `epoch` format.
+pub fn parse_dhcpd(text: &str) -> Result<Vec<Lease>, Error> {
+    let tokens = tokenize(text)?;
+    let mut leases: Vec<Lease> = Vec::new();
+    let mut depth = 0;
+    let mut index = 0;
+
+    while index < tokens.len() {
+        let token = tokens[index].as_str();
+        index += 1;
+
+        match token {
+            "{" => depth += 1,
+            "}" => depth -= 1,
+            "lease" if depth == 0 && tokens.get(index + 1).map(String::as_str) == Some("{") => {
+                let ip = parse_ip(&tokens[index])?;
+                let end = block_end(&tokens, index + 2)?;
+                let lease = parse_block(&tokens[index + 2..end], ip)?;
+                index = end + 1;
+
+                if let Some(lease) = lease {
+                    match leases.iter_mut().find(|earlier| earlier.ip == ip) {
+                        Some(earlier) => *earlier = lease,
+                        None => leases.push(lease),
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+
+    Ok(leases)
+}
+
+/// Parses an IP address from a lease file.
+fn parse_ip(ip: &str) -> Result<IpAddr, Error> {
+    ip.parse().map_err(|_| {
+        Error::Invalid(format!(
+            "Pass in leases with valid IP addresses (not {}).",
+            ip
+        ))
+    })
+}
+
+/// Splits a dhcpd lease file into words, quoted strings (without
+/// their quotes), and the punctuation `{`, `}`, and `;`, dropping
+/// comments.
+fn tokenize(text: &str) -> Result<Vec<String>, Error> {
+    let mut tokens = Vec::new();
+    let mut chars = text.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '#' => {
+                for c in chars.by_ref() {
+                    if c == '\n' {
+                        break;
+                    }
+                }
+            }
+            '"' => {
+                let mut token = String::new();
+
+                loop {
+                    match chars.next() {
+                        Some('"') => break,
+                        Some('\\') => token.extend(chars.next()),
+                        Some(c) => token.push(c),
+                        None => {
+                            return Err(Error::Invalid(String::from(
+                                "Pass in leases with closed quotes.",
+                            )))
+                        }
+                    }
+                }
+
+                tokens.push(token);
+            }
+            '{' | '}' | ';' => tokens.push(c.to_string()),
+            c if c.is_whitespace() => {}
+            c => {
+                let mut token = c.to_string();
+
+                while let Some(&c) = chars.peek() {
+                    if c.is_whitespace() || matches!(c, '{' | '}' | ';' | '"' | '#') {
+                        break;
+                    }
+
+                    token.push(c);
+                    chars.next();
+                }
+
+                tokens.push(token);
+            }
+        }
+    }
+
+    Ok(tokens)
+}
+
+/// Returns the index of the `}` that closes the block starting at
+/// `start`.
+fn block_end(tokens: &[String], start: usize) -> Result<usize, Error> {
+    let mut depth = 0;
+
+    for (index, token) in tokens.iter().enumerate().skip(start) {
+        match token.as_str() {
+            "{" => depth += 1,
+            "}" if depth == 0 => return Ok(index),
+            "}" => depth -= 1,
+            _ => {}
+        }
+    }
+
+    Err(Error::Invalid(String::from(
+        "Pass in leases with closed braces.",
+    )))
+}
+
+/// Reads the statements in one `lease` block, or returns `None` if
+/// it has no Ethernet address.
+fn parse_block(tokens: &[String], ip: IpAddr) -> Result<Option<Lease>, Error> {
+    let mut address = None;
+    let mut hostname = None;
+    let mut expiry = None;
+
+    for statement in tokens.split(|token| token == ";") {
+        let words: Vec<&str> = statement.iter().map(String::as_str).collect();
+
+        match words.as_slice() {
+            ["hardware", "ethernet", mac] => address = Some(MediaAccessControlAddress::new(mac)?),
+            ["client-hostname", name] => hostname = Some(name.to_string()),
+            ["ends", "never"] => expiry = None,
+            ["ends", "epoch", seconds] => {
+                expiry = Some(UNIX_EPOCH + Duration::from_secs(parse_number(seconds)?))
+            }
+            ["ends", _, date, time] => expiry = Some(parse_time(date, time)?),
+            _ => {}
+        }
+    }
+
+    Ok(address.map(|address| Lease {
+        address,
+        ip,
+        hostname,
+        expiry,
+    }))
+}
+
+/// Parses a number from a lease file.
+fn parse_number(number: &str) -> Result<u64, Error> {
+    number
+        .parse()
+        .map_err(|_| Error::Invalid(format!("Pass in leases with valid times (not {}).", number)))
+}
+
+/// Parses a UTC time in dhcpd's `yyyy/mm/dd hh:mm:ss` format.
+fn parse_time(date: &str, time: &str) -> Result<SystemTime, Error> {
+    let fields: Vec<u64> = date
+        .split('/')
+        .chain(time.split(':'))
+        .map(parse_number)
+        .collect::<Result<_, _>>()?;
+
+    let (year, month, day, hour, minute, second) = match fields.as_slice() {
+        [year, month @ 1..=12, day @ 1..=31, hour @ 0..=23, minute @ 0..=59, second @ 0..=60]
+            if *year >= 1970 =>
+        {
+            (*year, *month, *day, *hour, *minute, *second)
+        }
+        _ => {
+            return Err(Error::Invalid(format!(
+                "Pass in leases with times such as 2024/01/31 23:59:59 (not {} {}).",
+                date, time
+            )))
+        }
+    };
+
+    // Count the days from 1970/01/01 with years that start in March,
+    // so that leap days fall at their end.
+    let (year, month) = if month <= 2 {
+        (year - 1, month + 9)
+    } else {
+        (year, month - 3)
+    };
+    let days =
+        year * 365 + year / 4 - year / 100 + year / 400 + (153 * month + 2) / 5 + day - 1 - 719_468;
+
+    Ok(UNIX_EPOCH + Duration::from_secs(days * 86_400 + hour * 3_600 + minute * 60 + second))
+}
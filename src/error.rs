@@ -0,0 +1,102 @@
+use super::utils::MacParseError;
+use std::fmt;
+use std::io;
+
+/// Any failure from this crate, so that applications can propagate
+/// all of them with `?`.
+///
+/// Besides the address parsers, which return `MacParseError` or
+/// `ParseError`, every fallible function in the crate returns
+/// `Error`, with the variant that names the failure.  A `String` of your own converts
+/// into `Error::Other`.
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum Error {
+    /// A string was not an address in any supported notation.
+    Parse(MacParseError),
+    /// An argument was out of range, or input besides an address,
+    /// such as a prefix, a policy, a frame, or a capture file, was
+    /// malformed.
+    Invalid(String),
+    /// A file could not be read or written.
+    Io(io::Error),
+    /// A vendor registry or database could not be read, named a
+    /// registry that does not exist, or was malformed.
+    Registry(String),
+    /// A request over the network failed.
+    Network(String),
+    /// The operating system could not be queried, or does not
+    /// support the query.
+    System(String),
+    /// A persistent store could not be read or written.
+    Storage(String),
+    /// Anything else, such as an application's own message.
+    Other(String),
+}
+
+/// Clones the error, so that one failure can be reported for many
+/// addresses, as when a batch lookup fails.  An I/O error is cloned
+/// as a new one with the same kind and message.
+impl Clone for Error {
+    fn clone(&self) -> Self {
+        match self {
+            Error::Parse(error) => Error::Parse(*error),
+            Error::Io(error) => Error::Io(io::Error::new(error.kind(), error.to_string())),
+            Error::Invalid(message) => Error::Invalid(message.clone()),
+            Error::Registry(message) => Error::Registry(message.clone()),
+            Error::Network(message) => Error::Network(message.clone()),
+            Error::System(message) => Error::System(message.clone()),
+            Error::Storage(message) => Error::Storage(message.clone()),
+            Error::Other(message) => Error::Other(message.clone()),
+        }
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Error::Parse(error) => write!(f, "{}", error),
+            Error::Io(error) => write!(f, "{}", error),
+            Error::Invalid(message)
+            | Error::Registry(message)
+            | Error::Network(message)
+            | Error::System(message)
+            | Error::Storage(message)
+            | Error::Other(message) => write!(f, "{}", message),
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Error::Parse(error) => Some(error),
+            Error::Io(error) => Some(error),
+            _ => None,
+        }
+    }
+}
+
+impl From<MacParseError> for Error {
+    fn from(error: MacParseError) -> Self {
+        Error::Parse(error)
+    }
+}
+
+impl From<io::Error> for Error {
+    fn from(error: io::Error) -> Self {
+        Error::Io(error)
+    }
+}
+
+impl From<String> for Error {
+    fn from(message: String) -> Self {
+        Error::Other(message)
+    }
+}
+
+impl From<Error> for String {
+    fn from(error: Error) -> Self {
+        error.to_string()
+    }
+}
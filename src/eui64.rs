@@ -0,0 +1,73 @@
+use super::identifier::Identifier;
+use super::utils::{self, MacParseError, ParseError};
+use std::convert::TryFrom;
+use std::str::FromStr;
+
+/// `Eui64` makes it easy to work with 64-bit extended unique
+/// identifiers (EUI-64), such as those that IEEE 802.15.4 radios,
+/// FireWire devices, and modified EUI-64 IPv6 interface
+/// identifiers use.
+///
+/// `Eui64` is the 64-bit `Identifier`, so the methods that
+/// every width shares are documented there.
+pub type Eui64 = Identifier<8>;
+
+impl Identifier<8> {
+    /// Instantiates `Eui64` with 16 hexadecimal digits
+    /// (`0-9`, `A-F`, or `a-f`) in plain, hyphen, colon, dot,
+    /// four-digit-hyphen, or space notation
+    /// (for example, `a0-b1-c2-ff-fe-d3-e4-f5`).
+    ///
+    /// If `digits` is not in any of these notations, then the
+    /// error says why, with the byte offset of any offending
+    /// character.  `utils::diagnose_eui64` explains it in more
+    /// detail.
+    ///
+    /// `digits` may be a `&str`, a `String`, a `Cow<str>`, or
+    /// anything else that borrows as a `str`.
+    pub fn new<S: AsRef<str>>(digits: S) -> Result<Self, MacParseError> {
+        let digits = digits.as_ref();
+        utils::inspect(digits, 8).map_err(|error| error.kind())?;
+        Ok(Self {
+            octets: utils::to_octets(digits),
+        })
+    }
+
+    /// Instantiates `Eui64` the way `new` does, but explains a
+    /// failure for a person, the way `utils::diagnose_eui64` does.
+    pub fn new_with_diagnostics<S: AsRef<str>>(digits: S) -> Result<Self, ParseError> {
+        let digits = digits.as_ref();
+        utils::diagnose_eui64(digits)?;
+        Ok(Self {
+            octets: utils::to_octets(digits),
+        })
+    }
+}
+
+/// Instantiates `Eui64` the same way `new` does.
+impl TryFrom<&str> for Identifier<8> {
+    type Error = MacParseError;
+
+    fn try_from(digits: &str) -> Result<Self, Self::Error> {
+        Self::new(digits)
+    }
+}
+
+/// Instantiates `Eui64` the same way `new` does.
+impl TryFrom<String> for Identifier<8> {
+    type Error = MacParseError;
+
+    fn try_from(digits: String) -> Result<Self, Self::Error> {
+        Self::new(&digits)
+    }
+}
+
+/// Parses `Eui64` the same way `new` does, so that `str::parse`
+/// and generic parsing code accept it.
+impl FromStr for Identifier<8> {
+    type Err = MacParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::new(s)
+    }
+}
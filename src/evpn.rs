@@ -0,0 +1,127 @@
+use super::error::Error;
+use super::macaddress::MediaAccessControlAddress;
+use std::fmt;
+use std::str::FromStr;
+
+/// `EthernetSegmentId` is a 10-octet EVPN Ethernet Segment
+/// Identifier (ESI), as defined by RFC 7432.
+///
+/// The first octet is the ESI type.  For type 3 (MAC-based),
+/// the next six octets are the system MAC address and the last
+/// three are a local discriminator.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct EthernetSegmentId {
+    bytes: [u8; 10],
+}
+
+impl EthernetSegmentId {
+    /// Instantiates a type 3 (MAC-based) `EthernetSegmentId`
+    /// with a system MAC address and a 24-bit local discriminator.
+    pub fn type3(mac: &MediaAccessControlAddress, local_discriminator: u32) -> Result<Self, Error> {
+        if local_discriminator > 0x00ff_ffff {
+            return Err(Error::Invalid(String::from(
+                "Pass in a 24-bit local discriminator.",
+            )));
+        }
+
+        let mut bytes = [0; 10];
+        bytes[0] = 0x03;
+        bytes[1..7].copy_from_slice(&mac.to_octets());
+        bytes[7..].copy_from_slice(&local_discriminator.to_be_bytes()[1..]);
+
+        Ok(Self { bytes })
+    }
+
+    /// Instantiates `EthernetSegmentId` with its 10 octets,
+    /// whatever its type.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, Error> {
+        if bytes.len() == 10 {
+            let mut esi = Self { bytes: [0; 10] };
+            esi.bytes.copy_from_slice(bytes);
+            Ok(esi)
+        } else {
+            Err(Error::Invalid(String::from("Pass in 10 octets.")))
+        }
+    }
+
+    /// Returns the ESI type (the first octet).
+    pub fn esi_type(&self) -> u8 {
+        self.bytes[0]
+    }
+
+    /// Returns the system MAC address of a type 3 ESI, or `None`
+    /// for other types.
+    pub fn mac(&self) -> Option<MediaAccessControlAddress> {
+        if self.esi_type() == 0x03 {
+            MediaAccessControlAddress::from_octets(&self.bytes[1..7]).ok()
+        } else {
+            None
+        }
+    }
+
+    /// Returns the local discriminator of a type 3 ESI, or `None`
+    /// for other types.
+    pub fn local_discriminator(&self) -> Option<u32> {
+        if self.esi_type() == 0x03 {
+            Some(u32::from_be_bytes([
+                0,
+                self.bytes[7],
+                self.bytes[8],
+                self.bytes[9],
+            ]))
+        } else {
+            None
+        }
+    }
+
+    /// Returns the 10 octets.
+    pub fn to_bytes(&self) -> [u8; 10] {
+        self.bytes
+    }
+}
+
+/// Parses 10 colon-separated pairs of hexadecimal digits (for
+/// example, `03:a0:b1:c2:d3:e4:f5:00:00:01`).
+impl FromStr for EthernetSegmentId {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let groups: Vec<&str> = s.trim().split(':').collect();
+
+        // `from_str_radix` would accept a sign, as in `+1`.
+        if groups.len() != 10
+            || groups.iter().any(|group| {
+                group.len() != 2 || !group.bytes().all(|digit| digit.is_ascii_hexdigit())
+            })
+        {
+            return Err(Error::Invalid(String::from(
+                "Pass in 10 colon-separated pairs of hexadecimal digits.",
+            )));
+        }
+
+        let mut bytes = [0; 10];
+
+        for (byte, group) in bytes.iter_mut().zip(groups.iter()) {
+            *byte = u8::from_str_radix(group, 16).map_err(|_| {
+                Error::Invalid(String::from(
+                    "Pass in 10 colon-separated pairs of hexadecimal digits.",
+                ))
+            })?;
+        }
+
+        Ok(Self { bytes })
+    }
+}
+
+/// Formats the ESI as 10 colon-separated pairs of hexadecimal
+/// digits (for example, `03:a0:b1:c2:d3:e4:f5:00:00:01`).
+impl fmt::Display for EthernetSegmentId {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let groups: Vec<String> = self
+            .bytes
+            .iter()
+            .map(|byte| format!("{:02x}", byte))
+            .collect();
+        write!(f, "{}", groups.join(":"))
+    }
+}
@@ -0,0 +1,211 @@
+use super::error::Error;
+use super::format::Notation;
+use super::macaddress::MediaAccessControlAddress;
+use super::vendor::VendorResolver;
+use std::borrow::Borrow;
+use std::io::Write;
+
+/// The columns that `Exporter` writes, in order.
+pub const COLUMNS: [&str; 7] = ["address", "oui", "kind", "cast", "uaa", "laa", "vendor"];
+
+/// The encodings that `Exporter` can write.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Encoding {
+    /// Comma-separated values with a header row, for spreadsheets.
+    Csv,
+    /// One JSON array of objects.
+    Json,
+    /// One JSON object per line, as most SIEMs ingest.
+    JsonLines,
+    /// One YAML sequence of mappings, as Ansible inventories and
+    /// other automation tools read.
+    Yaml,
+    /// One TOML array of tables named `addresses`, with each
+    /// unknown vendor left out, since TOML has no null.
+    Toml,
+}
+
+/// Writes an inventory of MAC addresses, such as the addresses
+/// seen in a capture, one row per address, with the address's
+/// classification and vendor alongside it.
+#[derive(Debug)]
+pub struct Exporter<R> {
+    resolver: R,
+    encoding: Encoding,
+    notation: Notation,
+}
+
+impl<R: VendorResolver> Exporter<R> {
+    /// Instantiates `Exporter`, which looks vendors up with
+    /// `resolver` and writes addresses in colon notation.
+    pub fn new(resolver: R, encoding: Encoding) -> Self {
+        Self {
+            resolver,
+            encoding,
+            notation: Notation::Colon,
+        }
+    }
+
+    /// Writes addresses in `notation` instead.
+    pub fn with_notation(mut self, notation: Notation) -> Self {
+        self.notation = notation;
+        self
+    }
+
+    /// Writes one row per address into `writer` and returns the
+    /// number of rows.
+    ///
+    /// If the resolver fails for any address, then the error is
+    /// returned and the output is incomplete.
+    pub fn write<W, I>(&self, addresses: I, mut writer: W) -> Result<usize, Error>
+    where
+        W: Write,
+        I: IntoIterator,
+        I::Item: Borrow<MediaAccessControlAddress>,
+    {
+        let mut rows = 0;
+
+        match self.encoding {
+            Encoding::Csv => writeln!(writer, "{}", COLUMNS.join(",")),
+            Encoding::Json => write!(writer, "["),
+            Encoding::Yaml | Encoding::Toml | Encoding::JsonLines => Ok(()),
+        }?;
+
+        for mac in addresses {
+            let mac = mac.borrow();
+            let vendor = self.resolver.resolve(mac)?;
+            let values = [
+                Value::Text(mac.to_notation(self.notation)),
+                Value::Text(mac.to_fragments().0.to_string()),
+                Value::Text(mac.kind().to_string()),
+                Value::Text(mac.cast().to_string()),
+                Value::Flag(mac.is_uaa()),
+                Value::Flag(mac.is_laa()),
+                vendor.map_or(Value::Missing, Value::Text),
+            ];
+
+            let row = match self.encoding {
+                Encoding::Csv => to_csv(&values),
+                Encoding::Json if rows > 0 => format!(",\n{}", to_json(&values)),
+                Encoding::Json => format!("\n{}", to_json(&values)),
+                Encoding::JsonLines => format!("{}\n", to_json(&values)),
+                Encoding::Yaml => to_yaml(&values),
+                Encoding::Toml if rows > 0 => format!("\n{}", to_toml(&values)),
+                Encoding::Toml => to_toml(&values),
+            };
+
+            writer.write_all(row.as_bytes())?;
+            rows += 1;
+        }
+
+        if self.encoding == Encoding::Json {
+            writeln!(writer, "{}]", if rows > 0 { "\n" } else { "" })?;
+        }
+
+        // An empty YAML document would be null, not an empty list.
+        if self.encoding == Encoding::Yaml && rows == 0 {
+            writeln!(writer, "[]")?;
+        }
+
+        writer.flush()?;
+        Ok(rows)
+    }
+}
+
+/// One cell of a row.
+enum Value {
+    Text(String),
+    Flag(bool),
+    Missing,
+}
+
+/// Formats a row as CSV, quoting cells that need it.
+fn to_csv(values: &[Value]) -> String {
+    let cells: Vec<String> = values
+        .iter()
+        .map(|value| match value {
+            Value::Text(text) if text.contains([',', '"', '\n', '\r']) => {
+                format!("\"{}\"", text.replace('"', "\"\""))
+            }
+            Value::Text(text) => text.to_string(),
+            Value::Flag(flag) => flag.to_string(),
+            Value::Missing => String::new(),
+        })
+        .collect();
+
+    format!("{}\n", cells.join(","))
+}
+
+/// Formats a row as a JSON object keyed by `COLUMNS`.
+fn to_json(values: &[Value]) -> String {
+    let fields: Vec<String> = COLUMNS
+        .iter()
+        .zip(values.iter())
+        .map(|(column, value)| {
+            let value = match value {
+                Value::Text(text) => escape_json(text),
+                Value::Flag(flag) => flag.to_string(),
+                Value::Missing => String::from("null"),
+            };
+            format!("\"{}\":{}", column, value)
+        })
+        .collect();
+
+    format!("{{{}}}", fields.join(","))
+}
+
+/// Formats a row as one item of a YAML sequence, keyed by
+/// `COLUMNS`.
+fn to_yaml(values: &[Value]) -> String {
+    let mut item = String::new();
+
+    for (index, (column, value)) in COLUMNS.iter().zip(values.iter()).enumerate() {
+        let value = match value {
+            Value::Text(text) => escape_json(text),
+            Value::Flag(flag) => flag.to_string(),
+            Value::Missing => String::from("null"),
+        };
+        let indent = if index == 0 { "- " } else { "  " };
+        item.push_str(&format!("{}{}: {}\n", indent, column, value));
+    }
+
+    item
+}
+
+/// Formats a row as one table of a TOML array named `addresses`,
+/// keyed by `COLUMNS`.
+fn to_toml(values: &[Value]) -> String {
+    let mut table = String::from("[[addresses]]\n");
+
+    for (column, value) in COLUMNS.iter().zip(values.iter()) {
+        match value {
+            Value::Text(text) => table.push_str(&format!("{} = {}\n", column, escape_json(text))),
+            Value::Flag(flag) => table.push_str(&format!("{} = {}\n", column, flag)),
+            Value::Missing => {}
+        }
+    }
+
+    table
+}
+
+/// Quotes a string for JSON.  The result is also a valid YAML
+/// double-quoted scalar and TOML basic string, since it escapes
+/// every control character, including DEL.
+pub fn escape_json(text: &str) -> String {
+    let mut escaped = String::from("\"");
+
+    for character in text.chars() {
+        match character {
+            '"' => escaped.push_str("\\\""),
+            '\\' => escaped.push_str("\\\\"),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => escaped.push_str("\\r"),
+            '\t' => escaped.push_str("\\t"),
+            c if c.is_ascii_control() => escaped.push_str(&format!("\\u{:04x}", c as u32)),
+            c => escaped.push(c),
+        }
+    }
+
+    escaped.push('"');
+    escaped
+}
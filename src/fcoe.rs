@@ -0,0 +1,81 @@
+use super::error::Error;
+use super::macaddress::MediaAccessControlAddress;
+
+/// `Fpma` is a Fibre Channel over Ethernet (FCoE) Fabric-Provided
+/// MAC Address, which a fabric assigns to an ENode's virtual port:
+/// a 24-bit FC-MAP, which identifies the fabric, followed by the
+/// 24-bit FC_ID that the fabric gave the port at login (FC-BB-5).
+///
+/// FC-BB-5 confines FC-MAPs to `0e-fc-00` through `0e-fc-ff`, so
+/// every FPMA is a locally-administered unicast address.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Fpma {
+    fc_map: u32,
+    fc_id: u32,
+}
+
+impl Fpma {
+    /// The FC-MAP that fabrics use unless they are configured
+    /// otherwise (`0e-fc-00`).
+    pub const DEFAULT_FC_MAP: u32 = 0x0e_fc_00;
+
+    /// Instantiates `Fpma` with an FC-MAP (between `0x0efc00` and
+    /// `0x0efcff`) and an FC_ID (24 bits).
+    pub fn new(fc_map: u32, fc_id: u32) -> Result<Self, Error> {
+        if fc_map & 0xff_ff_ff_00 != Self::DEFAULT_FC_MAP {
+            return Err(Error::Invalid(String::from(
+                "Pass in an FC-MAP between 0x0efc00 and 0x0efcff.",
+            )));
+        }
+
+        if fc_id > 0xff_ff_ff {
+            return Err(Error::Invalid(String::from("Pass in a 24-bit FC_ID.")));
+        }
+
+        Ok(Self { fc_map, fc_id })
+    }
+
+    /// Instantiates `Fpma` with the default FC-MAP and an FC_ID
+    /// (24 bits).
+    pub fn with_default_fc_map(fc_id: u32) -> Result<Self, Error> {
+        Self::new(Self::DEFAULT_FC_MAP, fc_id)
+    }
+
+    /// Instantiates `Fpma` by splitting a MAC address into its
+    /// FC-MAP and FC_ID.
+    pub fn from_address(address: &MediaAccessControlAddress) -> Result<Self, Error> {
+        let octets = address.to_octets();
+        let fc_map = u32::from_be_bytes([0, octets[0], octets[1], octets[2]]);
+        let fc_id = u32::from_be_bytes([0, octets[3], octets[4], octets[5]]);
+
+        Self::new(fc_map, fc_id).map_err(|_| {
+            Error::Invalid(String::from(
+                "Pass in a MAC address that starts with 0e-fc.",
+            ))
+        })
+    }
+
+    /// Returns the FC-MAP.
+    pub fn fc_map(&self) -> u32 {
+        self.fc_map
+    }
+
+    /// Returns the FC_ID.
+    pub fn fc_id(&self) -> u32 {
+        self.fc_id
+    }
+
+    /// Returns the FC_ID's domain, area, and port, in that order.
+    pub fn fc_id_parts(&self) -> (u8, u8, u8) {
+        let [_, domain, area, port] = self.fc_id.to_be_bytes();
+        (domain, area, port)
+    }
+
+    /// Returns the MAC address.
+    pub fn address(&self) -> MediaAccessControlAddress {
+        let mut octets = [0; 6];
+        octets[..3].copy_from_slice(&self.fc_map.to_be_bytes()[1..]);
+        octets[3..].copy_from_slice(&self.fc_id.to_be_bytes()[1..]);
+        MediaAccessControlAddress::from(octets)
+    }
+}
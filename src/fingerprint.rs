@@ -0,0 +1,239 @@
+use super::classification::{Cast, Kind};
+use super::error::Error;
+use super::macaddress::MediaAccessControlAddress;
+use super::vendor::VendorResolver;
+use std::fmt;
+use std::str::FromStr;
+
+/// The prefixes (as their leading octets) that hypervisors and
+/// container runtimes assign to virtual interfaces, with the
+/// platform behind each.
+const VIRTUAL_PREFIXES: [(&[u8], &str); 13] = [
+    (&[0x00, 0x05, 0x69], "VMware"),
+    (&[0x00, 0x0c, 0x29], "VMware"),
+    (&[0x00, 0x1c, 0x14], "VMware"),
+    (&[0x00, 0x50, 0x56], "VMware"),
+    (&[0x00, 0x15, 0x5d], "Hyper-V"),
+    (&[0x00, 0x03, 0xff], "Virtual PC"),
+    (&[0x08, 0x00, 0x27], "VirtualBox"),
+    (&[0x0a, 0x00, 0x27], "VirtualBox"),
+    (&[0x00, 0x16, 0x3e], "Xen"),
+    (&[0x52, 0x54, 0x00], "QEMU/KVM"),
+    (&[0x00, 0x1a, 0x4a], "oVirt"),
+    (&[0x00, 0x1c, 0x42], "Parallels"),
+    (&[0x02, 0x42], "Docker"),
+];
+
+/// Where a unicast address most likely came from.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Origin {
+    /// Burned into a physical interface by its manufacturer
+    /// (a universally-administered address).
+    Physical,
+    /// Assigned by a hypervisor or container runtime to a virtual
+    /// interface.
+    Virtual,
+    /// Made up by the device, as phones and laptops do to avoid
+    /// being tracked (a locally-administered address that no known
+    /// platform claims).
+    Randomized,
+}
+
+impl Origin {
+    /// Returns the origin's stable name (`physical`, `virtual`, or
+    /// `randomized`).
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Origin::Physical => "physical",
+            Origin::Virtual => "virtual",
+            Origin::Randomized => "randomized",
+        }
+    }
+}
+
+impl fmt::Display for Origin {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.pad(self.as_str())
+    }
+}
+
+/// Parses an origin from its stable name, ignoring ASCII case.
+impl FromStr for Origin {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        [Origin::Physical, Origin::Virtual, Origin::Randomized]
+            .iter()
+            .find(|origin| origin.as_str().eq_ignore_ascii_case(s.trim()))
+            .copied()
+            .ok_or_else(|| {
+                Error::Invalid(String::from("Pass in physical, virtual, or randomized."))
+            })
+    }
+}
+
+/// The well-known protocol that an address is reserved for.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Role {
+    /// The broadcast address (`ff-ff-ff-ff-ff-ff`).
+    Broadcast,
+    /// One of the IEEE 1588 Precision Time Protocol addresses.
+    Ptp,
+    /// One of the IEEE 802.1 bridge-filtered addresses, which carry
+    /// link-local control traffic such as STP, LACP, and LLDP.
+    LinkLocalControl,
+    /// A Virtual Router Redundancy Protocol (VRRP) address.
+    Vrrp,
+    /// An address that maps an IPv4 multicast group.
+    Ipv4Multicast,
+    /// An address that maps an IPv6 multicast group.
+    Ipv6Multicast,
+    /// An address that maps an MPLS multicast label.
+    MplsMulticast,
+    /// One of the RFC 7042 documentation addresses, which should
+    /// never appear on a real network.
+    Documentation,
+}
+
+impl Role {
+    /// Returns the role's stable name (for example, `ptp` or
+    /// `ipv4-multicast`).
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Role::Broadcast => "broadcast",
+            Role::Ptp => "ptp",
+            Role::LinkLocalControl => "link-local-control",
+            Role::Vrrp => "vrrp",
+            Role::Ipv4Multicast => "ipv4-multicast",
+            Role::Ipv6Multicast => "ipv6-multicast",
+            Role::MplsMulticast => "mpls-multicast",
+            Role::Documentation => "documentation",
+        }
+    }
+}
+
+impl fmt::Display for Role {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.pad(self.as_str())
+    }
+}
+
+/// Parses a role from its stable name, ignoring ASCII case.
+impl FromStr for Role {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        [
+            Role::Broadcast,
+            Role::Ptp,
+            Role::LinkLocalControl,
+            Role::Vrrp,
+            Role::Ipv4Multicast,
+            Role::Ipv6Multicast,
+            Role::MplsMulticast,
+            Role::Documentation,
+        ]
+        .iter()
+        .find(|role| role.as_str().eq_ignore_ascii_case(s.trim()))
+        .copied()
+        .ok_or_else(|| {
+            Error::Invalid(String::from(
+                "Pass in broadcast, ptp, link-local-control, vrrp, ipv4-multicast, \
+                 ipv6-multicast, mpls-multicast, or documentation.",
+            ))
+        })
+    }
+}
+
+/// A structured judgment about the device (or protocol) behind a
+/// MAC address, as network access control and asset discovery
+/// engines need it.
+///
+/// It is a best guess from the address alone.  Virtual machines
+/// with manually-assigned addresses look physical, for example.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Fingerprint {
+    /// The address's kind.
+    pub kind: Kind,
+    /// Whether the address is unicast, multicast, or broadcast.
+    pub cast: Cast,
+    /// Where the address most likely came from, or `None` for
+    /// group addresses, which identify protocols rather than
+    /// devices.
+    pub origin: Option<Origin>,
+    /// The hypervisor or container runtime behind a virtual
+    /// address (for example, `VMware`).
+    pub platform: Option<&'static str>,
+    /// The organization that registered the address's block.
+    pub vendor: Option<String>,
+    /// The well-known protocol that the address is reserved for.
+    pub role: Option<Role>,
+}
+
+/// Returns the well-known protocol that the MAC address is
+/// reserved for, if any.
+pub fn role(mac: &MediaAccessControlAddress) -> Option<Role> {
+    if mac.is_broadcast() {
+        Some(Role::Broadcast)
+    } else if mac.is_ptp() {
+        Some(Role::Ptp)
+    } else if mac.is_bridge_filtered() {
+        Some(Role::LinkLocalControl)
+    } else if mac.is_vrrp() {
+        Some(Role::Vrrp)
+    } else if mac.is_documentation() {
+        Some(Role::Documentation)
+    } else if mac.is_ipv4_multicast() {
+        Some(Role::Ipv4Multicast)
+    } else if mac.is_mpls_multicast() {
+        Some(Role::MplsMulticast)
+    } else if mac.is_ipv6_multicast() {
+        Some(Role::Ipv6Multicast)
+    } else {
+        None
+    }
+}
+
+/// Returns the hypervisor or container runtime whose prefix the
+/// MAC address falls under, if any.
+pub fn platform(mac: &MediaAccessControlAddress) -> Option<&'static str> {
+    VIRTUAL_PREFIXES
+        .iter()
+        .find(|(prefix, _)| mac.octets.starts_with(prefix))
+        .map(|(_, platform)| *platform)
+}
+
+/// Returns where the MAC address most likely came from, or `None`
+/// if it is a group address.
+///
+/// A unicast address is judged virtual if a hypervisor or container
+/// runtime's prefix covers it, randomized if it is otherwise
+/// locally administered, and physical if it is universally
+/// administered.
+pub fn origin(mac: &MediaAccessControlAddress) -> Option<Origin> {
+    if !mac.is_unicast() {
+        None
+    } else if platform(mac).is_some() {
+        Some(Origin::Virtual)
+    } else if mac.is_laa() {
+        Some(Origin::Randomized)
+    } else {
+        Some(Origin::Physical)
+    }
+}
+
+/// Fingerprints the MAC address, looking its vendor up with
+/// `resolver`, and judging its origin as `origin` does.
+pub fn fingerprint<R: VendorResolver>(
+    mac: &MediaAccessControlAddress,
+    resolver: &R,
+) -> Result<Fingerprint, Error> {
+    Ok(Fingerprint {
+        kind: mac.kind(),
+        cast: mac.cast(),
+        origin: origin(mac),
+        platform: platform(mac),
+        vendor: resolver.resolve(mac)?,
+        role: role(mac),
+    })
+}
@@ -0,0 +1,243 @@
+use super::error::Error;
+use super::macaddress::MediaAccessControlAddress;
+use super::utils::{self, ParseError};
+use std::error;
+use std::fmt;
+use std::str::FromStr;
+
+/// The longest notation (hyphen, colon, space, or Solaris notation)
+/// of a MAC address requires this many bytes.
+pub const MAX_LEN: usize = 17;
+
+const LOWERCASE: &[u8; 16] = b"0123456789abcdef";
+const UPPERCASE: &[u8; 16] = b"0123456789ABCDEF";
+
+/// The notations in which a MAC address can be written.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Notation {
+    /// Plain notation (for example, `a0b1c2d3e4f5`).
+    Plain,
+    /// Hyphen notation (for example, `a0-b1-c2-d3-e4-f5`).
+    Hyphen,
+    /// Colon notation (for example, `a0:b1:c2:d3:e4:f5`).
+    Colon,
+    /// Dot notation (for example, `a0b1.c2d3.e4f5`).
+    Dot,
+    /// Four-digit-hyphen notation (for example, `a0b1-c2d3-e4f5`).
+    FourDigitHyphen,
+    /// Space notation (for example, `A0 B1 C2 D3 E4 F5`).
+    Space,
+    /// Solaris notation (for example, `0:3:ba:12:4:5`).
+    Solaris,
+}
+
+impl Notation {
+    /// Every notation, in the order in which parsing tries them.
+    pub const ALL: [Notation; 7] = [
+        Notation::Plain,
+        Notation::Hyphen,
+        Notation::Colon,
+        Notation::Dot,
+        Notation::FourDigitHyphen,
+        Notation::Space,
+        Notation::Solaris,
+    ];
+
+    /// Returns the notation's stable name (for example, `plain`
+    /// or `four-digit-hyphen`).
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Notation::Plain => "plain",
+            Notation::Hyphen => "hyphen",
+            Notation::Colon => "colon",
+            Notation::Dot => "dot",
+            Notation::FourDigitHyphen => "four-digit-hyphen",
+            Notation::Space => "space",
+            Notation::Solaris => "solaris",
+        }
+    }
+
+    /// Returns the most bytes that a MAC address in this notation
+    /// can require.
+    pub fn max_len(self) -> usize {
+        match self {
+            Notation::Plain => 12,
+            Notation::Dot | Notation::FourDigitHyphen => 14,
+            Notation::Hyphen | Notation::Colon | Notation::Space | Notation::Solaris => MAX_LEN,
+        }
+    }
+}
+
+impl fmt::Display for Notation {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.pad(self.as_str())
+    }
+}
+
+/// Parses a notation from its stable name, ignoring ASCII case.
+impl FromStr for Notation {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Notation::ALL
+            .iter()
+            .find(|notation| notation.as_str().eq_ignore_ascii_case(s.trim()))
+            .copied()
+            .ok_or_else(|| {
+                Error::Invalid(String::from(
+                    "Pass in plain, hyphen, colon, dot, four-digit-hyphen, space, or solaris.",
+                ))
+            })
+    }
+}
+
+/// The error returned when a buffer is too small to hold a
+/// formatted MAC address.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct BufferTooSmall {
+    /// The number of bytes the formatted address requires.
+    pub required: usize,
+}
+
+impl fmt::Display for BufferTooSmall {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Pass in a buffer of at least {} bytes.", self.required)
+    }
+}
+
+impl error::Error for BufferTooSmall {}
+
+/// Returns the separator, if any, that precedes the octet at
+/// `index` in the given notation.
+fn separator(notation: Notation, index: usize) -> Option<u8> {
+    if index == 0 {
+        return None;
+    }
+
+    match notation {
+        Notation::Plain => None,
+        Notation::Hyphen => Some(b'-'),
+        Notation::Colon | Notation::Solaris => Some(b':'),
+        Notation::Space => Some(b' '),
+        Notation::Dot if index & 1 == 0 => Some(b'.'),
+        Notation::FourDigitHyphen if index & 1 == 0 => Some(b'-'),
+        Notation::Dot | Notation::FourDigitHyphen => None,
+    }
+}
+
+/// Writes `N` octets (six for a MAC address) into `buffer` in the
+/// given notation, without allocating, and returns the written
+/// portion of `buffer` as a string slice.
+pub fn format_into<'a, const N: usize>(
+    octets: &[u8; N],
+    notation: Notation,
+    buffer: &'a mut [u8],
+) -> Result<&'a str, BufferTooSmall> {
+    let short = |octet: u8| notation == Notation::Solaris && octet <= 0x0f;
+
+    let required = octets
+        .iter()
+        .enumerate()
+        .map(|(index, octet)| {
+            let separator = separator(notation, index).map_or(0, |_| 1);
+            let digits = if short(*octet) { 1 } else { 2 };
+            separator + digits
+        })
+        .sum();
+
+    if buffer.len() < required {
+        return Err(BufferTooSmall { required });
+    }
+
+    let digits = match notation {
+        Notation::Space => UPPERCASE,
+        _ => LOWERCASE,
+    };
+    let mut length = 0;
+
+    for (index, octet) in octets.iter().enumerate() {
+        if let Some(separator) = separator(notation, index) {
+            buffer[length] = separator;
+            length += 1;
+        }
+
+        if !short(*octet) {
+            buffer[length] = digits[usize::from(octet >> 4)];
+            length += 1;
+        }
+
+        buffer[length] = digits[usize::from(octet & 0x0f)];
+        length += 1;
+    }
+
+    Ok(std::str::from_utf8(&buffer[..length]).unwrap())
+}
+
+/// Appends the MAC addresses to `buffer` in the given notation,
+/// with `separator` between them (for example, `"\n"`), reserving
+/// room for all of them at once and allocating nothing per address.
+///
+/// Exporters that render millions of addresses can reuse one buffer
+/// across batches, clearing it in between.
+pub fn format_many(
+    addresses: &[MediaAccessControlAddress],
+    notation: Notation,
+    separator: &str,
+    buffer: &mut String,
+) {
+    buffer.reserve(addresses.len() * (notation.max_len() + separator.len()));
+    let mut scratch = [0; MAX_LEN];
+
+    for (index, address) in addresses.iter().enumerate() {
+        if index > 0 {
+            buffer.push_str(separator);
+        }
+
+        buffer.push_str(format_into(&address.to_octets(), notation, &mut scratch).unwrap());
+    }
+}
+
+/// Appends the MAC addresses to `buffer` as `format_many` does, for
+/// writers that take bytes.
+pub fn format_many_bytes(
+    addresses: &[MediaAccessControlAddress],
+    notation: Notation,
+    separator: &[u8],
+    buffer: &mut Vec<u8>,
+) {
+    buffer.reserve(addresses.len() * (notation.max_len() + separator.len()));
+    let mut scratch = [0; MAX_LEN];
+
+    for (index, address) in addresses.iter().enumerate() {
+        if index > 0 {
+            buffer.extend_from_slice(separator);
+        }
+
+        let formatted = format_into(&address.to_octets(), notation, &mut scratch).unwrap();
+        buffer.extend_from_slice(formatted.as_bytes());
+    }
+}
+
+/// Returns `N` octets (six for a MAC address) in the given
+/// notation.
+pub fn format<const N: usize>(octets: &[u8; N], notation: Notation) -> String {
+    let mut buffer = vec![0; N * 3];
+    format_into(octets, notation, &mut buffer)
+        .unwrap()
+        .to_string()
+}
+
+/// Parses 12 hexadecimal digits in exactly the given notation
+/// into six octets.
+///
+/// Solaris notation accepts leading zeros, since it is colon
+/// notation that merely allows omitting them.
+pub fn parse(digits: &str, notation: Notation) -> Result<[u8; 6], ParseError> {
+    let found = utils::diagnose(digits)?;
+
+    if !utils::matches(digits, notation) {
+        return Err(ParseError::wrong_notation(found, notation));
+    }
+
+    Ok(utils::to_octets(digits))
+}
@@ -0,0 +1,29 @@
+use super::error::Error;
+use super::macaddress::MediaAccessControlAddress;
+
+/// The number of octets in an Ethernet header (destination,
+/// source, and EtherType).
+pub const HEADER_LEN: usize = 14;
+
+/// Returns the destination and source MAC addresses of an
+/// Ethernet (IEEE 802.3) frame, in that order.
+///
+/// The frame starts at the destination address; the preamble and
+/// start frame delimiter are not part of it, just as in a pcap
+/// file.  VLAN tags follow the source address, so they do not
+/// affect the result.
+pub fn addresses(
+    frame: &[u8],
+) -> Result<(MediaAccessControlAddress, MediaAccessControlAddress), Error> {
+    if frame.len() < HEADER_LEN {
+        return Err(Error::Invalid(format!(
+            "Pass in a frame of at least {} octets.",
+            HEADER_LEN
+        )));
+    }
+
+    let destination = MediaAccessControlAddress::from_octets(&frame[..6])?;
+    let source = MediaAccessControlAddress::from_octets(&frame[6..12])?;
+
+    Ok((destination, source))
+}
@@ -0,0 +1,258 @@
+use super::error::Error;
+use super::macaddress::MediaAccessControlAddress;
+
+/// The CRC-32 polynomial used by IEEE 802.3 (Ethernet).
+const ETHERNET_POLYNOMIAL: u32 = 0x04c1_1db7;
+
+/// Mixes a 64-bit value into a well-distributed 64-bit hash
+/// (the SplitMix64 finalizer).
+///
+/// It is fixed, rather than `std`'s randomly-keyed hasher, so that
+/// the same value hashes the same way on every machine and in
+/// every version of this crate.
+pub(crate) fn mix(mut value: u64) -> u64 {
+    value = value.wrapping_add(0x9e37_79b9_7f4a_7c15);
+    value = (value ^ (value >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+    value = (value ^ (value >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+    value ^ (value >> 31)
+}
+
+/// Returns the big-endian Ethernet CRC-32 of the MAC address's
+/// six octets, without the final inversion.
+///
+/// Each octet is fed into the CRC least-significant bit first,
+/// just as it is transmitted on the wire.  This matches the
+/// `ether_crc` routine found in many network drivers.
+pub fn ether_crc(mac: &MediaAccessControlAddress) -> u32 {
+    let mut crc: u32 = 0xffff_ffff;
+
+    for octet in mac.to_octets().iter() {
+        let mut octet = *octet;
+
+        for _ in 0..8 {
+            let carry = (crc >> 31) ^ u32::from(octet & 1);
+            crc <<= 1;
+            octet >>= 1;
+
+            if carry == 1 {
+                crc ^= ETHERNET_POLYNOMIAL;
+            }
+        }
+    }
+
+    crc
+}
+
+/// Returns the index of the multicast filter bucket into which
+/// a NIC with a `bits`-bit hash table places the MAC address.
+///
+/// The index is the `bits` most-significant bits of the
+/// address's Ethernet CRC-32 (see `ether_crc`), which is the
+/// scheme used by common Ethernet controllers.  For example,
+/// a controller with a 64-bucket filter uses a 6-bit index.
+///
+/// `bits` must be between 1 and 32.
+pub fn multicast_filter_index(mac: &MediaAccessControlAddress, bits: u32) -> Result<u32, Error> {
+    if (1..=32).contains(&bits) {
+        let crc = u64::from(ether_crc(mac));
+        Ok((crc >> (32 - bits)) as u32)
+    } else {
+        Err(Error::Invalid(String::from(
+            "Pass in between 1 and 32 bits.",
+        )))
+    }
+}
+
+/// Returns the bucket, between 0 and `buckets - 1`, that the MAC
+/// address belongs to, so that distributed collectors can
+/// partition per-device state consistently.
+///
+/// The key is the SplitMix64 finalizer applied to the address's
+/// 48-bit value (see `to_decimal_representation`), scaled into
+/// `0..buckets` by taking the high 64 bits of its product with
+/// `buckets`.  This scheme is part of the crate's stable interface,
+/// so the same address lands in the same bucket on every machine
+/// and in every version.
+///
+/// `buckets` must be at least 1.
+pub fn shard_key(mac: &MediaAccessControlAddress, buckets: u32) -> Result<u32, Error> {
+    if buckets == 0 {
+        return Err(Error::Invalid(String::from("Pass in at least 1 bucket.")));
+    }
+
+    let hash = mix(mac.to_decimal_representation());
+    Ok(((u128::from(hash) * u128::from(buckets)) >> 64) as u32)
+}
+
+/// The SHA-256 round constants: the first 32 bits of the
+/// fractional parts of the cube roots of the first 64 primes.
+const SHA256_ROUNDS: [u32; 64] = [
+    0x428a_2f98,
+    0x7137_4491,
+    0xb5c0_fbcf,
+    0xe9b5_dba5,
+    0x3956_c25b,
+    0x59f1_11f1,
+    0x923f_82a4,
+    0xab1c_5ed5,
+    0xd807_aa98,
+    0x1283_5b01,
+    0x2431_85be,
+    0x550c_7dc3,
+    0x72be_5d74,
+    0x80de_b1fe,
+    0x9bdc_06a7,
+    0xc19b_f174,
+    0xe49b_69c1,
+    0xefbe_4786,
+    0x0fc1_9dc6,
+    0x240c_a1cc,
+    0x2de9_2c6f,
+    0x4a74_84aa,
+    0x5cb0_a9dc,
+    0x76f9_88da,
+    0x983e_5152,
+    0xa831_c66d,
+    0xb003_27c8,
+    0xbf59_7fc7,
+    0xc6e0_0bf3,
+    0xd5a7_9147,
+    0x06ca_6351,
+    0x1429_2967,
+    0x27b7_0a85,
+    0x2e1b_2138,
+    0x4d2c_6dfc,
+    0x5338_0d13,
+    0x650a_7354,
+    0x766a_0abb,
+    0x81c2_c92e,
+    0x9272_2c85,
+    0xa2bf_e8a1,
+    0xa81a_664b,
+    0xc24b_8b70,
+    0xc76c_51a3,
+    0xd192_e819,
+    0xd699_0624,
+    0xf40e_3585,
+    0x106a_a070,
+    0x19a4_c116,
+    0x1e37_6c08,
+    0x2748_774c,
+    0x34b0_bcb5,
+    0x391c_0cb3,
+    0x4ed8_aa4a,
+    0x5b9c_ca4f,
+    0x682e_6ff3,
+    0x748f_82ee,
+    0x78a5_636f,
+    0x84c8_7814,
+    0x8cc7_0208,
+    0x90be_fffa,
+    0xa450_6ceb,
+    0xbef9_a3f7,
+    0xc671_78f2,
+];
+
+/// Returns the SHA-256 digest of `bytes`, for checking downloads,
+/// such as a vendor database, against a published digest.
+///
+/// Unlike the CRC-32 of `VendorDatabase::checksum`, which only
+/// catches accidental corruption, it cannot feasibly be forged.
+pub fn sha256(bytes: &[u8]) -> [u8; 32] {
+    let mut state: [u32; 8] = [
+        0x6a09_e667,
+        0xbb67_ae85,
+        0x3c6e_f372,
+        0xa54f_f53a,
+        0x510e_527f,
+        0x9b05_688c,
+        0x1f83_d9ab,
+        0x5be0_cd19,
+    ];
+
+    // Pad with a 1 bit, zeros, and the length in bits, up to a
+    // multiple of 64 octets.
+    let mut message = bytes.to_vec();
+    message.push(0x80);
+
+    while message.len() % 64 != 56 {
+        message.push(0);
+    }
+
+    message.extend_from_slice(&((bytes.len() as u64) * 8).to_be_bytes());
+
+    for block in message.chunks(64) {
+        let mut schedule = [0u32; 64];
+
+        for (word, octets) in schedule.iter_mut().zip(block.chunks(4)) {
+            *word = u32::from_be_bytes([octets[0], octets[1], octets[2], octets[3]]);
+        }
+
+        for index in 16..64 {
+            let previous = schedule[index - 15];
+            let first = previous.rotate_right(7) ^ previous.rotate_right(18) ^ (previous >> 3);
+            let previous = schedule[index - 2];
+            let second = previous.rotate_right(17) ^ previous.rotate_right(19) ^ (previous >> 10);
+            schedule[index] = schedule[index - 16]
+                .wrapping_add(first)
+                .wrapping_add(schedule[index - 7])
+                .wrapping_add(second);
+        }
+
+        let [mut a, mut b, mut c, mut d, mut e, mut f, mut g, mut h] = state;
+
+        for (constant, word) in SHA256_ROUNDS.iter().zip(schedule.iter()) {
+            let choice = (e & f) ^ (!e & g);
+            let first = h
+                .wrapping_add(e.rotate_right(6) ^ e.rotate_right(11) ^ e.rotate_right(25))
+                .wrapping_add(choice)
+                .wrapping_add(*constant)
+                .wrapping_add(*word);
+            let majority = (a & b) ^ (a & c) ^ (b & c);
+            let second = (a.rotate_right(2) ^ a.rotate_right(13) ^ a.rotate_right(22))
+                .wrapping_add(majority);
+
+            h = g;
+            g = f;
+            f = e;
+            e = d.wrapping_add(first);
+            d = c;
+            c = b;
+            b = a;
+            a = first.wrapping_add(second);
+        }
+
+        for (word, value) in state.iter_mut().zip([a, b, c, d, e, f, g, h].iter()) {
+            *word = word.wrapping_add(*value);
+        }
+    }
+
+    let mut digest = [0; 32];
+
+    for (octets, word) in digest.chunks_mut(4).zip(state.iter()) {
+        octets.copy_from_slice(&word.to_be_bytes());
+    }
+
+    digest
+}
+
+/// Returns the HMAC-SHA-256 (RFC 2104) of `message` under `key`, a
+/// keyed hash that, unlike `sha256` alone, cannot be recomputed, or
+/// its input guessed and checked, without the key.
+pub fn hmac_sha256(key: &[u8], message: &[u8]) -> [u8; 32] {
+    let mut block = [0; 64];
+
+    if key.len() > 64 {
+        block[..32].copy_from_slice(&sha256(key));
+    } else {
+        block[..key.len()].copy_from_slice(key);
+    }
+
+    let mut inner: Vec<u8> = block.iter().map(|octet| octet ^ 0x36).collect();
+    inner.extend_from_slice(message);
+
+    let mut outer: Vec<u8> = block.iter().map(|octet| octet ^ 0x5c).collect();
+    outer.extend_from_slice(&sha256(&inner));
+
+    sha256(&outer)
+}
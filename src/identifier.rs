@@ -0,0 +1,458 @@
+use super::classification::{Cast, Kind};
+use super::error::Error;
+use super::format::{self, BufferTooSmall, Notation};
+use std::fmt;
+
+/// `Identifier` is an IEEE extended identifier that is `N` octets
+/// wide.  It backs both `MediaAccessControlAddress` (`N` = 6)
+/// and `Eui64` (`N` = 8).
+///
+/// Methods that depend only on the identifier's octets, such as
+/// those that read the individual/group and universal/local bits,
+/// are available at every width.  Methods that are specific to a
+/// width, such as parsing, live with its alias.
+///
+/// The identifier is stored as its `N` octets, so it is `Copy`, and
+/// the methods that classify it are bit tests that never allocate.
+/// Identifiers compare and hash by their octets, so they order
+/// numerically and work as keys of a `HashMap` or a `BTreeMap`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Identifier<const N: usize> {
+    pub(crate) octets: [u8; N],
+}
+
+impl<const N: usize> Identifier<N> {
+    /// Instantiates `Identifier` with exactly `N` octets, which
+    /// cannot fail, so it works in constants, too.
+    pub const fn from_array(octets: [u8; N]) -> Self {
+        Self { octets }
+    }
+
+    /// Instantiates `Identifier` with `N` octets, such as the raw
+    /// `OCTET STRING` of an SNMP `PhysAddress`.
+    ///
+    /// Use `from_array` or `From` instead when the octets are
+    /// already an array of the right length.
+    pub fn from_octets(octets: &[u8]) -> Result<Self, Error> {
+        if octets.len() == N {
+            let mut identifier = Self { octets: [0; N] };
+            identifier.octets.copy_from_slice(octets);
+            Ok(identifier)
+        } else {
+            Err(Error::Invalid(format!("Pass in {} octets.", N)))
+        }
+    }
+
+    /// Returns the binary representation of the identifier.
+    /// *The most-significant digit of each octet appears first.*
+    pub fn to_binary_representation(&self) -> String {
+        let binary: Vec<String> = self
+            .to_octets()
+            .iter()
+            .map(|octet| format!("{:08b}", octet))
+            .collect();
+
+        binary.join("")
+    }
+
+    /// Returns the decimal representation of the identifier.
+    ///
+    /// The value is a `u64` rather than a `usize`, since a
+    /// `usize` cannot hold 48 bits on 32-bit targets.
+    pub fn to_decimal_representation(&self) -> u64 {
+        self.to_octets()
+            .iter()
+            .fold(0, |value, octet| value << 8 | u64::from(*octet))
+    }
+
+    /// Returns the `len` bits of the identifier that begin at bit
+    /// `start`, as an unsigned number.
+    ///
+    /// Bits are numbered the way `to_binary_representation` prints
+    /// them: bit 0 is the most-significant bit of the first octet,
+    /// and bit `8 * N - 1` is the least-significant bit of the last.
+    /// So, for a MAC address, `bits_range(0, 24)` is the OUI, and
+    /// `bits_range(6, 1)` is the universal/local bit.  The range must
+    /// lie within the identifier, and `len` must be between 1 and 64.
+    pub fn bits_range(&self, start: usize, len: usize) -> Result<u64, Error> {
+        let shift = Self::bits_shift(start, len)?;
+        Ok((self.to_decimal_representation() >> shift) & Self::bits_mask(len))
+    }
+
+    /// Returns a copy of the identifier with the `len` bits that
+    /// begin at bit `start` replaced by `value`, numbering the bits
+    /// as `bits_range` does.
+    ///
+    /// `value` must fit in `len` bits.
+    pub fn with_bits_range(&self, start: usize, len: usize, value: u64) -> Result<Self, Error> {
+        let shift = Self::bits_shift(start, len)?;
+        let mask = Self::bits_mask(len);
+
+        if value & !mask != 0 {
+            return Err(Error::Invalid(format!(
+                "Pass in a value that fits in {} bits (not {}).",
+                len, value
+            )));
+        }
+
+        let value = (self.to_decimal_representation() & !(mask << shift)) | (value << shift);
+        Self::from_octets(&value.to_be_bytes()[8 - N..])
+    }
+
+    /// Checks that `len` bits beginning at bit `start` lie within the
+    /// identifier and returns how far right the range must shift to
+    /// end at bit 0 of the identifier's value.
+    fn bits_shift(start: usize, len: usize) -> Result<usize, Error> {
+        if len == 0 || len > 64 || start + len > 8 * N {
+            Err(Error::Invalid(format!(
+                "Pass in a range of 1 or more bits that lies within the {} bits of the identifier.",
+                8 * N
+            )))
+        } else {
+            Ok(8 * N - start - len)
+        }
+    }
+
+    /// Returns a mask of the `len` least-significant bits.
+    fn bits_mask(len: usize) -> u64 {
+        u64::MAX >> (64 - len)
+    }
+
+    /// Returns the identifier's `N` octets, with the first
+    /// octet transmitted on the wire appearing first.
+    pub fn to_octets(&self) -> [u8; N] {
+        self.octets
+    }
+
+    /// Returns the identifier in the given notation.
+    pub fn to_notation(&self, notation: Notation) -> String {
+        format::format(&self.to_octets(), notation)
+    }
+
+    /// Writes the identifier into `buffer` in the given notation,
+    /// without allocating, and returns the written portion of
+    /// `buffer`.
+    ///
+    /// For a MAC address, a buffer of `format::MAX_LEN` bytes
+    /// fits every notation.
+    pub fn format_into<'a>(
+        &self,
+        buffer: &'a mut [u8],
+        notation: Notation,
+    ) -> Result<&'a str, BufferTooSmall> {
+        format::format_into(&self.to_octets(), notation, buffer)
+    }
+
+    /// Returns the identifier in plain notation
+    /// (for example, `a0b1c2d3e4f5`).
+    pub fn to_plain_notation(&self) -> String {
+        self.to_notation(Notation::Plain)
+    }
+
+    /// Returns the identifier in hyphen notation
+    /// (for example, `a0-b1-c2-d3-e4-f5`).
+    pub fn to_hyphen_notation(&self) -> String {
+        self.to_notation(Notation::Hyphen)
+    }
+
+    /// Returns the identifier in colon notation
+    /// (for example, `a0:b1:c2:d3:e4:f5`).
+    pub fn to_colon_notation(&self) -> String {
+        self.to_notation(Notation::Colon)
+    }
+
+    /// Returns the identifier in dot notation
+    /// (for example, `a0b1.c2d3.e4f5`).
+    pub fn to_dot_notation(&self) -> String {
+        self.to_notation(Notation::Dot)
+    }
+
+    /// Returns the identifier in four-digit-hyphen notation
+    /// (for example, `a0b1-c2d3-e4f5`).
+    pub fn to_four_digit_hyphen_notation(&self) -> String {
+        self.to_notation(Notation::FourDigitHyphen)
+    }
+
+    /// Returns the identifier in Solaris notation, which is
+    /// colon notation without leading zeros
+    /// (for example, `0:3:ba:12:4:5`).
+    pub fn to_solaris_notation(&self) -> String {
+        self.to_notation(Notation::Solaris)
+    }
+
+    /// Returns the identifier in space notation, the textual
+    /// convention MIB browsers use for an SNMP `PhysAddress`
+    /// (for example, `A0 B1 C2 D3 E4 F5`).
+    pub fn to_snmp_notation(&self) -> String {
+        self.to_notation(Notation::Space)
+    }
+
+    /// Returns the identifier's two "fragments,"
+    /// where the first 24 bits are an OUI or CID and
+    /// the remaining bits are specific to an interface
+    /// (for example, `(a0b1c2, d3e4f5)`.
+    pub fn to_fragments(&self) -> (String, String) {
+        let mut plain = self.to_plain_notation();
+        let second = plain.split_off(6);
+        (plain, second)
+    }
+
+    /// Returns the identifier's kind, where kind is
+    /// `Unique`, `Local`, or `Unknown`.
+    ///
+    /// The two least-significant bits in the first octet
+    /// of a MAC address/extended identifier determine
+    /// whether it is an EUI (`00` = `unique`).
+    ///
+    /// The four least-significant bits in the first octet
+    /// of a MAC address/extended identifier determine
+    /// whether it is an ELI (`1010` = `local`).
+    pub fn kind(&self) -> Kind {
+        if self.octets[0] & 0b0000_0011 == 0b0000_0000 {
+            Kind::Unique
+        } else if self.octets[0] & 0b0000_1111 == 0b0000_1010 {
+            Kind::Local
+        } else {
+            Kind::Unknown
+        }
+    }
+
+    /// Whether the MAC address/extended identifier has
+    /// an OUI.
+    ///
+    /// If the MAC address/exended identifier is an EUI,
+    /// then it has an OUI.
+    pub fn has_oui(&self) -> bool {
+        self.kind() == Kind::Unique
+    }
+
+    /// Whether the MAC address/extended identifier has
+    /// a CID.
+    ///
+    /// If the MAC address/extended identifier is an ELI,
+    /// then it has a CID.
+    pub fn has_cid(&self) -> bool {
+        self.kind() == Kind::Local
+    }
+
+    /// Whether the identifier is a broadcast address
+    /// (all ones, such as `ffffffffffff`).
+    pub fn is_broadcast(&self) -> bool {
+        self.octets.iter().all(|octet| *octet == 0xff)
+    }
+
+    /// Returns whether the identifier is a `Unicast`, `Multicast`,
+    /// or `Broadcast` address.
+    pub fn cast(&self) -> Cast {
+        if self.is_broadcast() {
+            Cast::Broadcast
+        } else if self.is_multicast() {
+            Cast::Multicast
+        } else {
+            Cast::Unicast
+        }
+    }
+
+    /// Whether the identifier is a multicast address
+    /// (layer-two multicast, not layer-three multicast).
+    ///
+    /// The least-significant bit in the first octet of
+    /// an identifier determines whether it is a multicast
+    /// or a unicast (`1` = multicast).
+    pub fn is_multicast(&self) -> bool {
+        self.octets[0] & 0b0000_0001 != 0
+    }
+
+    /// Whether the identifier is a unicast address.
+    ///
+    /// The least-significant bit in the first octet of
+    /// an identifier determines whether it is a multicast
+    /// or a unicast (`0` = unicast).
+    pub fn is_unicast(&self) -> bool {
+        !self.is_multicast()
+    }
+
+    /// Whether the identifier is a universally-administered
+    /// address (UAA).
+    ///
+    /// The second-least-significant bit in the first octet of
+    /// an identifier determines whether it is a UAA or an LAA
+    /// (`0` = UAA).
+    pub fn is_uaa(&self) -> bool {
+        self.is_unicast() && self.is_universal()
+    }
+
+    /// Whether the identifier is a locally-administered
+    /// address (LAA).
+    ///
+    /// The second-least-significant bit in the first octet of
+    /// an identifier determines whether it is a UAA or an LAA
+    /// (`1` = LAA).
+    pub fn is_laa(&self) -> bool {
+        self.is_unicast() && self.is_local()
+    }
+
+    /// Whether the universal/local bit of the identifier is
+    /// clear (`0` = universal).
+    ///
+    /// Unlike `is_uaa`, this reports the bit for multicast
+    /// addresses, too.
+    pub fn is_universal(&self) -> bool {
+        self.octets[0] & 0b0000_0010 == 0
+    }
+
+    /// Whether the universal/local bit of the identifier is
+    /// set (`1` = local).
+    ///
+    /// Unlike `is_laa`, this reports the bit for multicast
+    /// addresses, too.
+    pub fn is_local(&self) -> bool {
+        !self.is_universal()
+    }
+
+    /// Returns the identifier's octets with the universal/local
+    /// bit (the second-least-significant bit of the first octet)
+    /// cleared.
+    ///
+    /// Use it as a key to group an address with its variants
+    /// that differ only in that bit, such as a factory UAA and
+    /// the flipped form that modified EUI-64 interface identifiers
+    /// embed.
+    pub fn ul_normalized_key(&self) -> [u8; N] {
+        let mut octets = self.to_octets();
+        octets[0] &= 0b1111_1101;
+        octets
+    }
+
+    /// Whether the identifier equals `other` when the
+    /// universal/local bit is ignored.
+    pub fn eq_ignoring_ul_bit(&self, other: &Self) -> bool {
+        self.ul_normalized_key() == other.ul_normalized_key()
+    }
+
+    /// Returns the number of bits in which the identifier
+    /// differs from `other`.
+    ///
+    /// A small distance between two addresses in an inventory
+    /// often means that one of them was mistyped.
+    pub fn hamming_distance(&self, other: &Self) -> u32 {
+        self.to_octets()
+            .iter()
+            .zip(other.to_octets().iter())
+            .map(|(first, second)| (first ^ second).count_ones())
+            .sum()
+    }
+
+    /// Returns a copy of the identifier with the first octet's
+    /// bits replaced by `(first & mask) | set`.
+    fn with_first_octet(&self, mask: u8, set: u8) -> Self {
+        let mut identifier = *self;
+        identifier.octets[0] = (identifier.octets[0] & mask) | set;
+        identifier
+    }
+
+    /// Returns a copy of the identifier with the
+    /// individual/group bit cleared, making it a unicast address.
+    pub fn as_unicast(&self) -> Self {
+        self.with_first_octet(0b1111_1110, 0)
+    }
+
+    /// Returns a copy of the identifier with the
+    /// individual/group bit set, making it a multicast address.
+    pub fn as_multicast(&self) -> Self {
+        self.with_first_octet(0b1111_1111, 0b0000_0001)
+    }
+
+    /// Returns a copy of the identifier with the
+    /// universal/local bit cleared, making it universally
+    /// administered.
+    pub fn as_universal(&self) -> Self {
+        self.with_first_octet(0b1111_1101, 0)
+    }
+
+    /// Returns a copy of the identifier with the
+    /// universal/local bit set, making it locally administered.
+    pub fn as_local(&self) -> Self {
+        self.with_first_octet(0b1111_1111, 0b0000_0010)
+    }
+}
+
+/// Instantiates `Identifier` with `N` octets, as `from_array` does.
+impl<const N: usize> From<[u8; N]> for Identifier<N> {
+    fn from(octets: [u8; N]) -> Self {
+        Self::from_array(octets)
+    }
+}
+
+/// Formats the identifier in colon notation (for example,
+/// `a0:b1:c2:d3:e4:f5`), or in uppercase with the alternate flag
+/// (`{:#}`), so that it composes with `format!` and logging macros.
+/// Width, fill, and alignment apply to the whole identifier.
+impl<const N: usize> fmt::Display for Identifier<N> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let mut digits = self.to_colon_notation();
+
+        if f.alternate() {
+            digits.make_ascii_uppercase();
+        }
+
+        f.pad(&digits)
+    }
+}
+
+/// Logs the identifier in colon notation (for example,
+/// `a0:b1:c2:d3:e4:f5`) through `defmt`, which sends only the
+/// octets over the wire and leaves the formatting to the host.
+///
+/// This implementation requires the `defmt` feature.
+#[cfg(feature = "defmt")]
+impl<const N: usize> defmt::Format for Identifier<N> {
+    fn format(&self, f: defmt::Formatter) {
+        let octets = self.to_octets();
+        defmt::write!(f, "{=u8:02x}", octets[0]);
+
+        for octet in octets[1..].iter() {
+            defmt::write!(f, ":{=u8:02x}", *octet);
+        }
+    }
+}
+
+/// Serializes the identifier in colon notation (for example,
+/// `a0:b1:c2:d3:e4:f5`) for human-readable formats, such as JSON,
+/// and as its `N` octets for other formats.
+///
+/// This implementation requires the `serde` feature.
+#[cfg(feature = "serde")]
+impl<const N: usize> serde::Serialize for Identifier<N> {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        if serializer.is_human_readable() {
+            serializer.serialize_str(&self.to_colon_notation())
+        } else {
+            serializer.serialize_bytes(&self.to_octets())
+        }
+    }
+}
+
+/// Deserializes the identifier from a string in any notation that
+/// `new` accepts, for human-readable formats, or from its `N`
+/// octets, for other formats.
+///
+/// This implementation requires the `serde` feature.
+#[cfg(feature = "serde")]
+impl<'de, const N: usize> serde::Deserialize<'de> for Identifier<N>
+where
+    for<'a> Identifier<N>: std::convert::TryFrom<&'a str, Error = super::utils::MacParseError>,
+{
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        // The same check as `new`, with the message of `diagnose`.
+        super::utils::deserialize(
+            deserializer,
+            "an extended identifier",
+            |digits| {
+                super::utils::inspect(digits, N).map_err(String::from)?;
+                Ok(Self::from_array(super::utils::to_octets(digits)))
+            },
+            |octets| Self::from_octets(octets).map_err(String::from),
+        )
+    }
+}
@@ -0,0 +1,660 @@
+use super::error::Error;
+use super::macaddress::MediaAccessControlAddress;
+use super::set::MacSet;
+use super::vendor::VendorResolver;
+use std::collections::BTreeMap;
+use std::fmt;
+use std::sync::mpsc::{self, Receiver};
+use std::time::{Duration, SystemTime};
+
+#[cfg(feature = "pcap")]
+use super::capture::{Frame, LINKTYPE_ETHERNET};
+
+#[cfg(feature = "system")]
+use super::system::Neighbor;
+
+#[cfg(feature = "metrics")]
+use super::metrics::Metrics;
+
+#[cfg(feature = "metrics")]
+use std::sync::Arc;
+
+#[cfg(feature = "sqlite")]
+mod sqlite;
+
+#[cfg(feature = "sqlite")]
+pub use self::sqlite::SqliteStore;
+
+/// One device that an `Inventory` has seen, identified by its MAC
+/// address.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Device {
+    address: [u8; 6],
+    first_seen: SystemTime,
+    last_seen: SystemTime,
+    observations: u64,
+    tags: BTreeMap<String, String>,
+}
+
+impl Device {
+    /// Instantiates `Device` from its parts, as an `InventoryStore`
+    /// reads them back.
+    ///
+    /// `first_seen` must not come after `last_seen`.
+    pub fn from_parts(
+        address: &MediaAccessControlAddress,
+        first_seen: SystemTime,
+        last_seen: SystemTime,
+        observations: u64,
+        tags: BTreeMap<String, String>,
+    ) -> Result<Self, Error> {
+        if first_seen > last_seen {
+            return Err(Error::Invalid(String::from(
+                "Pass in a first sighting that does not come after the last.",
+            )));
+        }
+
+        Ok(Self {
+            address: address.to_octets(),
+            first_seen,
+            last_seen,
+            observations,
+            tags,
+        })
+    }
+
+    /// Returns the device's MAC address.
+    pub fn address(&self) -> MediaAccessControlAddress {
+        MediaAccessControlAddress::from(self.address)
+    }
+
+    /// Returns when the device was first seen.
+    pub fn first_seen(&self) -> SystemTime {
+        self.first_seen
+    }
+
+    /// Returns when the device was last seen.
+    pub fn last_seen(&self) -> SystemTime {
+        self.last_seen
+    }
+
+    /// Returns the number of times that the device has been seen.
+    pub fn observations(&self) -> u64 {
+        self.observations
+    }
+
+    /// Returns the device's vendor, as recorded by
+    /// `Inventory::tag_vendors`, if it has one.
+    pub fn vendor(&self) -> Option<&str> {
+        self.tag("vendor")
+    }
+
+    /// Returns the value of the tag named `name`, if the device has
+    /// it.
+    pub fn tag(&self, name: &str) -> Option<&str> {
+        self.tags.get(name).map(String::as_str)
+    }
+
+    /// Returns the device's tags, sorted by name.
+    pub fn tags(&self) -> &BTreeMap<String, String> {
+        &self.tags
+    }
+}
+
+/// The changes from one snapshot of an inventory to a later one,
+/// returned by `Inventory::diff`.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct InventoryDiff {
+    /// The addresses in the later snapshot but not the earlier one.
+    pub appeared: MacSet,
+    /// The addresses in the earlier snapshot but not the later one.
+    pub disappeared: MacSet,
+    /// The addresses in both snapshots whose vendors differ.
+    pub changed_vendor: MacSet,
+}
+
+impl InventoryDiff {
+    /// Whether nothing changed.
+    pub fn is_empty(&self) -> bool {
+        self.appeared.is_empty() && self.disappeared.is_empty() && self.changed_vendor.is_empty()
+    }
+}
+
+/// A report that an `Inventory` saw an address for the first time,
+/// or for the first time after an absence, passed to its watchers.
+#[derive(Debug)]
+pub struct Sighting {
+    /// The MAC address.
+    pub address: MediaAccessControlAddress,
+    /// When the address was seen.
+    pub at: SystemTime,
+    /// When the address was last seen before, or `None` if it is
+    /// new.
+    pub last_seen: Option<SystemTime>,
+}
+
+/// A callback registered with `Inventory::watch`.
+type Callback = Box<dyn FnMut(&Sighting) + Send>;
+
+/// The watchers of an inventory, each with its absence threshold.
+///
+/// Callbacks can be neither cloned nor compared, so a clone of an
+/// inventory starts with no watchers, and watchers are ignored when
+/// inventories are compared.
+#[derive(Default)]
+struct Watchers(Vec<(Option<Duration>, Callback)>);
+
+impl Clone for Watchers {
+    fn clone(&self) -> Self {
+        Self::default()
+    }
+}
+
+impl fmt::Debug for Watchers {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} watchers", self.0.len())
+    }
+}
+
+impl PartialEq for Watchers {
+    fn eq(&self, _: &Self) -> bool {
+        true
+    }
+}
+
+impl Eq for Watchers {}
+
+/// The metrics that an inventory updates, if any.
+///
+/// Like watchers, they are not inherited by a clone, so that it
+/// does not count twice, and are ignored when inventories are
+/// compared.
+#[cfg(feature = "metrics")]
+#[derive(Debug, Default)]
+struct Meter(Option<Arc<Metrics>>);
+
+#[cfg(feature = "metrics")]
+impl Clone for Meter {
+    fn clone(&self) -> Self {
+        Self::default()
+    }
+}
+
+#[cfg(feature = "metrics")]
+impl PartialEq for Meter {
+    fn eq(&self, _: &Self) -> bool {
+        true
+    }
+}
+
+#[cfg(feature = "metrics")]
+impl Eq for Meter {}
+
+/// An inventory of the devices seen on a network, each with when
+/// it was first and last seen, how many times it was seen, and any
+/// tags (for example, an owner or the IP address it last used).
+///
+/// Feed it observations from any source, such as frames from a
+/// capture or entries from the host's neighbor table, each with
+/// the time it was made.  Observations need not arrive in order.
+///
+/// To be told when a new device appears, for example to alert on
+/// rogue devices, register a callback with `watch` or a channel
+/// with `watch_channel`.  A clone of an inventory does not inherit
+/// its watchers.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Inventory {
+    devices: BTreeMap<[u8; 6], Device>,
+    watchers: Watchers,
+    #[cfg(feature = "metrics")]
+    meter: Meter,
+}
+
+impl Inventory {
+    /// Instantiates an empty `Inventory`.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the number of devices.
+    pub fn len(&self) -> usize {
+        self.devices.len()
+    }
+
+    /// Whether the inventory has no devices.
+    pub fn is_empty(&self) -> bool {
+        self.devices.is_empty()
+    }
+
+    /// Counts observations, new devices, and the number of devices
+    /// in `metrics` from now on.
+    ///
+    /// This method requires the `metrics` feature.
+    #[cfg(feature = "metrics")]
+    pub fn set_metrics(&mut self, metrics: Arc<Metrics>) {
+        metrics.set_devices(self.devices.len());
+        self.meter = Meter(Some(metrics));
+    }
+
+    /// Updates the number of devices in the metrics, if any.
+    fn count_devices(&self) {
+        #[cfg(feature = "metrics")]
+        if let Some(metrics) = &self.meter.0 {
+            metrics.set_devices(self.devices.len());
+        }
+    }
+
+    /// Calls `callback` whenever an address is seen for the first
+    /// time, or, if `absence` is set, for the first time after going
+    /// unseen for at least that long.
+    ///
+    /// Observations older than the device's last sighting never
+    /// count as a return.
+    pub fn watch<F>(&mut self, absence: Option<Duration>, callback: F)
+    where
+        F: FnMut(&Sighting) + Send + 'static,
+    {
+        self.watchers.0.push((absence, Box::new(callback)));
+    }
+
+    /// Returns a channel that receives a `Sighting` whenever an
+    /// address is seen for the first time, or after an absence, as
+    /// `watch` describes.
+    ///
+    /// Sightings are dropped once the receiver is.
+    pub fn watch_channel(&mut self, absence: Option<Duration>) -> Receiver<Sighting> {
+        let (sender, receiver) = mpsc::channel();
+
+        self.watch(absence, move |sighting| {
+            let _ = sender.send(Sighting {
+                address: sighting.address,
+                at: sighting.at,
+                last_seen: sighting.last_seen,
+            });
+        });
+
+        receiver
+    }
+
+    /// Records that the MAC address was seen at `at` and returns
+    /// its device, adding one if the address is new.
+    ///
+    /// If the address is new, or returns after an absence, then the
+    /// watchers are called first.
+    pub fn observe(&mut self, address: &MediaAccessControlAddress, at: SystemTime) -> &Device {
+        let octets = address.to_octets();
+        let last_seen = self.devices.get(&octets).map(|device| device.last_seen);
+
+        for (absence, callback) in self.watchers.0.iter_mut() {
+            let notify = match (last_seen, absence) {
+                (None, _) => true,
+                (Some(last_seen), Some(absence)) => at
+                    .duration_since(last_seen)
+                    .is_ok_and(|gap| gap >= *absence),
+                (Some(_), None) => false,
+            };
+
+            if notify {
+                callback(&Sighting {
+                    address: MediaAccessControlAddress::from(octets),
+                    at,
+                    last_seen,
+                });
+            }
+        }
+
+        #[cfg(feature = "metrics")]
+        if let Some(metrics) = &self.meter.0 {
+            metrics.record_observation(last_seen.is_none());
+            metrics.set_devices(self.devices.len() + usize::from(last_seen.is_none()));
+        }
+
+        let device = self.devices.entry(octets).or_insert_with(|| Device {
+            address: octets,
+            first_seen: at,
+            last_seen: at,
+            observations: 0,
+            tags: BTreeMap::new(),
+        });
+
+        device.first_seen = device.first_seen.min(at);
+        device.last_seen = device.last_seen.max(at);
+        device.observations += 1;
+        device
+    }
+
+    /// Records the source address of an Ethernet frame, captured at
+    /// `at`.
+    ///
+    /// Only sources are recorded, since destinations are often
+    /// group addresses rather than devices.  Frames on link types
+    /// other than Ethernet, and Ethernet frames too short to hold a
+    /// header, are skipped.
+    ///
+    /// This method requires the `pcap` feature.
+    #[cfg(feature = "pcap")]
+    pub fn observe_frame(&mut self, frame: &Frame, at: SystemTime) {
+        if frame.link_type != LINKTYPE_ETHERNET {
+            return;
+        }
+
+        if let Ok((_, source)) = super::frame::addresses(&frame.data) {
+            self.observe(&source, at);
+        }
+    }
+
+    /// Records the entries of a neighbor table, read at `at`, and
+    /// tags each device with the `ip` address and `interface` that
+    /// it was last seen with.
+    ///
+    /// This method requires the `system` feature.
+    #[cfg(feature = "system")]
+    pub fn observe_neighbors(&mut self, neighbors: &[Neighbor], at: SystemTime) {
+        for neighbor in neighbors {
+            let address = neighbor.address.to_octets();
+            self.observe(&neighbor.address, at);
+
+            let device = self.devices.get_mut(&address).unwrap();
+
+            if device.last_seen == at {
+                device
+                    .tags
+                    .insert(String::from("ip"), neighbor.ip.to_string());
+                device
+                    .tags
+                    .insert(String::from("interface"), neighbor.interface.clone());
+            }
+        }
+    }
+
+    /// Adds the device, replacing any device with the same
+    /// address.
+    fn insert(&mut self, device: Device) {
+        self.devices.insert(device.address, device);
+        self.count_devices();
+    }
+
+    /// Returns the device with the MAC address, if it has been
+    /// seen.
+    pub fn get(&self, address: &MediaAccessControlAddress) -> Option<&Device> {
+        self.devices.get(&address.to_octets())
+    }
+
+    /// Sets the tag named `name` on the device with the MAC address
+    /// and returns its previous value.
+    ///
+    /// The address must have been seen.
+    pub fn tag(
+        &mut self,
+        address: &MediaAccessControlAddress,
+        name: &str,
+        value: &str,
+    ) -> Result<Option<String>, Error> {
+        let device = self.devices.get_mut(&address.to_octets()).ok_or_else(|| {
+            Error::Invalid(format!(
+                "Pass in an address that the inventory has seen (not {}).",
+                address.to_colon_notation()
+            ))
+        })?;
+
+        Ok(device.tags.insert(name.to_string(), value.to_string()))
+    }
+
+    /// Removes the tag named `name` from the device with the MAC
+    /// address and returns its value, if it had one.
+    pub fn untag(&mut self, address: &MediaAccessControlAddress, name: &str) -> Option<String> {
+        self.devices
+            .get_mut(&address.to_octets())
+            .and_then(|device| device.tags.remove(name))
+    }
+
+    /// Forgets the device with the MAC address and returns it, if
+    /// it had been seen.
+    pub fn remove(&mut self, address: &MediaAccessControlAddress) -> Option<Device> {
+        let device = self.devices.remove(&address.to_octets());
+        self.count_devices();
+        device
+    }
+
+    /// Tags each device with its `vendor`, as reported by
+    /// `resolver`, and returns the number of devices tagged.
+    ///
+    /// Devices whose vendor is unknown lose any `vendor` tag.  If
+    /// the resolver fails for any device, then the error is
+    /// returned.
+    pub fn tag_vendors<R: VendorResolver>(&mut self, resolver: &R) -> Result<usize, Error> {
+        let mut tagged = 0;
+
+        for device in self.devices.values_mut() {
+            match resolver.resolve(&device.address())? {
+                Some(vendor) => {
+                    device.tags.insert(String::from("vendor"), vendor);
+                    tagged += 1;
+                }
+                None => {
+                    device.tags.remove("vendor");
+                }
+            }
+        }
+
+        Ok(tagged)
+    }
+
+    /// Returns the changes from this inventory to `other`, a later
+    /// snapshot (for example, from the next scheduled scan).
+    ///
+    /// Vendors are compared by their `vendor` tags, so tag both
+    /// snapshots with `tag_vendors` first.
+    pub fn diff(&self, other: &Inventory) -> InventoryDiff {
+        let mut diff = InventoryDiff::default();
+
+        for (octets, device) in &self.devices {
+            match other.devices.get(octets) {
+                Some(later) if later.vendor() != device.vendor() => {
+                    diff.changed_vendor.insert(&device.address());
+                }
+                Some(_) => {}
+                None => {
+                    diff.disappeared.insert(&device.address());
+                }
+            }
+        }
+
+        for (octets, device) in &other.devices {
+            if !self.devices.contains_key(octets) {
+                diff.appeared.insert(&device.address());
+            }
+        }
+
+        diff
+    }
+
+    /// Returns an iterator over the devices, sorted by address.
+    pub fn iter(&self) -> impl Iterator<Item = &Device> {
+        self.devices.values()
+    }
+
+    /// Returns an iterator over the devices last seen from `start`
+    /// up to, but not including, `end`, sorted by address.
+    pub fn seen_between(
+        &self,
+        start: SystemTime,
+        end: SystemTime,
+    ) -> impl Iterator<Item = &Device> {
+        self.devices
+            .values()
+            .filter(move |device| start <= device.last_seen && device.last_seen < end)
+    }
+}
+
+/// A place to keep an inventory between runs, such as a database,
+/// so that a long-running monitor survives restarts.
+///
+/// Implement this trait to plug in any backend.  With the `sqlite`
+/// feature, `SqliteStore` implements it with SQLite.
+pub trait InventoryStore {
+    /// Saves the device, replacing any device with the same address.
+    fn upsert(&mut self, device: &Device) -> Result<(), Error>;
+
+    /// Forgets the device with the MAC address, if there is one.
+    fn remove(&mut self, address: &MediaAccessControlAddress) -> Result<(), Error>;
+
+    /// Returns every device, sorted by address.
+    fn load(&self) -> Result<Vec<Device>, Error>;
+
+    /// Returns the devices last seen from `start` up to, but not
+    /// including, `end`, sorted by address.
+    fn seen_between(&self, start: SystemTime, end: SystemTime) -> Result<Vec<Device>, Error>;
+}
+
+/// An `Inventory` that writes every change through to an
+/// `InventoryStore`, so that it survives restarts.
+///
+/// Each observation upserts the device that it touches.  Reads are
+/// served from memory, except for `seen_between`, which the store
+/// answers, so that it can use an index.
+#[derive(Debug)]
+pub struct PersistentInventory<S> {
+    inventory: Inventory,
+    store: S,
+}
+
+impl<S: InventoryStore> PersistentInventory<S> {
+    /// Instantiates `PersistentInventory` with the devices already
+    /// in `store`.
+    pub fn open(store: S) -> Result<Self, Error> {
+        let mut inventory = Inventory::new();
+
+        for device in store.load()? {
+            inventory.insert(device);
+        }
+
+        Ok(Self { inventory, store })
+    }
+
+    /// Returns the inventory, for reading.
+    pub fn inventory(&self) -> &Inventory {
+        &self.inventory
+    }
+
+    /// Calls `callback` on new and returning addresses, as
+    /// `Inventory::watch` does.
+    pub fn watch<F>(&mut self, absence: Option<Duration>, callback: F)
+    where
+        F: FnMut(&Sighting) + Send + 'static,
+    {
+        self.inventory.watch(absence, callback);
+    }
+
+    /// Returns a channel that receives new and returning addresses,
+    /// as `Inventory::watch_channel` does.
+    pub fn watch_channel(&mut self, absence: Option<Duration>) -> Receiver<Sighting> {
+        self.inventory.watch_channel(absence)
+    }
+
+    /// Counts observations, new devices, and the number of devices
+    /// in `metrics`, as `Inventory::set_metrics` does.
+    ///
+    /// This method requires the `metrics` feature.
+    #[cfg(feature = "metrics")]
+    pub fn set_metrics(&mut self, metrics: Arc<Metrics>) {
+        self.inventory.set_metrics(metrics);
+    }
+
+    /// Returns the store.
+    pub fn store(&self) -> &S {
+        &self.store
+    }
+
+    /// Saves the device with the MAC address, if there is one.
+    fn save(&mut self, address: &MediaAccessControlAddress) -> Result<(), Error> {
+        match self.inventory.get(address) {
+            Some(device) => self.store.upsert(device),
+            None => Ok(()),
+        }
+    }
+
+    /// Records that the MAC address was seen at `at`, as
+    /// `Inventory::observe` does, and saves its device.
+    pub fn observe(
+        &mut self,
+        address: &MediaAccessControlAddress,
+        at: SystemTime,
+    ) -> Result<&Device, Error> {
+        self.inventory.observe(address, at);
+        self.save(address)?;
+        Ok(self.inventory.get(address).unwrap())
+    }
+
+    /// Records the source address of an Ethernet frame, as
+    /// `Inventory::observe_frame` does, and saves its device.
+    ///
+    /// This method requires the `pcap` feature.
+    #[cfg(feature = "pcap")]
+    pub fn observe_frame(&mut self, frame: &Frame, at: SystemTime) -> Result<(), Error> {
+        if frame.link_type != LINKTYPE_ETHERNET {
+            return Ok(());
+        }
+
+        match super::frame::addresses(&frame.data) {
+            Ok((_, source)) => self.observe(&source, at).map(|_| ()),
+            Err(_) => Ok(()),
+        }
+    }
+
+    /// Records the entries of a neighbor table, as
+    /// `Inventory::observe_neighbors` does, and saves their devices.
+    ///
+    /// This method requires the `system` feature.
+    #[cfg(feature = "system")]
+    pub fn observe_neighbors(
+        &mut self,
+        neighbors: &[Neighbor],
+        at: SystemTime,
+    ) -> Result<(), Error> {
+        self.inventory.observe_neighbors(neighbors, at);
+
+        for neighbor in neighbors {
+            self.save(&neighbor.address)?;
+        }
+
+        Ok(())
+    }
+
+    /// Sets a tag, as `Inventory::tag` does, and saves the device.
+    pub fn tag(
+        &mut self,
+        address: &MediaAccessControlAddress,
+        name: &str,
+        value: &str,
+    ) -> Result<Option<String>, Error> {
+        let previous = self.inventory.tag(address, name, value)?;
+        self.save(address)?;
+        Ok(previous)
+    }
+
+    /// Removes a tag, as `Inventory::untag` does, and saves the
+    /// device.
+    pub fn untag(
+        &mut self,
+        address: &MediaAccessControlAddress,
+        name: &str,
+    ) -> Result<Option<String>, Error> {
+        let previous = self.inventory.untag(address, name);
+        self.save(address)?;
+        Ok(previous)
+    }
+
+    /// Forgets the device with the MAC address, in memory and in the
+    /// store, and returns it, if it had been seen.
+    pub fn remove(&mut self, address: &MediaAccessControlAddress) -> Result<Option<Device>, Error> {
+        self.store.remove(address)?;
+        Ok(self.inventory.remove(address))
+    }
+
+    /// Returns the devices last seen from `start` up to, but not
+    /// including, `end`, sorted by address, as the store finds them.
+    pub fn seen_between(&self, start: SystemTime, end: SystemTime) -> Result<Vec<Device>, Error> {
+        self.store.seen_between(start, end)
+    }
+}
@@ -0,0 +1,195 @@
+use super::{Device, InventoryStore};
+use crate::error::Error;
+use crate::macaddress::MediaAccessControlAddress;
+use rusqlite::{params, Connection};
+use std::collections::BTreeMap;
+use std::path::Path;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+/// The tables, created if they are missing.  Devices are indexed by
+/// when they were last seen, for `seen_between`.
+const SCHEMA: &str = "
+    CREATE TABLE IF NOT EXISTS devices (
+        address INTEGER PRIMARY KEY,
+        first_seen INTEGER NOT NULL,
+        last_seen INTEGER NOT NULL,
+        observations INTEGER NOT NULL
+    );
+    CREATE INDEX IF NOT EXISTS devices_last_seen ON devices (last_seen);
+    CREATE TABLE IF NOT EXISTS tags (
+        address INTEGER NOT NULL REFERENCES devices (address) ON DELETE CASCADE,
+        name TEXT NOT NULL,
+        value TEXT NOT NULL,
+        PRIMARY KEY (address, name)
+    );
+";
+
+/// An `InventoryStore` that keeps the devices in a SQLite database,
+/// with each address as an integer and each time as nanoseconds from
+/// the UNIX epoch.
+///
+/// This type requires the `sqlite` feature.
+#[derive(Debug)]
+pub struct SqliteStore {
+    connection: Connection,
+}
+
+/// Describes a failure from SQLite.
+fn describe(error: rusqlite::Error) -> Error {
+    Error::Storage(error.to_string())
+}
+
+/// Converts a time into nanoseconds from the UNIX epoch, which are
+/// negative before it.
+fn to_nanos(time: SystemTime) -> i64 {
+    match time.duration_since(UNIX_EPOCH) {
+        Ok(after) => after.as_nanos() as i64,
+        Err(before) => -(before.duration().as_nanos() as i64),
+    }
+}
+
+/// Converts nanoseconds from the UNIX epoch back into a time.
+fn from_nanos(nanos: i64) -> SystemTime {
+    if nanos < 0 {
+        UNIX_EPOCH - Duration::from_nanos(nanos.unsigned_abs())
+    } else {
+        UNIX_EPOCH + Duration::from_nanos(nanos as u64)
+    }
+}
+
+impl SqliteStore {
+    /// Opens the SQLite database at `path`, creating it and its
+    /// tables if they are missing.
+    pub fn open<P: AsRef<Path>>(path: P) -> Result<Self, Error> {
+        Self::with_connection(Connection::open(path).map_err(describe)?)
+    }
+
+    /// Opens a SQLite database in memory, which lasts only as long
+    /// as the store.
+    pub fn open_in_memory() -> Result<Self, Error> {
+        Self::with_connection(Connection::open_in_memory().map_err(describe)?)
+    }
+
+    /// Creates the tables, if they are missing.
+    fn with_connection(connection: Connection) -> Result<Self, Error> {
+        connection
+            .execute_batch("PRAGMA foreign_keys = ON;")
+            .and_then(|_| connection.execute_batch(SCHEMA))
+            .map_err(describe)?;
+
+        Ok(Self { connection })
+    }
+
+    /// Returns the devices that match the condition on `devices`,
+    /// sorted by address.
+    fn select(&self, condition: &str, bounds: &[i64]) -> Result<Vec<Device>, Error> {
+        let mut tags: BTreeMap<i64, BTreeMap<String, String>> = BTreeMap::new();
+        let mut statement = self
+            .connection
+            .prepare(&format!(
+                "SELECT tags.address, tags.name, tags.value FROM tags \
+                 JOIN devices ON devices.address = tags.address WHERE {}",
+                condition
+            ))
+            .map_err(describe)?;
+        let rows = statement
+            .query_map(rusqlite::params_from_iter(bounds), |row| {
+                Ok((row.get(0)?, row.get(1)?, row.get(2)?))
+            })
+            .map_err(describe)?;
+
+        for row in rows {
+            let (address, name, value) = row.map_err(describe)?;
+            tags.entry(address).or_default().insert(name, value);
+        }
+
+        let mut statement = self
+            .connection
+            .prepare(&format!(
+                "SELECT address, first_seen, last_seen, observations FROM devices \
+                 WHERE {} ORDER BY address",
+                condition
+            ))
+            .map_err(describe)?;
+        let rows = statement
+            .query_map(rusqlite::params_from_iter(bounds), |row| {
+                Ok((row.get(0)?, row.get(1)?, row.get(2)?, row.get(3)?))
+            })
+            .map_err(describe)?;
+        let mut devices = Vec::new();
+
+        for row in rows {
+            let (address, first_seen, last_seen, observations): (i64, i64, i64, i64) =
+                row.map_err(describe)?;
+            let device = Device::from_parts(
+                &MediaAccessControlAddress::from_octets(&(address as u64).to_be_bytes()[2..])?,
+                from_nanos(first_seen),
+                from_nanos(last_seen),
+                observations as u64,
+                tags.remove(&address).unwrap_or_default(),
+            )
+            .map_err(|error| Error::Storage(error.to_string()))?;
+
+            devices.push(device);
+        }
+
+        Ok(devices)
+    }
+}
+
+impl InventoryStore for SqliteStore {
+    fn upsert(&mut self, device: &Device) -> Result<(), Error> {
+        let address = device.address().to_decimal_representation() as i64;
+        let transaction = self.connection.transaction().map_err(describe)?;
+
+        transaction
+            .execute(
+                "INSERT INTO devices (address, first_seen, last_seen, observations) \
+                 VALUES (?1, ?2, ?3, ?4) \
+                 ON CONFLICT (address) DO UPDATE SET first_seen = excluded.first_seen, \
+                 last_seen = excluded.last_seen, observations = excluded.observations",
+                params![
+                    address,
+                    to_nanos(device.first_seen()),
+                    to_nanos(device.last_seen()),
+                    device.observations() as i64
+                ],
+            )
+            .map_err(describe)?;
+        transaction
+            .execute("DELETE FROM tags WHERE address = ?1", params![address])
+            .map_err(describe)?;
+
+        for (name, value) in device.tags() {
+            transaction
+                .execute(
+                    "INSERT INTO tags (address, name, value) VALUES (?1, ?2, ?3)",
+                    params![address, name, value],
+                )
+                .map_err(describe)?;
+        }
+
+        transaction.commit().map_err(describe)
+    }
+
+    fn remove(&mut self, address: &MediaAccessControlAddress) -> Result<(), Error> {
+        self.connection
+            .execute(
+                "DELETE FROM devices WHERE address = ?1",
+                params![address.to_decimal_representation() as i64],
+            )
+            .map(|_| ())
+            .map_err(describe)
+    }
+
+    fn load(&self) -> Result<Vec<Device>, Error> {
+        self.select("1", &[])
+    }
+
+    fn seen_between(&self, start: SystemTime, end: SystemTime) -> Result<Vec<Device>, Error> {
+        self.select(
+            "devices.last_seen >= ?1 AND devices.last_seen < ?2",
+            &[to_nanos(start), to_nanos(end)],
+        )
+    }
+}
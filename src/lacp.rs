@@ -0,0 +1,106 @@
+use super::error::Error;
+use super::macaddress::MediaAccessControlAddress;
+use std::fmt;
+use std::str::FromStr;
+
+/// `LacpSystemId` identifies an IEEE 802.1AX (LACP) system,
+/// or actor/partner, with a 16-bit system priority and a MAC
+/// address.
+///
+/// System IDs compare the way LACP does: the lower priority
+/// wins, and the numerically lower MAC address breaks ties.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct LacpSystemId {
+    priority: u16,
+    system: [u8; 6],
+}
+
+impl LacpSystemId {
+    /// Instantiates `LacpSystemId` with a system priority and
+    /// a MAC address.
+    pub fn new(priority: u16, system: &MediaAccessControlAddress) -> Self {
+        Self {
+            priority,
+            system: system.to_octets(),
+        }
+    }
+
+    /// Instantiates `LacpSystemId` with its 8-octet on-wire
+    /// encoding (the priority in network byte order, then the
+    /// MAC address).
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, Error> {
+        if bytes.len() == 8 {
+            let mut system = [0; 6];
+            system.copy_from_slice(&bytes[2..]);
+
+            Ok(Self {
+                priority: u16::from_be_bytes([bytes[0], bytes[1]]),
+                system,
+            })
+        } else {
+            Err(Error::Invalid(String::from("Pass in 8 octets.")))
+        }
+    }
+
+    /// Returns the system priority.
+    pub fn priority(&self) -> u16 {
+        self.priority
+    }
+
+    /// Returns the MAC address.
+    pub fn system(&self) -> MediaAccessControlAddress {
+        MediaAccessControlAddress::from(self.system)
+    }
+
+    /// Returns the 8-octet on-wire encoding (the priority in
+    /// network byte order, then the MAC address).
+    pub fn to_bytes(&self) -> [u8; 8] {
+        let mut bytes = [0; 8];
+        bytes[..2].copy_from_slice(&self.priority.to_be_bytes());
+        bytes[2..].copy_from_slice(&self.system);
+        bytes
+    }
+}
+
+/// Parses the `priority,address` textual form (for example,
+/// `32768,a0-b1-c2-d3-e4-f5`), optionally wrapped in parentheses
+/// as IEEE 802.1AX writes it.  The address may be in any notation
+/// that `MediaAccessControlAddress::new` accepts.
+impl FromStr for LacpSystemId {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        let s = s
+            .strip_prefix('(')
+            .and_then(|s| s.strip_suffix(')'))
+            .unwrap_or(s);
+
+        let (priority, system) = s.split_once(',').ok_or_else(|| {
+            Error::Invalid(String::from(
+                "Pass in a priority and a MAC address separated by a comma.",
+            ))
+        })?;
+
+        let priority = priority
+            .trim()
+            .parse::<u16>()
+            .map_err(|_| Error::Invalid(String::from("Pass in a priority between 0 and 65535.")))?;
+        let system = MediaAccessControlAddress::new(system.trim())?;
+
+        Ok(Self::new(priority, &system))
+    }
+}
+
+/// Formats the system ID as `priority,address`, with the address
+/// in hyphen notation (for example, `32768,a0-b1-c2-d3-e4-f5`).
+impl fmt::Display for LacpSystemId {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{},{}",
+            self.priority,
+            self.system().to_hyphen_notation()
+        )
+    }
+}
@@ -4,34 +4,43 @@
 /// which you interact directly.  Using it is easy!
 ///
 /// ```
-/// /// Bring the `MediaAccessControlAddress` struct into scope in `main.rs`
-/// /// `lib.rs`, or some other relevant file.
+/// /// Bring the `MediaAccessControlAddress` struct (or its shorter alias,
+/// /// `MacAddress`) into scope in `main.rs`, `lib.rs`, or some other
+/// /// relevant file.
 ///
-/// use macaddress::macaddress::MediaAccessControlAddress;
+/// use macaddress::MediaAccessControlAddress;
 ///
 /// /// Instantiate `MediaAccessControlAddress` by calling the `new`
-/// /// method and passing in a MAC address in plain, hyphen, colon, or dot 
-/// /// notation.
+/// /// method and passing in a MAC address in plain, hyphen, colon, dot,
+/// /// four-digit-hyphen, space, or Solaris notation.
 ///
 /// /// Plain notation:
 ///
-/// let digits = String::from("a0b1c2d3e4f5");
-/// let mac = MediaAccessControlAddress::new(&digits).unwrap();
+/// let mac = MediaAccessControlAddress::new("a0b1c2d3e4f5").unwrap();
 ///
 /// /// Hyphen notation:
 ///
-/// let digits = String::from("a0-b1-c2-d3-e4-f5");
-/// let mac = MediaAccessControlAddress::new(&digits).unwrap();
+/// let mac = MediaAccessControlAddress::new("a0-b1-c2-d3-e4-f5").unwrap();
 ///
 /// /// Colon notation:
 ///
-/// let digits = String::from("a0:b1:c2:d3:e4:f5");
-/// let mac = MediaAccessControlAddress::new(&digits).unwrap();
+/// let mac = MediaAccessControlAddress::new("a0:b1:c2:d3:e4:f5").unwrap();
 ///
 /// /// Dot notation:
 ///
-/// let digits = String::from("a0b1.c2d3.e4f5");
-/// let mac = MediaAccessControlAddress::new(&digits).unwrap();
+/// let mac = MediaAccessControlAddress::new("a0b1.c2d3.e4f5").unwrap();
+///
+/// /// Four-digit-hyphen notation:
+///
+/// let mac = MediaAccessControlAddress::new("a0b1-c2d3-e4f5").unwrap();
+///
+/// /// Space notation:
+///
+/// let mac = MediaAccessControlAddress::new("A0 B1 C2 D3 E4 F5").unwrap();
+///
+/// /// Solaris notation:
+///
+/// let mac = MediaAccessControlAddress::new("0:3:ba:12:4:5").unwrap();
 ///
 /// /// Call one or more of `MediaAccessControlAddress`'s methods.
 ///
@@ -50,9 +59,30 @@
 /// let laa = mac.is_laa();
 /// println!("{}", &laa);
 ///
+/// let universal = mac.is_universal();
+/// println!("{}", &universal);
+///
+/// let local = mac.is_local();
+/// println!("{}", &local);
+///
+/// let bridge_filtered = mac.is_bridge_filtered();
+/// println!("{}", &bridge_filtered);
+///
+/// let ptp = mac.is_ptp();
+/// println!("{}", &ptp);
+///
+/// let documentation = mac.is_documentation();
+/// println!("{}", &documentation);
+///
+/// let iana = mac.is_iana_managed();
+/// println!("{}", &iana);
+///
 /// let kind = mac.kind();
 /// println!("{}", &kind);
 ///
+/// let cast = mac.cast();
+/// println!("{}", &cast);
+///
 /// let oui = mac.has_oui();
 /// println!("{}", &oui);
 ///
@@ -77,579 +107,859 @@
 /// let dot = mac.to_dot_notation();
 /// println!("{}", &dot);
 ///
+/// let four_digit_hyphen = mac.to_four_digit_hyphen_notation();
+/// println!("{}", &four_digit_hyphen);
+///
+/// let snmp = mac.to_snmp_notation();
+/// println!("{}", &snmp);
+///
+/// let solaris = mac.to_solaris_notation();
+/// println!("{}", &solaris);
+///
 /// let fragments = mac.to_fragments();
 /// println!("{:?}", &fragments);
+///
+/// let octets = mac.to_octets();
+/// println!("{:?}", &octets);
+/// ```
+pub mod macaddress;
+
+/// # The `error` module
+///
+/// This module contains `Error`, which covers every failure from
+/// this crate, so that applications can propagate any of them with
+/// `?`.
+///
+/// ```
+/// use macaddress::{Error, MediaAccessControlAddress};
+///
+/// fn parse(digits: &str) -> Result<MediaAccessControlAddress, Error> {
+///     let mac = MediaAccessControlAddress::new(digits)?;
+///     Ok(mac)
+/// }
+///
+/// match parse("a0:b1:c2:d3:e4") {
+///     Err(Error::Parse(error)) => println!("{}", &error),
+///     _ => unreachable!(),
+/// }
+/// ```
+pub mod error;
+
+/// # The `evpn` module
+///
+/// This module contains `EthernetSegmentId`, which identifies an
+/// EVPN Ethernet segment and can embed a MAC address.
+///
+/// ```
+/// use macaddress::evpn::EthernetSegmentId;
+/// use macaddress::MediaAccessControlAddress;
+///
+/// let mac = MediaAccessControlAddress::new("a0:b1:c2:d3:e4:f5").unwrap();
+///
+/// let esi = EthernetSegmentId::type3(&mac, 1).unwrap();
+/// println!("{}", &esi);
+///
+/// let esi: EthernetSegmentId = "03:0a:1b:2c:3d:4e:5f:00:00:02".parse().unwrap();
+/// println!("{:?}", &esi.mac());
+/// println!("{:?}", &esi.local_discriminator());
+/// ```
+pub mod evpn;
+
+/// # The `bloom` module
+///
+/// This module contains `MacBloom`, a Bloom filter that holds
+/// very large sets of MAC addresses in little memory, at a
+/// false-positive rate of your choice.
+///
+/// ```
+/// use macaddress::bloom::MacBloom;
+/// use macaddress::MediaAccessControlAddress;
+///
+/// let mut seen = MacBloom::new(1_000_000, 0.001).unwrap();
+///
+/// let mac = MediaAccessControlAddress::new("a0:b1:c2:d3:e4:f5").unwrap();
+/// seen.insert(&mac);
+///
+/// let bytes = seen.to_bytes();
+/// let restored = MacBloom::from_bytes(&bytes).unwrap();
+/// assert!(restored.contains(&mac));
+/// ```
+pub mod bloom;
+
+/// # The `capture` module
+///
+/// This module contains `Capture`, which reads the frames in a
+/// pcap or pcapng file, `observe`, which lists the unique MAC
+/// addresses in one, and (on Linux) `LiveCapture`, which feeds the
+/// frames on an interface into a `Monitor`.  Enable the `pcap`
+/// feature to use it.
+///
+/// ```no_run
+/// use macaddress::capture;
+/// use std::fs::File;
+/// use std::io::BufReader;
+///
+/// let file = BufReader::new(File::open("capture.pcapng").unwrap());
+///
+/// for observation in capture::observe(file).unwrap() {
+///     println!(
+///         "{} sent {} and received {} frames",
+///         &observation.address.to_colon_notation(),
+///         &observation.source_frames,
+///         &observation.destination_frames
+///     );
+/// }
+/// ```
+#[cfg(feature = "pcap")]
+pub mod capture;
+
+/// # The `classification` module
+///
+/// This module contains the `Kind` and `Cast` enums, which
+/// classify an address and parse from and render to stable
+/// strings.
+///
+/// ```
+/// use macaddress::classification::{Cast, Kind};
+/// use macaddress::MediaAccessControlAddress;
+///
+/// let mac = MediaAccessControlAddress::new("a0:b1:c2:d3:e4:f5").unwrap();
+///
+/// let kind: Kind = "unique".parse().unwrap();
+/// assert_eq!(mac.kind(), kind);
+/// assert_eq!(mac.cast().to_string(), "unicast");
+/// ```
+pub mod classification;
+
+/// # The `cli_parse` module
+///
+/// This module contains `parse`, which reads the MAC address table
+/// that a switch prints for `show mac address-table` (on Cisco IOS
+/// and NX-OS and Arista EOS) or `show ethernet-switching table` (on
+/// Junos) into a `MacTableEntry` per address, for when screen-scraped
+/// text is all that you have.
+///
+/// ```
+/// use macaddress::cli_parse::{self, EntryKind};
+///
+/// let text = "
+/// Vlan    Mac Address       Type        Ports
+/// ----    -----------       --------    -----
+///   10    a0b1.c2d3.e4f5    DYNAMIC     Gi1/0/1
+/// Total Mac Addresses for this criterion: 1
+/// ";
+///
+/// let entries = cli_parse::parse(text);
+/// assert_eq!(entries[0].vlan_id(), Some(10));
+/// assert_eq!(entries[0].interface, "Gi1/0/1");
+/// assert_eq!(entries[0].kind, EntryKind::Dynamic);
+/// ```
+pub mod cli_parse;
+
+/// # The `csv` module
+///
+/// This module contains `MacColumn`, which reads and normalizes the
+/// MAC address column of a CSV file whose addresses mix notations
+/// and casing.
+///
+/// ```
+/// use macaddress::csv::MacColumn;
+/// use macaddress::format::Notation;
+///
+/// let input = "host,MAC\nprinter,A0-B1-C2-D3-E4-F5\nswitch,a0b1.c2d3.e4f6\n";
+/// let mut output = Vec::new();
+///
+/// let column = MacColumn::named("mac").with_notation(Notation::Colon);
+/// column.normalize(input.as_bytes(), &mut output).unwrap();
+///
+/// assert_eq!(
+///     String::from_utf8(output).unwrap(),
+///     "host,MAC\nprinter,a0:b1:c2:d3:e4:f5\nswitch,a0:b1:c2:d3:e4:f6\n"
+/// );
+/// ```
+pub mod csv;
+
+/// # The `datalink` module
+///
+/// This module contains `DataLinkAddress`, which holds a MAC
+/// address, EUI-64, InfiniBand GUID, or Bluetooth address, so that
+/// heterogeneous inventories fit in one collection, and
+/// `parse_any`, which tells MAC addresses and EUI-64s apart.
+///
 /// ```
-pub mod macaddress {
-    use super::utils;
-
-    /// `MediaAccessControlAddress` makes it easy to work with
-    /// media access control (MAC) addresses.
-    ///
-    /// MAC addresses have properties of their own, but they also
-    /// inherit/share the properties of 48-bit IEEE extended identifiers.
-    ///
-    /// Extended identifiers are either extended unique identifiers
-    /// (EUI) or extended local identifiers (ELI).  EUIs have
-    /// organizationally-unique identifiers (OUO), while ELIs have
-    /// company IDs (CID).
-    ///
-    /// For more information, visit the following URL:
-    /// <https://standards.ieee.org/products-services/regauth/tut/index.html>.
-    #[derive(Debug)]
-    pub struct MediaAccessControlAddress {
-        value: String,
-    }
-
-    impl MediaAccessControlAddress {
-        /// Instantiates `MediaAccessControlAddress` with
-        /// 12 hexadecimal digits (`0-9`, `A-F`, or `a-f`) in
-        /// plain, hyphen, colon, or dot notation.
-        pub fn new(digits: &str) -> Result<Self, String> {
-            if utils::NOTATIONS.is_match(&digits) {
-                let address = utils::clean(&digits);
-                Ok(Self { value: address })
-            } else {
-                Err(String::from("Pass in 12 hexadecimal digits."))
-            }
-        }
-
-        /// Returns the binary representation of the MAC address.
-        /// *The most-significant digit of each octet appears first.*
-        pub fn to_binary_representation(&self) -> String {
-            let binary: Vec<String> = utils::TWO_DIGITS
-                .find_iter(&self.value)
-                .map(|element| {
-                    let element = element.as_str();
-                    let decimal = usize::from_str_radix(&element, 16).unwrap();
-                    format!("{:08b}", &decimal)
-                })
-                .collect();
-
-            binary.join("")
-        }
-
-        /// Returns the decimal representation of the MAC address.
-        pub fn to_decimal_representation(&self) -> usize {
-            let binary = self.to_binary_representation();
-            usize::from_str_radix(&binary, 2).unwrap()   
-        }
-
-        /// Returns the MAC address in plain notation
-        /// (for example, `a0b1c2d3e4f5`).
-        pub fn to_plain_notation(&self) -> String {
-            self.value.to_string()
-        }
-
-        /// Returns the MAC address in hyphen notation
-        /// (for example, `a0-b1-c2-d3-e4-f5`).
-        pub fn to_hyphen_notation(&self) -> String {
-            let hyphen: Vec<&str> = utils::TWO_DIGITS
-                .find_iter(&self.value)
-                .map(|element| element.as_str())
-                .collect();
-
-            hyphen.join("-")
-        }
-
-        /// Returns the MAC address in colon notation
-        /// (for example, `a0:b1:c2:d3:e4:f5`).
-        pub fn to_colon_notation(&self) -> String {
-            let colon: Vec<&str> = utils::TWO_DIGITS
-                .find_iter(&self.value)
-                .map(|element| element.as_str())
-                .collect();
-
-            colon.join(":")
-        }
-
-        /// Returns the MAC address in dot notation
-        /// (for example, `a0b1.c2d3.e4f5`).
-        pub fn to_dot_notation(&self) -> String {
-            let dot: Vec<&str> = utils::FOUR_DIGITS
-                .find_iter(&self.value)
-                .map(|element| element.as_str())
-                .collect();
-
-            dot.join(".")
-        }
-
-        /// Returns the MAC address's two "fragments,"
-        /// where the first 24 bits are an OUI or CID and
-        /// the second 24 bits are specific to an interface
-        /// (for example, `(a0b1c2, d3e4f5)`.
-        pub fn to_fragments(&self) -> (&str, &str) {
-            let (first, second) = &self.value.split_at(6);
-            (first, second)
-        }
-
-        /// Returns the MAC address's kind, where kind is
-        /// `unique`, `local`, or `unknown`.
-        ///
-        /// The two least-significant bits in the first octet
-        /// of a MAC address/extended identifier determine
-        /// whether it is an EUI (`00` = `unique`).
-        ///
-        /// The four least-significant bits in the first octet
-        /// of a MAC address/extended identifier determine
-        /// whether it is an ELI (`1010` = `local`).
-        pub fn kind(&self) -> String {
-            let binary = self.to_binary_representation();
-
-            if &binary[6..8] == "00" {
-                String::from("unique")
-            } else if &binary[4..8] == "1010" {
-                String::from("local")
-            } else {
-                String::from("unknown")
-            }
-        }
-
-        /// Whether the MAC address/extended identifier has
-        /// an OUI.
-        ///
-        /// If the MAC address/exended identifier is an EUI,
-        /// then it has an OUI.
-        pub fn has_oui(&self) -> bool {
-            self.kind() == "unique"
-        }
-
-        /// Whether the MAC address/extended identifier has
-        /// a CID.
-        ///
-        /// If the MAC address/extended identifier is an ELI,
-        /// then it has a CID.
-        pub fn has_cid(&self) -> bool {
-            self.kind() == "local"
-        }
-
-        /// Whether the MAC address is a broadcast address
-        /// (`ffffffffffff` = broadcast).
-        pub fn is_broadcast(&self) -> bool {
-            let address = &self.value;
-            address == "ffffffffffff"
-        }
-
-        /// Whether the MAC address is a multicast address
-        /// (layer-two multicast, not layer-three multicast).
-        ///
-        /// The least-significant bit in the first octet of
-        /// a MAC address determines whether it is a multicast
-        /// or a unicast (`1` = multicast).
-        pub fn is_multicast(&self) -> bool {
-            let binary = self.to_binary_representation();
-            &binary[7..8] == "1"
-        }
-
-        /// Whether the MAC address is a unicast address.
-        ///
-        /// The least-significant bit in the first octet of
-        /// a MAC address determines whether it is a multicast
-        /// or a unicast (`0` = unicast).
-        pub fn is_unicast(&self) -> bool {
-            !self.is_multicast()
-        }
-
-        /// Whether the MAC address is a universally-administered
-        /// address (UAA).
-        ///
-        /// The second-least-significant bit in the first octet of
-        /// a MAC address determines whether it is a UAA or an LAA
-        /// (`0` = UAA).
-        pub fn is_uaa(&self) -> bool {
-            let binary = self.to_binary_representation();
-            self.is_unicast() && &binary[6..7] == "0"
-        }
-
-        /// Whether the MAC address is a locally-administered
-        /// address (LAA).
-        ///
-        /// The second-least-significant bit in the first octet of
-        /// a MAC address determines whether it is a UAA or an LAA
-        /// (`1` = LAA).
-        pub fn is_laa(&self) -> bool {
-            let binary = self.to_binary_representation();
-            self.is_unicast() && &binary[6..7] == "1"
-        }
-    }
-}
+/// use macaddress::datalink::DataLinkAddress;
+/// use macaddress::{Eui64, MediaAccessControlAddress};
+///
+/// let inventory = vec![
+///     DataLinkAddress::Mac48(MediaAccessControlAddress::new("a0b1c2d3e4f5").unwrap()),
+///     DataLinkAddress::InfinibandGuid(Eui64::new("0002c90300a1b2c3").unwrap()),
+///     DataLinkAddress::Bluetooth(MediaAccessControlAddress::new("00:1a:7d:da:71:13").unwrap()),
+/// ];
+///
+/// for address in inventory.iter() {
+///     println!("{} {} {:?}", &address, &address.kind(), &address.to_bytes());
+/// }
+/// ```
+pub mod datalink;
+
+/// # The `dhcp` module
+///
+/// This module contains `parse_dnsmasq` and `parse_dhcpd`, which
+/// read the lease files of dnsmasq and ISC dhcpd into a `Lease` for
+/// each client, with its MAC address, IP address, hostname, and
+/// expiry.
+///
+/// ```
+/// use macaddress::dhcp;
+///
+/// let text = "1760000000 a0:b1:c2:d3:e4:f5 192.168.1.10 laptop 01:a0:b1:c2:d3:e4:f5\n";
+///
+/// for lease in dhcp::parse_dnsmasq(text).unwrap() {
+///     println!(
+///         "{} {} {:?} {:?}",
+///         &lease.address.to_colon_notation(),
+///         &lease.ip,
+///         &lease.hostname,
+///         &lease.address.kind()
+///     );
+/// }
+/// ```
+pub mod dhcp;
+
+/// # The `eui64` module
+///
+/// This module contains `Eui64`, which works with 64-bit extended
+/// unique identifiers the same way `MediaAccessControlAddress`
+/// works with MAC addresses.
+///
+/// ```
+/// use macaddress::Eui64;
+///
+/// let eui = Eui64::new("a0-b1-c2-ff-fe-d3-e4-f5").unwrap();
+///
+/// println!("{}", &eui.to_colon_notation());
+/// println!("{}", &eui.is_uaa());
+/// ```
+pub mod eui64;
+
+/// # The `export` module
+///
+/// This module contains `Exporter`, which writes an inventory of
+/// MAC addresses as CSV or JSON, with each address's
+/// classification and vendor.
+///
+/// ```
+/// use macaddress::export::{Encoding, Exporter};
+/// use macaddress::MediaAccessControlAddress;
+/// use std::collections::HashMap;
+///
+/// let mut vendors = HashMap::new();
+/// vendors.insert(0x00000c, String::from("Cisco Systems, Inc"));
+///
+/// let inventory = vec![
+///     MediaAccessControlAddress::new("00:00:0c:07:ac:01").unwrap(),
+///     MediaAccessControlAddress::new("a0:b1:c2:d3:e4:f5").unwrap(),
+/// ];
+///
+/// let exporter = Exporter::new(vendors, Encoding::Csv);
+/// exporter.write(&inventory, std::io::stdout()).unwrap();
+/// ```
+pub mod export;
+
+/// # The `fcoe` module
+///
+/// This module contains `Fpma`, a Fibre Channel over Ethernet
+/// (FCoE) Fabric-Provided MAC Address, which joins an FC-MAP and an
+/// FC_ID.
+///
+/// ```
+/// use macaddress::fcoe::Fpma;
+///
+/// let fpma = Fpma::new(0x0efc00, 0x010203).unwrap();
+/// let mac = fpma.address();
+///
+/// assert_eq!(mac.to_colon_notation(), "0e:fc:00:01:02:03");
+/// assert_eq!(Fpma::from_address(&mac).unwrap().fc_id(), 0x010203);
+/// ```
+pub mod fcoe;
+
+/// # The `fingerprint` module
+///
+/// This module contains `fingerprint`, which judges whether an
+/// address likely belongs to a physical, virtual, or randomized
+/// interface, and which well-known protocol, if any, it is
+/// reserved for.
+///
+/// ```
+/// use macaddress::fingerprint::{fingerprint, Origin};
+/// use macaddress::MediaAccessControlAddress;
+/// use std::collections::HashMap;
+///
+/// let mut vendors = HashMap::new();
+/// vendors.insert(0x005056, String::from("VMware, Inc."));
+///
+/// let mac = MediaAccessControlAddress::new("00:50:56:a1:b2:c3").unwrap();
+///
+/// let report = fingerprint(&mac, &vendors).unwrap();
+/// assert_eq!(report.origin, Some(Origin::Virtual));
+/// println!("{:?} {:?}", &report.platform, &report.vendor);
+/// ```
+pub mod fingerprint;
+
+/// # The `format` module
+///
+/// This module contains the `Notation` enum and functions that
+/// parse and format MAC addresses in a given notation, including
+/// `format_into`, which does not allocate and is useful on
+/// embedded targets.
+///
+/// ```
+/// use macaddress::format::{self, Notation, MAX_LEN};
+/// use macaddress::MediaAccessControlAddress;
+///
+/// let octets = format::parse("a0b1.c2d3.e4f5", Notation::Dot).unwrap();
+/// let colon = format::format(&octets, Notation::Colon);
+/// println!("{}", &colon);
+///
+/// let digits = String::from("a0b1c2d3e4f5");
+/// let mac = MediaAccessControlAddress::from_notation(&digits, Notation::Plain).unwrap();
+///
+/// let mut buffer = [0; MAX_LEN];
+/// let hyphen = mac.format_into(&mut buffer, Notation::Hyphen).unwrap();
+/// println!("{}", &hyphen);
+/// ```
+pub mod format;
+
+/// # The `frame` module
+///
+/// This module contains helpers that read MAC addresses out of
+/// raw Ethernet frames.
+///
+/// ```
+/// use macaddress::frame;
+///
+/// let bytes = [
+///     0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xa0, 0xb1, 0xc2, 0xd3, 0xe4, 0xf5, 0x08, 0x06,
+/// ];
+///
+/// let (destination, source) = frame::addresses(&bytes).unwrap();
+/// println!("{} -> {}", &source.to_colon_notation(), &destination.to_colon_notation());
+/// ```
+pub mod frame;
+
+/// # The `hash` module
+///
+/// This module contains functions that hash MAC addresses
+/// the same way network hardware does, along with a stable
+/// hash for partitioning addresses into buckets, SHA-256 for
+/// checking downloads, and HMAC-SHA-256 for keyed identifiers.
+///
+/// ```
+/// use macaddress::hash::multicast_filter_index;
+/// use macaddress::MediaAccessControlAddress;
+///
+/// let mac = MediaAccessControlAddress::new("01:00:5e:00:00:01").unwrap();
+///
+/// let index = multicast_filter_index(&mac, 6).unwrap();
+/// println!("{}", &index);
+/// ```
+pub mod hash;
+
+/// # The `identifier` module
+///
+/// This module contains `Identifier`, the const-generic extended
+/// identifier that backs both `MediaAccessControlAddress` and
+/// `Eui64`.
+pub mod identifier;
+
+/// # The `inventory` module
+///
+/// This module contains `Inventory`, which tracks the devices seen
+/// on a network with when each was first and last seen, how often,
+/// and any tags, and `Device`, which holds one of them.  It also
+/// contains `PersistentInventory`, which writes an inventory
+/// through to an `InventoryStore`, such as `SqliteStore` (with the
+/// `sqlite` feature), so that it survives restarts.
+///
+/// ```
+/// use macaddress::inventory::Inventory;
+/// use macaddress::MediaAccessControlAddress;
+/// use std::time::{Duration, SystemTime};
+///
+/// let mac = MediaAccessControlAddress::new("a0:b1:c2:d3:e4:f5").unwrap();
+/// let start = SystemTime::now();
+///
+/// let mut inventory = Inventory::new();
+/// inventory.observe(&mac, start);
+/// inventory.observe(&mac, start + Duration::from_secs(60));
+/// inventory.tag(&mac, "owner", "lab").unwrap();
+///
+/// let device = inventory.get(&mac).unwrap();
+/// assert_eq!(device.observations(), 2);
+/// assert_eq!(device.tag("owner"), Some("lab"));
+/// ```
+pub mod inventory;
+
+/// # The `lacp` module
+///
+/// This module contains `LacpSystemId`, which identifies a system
+/// taking part in IEEE 802.1AX link aggregation (LACP).
+///
+/// ```
+/// use macaddress::lacp::LacpSystemId;
+///
+/// let actor: LacpSystemId = "32768,a0-b1-c2-d3-e4-f5".parse().unwrap();
+/// let partner: LacpSystemId = "(100,0a-1b-2c-3d-4e-5f)".parse().unwrap();
+///
+/// println!("{}", &actor.min(partner));
+/// println!("{:?}", &actor.to_bytes());
+/// ```
+pub mod lacp;
+
+/// # The `metrics` module
+///
+/// This module contains `Metrics`, which counts what inventories and
+/// vendor lookups do and reports it in Prometheus's text format,
+/// and `MeteredResolver`, which counts a resolver's hits and misses.
+///
+/// This module requires the `metrics` feature.
+///
+/// ```
+/// use macaddress::inventory::Inventory;
+/// use macaddress::metrics::{MeteredResolver, Metrics};
+/// use macaddress::vendor::VendorResolver;
+/// use macaddress::MediaAccessControlAddress;
+/// use std::collections::HashMap;
+/// use std::sync::Arc;
+/// use std::time::SystemTime;
+///
+/// let metrics = Arc::new(Metrics::new());
+///
+/// let mut inventory = Inventory::new();
+/// inventory.set_metrics(Arc::clone(&metrics));
+///
+/// let vendors: HashMap<u32, String> = HashMap::new();
+/// let resolver = MeteredResolver::new(vendors, Arc::clone(&metrics));
+///
+/// let mac = MediaAccessControlAddress::new("a0:b1:c2:d3:e4:f5").unwrap();
+/// inventory.observe(&mac, SystemTime::now());
+/// resolver.resolve(&mac).unwrap();
+///
+/// println!("{}", metrics.to_prometheus());
+/// ```
+#[cfg(feature = "metrics")]
+pub mod metrics;
+
+/// # The `policy` module
+///
+/// This module contains `Policy`, an ordered list of allow and deny
+/// rules for addresses, prefixes, OUIs, wildcard masks, and
+/// classifications, which decides whether to let an address onto a
+/// network, as network access control and port-security tools do.
+///
+/// ```
+/// use macaddress::policy::Policy;
+/// use macaddress::MediaAccessControlAddress;
+///
+/// let policy: Policy = "
+///     deny randomized
+///     allow oui a0:b1:c2
+///     default deny
+/// "
+/// .parse()
+/// .unwrap();
+///
+/// let mac = MediaAccessControlAddress::new("a2:b1:c2:d3:e4:f5").unwrap();
+/// let decision = policy.decide(&mac);
+///
+/// assert!(!decision.is_allowed());
+/// println!("{}", &decision.rule().unwrap());
+/// ```
+pub mod policy;
+
+/// # The `prefix` module
+///
+/// This module contains `MacPrefix`, a block of MAC addresses
+/// that share their most-significant bits, such as every address
+/// under an OUI, and `MacPrefixMap`, which finds the most specific
+/// prefix that covers an address.
+///
+/// ```
+/// use macaddress::prefix::{MacPrefix, MacPrefixMap};
+/// use macaddress::MediaAccessControlAddress;
+///
+/// let prefix: MacPrefix = "a0:b1:c2:00:00:00/24".parse().unwrap();
+/// let mac = MediaAccessControlAddress::new("a0:b1:c2:d3:e4:f5").unwrap();
+///
+/// assert!(prefix.contains(&mac));
+/// println!("{} holds {} addresses", &prefix, &prefix.size());
+///
+/// let actions: MacPrefixMap<&str> = vec![
+///     (prefix, "allow"),
+///     ("a0:b1:c2:d0:00:00/28".parse().unwrap(), "deny"),
+/// ]
+/// .into_iter()
+/// .collect();
+///
+/// assert_eq!(actions.longest_match(&mac).unwrap().1, &"deny");
+/// ```
+pub mod prefix;
+
+/// # The `random` module
+///
+/// This module contains the `MacRng` trait and functions that
+/// generate random MAC addresses with it.  Enable the
+/// `rand` feature to draw from the operating system instead.
+///
+/// ```
+/// use macaddress::random::random_laa_with;
+///
+/// /// Plug in any source of random octets, such as a hardware RNG.
+///
+/// let mut state: u8 = 0;
+/// let mut rng = |octets: &mut [u8; 6]| {
+///     for octet in octets.iter_mut() {
+///         state = state.wrapping_mul(31).wrapping_add(7);
+///         *octet = state;
+///     }
+/// };
+///
+/// let mac = random_laa_with(&mut rng);
+/// println!("{}", &mac.to_colon_notation());
+/// ```
+pub mod random;
+
+/// # The `range` module
+///
+/// This module contains `MacRange`, an inclusive range of MAC
+/// addresses, and `summarize`, which collapses a list of addresses
+/// into ranges, which in turn split into the fewest prefixes that
+/// cover them.
+///
+/// ```
+/// use macaddress::range::{self, MacRange};
+/// use macaddress::MediaAccessControlAddress;
+///
+/// let range: MacRange = "a0:b1:c2:00:00:01-a0:b1:c2:00:00:06".parse().unwrap();
+///
+/// for prefix in range.to_prefixes() {
+///     println!("{}", &prefix);
+/// }
+///
+/// let addresses = vec![
+///     MediaAccessControlAddress::new("a0:b1:c2:00:00:02").unwrap(),
+///     MediaAccessControlAddress::new("a0:b1:c2:00:00:01").unwrap(),
+///     MediaAccessControlAddress::new("a0:b1:c2:00:00:09").unwrap(),
+/// ];
+///
+/// assert_eq!(range::summarize(&addresses).len(), 2);
+/// ```
+pub mod range;
+
+/// # The `report` module
+///
+/// This module contains `report`, which summarizes an inventory of
+/// MAC addresses by vendor, OUI, administration, origin, and
+/// protocol, and `Report`, which holds the summary and writes it as
+/// JSON for dashboards.
+///
+/// ```
+/// use macaddress::report::report;
+/// use macaddress::MediaAccessControlAddress;
+/// use std::collections::HashMap;
+///
+/// let mut vendors = HashMap::new();
+/// vendors.insert(0x00000c, String::from("Cisco Systems, Inc"));
+///
+/// let inventory: Vec<MediaAccessControlAddress> = [
+///     "00:00:0c:07:ac:01",
+///     "00:00:0c:9f:f0:01",
+///     "a2:b1:c2:d3:e4:f5",
+///     "00:00:0c:07:ac:01",
+/// ]
+/// .iter()
+/// .map(|digits| MediaAccessControlAddress::new(digits).unwrap())
+/// .collect();
+///
+/// let summary = report(&inventory, &vendors).unwrap();
+/// assert_eq!(summary.addresses, 3);
+/// assert_eq!(summary.vendors, vec![(String::from("Cisco Systems, Inc"), 2)]);
+/// println!("{}", summary.to_json());
+/// ```
+pub mod report;
+
+/// # The `wifi` module
+///
+/// This module contains functions that implement the
+/// IEEE 802.11 Multiple BSSID scheme, with which one radio
+/// advertises several virtual access points.
+///
+/// ```
+/// use macaddress::wifi::{multiple_bssids, transmitted_bssid};
+/// use macaddress::MediaAccessControlAddress;
+///
+/// let transmitted = MediaAccessControlAddress::new("a0:b1:c2:d3:e4:f5").unwrap();
+///
+/// let bssids = multiple_bssids(&transmitted, 2).unwrap();
+/// let recovered = transmitted_bssid(&bssids[3], 2, 3).unwrap();
+/// println!("{}", &recovered.to_colon_notation());
+/// ```
+pub mod wifi;
+
+/// # The `scan` module
+///
+/// This module contains `find_all`, which finds every MAC address
+/// in free text, such as a log file, in any supported notation,
+/// along with its byte offsets, and `Scanner`, which does the same
+/// for a reader, one line at a time.
+///
+/// ```
+/// use macaddress::scan;
+///
+/// let log = "DHCPACK(eth0) 192.168.1.10 a0:b1:c2:d3:e4:f5 laptop";
+///
+/// for found in scan::find_all(log) {
+///     println!(
+///         "{} at {}..{}",
+///         &found.address.to_colon_notation(),
+///         &found.start,
+///         &found.end
+///     );
+/// }
+/// ```
+pub mod scan;
+
+/// # The `scheme` module
+///
+/// This module contains `LaaScheme`, a bit-field layout over the
+/// locally-administered space that encodes fields such as a site,
+/// a VLAN, and a host into addresses and decodes them back.
+///
+/// ```
+/// use macaddress::scheme::LaaScheme;
+///
+/// let scheme: LaaScheme = "site:8,vlan:12,host:16".parse().unwrap();
+///
+/// let mac = scheme.encode(&[0x2a, 100, 261]).unwrap();
+/// assert_eq!(mac.to_colon_notation(), "02:02:a0:64:01:05");
+///
+/// assert_eq!(scheme.decode(&mac).unwrap(), vec![0x2a, 100, 261]);
+/// assert_eq!(scheme.get(&mac, "vlan").unwrap(), 100);
+/// ```
+pub mod scheme;
+
+/// # The `set` module
+///
+/// This module contains `MacSet`, an ordered set of MAC addresses
+/// that can list every address under a prefix, and
+/// `CompiledMacSet`, which reads large sets from a compact file
+/// without parsing them.
+///
+/// ```
+/// use macaddress::prefix::MacPrefix;
+/// use macaddress::set::MacSet;
+/// use macaddress::MediaAccessControlAddress;
+///
+/// let mut denied: MacSet = ["00:00:0c:07:ac:01", "a0:b1:c2:d3:e4:f5"]
+///     .iter()
+///     .map(|digits| MediaAccessControlAddress::new(digits).unwrap())
+///     .collect();
+///
+/// denied.extend(vec![MediaAccessControlAddress::new("a0:b1:c2:00:00:01").unwrap()]);
+///
+/// let oui: MacPrefix = "a0:b1:c2:00:00:00/24".parse().unwrap();
+/// assert_eq!(denied.range(&oui).count(), 2);
+/// ```
+pub mod set;
+
+/// # The `sort` module
+///
+/// This module contains `sort` and `sort_octets`, which radix-sort
+/// large lists of MAC addresses in linear time.
+///
+/// ```
+/// use macaddress::sort;
+/// use macaddress::MediaAccessControlAddress;
+///
+/// let mut addresses: Vec<MediaAccessControlAddress> =
+///     ["a0:b1:c2:d3:e4:f5", "00:00:0c:07:ac:01", "a0:b1:c2:00:00:01"]
+///         .iter()
+///         .map(|digits| MediaAccessControlAddress::new(digits).unwrap())
+///         .collect();
+///
+/// sort::sort(&mut addresses);
+/// assert_eq!(addresses[0].to_colon_notation(), "00:00:0c:07:ac:01");
+/// ```
+pub mod sort;
+
+/// # The `snmp` module
+///
+/// This module contains `SnmpClient`, which reads the forwarding
+/// database (the CAM table) of a switch over SNMPv2c, from
+/// Q-BRIDGE-MIB or BRIDGE-MIB, and returns an `FdbEntry` for each
+/// address that it has learned.  Enable the `snmp` feature to use
+/// it.
+///
+/// ```no_run
+/// use macaddress::snmp::SnmpClient;
+///
+/// let mut client = SnmpClient::new("switch.example.com:161", "public").unwrap();
+///
+/// for entry in client.fdb().unwrap() {
+///     println!(
+///         "{} is on port {} in VLAN {:?}",
+///         &entry.address.to_colon_notation(),
+///         &entry.port,
+///         &entry.vlan
+///     );
+/// }
+/// ```
+#[cfg(feature = "snmp")]
+pub mod snmp;
+
+/// # The `stp` module
+///
+/// This module contains `BridgeId`, which identifies a bridge
+/// taking part in IEEE 802.1D/802.1Q spanning tree.
+///
+/// ```
+/// use macaddress::stp::BridgeId;
+/// use macaddress::MediaAccessControlAddress;
+///
+/// let mac = MediaAccessControlAddress::new("a0:b1:c2:d3:e4:f5").unwrap();
+///
+/// let bridge = BridgeId::new(32768, 1, &mac).unwrap();
+/// let other: BridgeId = "1001.0a1b2c3d4e5f".parse().unwrap();
+///
+/// println!("{}", &bridge.min(other));
+/// ```
+pub mod stp;
+
+/// # The `system` module
+///
+/// This module contains functions that read MAC addresses from the
+/// host: `neighbors`, which returns its ARP and NDP table,
+/// `interfaces`, which returns its network interfaces, and
+/// `machine_id`, which derives a stable identifier for the host
+/// from its physical interfaces.  Enable the `system` feature to
+/// use it.
+///
+/// ```no_run
+/// use macaddress::system;
+///
+/// for neighbor in system::neighbors().unwrap() {
+///     println!(
+///         "{} is at {} on {}",
+///         &neighbor.ip,
+///         &neighbor.address.to_colon_notation(),
+///         &neighbor.interface
+///     );
+/// }
+///
+/// for interface in system::interfaces().unwrap() {
+///     println!("{} {:?}", &interface.name, &interface.source);
+/// }
+/// ```
+#[cfg(feature = "system")]
+pub mod system;
+
+/// # The `table` module
+///
+/// This module contains `MacTable`, an ordered map keyed by MAC
+/// address that answers prefix and nearest-neighbor queries.
+///
+/// ```
+/// use macaddress::prefix::MacPrefix;
+/// use macaddress::table::MacTable;
+/// use macaddress::MediaAccessControlAddress;
+///
+/// let mut ports = MacTable::new();
+/// ports.insert(&MediaAccessControlAddress::new("00:00:0c:07:ac:01").unwrap(), 1);
+/// ports.insert(&MediaAccessControlAddress::new("00:00:0c:9f:f0:01").unwrap(), 2);
+/// ports.insert(&MediaAccessControlAddress::new("a0:b1:c2:d3:e4:f5").unwrap(), 3);
+///
+/// let cisco = MacPrefix::oui(&MediaAccessControlAddress::new("00:00:0c:00:00:00").unwrap());
+///
+/// for (mac, port) in ports.range(&cisco) {
+///     println!("{} is on port {}", &mac.to_colon_notation(), &port);
+/// }
+/// ```
+pub mod table;
 
 /// # The `utils` module
 ///
-/// This module contains macros and functions required by the
-/// `macaddress` module.
-pub mod utils {
-    use lazy_static::lazy_static;
-    use regex::{Regex, RegexSet};
-
-    lazy_static! {
-        /// These patterns represent a MAC address in plain,
-        /// hyphen, colon, or dot notation.
-        pub static ref NOTATIONS: RegexSet = RegexSet::new(&[
-            "^[0-9A-Fa-f]{12}$",
-            "^([0-9A-Fa-f]{2}[-]{1}){5}[0-9A-Fa-f]{2}$",
-            "^([0-9A-Fa-f]{2}[:]{1}){5}[0-9A-Fa-f]{2}$",
-            "^([0-9A-Fa-f]{4}[.]{1}){2}[0-9A-Fa-f]{4}$"
-        ])
-        .unwrap();
-
-        /// This pattern represents any character that is not a
-        /// hexadecimal digit.
-        pub static ref NOT_DIGITS: Regex = Regex::new("[^0-9A-Fa-f]").unwrap();
-
-        /// This pattern represents a series of two hexadecimal
-        /// digits.
-        pub static ref TWO_DIGITS: Regex = Regex::new("[0-9a-f]{2}").unwrap();
-
-        /// This pattern represents a series of four hexadecimal
-        /// digits.
-        pub static ref FOUR_DIGITS: Regex = Regex::new("[0-9a-f]{4}").unwrap();
-    }
-
-    /// "Cleans" a MAC address by converting uppercase to lowercase 
-    /// letters and removing all hyphens, colons, and dots.
-    pub fn clean(digits: &str) -> String {
-        let lowercase = &digits.to_lowercase();
-        let clean = NOT_DIGITS.replace_all(&lowercase, "");
-        clean.into_owned()
-    }
-}
+/// This module contains the `validate` function and the
+/// `MacParseError` enum that it reports, the `diagnose` function
+/// and the `ParseError` struct that explains a failure, and other
+/// helpers required by the `macaddress` module.
+pub mod utils;
+
+/// # The `value_parser` module
+///
+/// This module contains `IdentifierParser`, a `clap` value parser
+/// for `MediaAccessControlAddress` and `Eui64`, so that
+/// command-line tools can take addresses as typed arguments.
+///
+/// This module requires the `clap` feature.
+///
+/// ```
+/// use clap::{value_parser, Arg, Command};
+/// use macaddress::MediaAccessControlAddress;
+///
+/// let matches = Command::new("wake")
+///     .arg(
+///         Arg::new("mac")
+///             .long("mac")
+///             .value_parser(value_parser!(MediaAccessControlAddress)),
+///     )
+///     .get_matches_from(["wake", "--mac", "A0-B1-C2-D3-E4-F5"]);
+///
+/// let mac = matches.get_one::<MediaAccessControlAddress>("mac").unwrap();
+/// println!("{}", &mac.to_colon_notation());
+/// ```
+#[cfg(feature = "clap")]
+pub mod value_parser;
+
+/// # The `vendor` module
+///
+/// This module contains the `VendorResolver` trait, which abstracts
+/// over backends that map MAC addresses to the organizations that
+/// registered them.  It also contains `VendorTable`, a compact
+/// backend that lives in static memory, `codegen`, which builds
+/// one from a registry CSV at compile time, `VendorFilter`, which
+/// trims the CSV to the vendors a build needs, `Enrich`, which pairs
+/// a stream of addresses with their vendors, `PhfVendorTable` and
+/// `codegen_phf`, which do the same with a perfect hash map (with
+/// the `phf` feature), and (with the `db`
+/// feature, which is on by default) `VendorDatabase`, which is
+/// loaded at runtime and updated with signed deltas.
+///
+/// ```
+/// use macaddress::vendor::{Record, VendorTable};
+/// use macaddress::MediaAccessControlAddress;
+///
+/// static VENDORS: VendorTable = VendorTable::new(&[Record {
+///     prefix: 0x00000c000000,
+///     length: 24,
+///     organization: "Cisco Systems, Inc",
+///     address: "170 West Tasman Drive San Jose CA US 95134",
+/// }]);
+///
+/// let mac = MediaAccessControlAddress::new("00:00:0c:07:ac:01").unwrap();
+///
+/// let vendor = mac.vendor_with(&VENDORS).unwrap();
+/// println!("{:?}", &vendor);
+/// ```
+pub mod vendor;
+
+pub use crate::error::Error;
+pub use crate::eui64::Eui64;
+pub use crate::identifier::Identifier;
+pub use crate::macaddress::MediaAccessControlAddress;
+pub use crate::macaddress::{PTP_PEER_DELAY, PTP_PRIMARY};
+
+/// A shorter name for `MediaAccessControlAddress`.
+pub use crate::macaddress::MediaAccessControlAddress as MacAddress;
 
 #[cfg(test)]
-mod tests {
-    use super::macaddress::MediaAccessControlAddress;
-
-    #[test]
-    #[should_panic]
-    fn test_invalid_addresses() {
-        let addresses = [
-            "0a",                 // Too few digits
-            "0a1b2c3d4e5f6",      // Too many digits
-            "0a1b2c3d4e5g",       // Invalid digit
-            "-0a-1b-2c-3d-4e-5f", // Leading hyphen
-            "0a-1b-2c-3d-4e-5f-", // Trailing hyphen
-            "0a-1b-2c-3d-4e5f",   // Missing hyphen
-            ":0a:1b:2c:3d:4e:5f", // Leading colon
-            "0a:1b:2c:3d:4e:5f:", // Trailing colon
-            "0a:1b:2c:3d:4e5f",   // Missing colon
-            ".0a1b.2c3d.4e5f",    // Leading dot
-            "0a1b.2c3d.4e5f.",    // Trailing dot
-            "0a1b.2c3d4e5f",      // Missing dot
-        ];
-
-        for element in addresses.into_iter() {
-            let digits = element.to_string();
-            MediaAccessControlAddress::new(&digits).unwrap();
-        }
-    }
-
-    // An EUI is a unicast address.
-    #[test]
-    fn test_unicast_eui_addresses() {
-        let addresses = [
-            (
-                "a0b1c2d3e4f5", // Plain notation (lowercase)
-                "101000001011000111000010110100111110010011110101",
-                176685338322165,
-                "a0b1c2d3e4f5",
-                "a0-b1-c2-d3-e4-f5",
-                "a0:b1:c2:d3:e4:f5",
-                "a0b1.c2d3.e4f5",
-                ("a0b1c2", "d3e4f5"),
-                "unique",
-                true,
-                false,
-                false,
-                false,
-                true,
-                true,
-                false,
-            ),
-            (
-                "A0B1C2D3E4F5", // Plain notation (uppercase)
-                "101000001011000111000010110100111110010011110101",
-                176685338322165,
-                "a0b1c2d3e4f5",
-                "a0-b1-c2-d3-e4-f5",
-                "a0:b1:c2:d3:e4:f5",
-                "a0b1.c2d3.e4f5",
-                ("a0b1c2", "d3e4f5"),
-                "unique",
-                true,
-                false,
-                false,
-                false,
-                true,
-                true,
-                false,
-            ),
-            (
-                "a0-b1-c2-d3-e4-f5", // Hyphen notation (lowercase)
-                "101000001011000111000010110100111110010011110101",
-                176685338322165,
-                "a0b1c2d3e4f5",
-                "a0-b1-c2-d3-e4-f5",
-                "a0:b1:c2:d3:e4:f5",
-                "a0b1.c2d3.e4f5",
-                ("a0b1c2", "d3e4f5"),
-                "unique",
-                true,
-                false,
-                false,
-                false,
-                true,
-                true,
-                false,
-            ),
-            (
-                "A0-B1-C2-D3-E4-F5", // Hyphen notation (uppercase)
-                "101000001011000111000010110100111110010011110101",
-                176685338322165,
-                "a0b1c2d3e4f5",
-                "a0-b1-c2-d3-e4-f5",
-                "a0:b1:c2:d3:e4:f5",
-                "a0b1.c2d3.e4f5",
-                ("a0b1c2", "d3e4f5"),
-                "unique",
-                true,
-                false,
-                false,
-                false,
-                true,
-                true,
-                false,
-            ),
-            (
-                "a0:b1:c2:d3:e4:f5", // Colon notation (lowercase)
-                "101000001011000111000010110100111110010011110101",
-                176685338322165,
-                "a0b1c2d3e4f5",
-                "a0-b1-c2-d3-e4-f5",
-                "a0:b1:c2:d3:e4:f5",
-                "a0b1.c2d3.e4f5",
-                ("a0b1c2", "d3e4f5"),
-                "unique",
-                true,
-                false,
-                false,
-                false,
-                true,
-                true,
-                false,
-            ),
-            (
-                "A0:B1:C2:D3:E4:F5", // Colon notation (uppercase)
-                "101000001011000111000010110100111110010011110101",
-                176685338322165,
-                "a0b1c2d3e4f5",
-                "a0-b1-c2-d3-e4-f5",
-                "a0:b1:c2:d3:e4:f5",
-                "a0b1.c2d3.e4f5",
-                ("a0b1c2", "d3e4f5"),
-                "unique",
-                true,
-                false,
-                false,
-                false,
-                true,
-                true,
-                false,
-            ),
-            (
-                "a0b1.c2d3.e4f5", // Dot notation (lowercase)
-                "101000001011000111000010110100111110010011110101",
-                176685338322165,
-                "a0b1c2d3e4f5",
-                "a0-b1-c2-d3-e4-f5",
-                "a0:b1:c2:d3:e4:f5",
-                "a0b1.c2d3.e4f5",
-                ("a0b1c2", "d3e4f5"),
-                "unique",
-                true,
-                false,
-                false,
-                false,
-                true,
-                true,
-                false,
-            ),
-            (
-                "A0B1.C2D3.E4F5", // Dot notation (uppercase)
-                "101000001011000111000010110100111110010011110101",
-                176685338322165,
-                "a0b1c2d3e4f5",
-                "a0-b1-c2-d3-e4-f5",
-                "a0:b1:c2:d3:e4:f5",
-                "a0b1.c2d3.e4f5",
-                ("a0b1c2", "d3e4f5"),
-                "unique",
-                true,
-                false,
-                false,
-                false,
-                true,
-                true,
-                false,
-            ),
-        ];
-
-        for element in addresses.into_iter() {
-            let digits = element.0.to_string();
-            let mac = MediaAccessControlAddress::new(&digits).unwrap();
-
-            assert_eq!(mac.to_binary_representation(), element.1);
-            assert_eq!(mac.to_decimal_representation(), element.2);
-            assert_eq!(mac.to_plain_notation(), element.3);
-            assert_eq!(mac.to_hyphen_notation(), element.4);
-            assert_eq!(mac.to_colon_notation(), element.5);
-            assert_eq!(mac.to_dot_notation(), element.6);
-
-            assert_eq!(mac.to_fragments(), element.7);
-            assert_eq!(mac.kind(), element.8);
-            assert_eq!(mac.has_oui(), element.9);
-            assert_eq!(mac.has_cid(), element.10);
-
-            assert_eq!(mac.is_broadcast(), element.11);
-            assert_eq!(mac.is_multicast(), element.12);
-            assert_eq!(mac.is_unicast(), element.13);
-            assert_eq!(mac.is_uaa(), element.14);
-            assert_eq!(mac.is_laa(), element.15);
-        }
-    }
-
-    // An ELI is a unicast address.
-    #[test]
-    fn test_unicast_eli_address() {
-        let address = (
-            "0a1b2c3d4e5f",
-            "000010100001101100101100001111010100111001011111",
-            11111822610015,
-            "0a1b2c3d4e5f",
-            "0a-1b-2c-3d-4e-5f",
-            "0a:1b:2c:3d:4e:5f",
-            "0a1b.2c3d.4e5f",
-            ("0a1b2c", "3d4e5f"),
-            "local",
-            false,
-            true,
-            false,
-            false,
-            true,
-            false,
-            true,
-        );
-
-        let digits = address.0.to_string();
-        let mac = MediaAccessControlAddress::new(&digits).unwrap();
-
-        assert_eq!(mac.to_binary_representation(), address.1);
-        assert_eq!(mac.to_decimal_representation(), address.2);
-        assert_eq!(mac.to_plain_notation(), address.3);
-        assert_eq!(mac.to_hyphen_notation(), address.4);
-        assert_eq!(mac.to_colon_notation(), address.5);
-        assert_eq!(mac.to_dot_notation(), address.6);
-
-        assert_eq!(mac.to_fragments(), address.7);
-        assert_eq!(mac.kind(), address.8);
-        assert_eq!(mac.has_oui(), address.9);
-        assert_eq!(mac.has_cid(), address.10);
-
-        assert_eq!(mac.is_broadcast(), address.11);
-        assert_eq!(mac.is_multicast(), address.12);
-        assert_eq!(mac.is_unicast(), address.13);
-        assert_eq!(mac.is_uaa(), address.14);
-        assert_eq!(mac.is_laa(), address.15);
-    }
-
-    #[test]
-    fn test_broadcast_address() {
-        let address = (
-            "ffffffffffff",
-            "111111111111111111111111111111111111111111111111",
-            281474976710655,
-            "ffffffffffff",
-            "ff-ff-ff-ff-ff-ff",
-            "ff:ff:ff:ff:ff:ff",
-            "ffff.ffff.ffff",
-            ("ffffff", "ffffff"),
-            "unknown",
-            false,
-            false,
-            true,
-            true,
-            false,
-            false,
-            false,
-        );
-
-        let digits = address.0.to_string();
-        let mac = MediaAccessControlAddress::new(&digits).unwrap();
-
-        assert_eq!(mac.to_binary_representation(), address.1);
-        assert_eq!(mac.to_decimal_representation(), address.2);
-        assert_eq!(mac.to_plain_notation(), address.3);
-        assert_eq!(mac.to_hyphen_notation(), address.4);
-        assert_eq!(mac.to_colon_notation(), address.5);
-        assert_eq!(mac.to_dot_notation(), address.6);
-
-        // These tests make little sense in the context
-        // of a broadcast address, but we run them for the
-        // sake of completeness.
-        assert_eq!(mac.to_fragments(), address.7);
-        assert_eq!(mac.kind(), address.8);
-        assert_eq!(mac.has_oui(), address.9);
-        assert_eq!(mac.has_cid(), address.10);
-
-        assert_eq!(mac.is_broadcast(), address.11);
-        assert_eq!(mac.is_multicast(), address.12);
-        assert_eq!(mac.is_unicast(), address.13);
-        assert_eq!(mac.is_uaa(), address.14);
-        assert_eq!(mac.is_laa(), address.15);
-    }
-
-    #[test]
-    fn test_multicast_address() {
-        let address = (
-            "0180c2000000", // Link-Layer Discovery Protocol
-            "000000011000000011000010000000000000000000000000",
-            1652522221568,
-            "0180c2000000",
-            "01-80-c2-00-00-00",
-            "01:80:c2:00:00:00",
-            "0180.c200.0000",
-            ("0180c2", "000000"),
-            "unknown",
-            false,
-            false,
-            false,
-            true,
-            false,
-            false,
-            false,
-        );
-
-        let digits = address.0.to_string();
-        let mac = MediaAccessControlAddress::new(&digits).unwrap();
-
-        assert_eq!(mac.to_binary_representation(), address.1);
-        assert_eq!(mac.to_decimal_representation(), address.2);
-        assert_eq!(mac.to_plain_notation(), address.3);
-        assert_eq!(mac.to_hyphen_notation(), address.4);
-        assert_eq!(mac.to_colon_notation(), address.5);
-        assert_eq!(mac.to_dot_notation(), address.6);
-
-        // These tests make little sense in the context
-        // of a multicast address, but we run them for the
-        // sake of completeness.
-        assert_eq!(mac.to_fragments(), address.7);
-        assert_eq!(mac.kind(), address.8);
-        assert_eq!(mac.has_oui(), address.9);
-        assert_eq!(mac.has_cid(), address.10);
-
-        assert_eq!(mac.is_broadcast(), address.11);
-        assert_eq!(mac.is_multicast(), address.12);
-        assert_eq!(mac.is_unicast(), address.13);
-        assert_eq!(mac.is_uaa(), address.14);
-        assert_eq!(mac.is_laa(), address.15);
-    }
-}
+mod tests;
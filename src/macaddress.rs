@@ -0,0 +1,445 @@
+use super::error::Error;
+use super::format::{self, Notation};
+use super::identifier::Identifier;
+use super::utils::{self, MacParseError, ParseError};
+use super::vendor::VendorResolver;
+use std::convert::TryFrom;
+use std::net::{Ipv4Addr, Ipv6Addr};
+use std::str::FromStr;
+
+/// The primary IEEE 1588 Precision Time Protocol (PTP) multicast
+/// address (`01-1b-19-00-00-00`).
+pub const PTP_PRIMARY: MediaAccessControlAddress =
+    MediaAccessControlAddress::from_array([0x01, 0x1b, 0x19, 0x00, 0x00, 0x00]);
+
+/// The IEEE 1588 Precision Time Protocol (PTP) peer-delay multicast
+/// address (`01-80-c2-00-00-0e`).
+pub const PTP_PEER_DELAY: MediaAccessControlAddress =
+    MediaAccessControlAddress::from_array([0x01, 0x80, 0xc2, 0x00, 0x00, 0x0e]);
+
+/// `MediaAccessControlAddress` makes it easy to work with
+/// media access control (MAC) addresses.
+///
+/// MAC addresses have properties of their own, but they also
+/// inherit/share the properties of 48-bit IEEE extended identifiers.
+///
+/// Extended identifiers are either extended unique identifiers
+/// (EUI) or extended local identifiers (ELI).  EUIs have
+/// organizationally-unique identifiers (OUO), while ELIs have
+/// company IDs (CID).
+///
+/// `MediaAccessControlAddress` is the 48-bit `Identifier`, so the
+/// methods that every width shares are documented there.
+///
+/// For more information, visit the following URL:
+/// <https://standards.ieee.org/products-services/regauth/tut/index.html>.
+pub type MediaAccessControlAddress = Identifier<6>;
+
+impl Identifier<6> {
+    /// Instantiates `MediaAccessControlAddress` with
+    /// 12 hexadecimal digits (`0-9`, `A-F`, or `a-f`) in
+    /// plain, hyphen, colon, dot, four-digit-hyphen, or space
+    /// notation.
+    ///
+    /// Space notation (for example, `A0 B1 C2 D3 E4 F5`) is how
+    /// MIB browsers display an SNMP `PhysAddress`.
+    ///
+    /// Colon notation may also omit leading zeros, as Solaris
+    /// does (for example, `0:3:ba:12:4:5`).
+    ///
+    /// If `digits` is not in any of these notations, then the
+    /// error says why, with the byte offset of any offending
+    /// character.  `utils::diagnose` explains it in more detail.
+    ///
+    /// `digits` may be a `&str`, a `String`, a `Cow<str>`, or
+    /// anything else that borrows as a `str`.
+    pub fn new<S: AsRef<str>>(digits: S) -> Result<Self, MacParseError> {
+        let digits = digits.as_ref();
+        utils::validate(digits)?;
+        Ok(Self {
+            octets: utils::to_octets(digits),
+        })
+    }
+
+    /// Instantiates `MediaAccessControlAddress` the way `new` does,
+    /// but explains a failure for a person, the way
+    /// `utils::diagnose` does.
+    pub fn new_with_diagnostics<S: AsRef<str>>(digits: S) -> Result<Self, ParseError> {
+        let digits = digits.as_ref();
+        utils::diagnose(digits)?;
+        Ok(Self {
+            octets: utils::to_octets(digits),
+        })
+    }
+
+    /// Instantiates `MediaAccessControlAddress` with 12 hexadecimal
+    /// digits in exactly the given notation.
+    pub fn from_notation(digits: &str, notation: Notation) -> Result<Self, MacParseError> {
+        let octets = format::parse(digits, notation).map_err(|error| error.kind())?;
+        Ok(Self::from(octets))
+    }
+
+    /// Instantiates `MediaAccessControlAddress` with an address
+    /// from the RFC 7042 documentation unicast range
+    /// (`00-00-5e-00-53-00` through `00-00-5e-00-53-ff`),
+    /// where `last` is the final octet.
+    pub fn documentation_unicast(last: u8) -> Self {
+        Self {
+            octets: [0x00, 0x00, 0x5e, 0x00, 0x53, last],
+        }
+    }
+
+    /// Instantiates `MediaAccessControlAddress` with an address
+    /// from the RFC 7042 documentation multicast range
+    /// (`01-00-5e-90-10-00` through `01-00-5e-90-10-ff`),
+    /// where `last` is the final octet.
+    pub fn documentation_multicast(last: u8) -> Self {
+        Self {
+            octets: [0x01, 0x00, 0x5e, 0x90, 0x10, last],
+        }
+    }
+
+    /// Instantiates `MediaAccessControlAddress` with the IANA
+    /// Virtual Router Redundancy Protocol (VRRP) address for IPv4
+    /// (`00-00-5e-00-01-{vrid}`).
+    pub fn vrrp_ipv4(vrid: u8) -> Self {
+        Self {
+            octets: [0x00, 0x00, 0x5e, 0x00, 0x01, vrid],
+        }
+    }
+
+    /// Instantiates `MediaAccessControlAddress` with the IANA
+    /// Virtual Router Redundancy Protocol (VRRP) address for IPv6
+    /// (`00-00-5e-00-02-{vrid}`).
+    pub fn vrrp_ipv6(vrid: u8) -> Self {
+        Self {
+            octets: [0x00, 0x00, 0x5e, 0x00, 0x02, vrid],
+        }
+    }
+
+    /// Instantiates `MediaAccessControlAddress` with the layer-two
+    /// multicast address for an IPv4 multicast group (RFC 1112).
+    ///
+    /// The low-order 23 bits of the group are placed into the
+    /// `01-00-5e-00-00-00` through `01-00-5e-7f-ff-ff` block.
+    pub fn from_ipv4_multicast(group: Ipv4Addr) -> Result<Self, Error> {
+        if group.is_multicast() {
+            let octets = group.octets();
+            Ok(Self {
+                octets: [0x01, 0x00, 0x5e, octets[1] & 0x7f, octets[2], octets[3]],
+            })
+        } else {
+            Err(Error::Invalid(String::from(
+                "Pass in an IPv4 multicast address.",
+            )))
+        }
+    }
+
+    /// Returns the 32 IPv4 multicast groups that map to the MAC
+    /// address, in ascending order, since the RFC 1112 mapping drops
+    /// the five bits after the `1110` prefix.
+    ///
+    /// Working backwards from a capture, any of them may be the
+    /// group that the frame was sent to.
+    pub fn to_ipv4_multicast_groups(&self) -> Result<Vec<Ipv4Addr>, Error> {
+        if !self.is_ipv4_multicast() {
+            return Err(Error::Invalid(String::from(
+                "Pass in a MAC address that maps an IPv4 multicast group.",
+            )));
+        }
+
+        let octets = self.to_octets();
+
+        Ok((0..32)
+            .map(|high| {
+                Ipv4Addr::new(
+                    224 | (high >> 1),
+                    (high & 1) << 7 | octets[3],
+                    octets[4],
+                    octets[5],
+                )
+            })
+            .collect())
+    }
+
+    /// Instantiates `MediaAccessControlAddress` with the layer-two
+    /// multicast address for an IPv6 multicast group (RFC 2464).
+    ///
+    /// The low-order 32 bits of the group are placed into the
+    /// `33-33-00-00-00-00` through `33-33-ff-ff-ff-ff` block.
+    pub fn from_ipv6_multicast(group: Ipv6Addr) -> Result<Self, Error> {
+        if group.is_multicast() {
+            let octets = group.octets();
+            Ok(Self {
+                octets: [0x33, 0x33, octets[12], octets[13], octets[14], octets[15]],
+            })
+        } else {
+            Err(Error::Invalid(String::from(
+                "Pass in an IPv6 multicast address.",
+            )))
+        }
+    }
+
+    /// Instantiates `MediaAccessControlAddress` with the layer-two
+    /// multicast address for the solicited-node multicast group of
+    /// an IPv6 unicast or anycast address (RFC 4291), to which
+    /// Neighbor Discovery sends neighbor solicitations for it.
+    ///
+    /// The group is `ff02::1:ff00:0/104` plus the low-order 24 bits
+    /// of the address, so the result is `33-33-ff` followed by
+    /// those bits (RFC 2464).
+    pub fn solicited_node_mac(address: Ipv6Addr) -> Result<Self, Error> {
+        if address.is_multicast() || address.is_unspecified() {
+            return Err(Error::Invalid(String::from(
+                "Pass in an IPv6 unicast or anycast address.",
+            )));
+        }
+
+        let octets = address.octets();
+        let group = Ipv6Addr::new(
+            0xff02,
+            0,
+            0,
+            0,
+            0,
+            0x0001,
+            0xff00 | u16::from(octets[13]),
+            u16::from_be_bytes([octets[14], octets[15]]),
+        );
+
+        Self::from_ipv6_multicast(group)
+    }
+
+    /// Instantiates `MediaAccessControlAddress` with the layer-two
+    /// multicast address for an MPLS multicast label (RFC 5332).
+    ///
+    /// The 20-bit label is placed into the `01-00-5e-80-00-00`
+    /// through `01-00-5e-8f-ff-ff` block.
+    pub fn from_mpls_multicast(label: u32) -> Result<Self, Error> {
+        if label <= 0xfffff {
+            let label = label.to_be_bytes();
+            Ok(Self {
+                octets: [0x01, 0x00, 0x5e, 0x80 | label[1], label[2], label[3]],
+            })
+        } else {
+            Err(Error::Invalid(String::from("Pass in a 20-bit MPLS label.")))
+        }
+    }
+
+    /// Instantiates `MediaAccessControlAddress` with the address
+    /// that Docker derives from a container's IPv4 address on a
+    /// bridge network: `02-42` followed by the four octets of the
+    /// IPv4 address.
+    pub fn from_docker_ipv4(ip: Ipv4Addr) -> Self {
+        let octets = ip.octets();
+        Self {
+            octets: [0x02, 0x42, octets[0], octets[1], octets[2], octets[3]],
+        }
+    }
+
+    /// Returns the container's IPv4 address that Docker derived
+    /// the MAC address from.
+    pub fn to_docker_ipv4(&self) -> Result<Ipv4Addr, Error> {
+        if !self.is_docker_derived() {
+            return Err(Error::Invalid(String::from(
+                "Pass in a MAC address that Docker derived from an IPv4 address.",
+            )));
+        }
+
+        let octets = self.to_octets();
+        Ok(Ipv4Addr::new(octets[2], octets[3], octets[4], octets[5]))
+    }
+
+    /// Returns the MAC address in the given notation as a
+    /// fixed-capacity `heapless::String`, without allocating.
+    #[cfg(feature = "heapless")]
+    pub fn to_heapless_string(&self, notation: Notation) -> heapless::String<{ format::MAX_LEN }> {
+        let mut buffer = [0; format::MAX_LEN];
+        let digits = self.format_into(&mut buffer, notation).unwrap();
+
+        let mut string = heapless::String::new();
+        string.push_str(digits).unwrap();
+        string
+    }
+
+    /// Returns the name of the organization that registered the
+    /// block containing the MAC address, as reported by `resolver`.
+    pub fn vendor_with<R: VendorResolver>(&self, resolver: &R) -> Result<Option<String>, Error> {
+        resolver.resolve(self)
+    }
+
+    /// Whether the MAC address is in the IEEE 802.1 bridge-filtered
+    /// range (`01-80-c2-00-00-00` through `01-80-c2-00-00-0f`).
+    ///
+    /// Bridges never forward frames sent to these reserved group
+    /// addresses, so they carry link-local control traffic
+    /// (for example, STP, LACP, and LLDP).
+    pub fn is_bridge_filtered(&self) -> bool {
+        self.octets[..5] == [0x01, 0x80, 0xc2, 0x00, 0x00] && self.octets[5] & 0xf0 == 0
+    }
+
+    /// Whether the MAC address is one of the IEEE 1588 Precision
+    /// Time Protocol (PTP) multicast addresses
+    /// (`01-1b-19-00-00-00` or `01-80-c2-00-00-0e`).
+    pub fn is_ptp(&self) -> bool {
+        *self == PTP_PRIMARY || *self == PTP_PEER_DELAY
+    }
+
+    /// Whether the MAC address is in the RFC 7042 documentation
+    /// unicast range (`00-00-5e-00-53-00` through `00-00-5e-00-53-ff`).
+    pub fn is_documentation_unicast(&self) -> bool {
+        self.octets.starts_with(&[0x00, 0x00, 0x5e, 0x00, 0x53])
+    }
+
+    /// Whether the MAC address is in the RFC 7042 documentation
+    /// multicast range (`01-00-5e-90-10-00` through `01-00-5e-90-10-ff`).
+    pub fn is_documentation_multicast(&self) -> bool {
+        self.octets.starts_with(&[0x01, 0x00, 0x5e, 0x90, 0x10])
+    }
+
+    /// Whether the MAC address is in either of the RFC 7042
+    /// documentation ranges.
+    pub fn is_documentation(&self) -> bool {
+        self.is_documentation_unicast() || self.is_documentation_multicast()
+    }
+
+    /// Whether the MAC address is in the IANA unicast block
+    /// (`00-00-5e-00-00-00` through `00-00-5e-ff-ff-ff`).
+    pub fn is_iana_unicast(&self) -> bool {
+        self.octets.starts_with(&[0x00, 0x00, 0x5e])
+    }
+
+    /// Whether the MAC address is in the IANA multicast block
+    /// (`01-00-5e-00-00-00` through `01-00-5e-ff-ff-ff`).
+    pub fn is_iana_multicast(&self) -> bool {
+        self.octets.starts_with(&[0x01, 0x00, 0x5e])
+    }
+
+    /// Whether the MAC address is managed by IANA, which covers
+    /// the IANA unicast and multicast blocks as well as the
+    /// IPv6 multicast block (`33-33-00-00-00-00` through
+    /// `33-33-ff-ff-ff-ff`).
+    pub fn is_iana_managed(&self) -> bool {
+        self.is_iana_unicast() || self.is_iana_multicast() || self.is_ipv6_multicast()
+    }
+
+    /// Whether the MAC address is a Virtual Router Redundancy
+    /// Protocol (VRRP) address for IPv4 or IPv6
+    /// (`00-00-5e-00-01-xx` or `00-00-5e-00-02-xx`).
+    pub fn is_vrrp(&self) -> bool {
+        self.octets.starts_with(&[0x00, 0x00, 0x5e, 0x00, 0x01])
+            || self.octets.starts_with(&[0x00, 0x00, 0x5e, 0x00, 0x02])
+    }
+
+    /// Whether the MAC address maps an IPv4 multicast group
+    /// (`01-00-5e-00-00-00` through `01-00-5e-7f-ff-ff`).
+    pub fn is_ipv4_multicast(&self) -> bool {
+        self.is_iana_multicast() && self.octets[3] & 0x80 == 0
+    }
+
+    /// Whether the MAC address maps an MPLS multicast label
+    /// (`01-00-5e-80-00-00` through `01-00-5e-8f-ff-ff`).
+    pub fn is_mpls_multicast(&self) -> bool {
+        self.is_iana_multicast() && self.octets[3] & 0xf0 == 0x80
+    }
+
+    /// Whether the MAC address maps an IPv6 multicast group
+    /// (`33-33-00-00-00-00` through `33-33-ff-ff-ff-ff`).
+    pub fn is_ipv6_multicast(&self) -> bool {
+        self.octets.starts_with(&[0x33, 0x33])
+    }
+
+    /// Whether the MAC address is one that Docker derived from a
+    /// container's IPv4 address (`02-42-00-00-00-00` through
+    /// `02-42-ff-ff-ff-ff`).
+    pub fn is_docker_derived(&self) -> bool {
+        self.octets.starts_with(&[0x02, 0x42])
+    }
+
+    /// Returns the next MAC address within the same OUI/CID,
+    /// or `None` if the MAC address is the last one
+    /// (`xx-xx-xx-ff-ff-ff`).
+    ///
+    /// Only the 24 interface-specific bits are incremented, so
+    /// sequential provisioning never rolls over into the next
+    /// organization's block.
+    pub fn next_in_oui(&self) -> Option<Self> {
+        let mut octets = self.to_octets();
+        let extension = u32::from_be_bytes([0, octets[3], octets[4], octets[5]]);
+
+        if extension == 0x00ff_ffff {
+            None
+        } else {
+            octets[3..].copy_from_slice(&(extension + 1).to_be_bytes()[1..]);
+            Some(Self::from(octets))
+        }
+    }
+
+    /// Returns an iterator over the MAC addresses that follow this
+    /// one within the same OUI/CID (see `next_in_oui`), which ends
+    /// when the block is exhausted.
+    pub fn successors_in_oui(&self) -> impl Iterator<Item = Self> {
+        std::iter::successors(self.next_in_oui(), Self::next_in_oui)
+    }
+}
+
+/// Instantiates `MediaAccessControlAddress` the same way `new` does.
+impl TryFrom<&str> for Identifier<6> {
+    type Error = MacParseError;
+
+    fn try_from(digits: &str) -> Result<Self, Self::Error> {
+        Self::new(digits)
+    }
+}
+
+/// Instantiates `MediaAccessControlAddress` the same way `new` does.
+impl TryFrom<String> for Identifier<6> {
+    type Error = MacParseError;
+
+    fn try_from(digits: String) -> Result<Self, Self::Error> {
+        Self::new(&digits)
+    }
+}
+
+/// Parses `MediaAccessControlAddress` the same way `new` does, so that `str::parse`
+/// and generic parsing code accept it.
+impl FromStr for Identifier<6> {
+    type Err = MacParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::new(s)
+    }
+}
+
+/// Converts a `mac_address::MacAddress`, such as the `mac_address`
+/// crate looks up on the host, into `MediaAccessControlAddress`.
+///
+/// This implementation requires the `mac_address` feature.
+#[cfg(feature = "mac_address")]
+impl From<mac_address::MacAddress> for Identifier<6> {
+    fn from(address: mac_address::MacAddress) -> Self {
+        Self::from(address.bytes())
+    }
+}
+
+/// Converts `MediaAccessControlAddress` into a
+/// `mac_address::MacAddress`.
+///
+/// This implementation requires the `mac_address` feature.
+#[cfg(feature = "mac_address")]
+impl From<Identifier<6>> for mac_address::MacAddress {
+    fn from(mac: Identifier<6>) -> Self {
+        mac_address::MacAddress::new(mac.to_octets())
+    }
+}
+
+/// Converts `&MediaAccessControlAddress` into a
+/// `mac_address::MacAddress`.
+///
+/// This implementation requires the `mac_address` feature.
+#[cfg(feature = "mac_address")]
+impl From<&Identifier<6>> for mac_address::MacAddress {
+    fn from(mac: &Identifier<6>) -> Self {
+        mac_address::MacAddress::new(mac.to_octets())
+    }
+}
@@ -0,0 +1,230 @@
+use super::error::Error;
+use super::macaddress::MediaAccessControlAddress;
+use super::vendor::VendorResolver;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+#[cfg(feature = "db")]
+use super::vendor::VendorDatabase;
+
+/// Counters and gauges for the monitoring parts of this crate, which
+/// a daemon built on it can expose to Prometheus.
+///
+/// Share one `Metrics` (in an `Arc`) between an `Inventory`, through
+/// `Inventory::set_metrics`, and a `MeteredResolver`, and serve
+/// `to_prometheus` from the daemon's `/metrics` endpoint.  Every
+/// update is a relaxed atomic operation, so metering costs next to
+/// nothing.
+#[derive(Debug, Default)]
+pub struct Metrics {
+    observations: AtomicU64,
+    new_devices: AtomicU64,
+    devices: AtomicU64,
+    vendor_hits: AtomicU64,
+    vendor_misses: AtomicU64,
+    vendor_errors: AtomicU64,
+    database_built: AtomicU64,
+}
+
+impl Metrics {
+    /// Instantiates `Metrics` with every count at 0.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the number of addresses observed, counting repeats.
+    pub fn observations(&self) -> u64 {
+        self.observations.load(Ordering::Relaxed)
+    }
+
+    /// Returns the number of addresses observed for the first time.
+    pub fn new_devices(&self) -> u64 {
+        self.new_devices.load(Ordering::Relaxed)
+    }
+
+    /// Returns the number of devices in the inventory.
+    pub fn devices(&self) -> u64 {
+        self.devices.load(Ordering::Relaxed)
+    }
+
+    /// Returns the number of vendor lookups that found a vendor.
+    pub fn vendor_hits(&self) -> u64 {
+        self.vendor_hits.load(Ordering::Relaxed)
+    }
+
+    /// Returns the number of vendor lookups that found no vendor.
+    pub fn vendor_misses(&self) -> u64 {
+        self.vendor_misses.load(Ordering::Relaxed)
+    }
+
+    /// Returns the number of vendor lookups that failed.
+    pub fn vendor_errors(&self) -> u64 {
+        self.vendor_errors.load(Ordering::Relaxed)
+    }
+
+    /// Records an observation, of a new device if `new`.
+    pub fn record_observation(&self, new: bool) {
+        self.observations.fetch_add(1, Ordering::Relaxed);
+
+        if new {
+            self.new_devices.fetch_add(1, Ordering::Relaxed);
+        }
+    }
+
+    /// Sets the number of devices in the inventory.
+    pub fn set_devices(&self, devices: usize) {
+        self.devices.store(devices as u64, Ordering::Relaxed);
+    }
+
+    /// Records the outcome of a vendor lookup.
+    pub fn record_vendor(&self, outcome: &Result<Option<String>, Error>) {
+        let counter = match outcome {
+            Ok(Some(_)) => &self.vendor_hits,
+            Ok(None) => &self.vendor_misses,
+            Err(_) => &self.vendor_errors,
+        };
+
+        counter.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// Sets when the vendor database was built, from which its age
+    /// is reported.
+    pub fn set_database_built(&self, built: SystemTime) {
+        let seconds = built
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |duration| duration.as_secs());
+        self.database_built.store(seconds, Ordering::Relaxed);
+    }
+
+    /// Sets when the vendor database was built from its metadata,
+    /// if it knows.
+    ///
+    /// This method requires the `db` feature.
+    #[cfg(feature = "db")]
+    pub fn set_database(&self, database: &VendorDatabase) {
+        if let Some(built) = database.metadata().built {
+            self.set_database_built(built);
+        }
+    }
+
+    /// Returns the age of the vendor database, in seconds, or `None`
+    /// if it has not been set.
+    pub fn database_age(&self) -> Option<u64> {
+        match self.database_built.load(Ordering::Relaxed) {
+            0 => None,
+            built => {
+                let now = SystemTime::now()
+                    .duration_since(UNIX_EPOCH)
+                    .map_or(0, |duration| duration.as_secs());
+                Some(now.saturating_sub(built))
+            }
+        }
+    }
+
+    /// Returns the metrics in Prometheus's text exposition format,
+    /// each named with a `macaddress_` prefix.  The database's age
+    /// is left out until it is set.
+    pub fn to_prometheus(&self) -> String {
+        let mut text = String::new();
+
+        let mut metric = |name: &str, kind: &str, help: &str, samples: &[(&str, u64)]| {
+            text.push_str(&format!("# HELP macaddress_{} {}\n", name, help));
+            text.push_str(&format!("# TYPE macaddress_{} {}\n", name, kind));
+
+            for (labels, value) in samples {
+                text.push_str(&format!("macaddress_{}{} {}\n", name, labels, value));
+            }
+        };
+
+        metric(
+            "observations_total",
+            "counter",
+            "Addresses observed, counting repeats.",
+            &[("", self.observations())],
+        );
+        metric(
+            "new_devices_total",
+            "counter",
+            "Addresses observed for the first time.",
+            &[("", self.new_devices())],
+        );
+        metric(
+            "devices",
+            "gauge",
+            "Devices in the inventory.",
+            &[("", self.devices())],
+        );
+        metric(
+            "vendor_lookups_total",
+            "counter",
+            "Vendor lookups, by whether they found a vendor.",
+            &[
+                ("{result=\"hit\"}", self.vendor_hits()),
+                ("{result=\"miss\"}", self.vendor_misses()),
+                ("{result=\"error\"}", self.vendor_errors()),
+            ],
+        );
+
+        if let Some(age) = self.database_age() {
+            metric(
+                "vendor_database_age_seconds",
+                "gauge",
+                "Seconds since the vendor database was built.",
+                &[("", age)],
+            );
+        }
+
+        text
+    }
+}
+
+/// A `VendorResolver` that counts the hits, misses, and errors of
+/// another resolver in `Metrics`.
+///
+/// Wrap a `CachedResolver` in it to count what the application
+/// asked for, or put it inside one to count what reached the
+/// backend.
+#[derive(Debug)]
+pub struct MeteredResolver<R> {
+    inner: R,
+    metrics: Arc<Metrics>,
+}
+
+impl<R: VendorResolver> MeteredResolver<R> {
+    /// Instantiates `MeteredResolver` around `inner`.
+    pub fn new(inner: R, metrics: Arc<Metrics>) -> Self {
+        Self { inner, metrics }
+    }
+
+    /// Returns the wrapped resolver.
+    pub fn inner(&self) -> &R {
+        &self.inner
+    }
+
+    /// Returns the metrics.
+    pub fn metrics(&self) -> &Arc<Metrics> {
+        &self.metrics
+    }
+}
+
+impl<R: VendorResolver> VendorResolver for MeteredResolver<R> {
+    fn resolve(&self, mac: &MediaAccessControlAddress) -> Result<Option<String>, Error> {
+        let outcome = self.inner.resolve(mac);
+        self.metrics.record_vendor(&outcome);
+        outcome
+    }
+
+    fn resolve_batch(
+        &self,
+        macs: &[&MediaAccessControlAddress],
+    ) -> Vec<Result<Option<String>, Error>> {
+        let outcomes = self.inner.resolve_batch(macs);
+
+        for outcome in outcomes.iter() {
+            self.metrics.record_vendor(outcome);
+        }
+
+        outcomes
+    }
+}
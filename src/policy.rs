@@ -0,0 +1,381 @@
+use super::classification::Cast;
+use super::error::Error;
+use super::fingerprint::{self, Origin, Role};
+use super::format::{self, Notation};
+use super::macaddress::MediaAccessControlAddress;
+use super::prefix::MacPrefix;
+use std::fmt;
+use std::fs;
+use std::path::Path;
+use std::str::FromStr;
+
+/// What a policy does with an address.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Action {
+    /// Let the address on.
+    Allow,
+    /// Keep the address off.
+    Deny,
+}
+
+impl Action {
+    /// Returns the action's stable name (`allow` or `deny`).
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Action::Allow => "allow",
+            Action::Deny => "deny",
+        }
+    }
+}
+
+impl fmt::Display for Action {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.pad(self.as_str())
+    }
+}
+
+/// Parses an action from its stable name, ignoring ASCII case.
+impl FromStr for Action {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        [Action::Allow, Action::Deny]
+            .iter()
+            .find(|action| action.as_str().eq_ignore_ascii_case(s.trim()))
+            .copied()
+            .ok_or_else(|| Error::Invalid(format!("Pass in allow or deny (not {}).", s.trim())))
+    }
+}
+
+/// The addresses that a rule applies to.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub enum Matcher {
+    /// Every address.
+    Any,
+    /// One address.
+    Address([u8; 6]),
+    /// The addresses in a prefix, such as an OUI.
+    Prefix(MacPrefix),
+    /// The addresses that equal `address` in every bit that is clear
+    /// in `wildcard`, as in a Cisco MAC access list.
+    Wildcard {
+        /// The bits to compare.
+        address: [u8; 6],
+        /// The bits to ignore.
+        wildcard: [u8; 6],
+    },
+    /// The addresses with a cast (for example, every multicast
+    /// address).
+    Cast(Cast),
+    /// The unicast addresses with an origin, as `fingerprint::origin`
+    /// judges it (for example, every randomized address).
+    Origin(Origin),
+    /// The addresses reserved for a well-known protocol, as
+    /// `fingerprint::role` judges it.
+    Role(Role),
+    /// The universally-administered addresses (UAAs).
+    Universal,
+    /// The locally-administered addresses (LAAs).
+    Local,
+}
+
+impl Matcher {
+    /// Whether the matcher applies to the MAC address.
+    pub fn matches(&self, mac: &MediaAccessControlAddress) -> bool {
+        match self {
+            Matcher::Any => true,
+            Matcher::Address(address) => mac.to_octets() == *address,
+            Matcher::Prefix(prefix) => prefix.contains(mac),
+            Matcher::Wildcard { address, wildcard } => mac
+                .to_octets()
+                .iter()
+                .zip(address.iter().zip(wildcard.iter()))
+                .all(|(octet, (address, wildcard))| (octet ^ address) & !wildcard == 0),
+            Matcher::Cast(cast) => mac.cast() == *cast,
+            Matcher::Origin(origin) => fingerprint::origin(mac) == Some(*origin),
+            Matcher::Role(role) => fingerprint::role(mac) == Some(*role),
+            Matcher::Universal => mac.is_uaa(),
+            Matcher::Local => mac.is_laa(),
+        }
+    }
+}
+
+/// Formats the matcher the way `from_str` parses it, with
+/// addresses in colon notation.
+impl fmt::Display for Matcher {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Matcher::Any => write!(f, "any"),
+            Matcher::Address(address) => write!(f, "{}", format::format(address, Notation::Colon)),
+            Matcher::Prefix(prefix) => write!(f, "{}", prefix),
+            Matcher::Wildcard { address, wildcard } => write!(
+                f,
+                "{} {}",
+                format::format(address, Notation::Colon),
+                format::format(wildcard, Notation::Colon)
+            ),
+            Matcher::Cast(cast) => write!(f, "{}", cast),
+            Matcher::Origin(origin) => write!(f, "{}", origin),
+            Matcher::Role(role) => write!(f, "{}", role),
+            Matcher::Universal => write!(f, "uaa"),
+            Matcher::Local => write!(f, "laa"),
+        }
+    }
+}
+
+/// Parses a matcher: `any`; an address; a prefix (for example,
+/// `a0:b1:c2:00:00:00/24`); `oui` and an OUI in any notation (for
+/// example, `oui a0:b1:c2`); an address and a wildcard mask (for
+/// example, `a0:b1:c2:00:00:00 00:00:00:ff:ff:ff`); a cast, an
+/// origin, or a role by its stable name (for example, `multicast`,
+/// `randomized`, or `link-local-control`); or `uaa` or `laa`.
+impl FromStr for Matcher {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let words: Vec<&str> = s.split_whitespace().collect();
+
+        match words.as_slice() {
+            ["any"] => Ok(Matcher::Any),
+            ["uaa"] => Ok(Matcher::Universal),
+            ["laa"] => Ok(Matcher::Local),
+            ["oui", oui] => {
+                let digits: String = oui.chars().filter(|c| !":-. ".contains(*c)).collect();
+
+                let invalid = || {
+                    Error::Invalid(format!(
+                        "Pass in an OUI of 6 hexadecimal digits (not {}).",
+                        oui
+                    ))
+                };
+
+                if digits.len() != 6 {
+                    return Err(invalid());
+                }
+
+                let address = MediaAccessControlAddress::new(format!("{}000000", digits))
+                    .map_err(|_| invalid())?;
+                Ok(Matcher::Prefix(MacPrefix::oui(&address)))
+            }
+            [address, wildcard] => Ok(Matcher::Wildcard {
+                address: MediaAccessControlAddress::new(address)?.to_octets(),
+                wildcard: MediaAccessControlAddress::new(wildcard)?.to_octets(),
+            }),
+            [word] if word.contains('/') => Ok(Matcher::Prefix(word.parse()?)),
+            [word] => {
+                if let Ok(address) = MediaAccessControlAddress::new(word) {
+                    Ok(Matcher::Address(address.to_octets()))
+                } else if let Ok(cast) = word.parse() {
+                    Ok(Matcher::Cast(cast))
+                } else if let Ok(origin) = word.parse() {
+                    Ok(Matcher::Origin(origin))
+                } else if let Ok(role) = word.parse() {
+                    Ok(Matcher::Role(role))
+                } else {
+                    Err(Error::Invalid(format!(
+                        "Pass in any, an address, a prefix, an OUI, an address and a wildcard \
+                         mask, a classification, uaa, or laa (not {}).",
+                        word
+                    )))
+                }
+            }
+            _ => Err(Error::Invalid(format!(
+                "Pass in one matcher per rule (not {}).",
+                s.trim()
+            ))),
+        }
+    }
+}
+
+/// A rule in a policy: an action and the addresses that it applies
+/// to.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Rule {
+    /// What to do with the addresses.
+    pub action: Action,
+    /// The addresses.
+    pub matcher: Matcher,
+    /// The line of the policy file that the rule came from, if any.
+    pub line: Option<usize>,
+}
+
+impl Rule {
+    /// Instantiates `Rule`.
+    pub fn new(action: Action, matcher: Matcher) -> Self {
+        Self {
+            action,
+            matcher,
+            line: None,
+        }
+    }
+}
+
+/// Formats the rule the way `from_str` parses it (for example,
+/// `deny randomized`).
+impl fmt::Display for Rule {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} {}", self.action, self.matcher)
+    }
+}
+
+/// Parses an action and a matcher, separated by whitespace (for
+/// example, `allow oui a0:b1:c2`).
+impl FromStr for Rule {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (action, matcher) = s.trim().split_once(char::is_whitespace).ok_or_else(|| {
+            Error::Invalid(format!(
+                "Pass in an action and a matcher (not {}).",
+                s.trim()
+            ))
+        })?;
+
+        Ok(Self::new(action.parse()?, matcher.parse()?))
+    }
+}
+
+/// What a policy decided about an address, and the rule that decided
+/// it, or `None` if no rule matched and the default applied.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Decision<'a> {
+    /// Let the address on.
+    Allow {
+        /// The rule that matched.
+        rule: Option<&'a Rule>,
+    },
+    /// Keep the address off.
+    Deny {
+        /// The rule that matched.
+        rule: Option<&'a Rule>,
+    },
+}
+
+impl<'a> Decision<'a> {
+    /// Whether the policy allows the address.
+    pub fn is_allowed(&self) -> bool {
+        matches!(self, Decision::Allow { .. })
+    }
+
+    /// Returns the rule that decided, or `None` if the default
+    /// applied.
+    pub fn rule(&self) -> Option<&'a Rule> {
+        match self {
+            Decision::Allow { rule } | Decision::Deny { rule } => *rule,
+        }
+    }
+}
+
+/// An ordered list of allow and deny rules, such as a network
+/// access control or port-security tool enforces.  The first rule
+/// that matches an address decides it, and addresses that no rule
+/// matches get the default action (`Allow`, unless set otherwise).
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Policy {
+    rules: Vec<Rule>,
+    default: Action,
+}
+
+impl Default for Policy {
+    fn default() -> Self {
+        Self {
+            rules: Vec::new(),
+            default: Action::Allow,
+        }
+    }
+}
+
+impl Policy {
+    /// Instantiates a `Policy` without rules that allows every
+    /// address.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Gives addresses that no rule matches `action`.
+    pub fn with_default(mut self, action: Action) -> Self {
+        self.default = action;
+        self
+    }
+
+    /// Appends a rule, which applies only to addresses that the
+    /// earlier rules do not match.
+    pub fn with_rule(mut self, rule: Rule) -> Self {
+        self.rules.push(rule);
+        self
+    }
+
+    /// Returns the rules, in order.
+    pub fn rules(&self) -> &[Rule] {
+        &self.rules
+    }
+
+    /// Returns the action for addresses that no rule matches.
+    pub fn default_action(&self) -> Action {
+        self.default
+    }
+
+    /// Reads a policy file in the format that `from_str` parses,
+    /// failing with `Error::Io` if it cannot be read and
+    /// `Error::Invalid` if it cannot be parsed.
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<Self, Error> {
+        fs::read_to_string(path)?.parse()
+    }
+
+    /// Decides whether to allow the MAC address.
+    pub fn decide(&self, mac: &MediaAccessControlAddress) -> Decision<'_> {
+        let rule = self.rules.iter().find(|rule| rule.matcher.matches(mac));
+
+        match rule.map_or(self.default, |rule| rule.action) {
+            Action::Allow => Decision::Allow { rule },
+            Action::Deny => Decision::Deny { rule },
+        }
+    }
+}
+
+/// Parses a policy file, with one rule per line, in the format that
+/// `Rule::from_str` parses, and optionally a line such as `default
+/// deny` to set the default action.  Blank lines and everything
+/// after a `#` are ignored.
+///
+/// ```text
+/// # Let the lab's OUI on, but not randomized addresses.
+/// default deny
+/// deny randomized
+/// allow oui a0:b1:c2
+/// allow a0:b1:c2:00:00:00 00:00:00:00:ff:ff
+/// ```
+impl FromStr for Policy {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut policy = Self::new();
+
+        for (number, line) in s.lines().enumerate() {
+            let line = line.split('#').next().unwrap_or_default().trim();
+
+            if line.is_empty() {
+                continue;
+            }
+
+            let fail = |error: Error| {
+                Error::Invalid(format!(
+                    "{} on line {}.",
+                    error.to_string().trim_end_matches('.'),
+                    number + 1
+                ))
+            };
+
+            match line.split_once(char::is_whitespace) {
+                Some(("default", action)) => policy.default = action.parse().map_err(fail)?,
+                _ => {
+                    let mut rule: Rule = line.parse().map_err(fail)?;
+                    rule.line = Some(number + 1);
+                    policy.rules.push(rule);
+                }
+            }
+        }
+
+        Ok(policy)
+    }
+}
@@ -0,0 +1,269 @@
+use super::error::Error;
+use super::macaddress::MediaAccessControlAddress;
+use std::collections::BTreeMap;
+use std::fmt;
+use std::iter::FromIterator;
+use std::str::FromStr;
+
+/// `MacPrefix` is a block of MAC addresses that share their
+/// `length` most-significant bits, such as every address under an
+/// OUI (a 24-bit prefix).
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct MacPrefix {
+    base: [u8; 6],
+    length: u8,
+}
+
+/// Converts six octets into a 48-bit value.
+pub(crate) fn to_value(octets: &[u8; 6]) -> u64 {
+    let mut bytes = [0; 8];
+    bytes[2..].copy_from_slice(octets);
+    u64::from_be_bytes(bytes)
+}
+
+/// Converts a 48-bit value into six octets.
+pub(crate) fn to_octets(value: u64) -> [u8; 6] {
+    let mut octets = [0; 6];
+    octets.copy_from_slice(&value.to_be_bytes()[2..]);
+    octets
+}
+
+impl MacPrefix {
+    /// Instantiates `MacPrefix` with the `length` (between 0 and
+    /// 48) most-significant bits of a MAC address, clearing the
+    /// others.
+    pub fn new(address: &MediaAccessControlAddress, length: u8) -> Result<Self, Error> {
+        if length > 48 {
+            return Err(Error::Invalid(String::from(
+                "Pass in a length between 0 and 48.",
+            )));
+        }
+
+        let value = to_value(&address.to_octets()) & Self::mask(length);
+
+        Ok(Self {
+            base: to_octets(value),
+            length,
+        })
+    }
+
+    /// Instantiates `MacPrefix` with the OUI/CID of a MAC address
+    /// (its 24 most-significant bits).
+    pub fn oui(address: &MediaAccessControlAddress) -> Self {
+        Self::new(address, 24).unwrap()
+    }
+
+    /// Returns a 48-bit value with the `length` most-significant
+    /// bits set.
+    fn mask(length: u8) -> u64 {
+        (0x0000_ffff_ffff_ffff >> (48 - u32::from(length))) << (48 - u32::from(length))
+    }
+
+    /// Returns the number of bits that the prefix fixes.
+    pub fn length(&self) -> u8 {
+        self.length
+    }
+
+    /// Returns the first MAC address in the block.
+    pub fn first(&self) -> MediaAccessControlAddress {
+        MediaAccessControlAddress::from(self.base)
+    }
+
+    /// Returns the last MAC address in the block.
+    pub fn last(&self) -> MediaAccessControlAddress {
+        let value = to_value(&self.base) | (!Self::mask(self.length) & 0x0000_ffff_ffff_ffff);
+        MediaAccessControlAddress::from(to_octets(value))
+    }
+
+    /// Returns the first and last addresses in the block as raw
+    /// octets, for range queries over ordered octet keys.
+    pub(crate) fn bounds(&self) -> ([u8; 6], [u8; 6]) {
+        (self.base, self.last().to_octets())
+    }
+
+    /// Returns the number of addresses in the block.
+    pub fn size(&self) -> u64 {
+        1 << (48 - u32::from(self.length))
+    }
+
+    /// Whether the block contains the MAC address.
+    pub fn contains(&self, address: &MediaAccessControlAddress) -> bool {
+        to_value(&address.to_octets()) & Self::mask(self.length) == to_value(&self.base)
+    }
+}
+
+/// Parses `address/length`, where the address may be in any
+/// notation that `MediaAccessControlAddress::new` accepts (for
+/// example, `a0:b1:c2:00:00:00/24`).
+impl FromStr for MacPrefix {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (address, length) = s.trim().split_once('/').ok_or_else(|| {
+            Error::Invalid(String::from(
+                "Pass in a MAC address, a slash, and a length.",
+            ))
+        })?;
+
+        let address = MediaAccessControlAddress::new(address)?;
+        let length = length
+            .parse()
+            .map_err(|_| Error::Invalid(String::from("Pass in a length between 0 and 48.")))?;
+
+        Self::new(&address, length)
+    }
+}
+
+/// Formats the prefix as `address/length`, with the first address
+/// in colon notation (for example, `a0:b1:c2:00:00:00/24`).
+impl fmt::Display for MacPrefix {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}/{}", self.first().to_colon_notation(), self.length)
+    }
+}
+
+/// `MacPrefixMap` is a map from prefixes to values, such as an
+/// access list from blocks of addresses to actions, that finds the
+/// most specific prefix covering an address.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct MacPrefixMap<V> {
+    entries: BTreeMap<MacPrefix, V>,
+    lengths: BTreeMap<u8, usize>,
+}
+
+impl<V> Default for MacPrefixMap<V> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<V> MacPrefixMap<V> {
+    /// Instantiates an empty `MacPrefixMap`.
+    pub fn new() -> Self {
+        Self {
+            entries: BTreeMap::new(),
+            lengths: BTreeMap::new(),
+        }
+    }
+
+    /// Returns the number of entries.
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Whether the map has no entries.
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Inserts a value for the prefix and returns the value that it
+    /// replaced, if any.
+    pub fn insert(&mut self, prefix: MacPrefix, value: V) -> Option<V> {
+        let replaced = self.entries.insert(prefix, value);
+
+        if replaced.is_none() {
+            *self.lengths.entry(prefix.length).or_insert(0) += 1;
+        }
+
+        replaced
+    }
+
+    /// Returns the value for exactly this prefix, if any.
+    pub fn get(&self, prefix: &MacPrefix) -> Option<&V> {
+        self.entries.get(prefix)
+    }
+
+    /// Removes the entry for exactly this prefix and returns its
+    /// value, if any.
+    pub fn remove(&mut self, prefix: &MacPrefix) -> Option<V> {
+        let removed = self.entries.remove(prefix)?;
+        let count = self.lengths.get_mut(&prefix.length).unwrap();
+        *count -= 1;
+
+        if *count == 0 {
+            self.lengths.remove(&prefix.length);
+        }
+
+        Some(removed)
+    }
+
+    /// Removes every entry.
+    pub fn clear(&mut self) {
+        self.entries.clear();
+        self.lengths.clear();
+    }
+
+    /// Returns an iterator over the entries, ordered by first
+    /// address and then by length.
+    pub fn iter(&self) -> impl Iterator<Item = (MacPrefix, &V)> {
+        self.entries.iter().map(|(prefix, value)| (*prefix, value))
+    }
+
+    /// Returns the entry with the longest prefix that covers the
+    /// MAC address, if any.
+    pub fn longest_match(&self, address: &MediaAccessControlAddress) -> Option<(MacPrefix, &V)> {
+        // Only the lengths in use need to be tried, from the most
+        // specific down.
+        self.lengths.keys().rev().find_map(|length| {
+            let prefix = MacPrefix::new(address, *length).unwrap();
+            self.entries.get(&prefix).map(|value| (prefix, value))
+        })
+    }
+}
+
+impl<V> FromIterator<(MacPrefix, V)> for MacPrefixMap<V> {
+    fn from_iter<I: IntoIterator<Item = (MacPrefix, V)>>(iter: I) -> Self {
+        let mut map = Self::new();
+        map.extend(iter);
+        map
+    }
+}
+
+impl<V> Extend<(MacPrefix, V)> for MacPrefixMap<V> {
+    fn extend<I: IntoIterator<Item = (MacPrefix, V)>>(&mut self, iter: I) {
+        for (prefix, value) in iter {
+            self.insert(prefix, value);
+        }
+    }
+}
+
+/// Serializes the prefix as `address/length` (for example,
+/// `a0:b1:c2:00:00:00/24`) for human-readable formats, such as JSON,
+/// and as the six octets of its first address followed by its
+/// length for other formats.
+///
+/// This implementation requires the `serde` feature.
+#[cfg(feature = "serde")]
+impl serde::Serialize for MacPrefix {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        if serializer.is_human_readable() {
+            serializer.collect_str(self)
+        } else {
+            let mut bytes = [0; 7];
+            bytes[..6].copy_from_slice(&self.base);
+            bytes[6] = self.length;
+            serializer.serialize_bytes(&bytes)
+        }
+    }
+}
+
+/// Deserializes the prefix from the forms that it serializes to.
+///
+/// This implementation requires the `serde` feature.
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for MacPrefix {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        super::utils::deserialize(
+            deserializer,
+            "a MAC prefix",
+            |text| text.parse().map_err(String::from),
+            |bytes| match bytes {
+                [octets @ .., length] if octets.len() == 6 => {
+                    Self::new(&MediaAccessControlAddress::from_octets(octets)?, *length)
+                        .map_err(String::from)
+                }
+                _ => Err(String::from("Pass in 7 bytes: six octets and a length.")),
+            },
+        )
+    }
+}
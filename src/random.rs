@@ -0,0 +1,179 @@
+use super::error::Error;
+use super::fingerprint;
+use super::macaddress::MediaAccessControlAddress;
+use super::prefix::MacPrefix;
+
+/// A source of random octets for generating MAC addresses.
+///
+/// Implement this trait to plug in any random number generator,
+/// such as a microcontroller's hardware RNG, without depending
+/// on `rand`.  Closures that fill six octets implement it, too.
+pub trait MacRng {
+    /// Fills all six octets with random values.
+    fn fill_octets(&mut self, octets: &mut [u8; 6]);
+}
+
+impl<F> MacRng for F
+where
+    F: FnMut(&mut [u8; 6]),
+{
+    fn fill_octets(&mut self, octets: &mut [u8; 6]) {
+        self(octets)
+    }
+}
+
+/// Clears the multicast bit and sets the local bit in the
+/// first octet, leaving the other 46 bits untouched.
+fn to_laa(mut octets: [u8; 6]) -> MediaAccessControlAddress {
+    octets[0] = (octets[0] & 0b1111_1100) | 0b0000_0010;
+    MediaAccessControlAddress::from(octets)
+}
+
+/// Returns a random locally-administered unicast MAC address,
+/// drawing its bits from `rng`.
+///
+/// Of the 48 bits, 46 are random.  The least-significant bit
+/// of the first octet is cleared (unicast) and the
+/// second-least-significant bit is set (locally administered).
+pub fn random_laa_with<R: MacRng>(rng: &mut R) -> MediaAccessControlAddress {
+    let mut octets = [0; 6];
+    rng.fill_octets(&mut octets);
+    to_laa(octets)
+}
+
+/// Returns a random locally-administered unicast MAC address,
+/// drawing its bits from the operating system's random number
+/// generator through `getrandom`.
+///
+/// This function requires the `rand` feature.
+#[cfg(feature = "rand")]
+pub fn random_laa() -> Result<MediaAccessControlAddress, Error> {
+    let mut octets = [0; 6];
+    getrandom::fill(&mut octets).map_err(|error| Error::System(error.to_string()))?;
+    Ok(to_laa(octets))
+}
+
+/// Returns a random address that is safe to fabricate, drawing its
+/// bits from `rng`.
+///
+/// The address is locally administered and unicast, and it lies in
+/// the IEEE 802c Administratively Assigned Identifier quadrant (the
+/// second hexadecimal digit is `2`), so it is never an Extended
+/// Local Identifier, a Standard Assigned Identifier, or in the
+/// quadrant that IEEE reserves.  It also never falls under a known
+/// hypervisor or container runtime's prefix (such as QEMU's
+/// `52:54:00` or Docker's `02:42`) or any range that a well-known
+/// protocol or documentation reserves.  Of the 48 bits, 44 are
+/// random.
+pub fn random_safe_with<R: MacRng>(rng: &mut R) -> MediaAccessControlAddress {
+    let mut octets = [0; 6];
+    rng.fill_octets(&mut octets);
+    octets[0] = (octets[0] & 0b1111_0000) | 0b0000_0010;
+
+    let mac = MediaAccessControlAddress::from(octets);
+
+    // Flipping the top bit of the second octet steers clear of every
+    // virtual prefix in the quadrant.
+    if fingerprint::platform(&mac).is_some() || fingerprint::role(&mac).is_some() {
+        octets[1] ^= 0x80;
+    }
+
+    MediaAccessControlAddress::from(octets)
+}
+
+/// Returns a random address that is safe to fabricate, as
+/// `random_safe_with` does, drawing its bits from the operating
+/// system's random number generator through `getrandom`.
+///
+/// This function requires the `rand` feature.
+#[cfg(feature = "rand")]
+pub fn random_safe() -> Result<MediaAccessControlAddress, Error> {
+    let mut octets = [0; 6];
+    getrandom::fill(&mut octets).map_err(|error| Error::System(error.to_string()))?;
+    Ok(random_safe_with(&mut |random: &mut [u8; 6]| {
+        *random = octets
+    }))
+}
+
+/// The hypervisors whose own allocations `random_hypervisor_with`
+/// draws addresses from, so that provisioning code produces
+/// addresses that each platform accepts.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Hypervisor {
+    /// VMware's range for manually-assigned addresses,
+    /// `00:50:56:00:00:00` through `00:50:56:3f:ff:ff`, which is the
+    /// only part of its OUI that vSphere accepts from an
+    /// administrator.
+    Vmware,
+    /// The range from which the vCenter Server with the given
+    /// instance ID (between 0 and 63) generates addresses,
+    /// `00:50:56:80:00:00` plus the ID in the fourth octet.
+    VmwareVcenter(u8),
+    /// QEMU and libvirt's `52:54:00` prefix, for KVM.  QEMU's own
+    /// default, `52:54:00:12:34:56`, and the addresses after it that
+    /// QEMU gives further interfaces are never drawn.
+    Kvm,
+    /// Xen's `00:16:3e` OUI, with the top bit of the fourth octet
+    /// clear, as Xen's tools leave it (`00:16:3e:00:00:00` through
+    /// `00:16:3e:7f:ff:ff`).
+    Xen,
+}
+
+impl Hypervisor {
+    /// Returns the prefix that the hypervisor's addresses are drawn
+    /// from.
+    pub fn prefix(self) -> Result<MacPrefix, Error> {
+        let (octets, length) = match self {
+            Hypervisor::Vmware => ([0x00, 0x50, 0x56, 0x00, 0x00, 0x00], 26),
+            Hypervisor::VmwareVcenter(instance) if instance < 64 => {
+                ([0x00, 0x50, 0x56, 0x80 | instance, 0x00, 0x00], 32)
+            }
+            Hypervisor::VmwareVcenter(_) => {
+                return Err(Error::Invalid(String::from(
+                    "Pass in a vCenter Server instance ID between 0 and 63.",
+                )))
+            }
+            Hypervisor::Kvm => ([0x52, 0x54, 0x00, 0x00, 0x00, 0x00], 24),
+            Hypervisor::Xen => ([0x00, 0x16, 0x3e, 0x00, 0x00, 0x00], 25),
+        };
+
+        MacPrefix::new(&MediaAccessControlAddress::from(octets), length)
+    }
+}
+
+/// Returns a random MAC address from the hypervisor's allocation,
+/// drawing its free bits from `rng`.
+pub fn random_hypervisor_with<R: MacRng>(
+    hypervisor: Hypervisor,
+    rng: &mut R,
+) -> Result<MediaAccessControlAddress, Error> {
+    let prefix = hypervisor.prefix()?;
+    let mut random = [0; 6];
+    rng.fill_octets(&mut random);
+
+    let mut octets = prefix.first().to_octets();
+    let free = (prefix.size() - 1).to_be_bytes();
+
+    for (index, octet) in octets.iter_mut().enumerate() {
+        *octet |= random[index] & free[index + 2];
+    }
+
+    // Steer clear of QEMU's default addresses.
+    if hypervisor == Hypervisor::Kvm && octets[3..5] == [0x12, 0x34] && octets[5] >= 0x56 {
+        octets[3] ^= 0x80;
+    }
+
+    Ok(MediaAccessControlAddress::from(octets))
+}
+
+/// Returns a random MAC address from the hypervisor's allocation,
+/// drawing its free bits from the operating system's random number
+/// generator through `getrandom`.
+///
+/// This function requires the `rand` feature.
+#[cfg(feature = "rand")]
+pub fn random_hypervisor(hypervisor: Hypervisor) -> Result<MediaAccessControlAddress, Error> {
+    let mut octets = [0; 6];
+    getrandom::fill(&mut octets).map_err(|error| Error::System(error.to_string()))?;
+    random_hypervisor_with(hypervisor, &mut |random: &mut [u8; 6]| *random = octets)
+}
@@ -0,0 +1,219 @@
+use super::error::Error;
+use super::macaddress::MediaAccessControlAddress;
+use super::prefix::{to_octets, to_value, MacPrefix};
+use std::borrow::Borrow;
+use std::fmt;
+use std::str::FromStr;
+
+/// `MacRange` is an inclusive range of MAC addresses, from `first`
+/// through `last`, which need not line up with a prefix.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct MacRange {
+    first: [u8; 6],
+    last: [u8; 6],
+}
+
+/// Converts a 48-bit value into a MAC address.
+fn to_address(value: u64) -> MediaAccessControlAddress {
+    MediaAccessControlAddress::from(to_octets(value))
+}
+
+impl MacRange {
+    /// Instantiates `MacRange` from `first` through `last`, which
+    /// must not come before `first`.
+    pub fn new(
+        first: &MediaAccessControlAddress,
+        last: &MediaAccessControlAddress,
+    ) -> Result<Self, Error> {
+        let (first, last) = (first.to_octets(), last.to_octets());
+
+        if first <= last {
+            Ok(Self { first, last })
+        } else {
+            Err(Error::Invalid(String::from(
+                "Pass in a first address that does not come after the last.",
+            )))
+        }
+    }
+
+    /// Returns the first MAC address in the range.
+    pub fn first(&self) -> MediaAccessControlAddress {
+        MediaAccessControlAddress::from(self.first)
+    }
+
+    /// Returns the last MAC address in the range.
+    pub fn last(&self) -> MediaAccessControlAddress {
+        MediaAccessControlAddress::from(self.last)
+    }
+
+    /// Returns the number of addresses in the range.
+    pub fn size(&self) -> u64 {
+        to_value(&self.last) - to_value(&self.first) + 1
+    }
+
+    /// Whether the range contains the MAC address.
+    pub fn contains(&self, address: &MediaAccessControlAddress) -> bool {
+        let octets = address.to_octets();
+        self.first <= octets && octets <= self.last
+    }
+
+    /// Returns an iterator over the addresses in the range, in
+    /// order.
+    pub fn iter(&self) -> impl Iterator<Item = MediaAccessControlAddress> {
+        (to_value(&self.first)..=to_value(&self.last)).map(to_address)
+    }
+
+    /// Returns the fewest prefixes that cover the range exactly,
+    /// in order, as filter lists and ACLs need them.
+    pub fn to_prefixes(&self) -> Vec<MacPrefix> {
+        let mut prefixes = Vec::new();
+        let mut next = to_value(&self.first);
+        let last = to_value(&self.last);
+
+        while next <= last {
+            // The largest block that starts at `next` is as large
+            // as its trailing zero bits allow, and no larger than
+            // what remains of the range.
+            let mut bits = next.trailing_zeros().min(48);
+
+            while bits > 0 && next + (1 << bits) - 1 > last {
+                bits -= 1;
+            }
+
+            prefixes.push(MacPrefix::new(&to_address(next), 48 - bits as u8).unwrap());
+            next += 1 << bits;
+        }
+
+        prefixes
+    }
+}
+
+impl From<MacPrefix> for MacRange {
+    fn from(prefix: MacPrefix) -> Self {
+        Self {
+            first: prefix.first().to_octets(),
+            last: prefix.last().to_octets(),
+        }
+    }
+}
+
+/// Parses `first-last`, where both addresses are in the same
+/// notation, which may be any that `MediaAccessControlAddress::new`
+/// accepts (for example, `a0:b1:c2:00:00:00-a0:b1:c2:00:ff:ff`).
+impl FromStr for MacRange {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        // Addresses in hyphen notations contain hyphens, too, so
+        // split at the hyphen in the middle.
+        let s = s.trim();
+        let hyphens: Vec<usize> = s.match_indices('-').map(|(index, _)| index).collect();
+
+        if hyphens.len() & 1 == 0 {
+            return Err(Error::Invalid(String::from(
+                "Pass in two addresses in the same notation, separated by a hyphen.",
+            )));
+        }
+
+        let middle = hyphens[hyphens.len() / 2];
+        let first = MediaAccessControlAddress::new(s[..middle].trim())?;
+        let last = MediaAccessControlAddress::new(s[middle + 1..].trim())?;
+        Self::new(&first, &last)
+    }
+}
+
+/// Formats the range as `first-last`, with both addresses in colon
+/// notation.
+impl fmt::Display for MacRange {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{}-{}",
+            self.first().to_colon_notation(),
+            self.last().to_colon_notation()
+        )
+    }
+}
+
+/// Collapses MAC addresses, in any order and with any duplicates,
+/// into the fewest ranges that hold them all and nothing else, in
+/// order.
+///
+/// Call `MacRange::to_prefixes` on each range to turn a list of
+/// addresses into a filter list.
+pub fn summarize<I>(addresses: I) -> Vec<MacRange>
+where
+    I: IntoIterator,
+    I::Item: Borrow<MediaAccessControlAddress>,
+{
+    let mut values: Vec<u64> = addresses
+        .into_iter()
+        .map(|address| to_value(&address.borrow().to_octets()))
+        .collect();
+    values.sort_unstable();
+    values.dedup();
+
+    let mut ranges: Vec<(u64, u64)> = Vec::new();
+
+    for value in values {
+        match ranges.last_mut() {
+            Some((_, last)) if *last + 1 == value => *last = value,
+            _ => ranges.push((value, value)),
+        }
+    }
+
+    ranges
+        .into_iter()
+        .map(|(first, last)| MacRange {
+            first: to_octets(first),
+            last: to_octets(last),
+        })
+        .collect()
+}
+
+/// Serializes the range as `first-last` (for example,
+/// `a0:b1:c2:00:00:00-a0:b1:c2:00:ff:ff`) for human-readable
+/// formats, such as JSON, and as the six octets of each address for
+/// other formats.
+///
+/// This implementation requires the `serde` feature.
+#[cfg(feature = "serde")]
+impl serde::Serialize for MacRange {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        if serializer.is_human_readable() {
+            serializer.collect_str(self)
+        } else {
+            let mut bytes = [0; 12];
+            bytes[..6].copy_from_slice(&self.first);
+            bytes[6..].copy_from_slice(&self.last);
+            serializer.serialize_bytes(&bytes)
+        }
+    }
+}
+
+/// Deserializes the range from the forms that it serializes to.
+///
+/// This implementation requires the `serde` feature.
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for MacRange {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        super::utils::deserialize(
+            deserializer,
+            "a range of MAC addresses",
+            |text| text.parse().map_err(String::from),
+            |bytes| {
+                if bytes.len() != 12 {
+                    return Err(String::from(
+                        "Pass in 12 bytes: six octets for each address.",
+                    ));
+                }
+
+                Self::new(
+                    &MediaAccessControlAddress::from_octets(&bytes[..6])?,
+                    &MediaAccessControlAddress::from_octets(&bytes[6..])?,
+                )
+                .map_err(String::from)
+            },
+        )
+    }
+}
@@ -0,0 +1,334 @@
+use super::error::Error;
+use super::export::escape_json;
+use super::fingerprint::{fingerprint, Origin, Role};
+use super::macaddress::MediaAccessControlAddress;
+use super::prefix::MacPrefix;
+use super::set::MacSet;
+use super::vendor::VendorResolver;
+use std::borrow::Borrow;
+use std::collections::HashMap;
+use std::hash::Hash;
+
+/// A summary of an inventory of MAC addresses, such as the
+/// addresses seen in a capture, for dashboards and reports.
+///
+/// Every count is of distinct addresses, however many times each
+/// was observed.  The lists are sorted from the largest count to
+/// the smallest, and then by name, so the top entries come first.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Report {
+    /// The number of addresses observed, counting repeats.
+    pub observations: usize,
+    /// The number of distinct addresses.
+    pub addresses: usize,
+    /// The number of universally-administered unicast addresses.
+    pub uaa: usize,
+    /// The number of locally-administered unicast addresses.
+    pub laa: usize,
+    /// The number of multicast addresses, including the broadcast
+    /// address.
+    pub multicast: usize,
+    /// The number of unicast addresses from each origin (physical,
+    /// virtual, and randomized, in that order), as `fingerprint`
+    /// judges them.
+    pub origins: Vec<(Origin, usize)>,
+    /// The number of addresses from each vendor.
+    pub vendors: Vec<(String, usize)>,
+    /// The number of addresses whose vendor is unknown.
+    pub unknown_vendors: usize,
+    /// The number of addresses under each OUI.
+    pub prefixes: Vec<(MacPrefix, usize)>,
+    /// The number of addresses reserved for each well-known
+    /// protocol.
+    pub protocols: Vec<(Role, usize)>,
+}
+
+/// One list in a report: its name, the name of its entries, and the
+/// entries as text, with their counts.
+type List = (&'static str, &'static str, Vec<(String, usize)>);
+
+/// Sorts counts from the largest to the smallest, and then by key.
+fn ranked<K: Eq + Hash, S: Ord>(
+    counts: HashMap<K, usize>,
+    key: impl Fn(&K) -> S,
+) -> Vec<(K, usize)> {
+    let mut counts: Vec<(K, usize)> = counts.into_iter().collect();
+    counts.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| key(&a.0).cmp(&key(&b.0))));
+    counts
+}
+
+/// Returns a report on the MAC addresses, looking up each distinct
+/// address's vendor with `resolver` once.
+///
+/// If the resolver fails for any address, then the error is
+/// returned.
+pub fn report<R, I>(addresses: I, resolver: &R) -> Result<Report, Error>
+where
+    R: VendorResolver,
+    I: IntoIterator,
+    I::Item: Borrow<MediaAccessControlAddress>,
+{
+    let mut observations = 0;
+    let mut distinct = MacSet::new();
+
+    for mac in addresses {
+        distinct.insert(mac.borrow());
+        observations += 1;
+    }
+
+    let mut report = Report {
+        observations,
+        addresses: distinct.len(),
+        ..Report::default()
+    };
+
+    let mut origins = HashMap::new();
+    let mut vendors = HashMap::new();
+    let mut prefixes = HashMap::new();
+    let mut protocols = HashMap::new();
+
+    for mac in distinct.iter() {
+        let fingerprint = fingerprint(&mac, resolver)?;
+
+        if mac.is_uaa() {
+            report.uaa += 1;
+        } else if mac.is_laa() {
+            report.laa += 1;
+        }
+
+        if mac.is_multicast() {
+            report.multicast += 1;
+        }
+
+        if let Some(origin) = fingerprint.origin {
+            *origins.entry(origin).or_insert(0) += 1;
+        }
+
+        match fingerprint.vendor {
+            Some(vendor) => *vendors.entry(vendor).or_insert(0) += 1,
+            None => report.unknown_vendors += 1,
+        }
+
+        *prefixes.entry(MacPrefix::oui(&mac)).or_insert(0) += 1;
+
+        if let Some(role) = fingerprint.role {
+            *protocols.entry(role).or_insert(0) += 1;
+        }
+    }
+
+    report.origins = [Origin::Physical, Origin::Virtual, Origin::Randomized]
+        .iter()
+        .map(|origin| (*origin, origins.get(origin).copied().unwrap_or(0)))
+        .collect();
+    report.vendors = ranked(vendors, String::clone);
+    report.prefixes = ranked(prefixes, |prefix| *prefix);
+    report.protocols = ranked(protocols, |role| role.as_str());
+    Ok(report)
+}
+
+impl Report {
+    /// Returns `count` as a fraction of the distinct addresses (for
+    /// example, `report.ratio(report.laa)`), or 0 if there are
+    /// none.
+    pub fn ratio(&self, count: usize) -> f64 {
+        if self.addresses == 0 {
+            0.0
+        } else {
+            count as f64 / self.addresses as f64
+        }
+    }
+
+    /// Returns the number of unicast addresses from `origin`.
+    pub fn origin(&self, origin: Origin) -> usize {
+        self.origins
+            .iter()
+            .find(|(other, _)| *other == origin)
+            .map_or(0, |(_, count)| *count)
+    }
+
+    /// Returns the lists of vendors, prefixes, and protocols, each
+    /// with the name of its entries, as text.
+    fn lists(&self) -> [List; 3] {
+        [
+            ("vendors", "vendor", self.vendors.clone()),
+            (
+                "prefixes",
+                "prefix",
+                self.prefixes
+                    .iter()
+                    .map(|(prefix, count)| (prefix.to_string(), *count))
+                    .collect(),
+            ),
+            (
+                "protocols",
+                "protocol",
+                self.protocols
+                    .iter()
+                    .map(|(role, count)| (role.to_string(), *count))
+                    .collect(),
+            ),
+        ]
+    }
+
+    /// Returns the counts that stand alone, by name.
+    fn counts(&self) -> [(&'static str, usize); 6] {
+        [
+            ("observations", self.observations),
+            ("addresses", self.addresses),
+            ("uaa", self.uaa),
+            ("laa", self.laa),
+            ("multicast", self.multicast),
+            ("unknown_vendors", self.unknown_vendors),
+        ]
+    }
+
+    /// Returns the UAA, LAA, and randomized ratios, by name.
+    fn ratios(&self) -> [(&'static str, f64); 3] {
+        [
+            ("uaa", self.ratio(self.uaa)),
+            ("laa", self.ratio(self.laa)),
+            ("randomized", self.ratio(self.origin(Origin::Randomized))),
+        ]
+    }
+
+    /// Returns the report as one YAML mapping with the same
+    /// structure as `to_json`, for automation tools such as Ansible.
+    pub fn to_yaml(&self) -> String {
+        let mut yaml = String::new();
+
+        for (name, count) in self.counts().iter() {
+            yaml.push_str(&format!("{}: {}\n", name, count));
+        }
+
+        yaml.push_str("ratios:\n");
+
+        for (name, ratio) in self.ratios().iter() {
+            yaml.push_str(&format!("  {}: {:?}\n", name, ratio));
+        }
+
+        yaml.push_str("origins:\n");
+
+        for (origin, count) in self.origins.iter() {
+            yaml.push_str(&format!("  {}: {}\n", origin, count));
+        }
+
+        for (name, key, entries) in self.lists().iter() {
+            if entries.is_empty() {
+                yaml.push_str(&format!("{}: []\n", name));
+                continue;
+            }
+
+            yaml.push_str(&format!("{}:\n", name));
+
+            for (entry, count) in entries.iter() {
+                yaml.push_str(&format!(
+                    "  - {}: {}\n    count: {}\n",
+                    key,
+                    escape_json(entry),
+                    count
+                ));
+            }
+        }
+
+        yaml
+    }
+
+    /// Returns the report as one TOML document with the same
+    /// structure as `to_json`, with each list as an array of
+    /// tables.
+    pub fn to_toml(&self) -> String {
+        let lists = self.lists();
+        let mut toml = String::new();
+
+        for (name, count) in self.counts().iter() {
+            toml.push_str(&format!("{} = {}\n", name, count));
+        }
+
+        // Empty lists go before the first table, as plain arrays.
+        for (name, _, entries) in lists.iter() {
+            if entries.is_empty() {
+                toml.push_str(&format!("{} = []\n", name));
+            }
+        }
+
+        toml.push_str("\n[ratios]\n");
+
+        for (name, ratio) in self.ratios().iter() {
+            toml.push_str(&format!("{} = {:?}\n", name, ratio));
+        }
+
+        toml.push_str("\n[origins]\n");
+
+        for (origin, count) in self.origins.iter() {
+            toml.push_str(&format!("{} = {}\n", origin, count));
+        }
+
+        for (name, key, entries) in lists.iter() {
+            for (entry, count) in entries.iter() {
+                toml.push_str(&format!(
+                    "\n[[{}]]\n{} = {}\ncount = {}\n",
+                    name,
+                    key,
+                    escape_json(entry),
+                    count
+                ));
+            }
+        }
+
+        toml
+    }
+
+    /// Returns the report as one JSON object, with the UAA, LAA,
+    /// and randomized ratios alongside the counts.
+    pub fn to_json(&self) -> String {
+        let list = |name: &str, entries: Vec<(String, usize)>| {
+            let entries: Vec<String> = entries
+                .iter()
+                .map(|(key, count)| {
+                    format!("{{\"{}\":{},\"count\":{}}}", name, escape_json(key), count)
+                })
+                .collect();
+            format!("[{}]", entries.join(","))
+        };
+
+        let origins: Vec<String> = self
+            .origins
+            .iter()
+            .map(|(origin, count)| format!("\"{}\":{}", origin, count))
+            .collect();
+
+        format!(
+            concat!(
+                "{{\"observations\":{},\"addresses\":{},\"uaa\":{},\"laa\":{},\"multicast\":{},",
+                "\"ratios\":{{\"uaa\":{},\"laa\":{},\"randomized\":{}}},",
+                "\"origins\":{{{}}},\"vendors\":{},\"unknown_vendors\":{},",
+                "\"prefixes\":{},\"protocols\":{}}}"
+            ),
+            self.observations,
+            self.addresses,
+            self.uaa,
+            self.laa,
+            self.multicast,
+            self.ratio(self.uaa),
+            self.ratio(self.laa),
+            self.ratio(self.origin(Origin::Randomized)),
+            origins.join(","),
+            list("vendor", self.vendors.clone()),
+            self.unknown_vendors,
+            list(
+                "prefix",
+                self.prefixes
+                    .iter()
+                    .map(|(prefix, count)| (prefix.to_string(), *count))
+                    .collect()
+            ),
+            list(
+                "protocol",
+                self.protocols
+                    .iter()
+                    .map(|(role, count)| (role.to_string(), *count))
+                    .collect()
+            ),
+        )
+    }
+}
@@ -0,0 +1,188 @@
+use super::format::Notation;
+use super::macaddress::MediaAccessControlAddress;
+use super::utils;
+use std::collections::VecDeque;
+use std::io::{self, BufRead};
+
+/// A MAC address found in text, with where it was found.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Match {
+    /// The byte offset at which the address starts.
+    pub start: usize,
+    /// The byte offset just past the address.
+    pub end: usize,
+    /// The notation in which the address was written.
+    pub notation: Notation,
+    /// The address.
+    pub address: MediaAccessControlAddress,
+}
+
+/// Returns the separator between groups of digits in a notation.
+fn separator(notation: Notation) -> Option<u8> {
+    match notation {
+        Notation::Plain => None,
+        Notation::Hyphen | Notation::FourDigitHyphen => Some(b'-'),
+        Notation::Colon | Notation::Solaris => Some(b':'),
+        Notation::Dot => Some(b'.'),
+        Notation::Space => Some(b' '),
+    }
+}
+
+/// Checks whether a byte could continue a word.
+fn is_word(byte: u8) -> bool {
+    byte.is_ascii_alphanumeric() || byte == b'_'
+}
+
+/// Checks whether the address at `start..end` stands on its own,
+/// rather than being part of a longer word or a longer run of
+/// groups (for example, an EUI-64 or an IPv6 address).
+fn stands_alone(bytes: &[u8], start: usize, end: usize, notation: Notation) -> bool {
+    let before = |offset: usize| start.checked_sub(offset).map(|index| bytes[index]);
+    let after = |offset: usize| bytes.get(end + offset - 1).copied();
+    let hex = |byte: Option<u8>| byte.is_some_and(|byte| byte.is_ascii_hexdigit());
+
+    if before(1).is_some_and(is_word) || after(1).is_some_and(is_word) {
+        return false;
+    }
+
+    let (before_run, after_run) = match separator(notation) {
+        // In space notation, only a whole group of two digits
+        // continues the run, so that words such as "at" do not.
+        Some(b' ') => (
+            before(1) == Some(b' ')
+                && hex(before(2))
+                && hex(before(3))
+                && !before(4).is_some_and(is_word),
+            after(1) == Some(b' ')
+                && hex(after(2))
+                && hex(after(3))
+                && !after(4).is_some_and(is_word),
+        ),
+        Some(separator) => (
+            before(1) == Some(separator) && hex(before(2)),
+            after(1) == Some(separator) && hex(after(2)),
+        ),
+        None => (false, false),
+    };
+
+    !before_run && !after_run
+}
+
+/// Finds the addresses in one run of bytes, with offsets from
+/// `offset`.
+fn find_in(bytes: &[u8], offset: usize, notations: &[Notation], matches: &mut VecDeque<Match>) {
+    let mut start = 0;
+
+    'outer: while start < bytes.len() {
+        if !bytes[start].is_ascii_hexdigit() || (start > 0 && is_word(bytes[start - 1])) {
+            start += 1;
+            continue;
+        }
+
+        // The longest notations take 17 bytes, and the shortest
+        // (Solaris notation, omitting every leading zero) take 11.
+        for length in (11..=17).rev() {
+            let end = start + length;
+
+            let candidate = match bytes.get(start..end).map(std::str::from_utf8) {
+                Some(Ok(candidate)) => candidate,
+                _ => continue,
+            };
+
+            let notation = match utils::notation(candidate) {
+                Some(notation) => notation,
+                None => continue,
+            };
+
+            if notations.contains(&notation) && stands_alone(bytes, start, end, notation) {
+                matches.push_back(Match {
+                    start: offset + start,
+                    end: offset + end,
+                    notation,
+                    address: MediaAccessControlAddress::new(candidate).unwrap(),
+                });
+                start = end;
+                continue 'outer;
+            }
+        }
+
+        start += 1;
+    }
+}
+
+/// Finds every MAC address in `text` (for example, a log file), in
+/// any supported notation, in the order in which they appear.
+///
+/// An address counts only if it stands on its own: not inside a
+/// longer word (such as a hash) or a longer run of groups (such as
+/// an EUI-64 or an IPv6 address).
+pub fn find_all(text: &str) -> Vec<Match> {
+    find_all_with(text, &Notation::ALL)
+}
+
+/// Finds every MAC address in `text` in one of `notations`.
+///
+/// Plain notation in particular matches any 12 hexadecimal digits,
+/// including 12 decimal ones, so leave it out when scanning text
+/// that is full of numbers.
+pub fn find_all_with(text: &str, notations: &[Notation]) -> Vec<Match> {
+    let mut matches = VecDeque::new();
+    find_in(text.as_bytes(), 0, notations, &mut matches);
+    matches.into()
+}
+
+/// An iterator over the MAC addresses in a reader, which reads one
+/// line at a time, so that it can scan files too large to hold in
+/// memory.
+///
+/// Offsets count bytes from the start of the reader.  Lines need
+/// not be valid UTF-8, since addresses are found in their bytes.
+#[derive(Debug)]
+pub struct Scanner<R> {
+    reader: R,
+    notations: Vec<Notation>,
+    offset: usize,
+    line: Vec<u8>,
+    matches: VecDeque<Match>,
+}
+
+impl<R: BufRead> Scanner<R> {
+    /// Instantiates `Scanner` to find addresses in any supported
+    /// notation.
+    pub fn new(reader: R) -> Self {
+        Self {
+            reader,
+            notations: Notation::ALL.to_vec(),
+            offset: 0,
+            line: Vec::new(),
+            matches: VecDeque::new(),
+        }
+    }
+
+    /// Finds addresses in only the given notations instead.
+    pub fn with_notations(mut self, notations: &[Notation]) -> Self {
+        self.notations = notations.to_vec();
+        self
+    }
+}
+
+impl<R: BufRead> Iterator for Scanner<R> {
+    type Item = io::Result<Match>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while self.matches.is_empty() {
+            self.line.clear();
+
+            match self.reader.read_until(b'\n', &mut self.line) {
+                Ok(0) => return None,
+                Ok(length) => {
+                    find_in(&self.line, self.offset, &self.notations, &mut self.matches);
+                    self.offset += length;
+                }
+                Err(error) => return Some(Err(error)),
+            }
+        }
+
+        self.matches.pop_front().map(Ok)
+    }
+}
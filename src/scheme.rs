@@ -0,0 +1,214 @@
+use super::error::Error;
+use super::macaddress::MediaAccessControlAddress;
+use std::fmt;
+use std::str::FromStr;
+
+/// The number of bits in a locally-administered unicast address
+/// that are free to carry fields (all but the multicast and local
+/// bits).
+const PAYLOAD_BITS: u8 = 46;
+
+/// `LaaScheme` is a bit-field layout over the locally-administered
+/// unicast space (for example, 8 bits of site, 12 bits of VLAN, and
+/// 16 bits of host), for organizations that assign LAAs
+/// systematically rather than at random.
+///
+/// The fields are packed into the 46 free bits from the
+/// least-significant end, so the last field occupies the last
+/// octets of the address and the first field sits just above it.
+/// The multicast and local bits of the first octet are skipped, and
+/// any bits above the first field are zero.  With nibble-sized
+/// fields, each one reads directly off the hexadecimal address:
+/// under `site:8,vlan:12,host:16`, site `0x2a`, VLAN `0x064`, and
+/// host `0x0105` encode as `02:02:a0:64:01:05`.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct LaaScheme {
+    fields: Vec<(String, u8)>,
+}
+
+/// Spreads 46 bits of payload around the multicast and local bits,
+/// setting the local bit.
+fn to_address(payload: u64) -> MediaAccessControlAddress {
+    MediaAccessControlAddress::from([0b0000_0010, 0, 0, 0, 0, 0])
+        .with_bits_range(0, 6, payload >> 40)
+        .and_then(|address| address.with_bits_range(8, 40, payload & 0xff_ffff_ffff))
+        .unwrap()
+}
+
+/// Gathers the 46 bits of payload from around the multicast and
+/// local bits.
+fn to_payload(address: &MediaAccessControlAddress) -> u64 {
+    (address.bits_range(0, 6).unwrap() << 40) | address.bits_range(8, 40).unwrap()
+}
+
+impl LaaScheme {
+    /// Instantiates `LaaScheme` with the fields' names and widths in
+    /// bits, from the most-significant field to the least.
+    ///
+    /// Each field must have a distinct, non-empty name and at least
+    /// 1 bit, and the fields together may have no more than 46 bits.
+    pub fn new(fields: &[(&str, u8)]) -> Result<Self, Error> {
+        let mut total: u32 = 0;
+
+        for (index, (name, width)) in fields.iter().enumerate() {
+            if name.is_empty() || name.contains([':', ',']) || name.trim() != *name {
+                return Err(Error::Invalid(format!(
+                    "Pass in field names without colons, commas, or surrounding spaces (not {:?}).",
+                    name
+                )));
+            }
+
+            if fields[..index].iter().any(|(other, _)| other == name) {
+                return Err(Error::Invalid(format!(
+                    "Pass in distinct field names ({} repeats).",
+                    name
+                )));
+            }
+
+            if *width == 0 {
+                return Err(Error::Invalid(format!(
+                    "Pass in at least 1 bit for field {}.",
+                    name
+                )));
+            }
+
+            total += u32::from(*width);
+        }
+
+        if fields.is_empty() || total > u32::from(PAYLOAD_BITS) {
+            return Err(Error::Invalid(String::from(
+                "Pass in at least 1 field and no more than 46 bits in all.",
+            )));
+        }
+
+        Ok(Self {
+            fields: fields
+                .iter()
+                .map(|(name, width)| (name.to_string(), *width))
+                .collect(),
+        })
+    }
+
+    /// Returns an iterator over the fields' names and widths, from
+    /// the most-significant field to the least.
+    pub fn fields(&self) -> impl Iterator<Item = (&str, u8)> + '_ {
+        self.fields
+            .iter()
+            .map(|(name, width)| (name.as_str(), *width))
+    }
+
+    /// Returns the number of bits that the fields use.
+    pub fn bits(&self) -> u8 {
+        self.fields.iter().map(|(_, width)| width).sum()
+    }
+
+    /// Returns the locally-administered unicast address that holds
+    /// `values`, one for each field, in the fields' order.
+    ///
+    /// Each value must fit in its field.
+    pub fn encode(&self, values: &[u64]) -> Result<MediaAccessControlAddress, Error> {
+        if values.len() != self.fields.len() {
+            return Err(Error::Invalid(format!(
+                "Pass in {} values, one for each field.",
+                self.fields.len()
+            )));
+        }
+
+        let mut payload = 0;
+
+        for ((name, width), value) in self.fields.iter().zip(values) {
+            if value >> width != 0 {
+                return Err(Error::Invalid(format!(
+                    "Pass in a value for field {} that fits in {} bits (not {}).",
+                    name, width, value
+                )));
+            }
+
+            payload = (payload << width) | value;
+        }
+
+        Ok(to_address(payload))
+    }
+
+    /// Returns the values of the fields in the address, in the
+    /// fields' order.
+    ///
+    /// The address must be a locally-administered unicast address
+    /// whose bits above the first field are zero, as `encode`
+    /// leaves them.
+    pub fn decode(&self, address: &MediaAccessControlAddress) -> Result<Vec<u64>, Error> {
+        let mut payload = to_payload(address);
+
+        if !address.is_laa() || !address.is_unicast() || payload >> self.bits() != 0 {
+            return Err(Error::Invalid(format!(
+                "Pass in an address that this scheme encodes (not {}).",
+                address.to_colon_notation()
+            )));
+        }
+
+        let mut values = vec![0; self.fields.len()];
+
+        for ((_, width), value) in self.fields.iter().zip(values.iter_mut()).rev() {
+            *value = payload & ((1 << width) - 1);
+            payload >>= width;
+        }
+
+        Ok(values)
+    }
+
+    /// Returns the value of the named field in the address.
+    pub fn get(&self, address: &MediaAccessControlAddress, name: &str) -> Result<u64, Error> {
+        let index = self
+            .fields
+            .iter()
+            .position(|(other, _)| other == name)
+            .ok_or_else(|| {
+                Error::Invalid(format!(
+                    "Pass in the name of a field in the scheme (not {}).",
+                    name
+                ))
+            })?;
+
+        Ok(self.decode(address)?[index])
+    }
+}
+
+/// Parses a comma-separated list of `name:width` fields (for
+/// example, `site:8,vlan:12,host:16`), from the most-significant
+/// field to the least.
+impl FromStr for LaaScheme {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut fields = Vec::new();
+
+        for field in s.split(',') {
+            let (name, width) = field
+                .split_once(':')
+                .and_then(|(name, width)| Some((name.trim(), width.trim().parse().ok()?)))
+                .ok_or_else(|| {
+                    Error::Invalid(format!(
+                        "Pass in fields as name:width, separated by commas (not {}).",
+                        field.trim()
+                    ))
+                })?;
+
+            fields.push((name, width));
+        }
+
+        Self::new(&fields)
+    }
+}
+
+/// Formats the scheme the way `from_str` parses it.
+impl fmt::Display for LaaScheme {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let fields: Vec<String> = self
+            .fields
+            .iter()
+            .map(|(name, width)| format!("{}:{}", name, width))
+            .collect();
+
+        f.pad(&fields.join(","))
+    }
+}
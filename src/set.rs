@@ -0,0 +1,328 @@
+use super::error::Error;
+use super::macaddress::MediaAccessControlAddress;
+use super::prefix::MacPrefix;
+use std::collections::BTreeSet;
+use std::fs::{self, File};
+use std::io::{self, BufWriter, Write};
+use std::iter::FromIterator;
+use std::path::Path;
+
+/// The first six bytes of a compiled set.
+const MAGIC: &[u8; 6] = b"MACSET";
+
+/// The version of the compiled format that this crate writes.
+const VERSION: u16 = 1;
+
+/// The length of a compiled set's header: the magic bytes, the
+/// version, and the number of addresses.
+const HEADER: usize = 16;
+
+/// `MacSet` is an ordered set of MAC addresses, such as a deny list
+/// or a list of devices seen on a network.
+///
+/// It holds each address as six octets and keeps them in numeric
+/// order, so it can list every address under a prefix.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct MacSet {
+    addresses: BTreeSet<[u8; 6]>,
+}
+
+/// Converts octets back into a MAC address.
+fn to_address(octets: &[u8; 6]) -> MediaAccessControlAddress {
+    MediaAccessControlAddress::from(*octets)
+}
+
+impl MacSet {
+    /// Instantiates an empty `MacSet`.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the number of addresses.
+    pub fn len(&self) -> usize {
+        self.addresses.len()
+    }
+
+    /// Whether the set has no addresses.
+    pub fn is_empty(&self) -> bool {
+        self.addresses.is_empty()
+    }
+
+    /// Adds the MAC address and returns whether it was new.
+    pub fn insert(&mut self, address: &MediaAccessControlAddress) -> bool {
+        self.addresses.insert(address.to_octets())
+    }
+
+    /// Whether the set holds the MAC address.
+    pub fn contains(&self, address: &MediaAccessControlAddress) -> bool {
+        self.addresses.contains(&address.to_octets())
+    }
+
+    /// Removes the MAC address and returns whether it was there.
+    pub fn remove(&mut self, address: &MediaAccessControlAddress) -> bool {
+        self.addresses.remove(&address.to_octets())
+    }
+
+    /// Removes every address.
+    pub fn clear(&mut self) {
+        self.addresses.clear();
+    }
+
+    /// Returns an iterator over the addresses, in order.
+    pub fn iter(&self) -> impl Iterator<Item = MediaAccessControlAddress> + '_ {
+        self.addresses.iter().map(to_address)
+    }
+
+    /// Returns an iterator over the addresses that fall under
+    /// `prefix`, in order.
+    pub fn range(
+        &self,
+        prefix: &MacPrefix,
+    ) -> impl Iterator<Item = MediaAccessControlAddress> + '_ {
+        let (first, last) = prefix.bounds();
+        self.addresses.range(first..=last).map(to_address)
+    }
+
+    /// Writes the set in the compiled format that `CompiledMacSet`
+    /// reads.
+    pub fn write_compiled<W: Write>(&self, mut writer: W) -> io::Result<()> {
+        writer.write_all(MAGIC)?;
+        writer.write_all(&VERSION.to_be_bytes())?;
+        writer.write_all(&(self.addresses.len() as u64).to_be_bytes())?;
+
+        for octets in self.addresses.iter() {
+            writer.write_all(octets)?;
+        }
+
+        Ok(())
+    }
+
+    /// Writes the set to a file in the compiled format that
+    /// `CompiledMacSet` reads.
+    pub fn save_compiled<P: AsRef<Path>>(&self, path: P) -> Result<(), Error> {
+        let mut writer = BufWriter::new(File::create(path)?);
+        self.write_compiled(&mut writer)?;
+        writer.flush()?;
+        Ok(())
+    }
+}
+
+impl FromIterator<MediaAccessControlAddress> for MacSet {
+    fn from_iter<I: IntoIterator<Item = MediaAccessControlAddress>>(iter: I) -> Self {
+        let mut set = Self::new();
+        set.extend(iter);
+        set
+    }
+}
+
+impl<'a> FromIterator<&'a MediaAccessControlAddress> for MacSet {
+    fn from_iter<I: IntoIterator<Item = &'a MediaAccessControlAddress>>(iter: I) -> Self {
+        let mut set = Self::new();
+        set.extend(iter);
+        set
+    }
+}
+
+impl Extend<MediaAccessControlAddress> for MacSet {
+    fn extend<I: IntoIterator<Item = MediaAccessControlAddress>>(&mut self, iter: I) {
+        self.addresses
+            .extend(iter.into_iter().map(|address| address.to_octets()));
+    }
+}
+
+impl<'a> Extend<&'a MediaAccessControlAddress> for MacSet {
+    fn extend<I: IntoIterator<Item = &'a MediaAccessControlAddress>>(&mut self, iter: I) {
+        self.addresses
+            .extend(iter.into_iter().map(|address| address.to_octets()));
+    }
+}
+
+/// `CompiledMacSet` is a read-only set of MAC addresses in a compact
+/// file format, for sets too large to parse at startup, such as deny
+/// lists or lists of devices seen on a network.  Build one offline
+/// with `MacSet::save_compiled`, and then open it at runtime with
+/// `open` (which memory-maps it) or `load`.
+///
+/// The format is a 16-byte header, followed by the addresses as six
+/// octets each, in numeric order and without duplicates.  The
+/// header holds the magic bytes `MACSET`, the format's version (1)
+/// as two bytes, and the number of addresses as eight bytes, both
+/// most-significant byte first.
+///
+/// Lookups search the addresses where they lie, so instantiating
+/// `CompiledMacSet` checks only the header and the length, not the
+/// order.  Only open files that `save_compiled` wrote.
+#[derive(Debug)]
+pub struct CompiledMacSet<B> {
+    bytes: B,
+}
+
+impl<B: AsRef<[u8]>> CompiledMacSet<B> {
+    /// Instantiates `CompiledMacSet` with the bytes of a compiled
+    /// set, such as a memory map or a `Vec<u8>`.
+    pub fn from_bytes(bytes: B) -> Result<Self, Error> {
+        let data = bytes.as_ref();
+
+        if data.len() < HEADER || &data[..6] != MAGIC {
+            return Err(Error::Storage(String::from(
+                "Pass in a compiled set of MAC addresses.",
+            )));
+        }
+
+        let version = u16::from_be_bytes([data[6], data[7]]);
+
+        if version != VERSION {
+            return Err(Error::Storage(format!(
+                "Pass in a compiled set of version {} (not {}).",
+                VERSION, version
+            )));
+        }
+
+        let mut count = [0; 8];
+        count.copy_from_slice(&data[8..HEADER]);
+        let count = u64::from_be_bytes(count);
+
+        if (data.len() - HEADER) as u64 != count.saturating_mul(6) {
+            return Err(Error::Storage(format!(
+                "Pass in a compiled set that holds all {} of its addresses.",
+                count
+            )));
+        }
+
+        Ok(Self { bytes })
+    }
+
+    /// Returns the addresses as six octets each, in numeric order.
+    pub fn as_octets(&self) -> &[[u8; 6]] {
+        self.bytes.as_ref()[HEADER..].as_chunks().0
+    }
+
+    /// Returns the number of addresses.
+    pub fn len(&self) -> usize {
+        self.as_octets().len()
+    }
+
+    /// Whether the set has no addresses.
+    pub fn is_empty(&self) -> bool {
+        self.as_octets().is_empty()
+    }
+
+    /// Whether the set holds the MAC address.
+    pub fn contains(&self, address: &MediaAccessControlAddress) -> bool {
+        self.as_octets().binary_search(&address.to_octets()).is_ok()
+    }
+
+    /// Returns an iterator over the addresses, in order.
+    pub fn iter(&self) -> impl Iterator<Item = MediaAccessControlAddress> + '_ {
+        self.as_octets().iter().map(to_address)
+    }
+
+    /// Returns an iterator over the addresses that fall under
+    /// `prefix`, in order.
+    pub fn range(
+        &self,
+        prefix: &MacPrefix,
+    ) -> impl Iterator<Item = MediaAccessControlAddress> + '_ {
+        let (first, last) = prefix.bounds();
+        let octets = self.as_octets();
+        let start = octets.partition_point(|octets| *octets < first);
+        let end = octets.partition_point(|octets| *octets <= last);
+        octets[start..end].iter().map(to_address)
+    }
+
+    /// Copies the addresses into a `MacSet`, which can be changed.
+    pub fn to_set(&self) -> MacSet {
+        MacSet {
+            addresses: self.as_octets().iter().copied().collect(),
+        }
+    }
+}
+
+impl CompiledMacSet<Vec<u8>> {
+    /// Reads a compiled set from a file into memory.
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<Self, Error> {
+        Self::from_bytes(fs::read(path)?)
+    }
+}
+
+#[cfg(feature = "mmap")]
+impl CompiledMacSet<memmap2::Mmap> {
+    /// Memory-maps a compiled set read-only, so that it is ready
+    /// at once, however large it is, and the operating system pages
+    /// in only the parts that lookups touch.
+    ///
+    /// Do not change the file while it is mapped, since the set
+    /// would change with it, and reading past the end of a file that
+    /// shrank kills the process.  Replace it instead, by writing a new
+    /// file and renaming it over the old one.
+    ///
+    /// This function requires the `mmap` feature.
+    pub fn open<P: AsRef<Path>>(path: P) -> Result<Self, Error> {
+        let file = File::open(path)?;
+        // The map is read-only, and the documentation above warns
+        // against changing the file while it is mapped.
+        let map = unsafe { memmap2::Mmap::map(&file)? };
+        Self::from_bytes(map)
+    }
+}
+
+/// Serializes the set as a list of addresses in colon notation for
+/// human-readable formats, such as JSON, and as the six octets of
+/// each address, in order, for other formats.
+///
+/// This implementation requires the `serde` feature.
+#[cfg(feature = "serde")]
+impl serde::Serialize for MacSet {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        if serializer.is_human_readable() {
+            serializer.collect_seq(self.iter())
+        } else {
+            serializer.serialize_bytes(
+                &self
+                    .addresses
+                    .iter()
+                    .flatten()
+                    .copied()
+                    .collect::<Vec<u8>>(),
+            )
+        }
+    }
+}
+
+/// Deserializes the set from the forms that it serializes to.  The
+/// addresses may be in any order and repeat.
+///
+/// This implementation requires the `serde` feature.
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for MacSet {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        if deserializer.is_human_readable() {
+            return Ok(<Vec<MediaAccessControlAddress>>::deserialize(deserializer)?
+                .into_iter()
+                .collect());
+        }
+
+        super::utils::deserialize(
+            deserializer,
+            "a set of MAC addresses",
+            |_| Err(String::from("Pass in the octets of the addresses.")),
+            |bytes| {
+                let octets = bytes.chunks_exact(6);
+
+                if octets.remainder().is_empty() {
+                    Ok(Self {
+                        addresses: octets
+                            .map(|octets| {
+                                let mut address = [0; 6];
+                                address.copy_from_slice(octets);
+                                address
+                            })
+                            .collect(),
+                    })
+                } else {
+                    Err(String::from("Pass in six octets for each address."))
+                }
+            },
+        )
+    }
+}
@@ -0,0 +1,591 @@
+use super::error::Error;
+use super::macaddress::MediaAccessControlAddress;
+use std::collections::BTreeMap;
+use std::net::{ToSocketAddrs, UdpSocket};
+use std::time::Duration;
+
+/// `dot1dTpFdbEntry` in BRIDGE-MIB (RFC 4188), indexed by address.
+const DOT1D_TP_FDB_ENTRY: [u32; 10] = [1, 3, 6, 1, 2, 1, 17, 4, 3, 1];
+
+/// `dot1qTpFdbEntry` in Q-BRIDGE-MIB (RFC 4363), indexed by
+/// filtering database and then by address.
+const DOT1Q_TP_FDB_ENTRY: [u32; 12] = [1, 3, 6, 1, 2, 1, 17, 7, 1, 2, 2, 1];
+
+/// `dot1dBasePortIfIndex` in BRIDGE-MIB, which maps bridge ports
+/// to interfaces.
+const DOT1D_BASE_PORT_IF_INDEX: [u32; 11] = [1, 3, 6, 1, 2, 1, 17, 1, 4, 1, 2];
+
+/// The number of rows that each `GetBulkRequest` asks for.
+const MAX_REPETITIONS: u32 = 32;
+
+/// How a switch came to hold an entry in its forwarding database,
+/// as `dot1dTpFdbStatus` and `dot1qTpFdbStatus` report it.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum FdbStatus {
+    /// None of the others (for example, a static entry).
+    Other,
+    /// No longer valid, but not yet flushed.
+    Invalid,
+    /// Learned from traffic on the port.
+    Learned,
+    /// One of the switch's own addresses.
+    SelfAddress,
+    /// Also in the static filtering database.
+    Mgmt,
+}
+
+impl FdbStatus {
+    /// Returns the status for its value in the MIB, or `Other`.
+    fn from_value(value: i64) -> Self {
+        match value {
+            2 => FdbStatus::Invalid,
+            3 => FdbStatus::Learned,
+            4 => FdbStatus::SelfAddress,
+            5 => FdbStatus::Mgmt,
+            _ => FdbStatus::Other,
+        }
+    }
+}
+
+/// One entry in a switch's forwarding database (its CAM table): a
+/// MAC address and the port on which the switch last saw it.
+#[derive(Debug)]
+pub struct FdbEntry {
+    /// The MAC address.
+    pub address: MediaAccessControlAddress,
+    /// The bridge port (`dot1dBasePort`), or 0 if the switch does
+    /// not know it.
+    pub port: u32,
+    /// The interface (`ifIndex`) behind the bridge port, if the
+    /// switch reports one.
+    pub if_index: Option<u32>,
+    /// The filtering database, from Q-BRIDGE-MIB, which is the VLAN
+    /// ID on switches with independent VLAN learning (as most are),
+    /// or `None` from BRIDGE-MIB.
+    pub vlan: Option<u32>,
+    /// How the switch came to hold the entry.
+    pub status: FdbStatus,
+}
+
+/// A value in an SNMP variable binding.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(crate) enum Value {
+    Integer(i64),
+    Bytes(Vec<u8>),
+    Oid(Vec<u32>),
+    Unsigned(u64),
+    Null,
+    EndOfMibView,
+    Other(u8),
+}
+
+/// A variable binding: an object identifier and its value.
+pub(crate) type Binding = (Vec<u32>, Value);
+
+/// An SNMPv2c message, with the fields that this module uses.
+#[derive(Debug, PartialEq, Eq)]
+pub(crate) struct Message {
+    pub(crate) community: Vec<u8>,
+    /// The PDU's tag (for example, `0xa5` for `GetBulkRequest` and
+    /// `0xa2` for `Response`).
+    pub(crate) tag: u8,
+    pub(crate) request_id: i32,
+    /// The error status in a response, or the non-repeaters in a
+    /// `GetBulkRequest`.
+    pub(crate) first: i64,
+    /// The error index in a response, or the max-repetitions in a
+    /// `GetBulkRequest`.
+    pub(crate) second: i64,
+    pub(crate) bindings: Vec<Binding>,
+}
+
+/// Appends a BER tag, length, and contents.
+fn put(buffer: &mut Vec<u8>, tag: u8, contents: &[u8]) {
+    buffer.push(tag);
+
+    if contents.len() < 0x80 {
+        buffer.push(contents.len() as u8);
+    } else {
+        let length = (contents.len() as u32).to_be_bytes();
+        let skip = length.iter().take_while(|octet| **octet == 0).count();
+        buffer.push(0x80 | (4 - skip) as u8);
+        buffer.extend_from_slice(&length[skip..]);
+    }
+
+    buffer.extend_from_slice(contents);
+}
+
+/// Encodes a signed integer in the fewest octets.
+fn integer(value: i64) -> Vec<u8> {
+    let octets = value.to_be_bytes();
+    let mut start = 0;
+
+    while start < 7
+        && ((octets[start] == 0 && octets[start + 1] & 0x80 == 0)
+            || (octets[start] == 0xff && octets[start + 1] & 0x80 != 0))
+    {
+        start += 1;
+    }
+
+    octets[start..].to_vec()
+}
+
+/// Encodes an unsigned integer in the fewest octets.
+fn unsigned(value: u64) -> Vec<u8> {
+    let mut octets = vec![0];
+    octets.extend_from_slice(&value.to_be_bytes());
+    let start = octets
+        .windows(2)
+        .position(|pair| pair[0] != 0 || pair[1] & 0x80 != 0)
+        .unwrap_or(octets.len() - 1);
+    octets[start..].to_vec()
+}
+
+/// Encodes an object identifier.
+fn oid(arcs: &[u32]) -> Vec<u8> {
+    let mut octets = Vec::new();
+    let first = arcs.first().copied().unwrap_or(0) * 40 + arcs.get(1).copied().unwrap_or(0);
+
+    for arc in std::iter::once(first).chain(arcs.iter().skip(2).copied()) {
+        let mut groups = vec![(arc & 0x7f) as u8];
+        let mut rest = arc >> 7;
+
+        while rest > 0 {
+            groups.push(0x80 | (rest & 0x7f) as u8);
+            rest >>= 7;
+        }
+
+        octets.extend(groups.iter().rev());
+    }
+
+    octets
+}
+
+impl Message {
+    /// Encodes the message.
+    pub(crate) fn encode(&self) -> Vec<u8> {
+        let mut bindings = Vec::new();
+
+        for (name, value) in self.bindings.iter() {
+            let mut binding = Vec::new();
+            put(&mut binding, 0x06, &oid(name));
+
+            match value {
+                Value::Integer(value) => put(&mut binding, 0x02, &integer(*value)),
+                Value::Bytes(bytes) => put(&mut binding, 0x04, bytes),
+                Value::Oid(arcs) => put(&mut binding, 0x06, &oid(arcs)),
+                Value::Unsigned(value) => put(&mut binding, 0x42, &unsigned(*value)),
+                Value::Null => put(&mut binding, 0x05, &[]),
+                Value::EndOfMibView => put(&mut binding, 0x82, &[]),
+                Value::Other(tag) => put(&mut binding, *tag, &[]),
+            }
+
+            put(&mut bindings, 0x30, &binding);
+        }
+
+        let mut pdu = Vec::new();
+        put(&mut pdu, 0x02, &integer(i64::from(self.request_id)));
+        put(&mut pdu, 0x02, &integer(self.first));
+        put(&mut pdu, 0x02, &integer(self.second));
+        put(&mut pdu, 0x30, &bindings);
+
+        let mut message = Vec::new();
+        put(&mut message, 0x02, &integer(1));
+        put(&mut message, 0x04, &self.community);
+        put(&mut message, self.tag, &pdu);
+
+        let mut encoded = Vec::new();
+        put(&mut encoded, 0x30, &message);
+        encoded
+    }
+
+    /// Decodes a message, or returns `None` if it is malformed.
+    pub(crate) fn decode(bytes: &[u8]) -> Option<Self> {
+        let mut reader = Reader(bytes);
+        let mut message = Reader(reader.expect(0x30)?);
+        message.expect(0x02)?;
+        let community = message.expect(0x04)?.to_vec();
+        let (tag, pdu) = message.next()?;
+        let mut pdu = Reader(pdu);
+        let request_id = to_integer(pdu.expect(0x02)?)? as i32;
+        let first = to_integer(pdu.expect(0x02)?)?;
+        let second = to_integer(pdu.expect(0x02)?)?;
+        let mut list = Reader(pdu.expect(0x30)?);
+        let mut bindings = Vec::new();
+
+        while !list.0.is_empty() {
+            let mut binding = Reader(list.expect(0x30)?);
+            let name = to_oid(binding.expect(0x06)?)?;
+            let (tag, contents) = binding.next()?;
+            let value = match tag {
+                0x02 => Value::Integer(to_integer(contents)?),
+                0x04 => Value::Bytes(contents.to_vec()),
+                0x06 => Value::Oid(to_oid(contents)?),
+                0x41 | 0x42 | 0x43 | 0x46 => Value::Unsigned(to_unsigned(contents)?),
+                0x05 => Value::Null,
+                0x82 => Value::EndOfMibView,
+                tag => Value::Other(tag),
+            };
+            bindings.push((name, value));
+        }
+
+        Some(Self {
+            community,
+            tag,
+            request_id,
+            first,
+            second,
+            bindings,
+        })
+    }
+}
+
+/// Reads BER elements one at a time.
+struct Reader<'a>(&'a [u8]);
+
+impl<'a> Reader<'a> {
+    /// Returns the next element's tag and contents.
+    fn next(&mut self) -> Option<(u8, &'a [u8])> {
+        let (&tag, rest) = self.0.split_first()?;
+        let (&first, rest) = rest.split_first()?;
+
+        let (length, rest) = if first < 0x80 {
+            (usize::from(first), rest)
+        } else {
+            let count = usize::from(first & 0x7f);
+
+            if count == 0 || count > 4 || rest.len() < count {
+                return None;
+            }
+
+            let length = rest[..count]
+                .iter()
+                .fold(0, |length, octet| (length << 8) | usize::from(*octet));
+            (length, &rest[count..])
+        };
+
+        if rest.len() < length {
+            return None;
+        }
+
+        self.0 = &rest[length..];
+        Some((tag, &rest[..length]))
+    }
+
+    /// Returns the next element's contents, if it has the tag.
+    fn expect(&mut self, tag: u8) -> Option<&'a [u8]> {
+        match self.next()? {
+            (found, contents) if found == tag => Some(contents),
+            _ => None,
+        }
+    }
+}
+
+/// Decodes a signed integer.
+fn to_integer(contents: &[u8]) -> Option<i64> {
+    if contents.is_empty() || contents.len() > 8 {
+        return None;
+    }
+
+    let sign = if contents[0] & 0x80 != 0 { -1 } else { 0 };
+    Some(
+        contents
+            .iter()
+            .fold(sign, |value, octet| (value << 8) | i64::from(*octet)),
+    )
+}
+
+/// Decodes an unsigned integer.
+fn to_unsigned(contents: &[u8]) -> Option<u64> {
+    let contents = match contents.split_first() {
+        Some((0, rest)) => rest,
+        _ => contents,
+    };
+
+    if contents.len() > 8 {
+        return None;
+    }
+
+    Some(
+        contents
+            .iter()
+            .fold(0, |value, octet| (value << 8) | u64::from(*octet)),
+    )
+}
+
+/// Decodes an object identifier.
+fn to_oid(contents: &[u8]) -> Option<Vec<u32>> {
+    let mut arcs = Vec::new();
+    let mut arc: u32 = 0;
+
+    for octet in contents.iter() {
+        arc = arc.checked_mul(128)? | u32::from(octet & 0x7f);
+
+        if octet & 0x80 == 0 {
+            if arcs.is_empty() {
+                arcs.push((arc / 40).min(2));
+                arcs.push(arc - arcs[0] * 40);
+            } else {
+                arcs.push(arc);
+            }
+
+            arc = 0;
+        }
+    }
+
+    if arcs.is_empty() {
+        None
+    } else {
+        Some(arcs)
+    }
+}
+
+/// A client that reads switches' forwarding databases over SNMPv2c,
+/// for finding where a MAC address is plugged in.
+///
+/// This client requires the `snmp` feature.
+#[derive(Debug)]
+pub struct SnmpClient {
+    socket: UdpSocket,
+    community: Vec<u8>,
+    retries: u32,
+    request_id: i32,
+}
+
+/// Describes a failure to talk to the agent.
+fn describe(error: std::io::Error) -> Error {
+    Error::Network(error.to_string())
+}
+
+impl SnmpClient {
+    /// Instantiates `SnmpClient` for the agent at `agent` (for
+    /// example, `"switch.example.com:161"`) with a community string.
+    ///
+    /// By default, it waits 2 seconds for each response and retries
+    /// each request twice.
+    pub fn new<A: ToSocketAddrs>(agent: A, community: &str) -> Result<Self, Error> {
+        let agent = agent
+            .to_socket_addrs()
+            .map_err(describe)?
+            .next()
+            .ok_or_else(|| Error::Network(String::from("Pass in an agent that resolves.")))?;
+        let local = if agent.is_ipv4() {
+            "0.0.0.0:0"
+        } else {
+            "[::]:0"
+        };
+        let socket = UdpSocket::bind(local).map_err(describe)?;
+        socket.connect(agent).map_err(describe)?;
+        socket
+            .set_read_timeout(Some(Duration::from_secs(2)))
+            .map_err(describe)?;
+
+        Ok(Self {
+            socket,
+            community: community.as_bytes().to_vec(),
+            retries: 2,
+            request_id: 0,
+        })
+    }
+
+    /// Waits `timeout` for each response instead.
+    pub fn with_timeout(self, timeout: Duration) -> Result<Self, Error> {
+        self.socket
+            .set_read_timeout(Some(timeout))
+            .map_err(describe)?;
+        Ok(self)
+    }
+
+    /// Retries each request up to `retries` times instead.
+    pub fn with_retries(mut self, retries: u32) -> Self {
+        self.retries = retries;
+        self
+    }
+
+    /// Sends a `GetBulkRequest` for the rows after `start` and
+    /// returns the response's bindings.
+    fn get_bulk(&mut self, start: &[u32]) -> Result<Vec<Binding>, Error> {
+        self.request_id = self.request_id.wrapping_add(1) & 0x7fff_ffff;
+
+        let request = Message {
+            community: self.community.clone(),
+            tag: 0xa5,
+            request_id: self.request_id,
+            first: 0,
+            second: i64::from(MAX_REPETITIONS),
+            bindings: vec![(start.to_vec(), Value::Null)],
+        }
+        .encode();
+        let mut buffer = vec![0; 65535];
+
+        for _ in 0..=self.retries {
+            self.socket.send(&request).map_err(describe)?;
+
+            // Skip stray responses to earlier requests.
+            loop {
+                let length = match self.socket.recv(&mut buffer) {
+                    Ok(length) => length,
+                    Err(error)
+                        if error.kind() == std::io::ErrorKind::WouldBlock
+                            || error.kind() == std::io::ErrorKind::TimedOut =>
+                    {
+                        break
+                    }
+                    Err(error) => return Err(describe(error)),
+                };
+
+                let response = match Message::decode(&buffer[..length]) {
+                    Some(response) if response.request_id == self.request_id => response,
+                    _ => continue,
+                };
+
+                if response.first != 0 {
+                    return Err(Error::Network(format!(
+                        "The agent answered with error status {}.",
+                        response.first
+                    )));
+                }
+
+                return Ok(response.bindings);
+            }
+        }
+
+        Err(Error::Network(String::from(
+            "The agent did not answer; check its address and community.",
+        )))
+    }
+
+    /// Returns every binding under `root`, in order.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(level = "debug", skip(self), err)
+    )]
+    fn walk(&mut self, root: &[u32]) -> Result<Vec<Binding>, Error> {
+        let mut bindings: Vec<Binding> = Vec::new();
+        let mut next = root.to_vec();
+
+        loop {
+            let response = self.get_bulk(&next)?;
+
+            if response.is_empty() {
+                return Ok(bindings);
+            }
+
+            for (name, value) in response {
+                if !name.starts_with(root) || value == Value::EndOfMibView {
+                    return Ok(bindings);
+                }
+
+                if name <= next {
+                    return Err(Error::Network(String::from(
+                        "The agent returned objects out of order.",
+                    )));
+                }
+
+                next = name.clone();
+                bindings.push((name, value));
+            }
+        }
+    }
+
+    /// Returns the bridge ports' interfaces, by port.
+    fn port_interfaces(&mut self) -> Result<BTreeMap<u32, u32>, Error> {
+        let mut interfaces = BTreeMap::new();
+
+        for (name, value) in self.walk(&DOT1D_BASE_PORT_IF_INDEX)? {
+            if let (Some(port), Value::Integer(if_index)) = (name.last(), value) {
+                interfaces.insert(*port, if_index as u32);
+            }
+        }
+
+        Ok(interfaces)
+    }
+
+    /// Returns the switch's forwarding database from BRIDGE-MIB's
+    /// `dot1dTpFdbTable`, which does not distinguish VLANs.
+    pub fn dot1d_fdb(&mut self) -> Result<Vec<FdbEntry>, Error> {
+        let bindings = self.walk(&DOT1D_TP_FDB_ENTRY)?;
+        let interfaces = self.port_interfaces()?;
+        Ok(fdb_entries(
+            &bindings,
+            DOT1D_TP_FDB_ENTRY.len(),
+            false,
+            &interfaces,
+        ))
+    }
+
+    /// Returns the switch's forwarding database from
+    /// Q-BRIDGE-MIB's `dot1qTpFdbTable`, with the VLAN of each entry.
+    pub fn dot1q_fdb(&mut self) -> Result<Vec<FdbEntry>, Error> {
+        let bindings = self.walk(&DOT1Q_TP_FDB_ENTRY)?;
+        let interfaces = self.port_interfaces()?;
+        Ok(fdb_entries(
+            &bindings,
+            DOT1Q_TP_FDB_ENTRY.len(),
+            true,
+            &interfaces,
+        ))
+    }
+
+    /// Returns the switch's forwarding database from Q-BRIDGE-MIB,
+    /// or from BRIDGE-MIB if the switch does not support
+    /// Q-BRIDGE-MIB.
+    pub fn fdb(&mut self) -> Result<Vec<FdbEntry>, Error> {
+        let entries = self.dot1q_fdb()?;
+
+        if entries.is_empty() {
+            self.dot1d_fdb()
+        } else {
+            Ok(entries)
+        }
+    }
+}
+
+/// Gathers the columns of a walked FDB table into entries, sorted by
+/// VLAN and then by address.
+///
+/// Each row is indexed by six arcs of address, after one arc of
+/// filtering database if `vlan` is set.  The port column is 2, and
+/// the status column is 3.
+fn fdb_entries(
+    bindings: &[Binding],
+    root: usize,
+    vlan: bool,
+    interfaces: &BTreeMap<u32, u32>,
+) -> Vec<FdbEntry> {
+    let index_len = if vlan { 7 } else { 6 };
+    let mut rows: BTreeMap<(u32, [u8; 6]), (u32, FdbStatus)> = BTreeMap::new();
+
+    for (name, value) in bindings.iter() {
+        if name.len() != root + 1 + index_len {
+            continue;
+        }
+
+        let column = name[root];
+        let index = &name[root + 1..];
+        let fdb = if vlan { index[0] } else { 0 };
+        let mut octets = [0; 6];
+
+        for (octet, arc) in octets.iter_mut().zip(index[index_len - 6..].iter()) {
+            *octet = *arc as u8;
+        }
+
+        let row = rows.entry((fdb, octets)).or_insert((0, FdbStatus::Other));
+
+        match (column, value) {
+            (2, Value::Integer(port)) => row.0 = *port as u32,
+            (3, Value::Integer(status)) => row.1 = FdbStatus::from_value(*status),
+            _ => {}
+        }
+    }
+
+    rows.into_iter()
+        .map(|((fdb, octets), (port, status))| FdbEntry {
+            address: MediaAccessControlAddress::from(octets),
+            port,
+            if_index: interfaces.get(&port).copied(),
+            vlan: if vlan { Some(fdb) } else { None },
+            status,
+        })
+        .collect()
+}
@@ -0,0 +1,68 @@
+use super::macaddress::MediaAccessControlAddress;
+
+/// Sorts `items` by their 48-bit keys with a least-significant-byte
+/// radix sort: six counting passes, one per octet, through a
+/// scratch buffer as long as `items`.  Passes in which every key
+/// has the same octet are skipped.
+fn radix_sort<T: Copy, F: Fn(&T) -> [u8; 6]>(items: &mut [T], key: F) {
+    if items.len() < 2 {
+        return;
+    }
+
+    let mut scratch = items.to_vec();
+    let mut in_items = true;
+
+    for octet in (0..6).rev() {
+        let mut counts = [0usize; 256];
+        let source: &[T] = if in_items { items } else { &scratch };
+
+        for item in source {
+            counts[key(item)[octet] as usize] += 1;
+        }
+
+        if counts.contains(&source.len()) {
+            continue;
+        }
+
+        let mut offsets = [0usize; 256];
+        let mut total = 0;
+
+        for (offset, count) in offsets.iter_mut().zip(counts.iter()) {
+            *offset = total;
+            total += count;
+        }
+
+        let (source, destination): (&[T], &mut [T]) = if in_items {
+            (items, &mut scratch)
+        } else {
+            (&scratch, items)
+        };
+
+        for item in source {
+            let digit = key(item)[octet] as usize;
+            destination[offsets[digit]] = *item;
+            offsets[digit] += 1;
+        }
+
+        in_items = !in_items;
+    }
+
+    if !in_items {
+        items.copy_from_slice(&scratch);
+    }
+}
+
+/// Sorts MAC addresses, as six octets each, in numeric order.
+///
+/// This is a radix sort, which takes time linear in the number of
+/// addresses, so it is much faster than `sort_unstable` on the tens
+/// of millions of addresses that capture analysis can produce.  It
+/// is stable, and it allocates a buffer as large as `octets`.
+pub fn sort_octets(octets: &mut [[u8; 6]]) {
+    radix_sort(octets, |octets| *octets);
+}
+
+/// Sorts MAC addresses in numeric order (see `sort_octets`).
+pub fn sort(addresses: &mut [MediaAccessControlAddress]) {
+    radix_sort(addresses, MediaAccessControlAddress::to_octets);
+}
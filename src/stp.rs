@@ -0,0 +1,143 @@
+use super::error::Error;
+use super::macaddress::MediaAccessControlAddress;
+use std::fmt;
+use std::str::FromStr;
+
+/// `BridgeId` identifies an IEEE 802.1D/802.1Q spanning-tree
+/// bridge with a 4-bit priority, a 12-bit system ID extension
+/// (the VLAN or MST instance), and a MAC address.
+///
+/// Bridge IDs compare the way spanning tree elects a root bridge:
+/// as 64-bit numbers, so the lower priority wins, then the lower
+/// system ID extension, then the numerically lower MAC address.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct BridgeId {
+    priority: u16,
+    address: [u8; 6],
+}
+
+impl BridgeId {
+    /// Instantiates `BridgeId` with a priority (a multiple of
+    /// 4096 between 0 and 61440), a system ID extension (between
+    /// 0 and 4095), and a MAC address.
+    pub fn new(
+        priority: u16,
+        system_id_extension: u16,
+        address: &MediaAccessControlAddress,
+    ) -> Result<Self, Error> {
+        if priority & 0x0fff != 0 {
+            return Err(Error::Invalid(String::from(
+                "Pass in a priority that is a multiple of 4096.",
+            )));
+        }
+
+        if system_id_extension > 0x0fff {
+            return Err(Error::Invalid(String::from(
+                "Pass in a system ID extension between 0 and 4095.",
+            )));
+        }
+
+        Ok(Self {
+            priority: priority | system_id_extension,
+            address: address.to_octets(),
+        })
+    }
+
+    /// Instantiates `BridgeId` with its 8-octet on-wire encoding
+    /// (the priority and system ID extension in network byte
+    /// order, then the MAC address).
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, Error> {
+        if bytes.len() == 8 {
+            let mut address = [0; 6];
+            address.copy_from_slice(&bytes[2..]);
+
+            Ok(Self {
+                priority: u16::from_be_bytes([bytes[0], bytes[1]]),
+                address,
+            })
+        } else {
+            Err(Error::Invalid(String::from("Pass in 8 octets.")))
+        }
+    }
+
+    /// Returns the priority (a multiple of 4096).
+    pub fn priority(&self) -> u16 {
+        self.priority & 0xf000
+    }
+
+    /// Returns the system ID extension.
+    pub fn system_id_extension(&self) -> u16 {
+        self.priority & 0x0fff
+    }
+
+    /// Returns the bridge priority, which is the sum of the
+    /// priority and the system ID extension (for example,
+    /// 32769 for priority 32768 on VLAN 1).
+    pub fn bridge_priority(&self) -> u16 {
+        self.priority
+    }
+
+    /// Returns the MAC address.
+    pub fn address(&self) -> MediaAccessControlAddress {
+        MediaAccessControlAddress::from(self.address)
+    }
+
+    /// Returns the 8-octet on-wire encoding (the priority and
+    /// system ID extension in network byte order, then the MAC
+    /// address).
+    pub fn to_bytes(&self) -> [u8; 8] {
+        let mut bytes = [0; 8];
+        bytes[..2].copy_from_slice(&self.priority.to_be_bytes());
+        bytes[2..].copy_from_slice(&self.address);
+        bytes
+    }
+}
+
+/// Parses the `pppp.address` textual form, where `pppp` is the
+/// bridge priority in four hexadecimal digits (for example,
+/// `8001.a0b1c2d3e4f5`).  The address may be in any notation
+/// that `MediaAccessControlAddress::new` accepts.
+impl FromStr for BridgeId {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (priority, address) = s
+            .trim()
+            .split_once('.')
+            .filter(|(priority, _)| {
+                // `from_str_radix` would accept a sign, as in `+001`.
+                priority.len() == 4 && priority.bytes().all(|digit| digit.is_ascii_hexdigit())
+            })
+            .ok_or_else(|| {
+                Error::Invalid(String::from(
+                    "Pass in four hexadecimal digits, a dot, and a MAC address.",
+                ))
+            })?;
+
+        let priority = u16::from_str_radix(priority, 16).map_err(|_| {
+            Error::Invalid(String::from(
+                "Pass in a priority in four hexadecimal digits.",
+            ))
+        })?;
+        let address = MediaAccessControlAddress::new(address)?;
+
+        Ok(Self {
+            priority,
+            address: address.to_octets(),
+        })
+    }
+}
+
+/// Formats the bridge ID as `pppp.address`, with the bridge
+/// priority in four hexadecimal digits and the address in plain
+/// notation (for example, `8001.a0b1c2d3e4f5`), as `brctl` does.
+impl fmt::Display for BridgeId {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{:04x}.{}",
+            self.priority,
+            self.address().to_plain_notation()
+        )
+    }
+}
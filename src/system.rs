@@ -0,0 +1,346 @@
+use super::error::Error;
+use super::fingerprint;
+use super::hash::hmac_sha256;
+use super::macaddress::MediaAccessControlAddress;
+use std::fmt;
+use std::fs;
+use std::net::IpAddr;
+use std::path::Path;
+
+/// One entry in the host's neighbor table: an IPv4 address learned
+/// with ARP or an IPv6 address learned with NDP, along with the MAC
+/// address that it resolved to.
+#[derive(Debug)]
+pub struct Neighbor {
+    /// The IP address.
+    pub ip: IpAddr,
+    /// The MAC address that the IP address resolved to.
+    pub address: MediaAccessControlAddress,
+    /// The interface on which the neighbor was learned.
+    pub interface: String,
+    /// The state of the entry (for example, `REACHABLE` or
+    /// `STALE`), if the system reports one.
+    pub state: Option<String>,
+}
+
+/// Parses the ARP table in the format of `/proc/net/arp`, skipping
+/// the header, incomplete entries, and entries without a MAC
+/// address, such as those on IPoIB interfaces.
+pub fn parse_proc_arp(text: &str) -> Result<Vec<Neighbor>, Error> {
+    let mut neighbors = Vec::new();
+
+    for line in text.lines().skip(1) {
+        let columns: Vec<&str> = line.split_whitespace().collect();
+
+        if columns.len() < 6 {
+            continue;
+        }
+
+        // Entries that are still being resolved have flags of 0x0
+        // and an all-zero address.
+        let flags = u32::from_str_radix(columns[2].trim_start_matches("0x"), 16).map_err(|_| {
+            Error::System(format!(
+                "Pass in an ARP table with hexadecimal flags (not {}).",
+                columns[2]
+            ))
+        })?;
+
+        if flags & 0x2 == 0 {
+            continue;
+        }
+
+        let address = match link_layer_address(columns[3])? {
+            Some(address) => address,
+            None => continue,
+        };
+
+        neighbors.push(Neighbor {
+            ip: columns[0].parse().map_err(|_| {
+                Error::System(format!(
+                    "Pass in an ARP table with valid IP addresses (not {}).",
+                    columns[0]
+                ))
+            })?,
+            address,
+            interface: columns[5].to_string(),
+            state: None,
+        });
+    }
+
+    Ok(neighbors)
+}
+
+/// Parses the neighbor table in the format of `ip neigh show`,
+/// which covers both ARP and NDP, skipping entries without a MAC
+/// address, such as those that are still being resolved and those
+/// on IPoIB or tunnel interfaces.
+pub fn parse_ip_neigh(text: &str) -> Result<Vec<Neighbor>, Error> {
+    let mut neighbors = Vec::new();
+
+    for line in text.lines() {
+        let mut words = line.split_whitespace();
+
+        let ip = match words.next() {
+            Some(ip) => ip,
+            None => continue,
+        };
+
+        let mut address = None;
+        let mut interface = String::new();
+        let mut state = None;
+
+        while let Some(word) = words.next() {
+            match word {
+                "dev" => interface = words.next().unwrap_or_default().to_string(),
+                "lladdr" => address = words.next(),
+                "router" | "proxy" | "extern_learn" => {}
+                word if word.chars().all(|c| c.is_ascii_uppercase() || c == '_') => {
+                    state = Some(word.to_string());
+                }
+                _ => {}
+            }
+        }
+
+        // Entries that failed or are still being resolved have no
+        // link-layer address.
+        let address = match address.map(link_layer_address).transpose()?.flatten() {
+            Some(address) => address,
+            None => continue,
+        };
+
+        neighbors.push(Neighbor {
+            ip: ip.parse().map_err(|_| {
+                Error::System(format!(
+                    "Pass in a neighbor table with valid IP addresses (not {}).",
+                    ip
+                ))
+            })?,
+            address,
+            interface,
+            state,
+        });
+    }
+
+    Ok(neighbors)
+}
+
+/// Returns whether `lladdr` is a well-formed link-layer address
+/// other than a 48-bit MAC address, such as the 20-octet addresses
+/// of IPoIB or the IP addresses of GRE and ip6tnl tunnels.
+fn is_other_link_layer(lladdr: &str) -> bool {
+    lladdr.parse::<IpAddr>().is_ok()
+        || lladdr.split(':').all(|octet| {
+            (1..=2).contains(&octet.len()) && octet.bytes().all(|byte| byte.is_ascii_hexdigit())
+        })
+}
+
+/// Parses a neighbor's link-layer address, or returns `None` if it
+/// is well-formed but not a MAC address (see `is_other_link_layer`).
+fn link_layer_address(lladdr: &str) -> Result<Option<MediaAccessControlAddress>, Error> {
+    match MediaAccessControlAddress::new(lladdr) {
+        Ok(address) => Ok(Some(address)),
+        Err(_) if is_other_link_layer(lladdr) => {
+            #[cfg(feature = "tracing")]
+            tracing::trace!(lladdr, "skipping neighbor without a 48-bit address");
+            Ok(None)
+        }
+        Err(error) => Err(error.into()),
+    }
+}
+
+/// Returns the host's neighbor table.
+///
+/// On Linux, it runs `ip neigh show`, which lists both ARP and NDP
+/// entries, and falls back to `/proc/net/arp`, which lists only ARP
+/// entries, if `ip` is unavailable.  Other systems are not yet
+/// supported.
+#[cfg(target_os = "linux")]
+#[cfg_attr(feature = "tracing", tracing::instrument(level = "debug", err))]
+pub fn neighbors() -> Result<Vec<Neighbor>, Error> {
+    use std::process::Command;
+
+    match Command::new("ip").args(["neigh", "show"]).output() {
+        Ok(output) if output.status.success() => {
+            parse_ip_neigh(&String::from_utf8_lossy(&output.stdout))
+        }
+        _ => {
+            #[cfg(feature = "tracing")]
+            tracing::debug!("ip neigh failed, so falling back to /proc/net/arp");
+
+            let text = fs::read_to_string("/proc/net/arp")
+                .map_err(|error| Error::System(format!("/proc/net/arp: {}", error)))?;
+            parse_proc_arp(&text)
+        }
+    }
+}
+
+/// Returns the host's neighbor table.
+///
+/// Only Linux is supported so far.
+#[cfg(not(target_os = "linux"))]
+pub fn neighbors() -> Result<Vec<Neighbor>, Error> {
+    Err(Error::System(String::from(
+        "Reading the neighbor table is only supported on Linux.",
+    )))
+}
+
+/// How the operating system says an interface got its address.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum AddressSource {
+    /// Burned in by the manufacturer.
+    Permanent,
+    /// Generated at random by the driver or the operating system.
+    Random,
+    /// Copied from another interface, as bonds and bridges do.
+    Stolen,
+    /// Set by an administrator or a program.
+    Set,
+}
+
+impl AddressSource {
+    /// Returns the source's stable name (`permanent`, `random`,
+    /// `stolen`, or `set`).
+    pub fn as_str(self) -> &'static str {
+        match self {
+            AddressSource::Permanent => "permanent",
+            AddressSource::Random => "random",
+            AddressSource::Stolen => "stolen",
+            AddressSource::Set => "set",
+        }
+    }
+}
+
+impl fmt::Display for AddressSource {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.pad(self.as_str())
+    }
+}
+
+/// One of the host's network interfaces.
+#[derive(Debug)]
+pub struct Interface {
+    /// The interface's name (for example, `eth0`).
+    pub name: String,
+    /// The interface's current MAC address.
+    pub address: MediaAccessControlAddress,
+    /// How the interface got its address, if the system reports it.
+    pub source: Option<AddressSource>,
+}
+
+/// The link type of loopback interfaces (`ARPHRD_LOOPBACK`).
+const ARPHRD_LOOPBACK: u32 = 772;
+
+/// Reads the interfaces under a directory laid out like
+/// `/sys/class/net`, sorted by name.
+///
+/// Loopback interfaces and interfaces without a 48-bit address
+/// (such as tunnels) are skipped.
+pub fn parse_sys_class_net(root: &Path) -> Result<Vec<Interface>, Error> {
+    let read = |path: &Path| fs::read_to_string(path).map(|text| text.trim().to_string());
+    let describe = |error| Error::System(format!("{}: {}", root.display(), error));
+    let entries = fs::read_dir(root).map_err(describe)?;
+    let mut interfaces = Vec::new();
+
+    for entry in entries {
+        let entry = entry.map_err(describe)?;
+        let directory = entry.path();
+
+        let link_type = read(&directory.join("type"))
+            .ok()
+            .and_then(|text| text.parse().ok());
+
+        if link_type == Some(ARPHRD_LOOPBACK) {
+            continue;
+        }
+
+        let address = match read(&directory.join("address")) {
+            Ok(digits) => match MediaAccessControlAddress::new(&digits) {
+                Ok(address) => address,
+                Err(_) => {
+                    #[cfg(feature = "tracing")]
+                    tracing::trace!(interface = ?entry.file_name(), "skipping interface without a 48-bit address");
+                    continue;
+                }
+            },
+            Err(_) => continue,
+        };
+
+        let source = match read(&directory.join("addr_assign_type")).as_deref() {
+            Ok("0") => Some(AddressSource::Permanent),
+            Ok("1") => Some(AddressSource::Random),
+            Ok("2") => Some(AddressSource::Stolen),
+            Ok("3") => Some(AddressSource::Set),
+            _ => None,
+        };
+
+        interfaces.push(Interface {
+            name: entry.file_name().to_string_lossy().into_owned(),
+            address,
+            source,
+        });
+    }
+
+    interfaces.sort_by(|a, b| a.name.cmp(&b.name));
+    Ok(interfaces)
+}
+
+/// Returns the host's network interfaces, sorted by name.
+///
+/// On Linux, it reads `/sys/class/net`.  Other systems are not yet
+/// supported.
+#[cfg(target_os = "linux")]
+#[cfg_attr(feature = "tracing", tracing::instrument(level = "debug", err))]
+pub fn interfaces() -> Result<Vec<Interface>, Error> {
+    parse_sys_class_net(Path::new("/sys/class/net"))
+}
+
+/// Returns the host's network interfaces.
+///
+/// Only Linux is supported so far.
+#[cfg(not(target_os = "linux"))]
+pub fn interfaces() -> Result<Vec<Interface>, Error> {
+    Err(Error::System(String::from(
+        "Listing network interfaces is only supported on Linux.",
+    )))
+}
+
+/// Derives a stable identifier for a host from the addresses of its
+/// physical interfaces (unicast UAAs that no hypervisor claims and
+/// that the system did not randomize), keyed with `salt`.
+///
+/// It is the first eight octets, read as a big-endian number, of the
+/// HMAC-SHA-256 of the distinct addresses in ascending order, keyed
+/// with `salt`.  It survives changes in interface names and order,
+/// and without the salt it cannot be correlated across applications
+/// or reversed into the addresses, so keep each application's salt
+/// secret.
+pub fn machine_id_from(interfaces: &[Interface], salt: &[u8]) -> Result<u64, Error> {
+    let mut addresses: Vec<[u8; 6]> = interfaces
+        .iter()
+        .filter(|interface| interface.source != Some(AddressSource::Random))
+        .map(|interface| &interface.address)
+        .filter(|address| address.is_uaa() && fingerprint::platform(address).is_none())
+        .map(|address| address.to_octets())
+        .collect();
+
+    addresses.sort_unstable();
+    addresses.dedup();
+
+    if addresses.is_empty() {
+        return Err(Error::System(String::from(
+            "Pass in at least one interface with a physical address.",
+        )));
+    }
+
+    let digest = hmac_sha256(salt, &addresses.concat());
+    let mut id = [0; 8];
+    id.copy_from_slice(&digest[..8]);
+    Ok(u64::from_be_bytes(id))
+}
+
+/// Derives a stable identifier for the host from the addresses of
+/// its physical interfaces, keyed with `salt`, as
+/// `machine_id_from` does.
+pub fn machine_id(salt: &[u8]) -> Result<u64, Error> {
+    machine_id_from(&interfaces()?, salt)
+}
@@ -0,0 +1,142 @@
+use super::macaddress::MediaAccessControlAddress;
+use super::prefix::{to_value, MacPrefix};
+use std::collections::BTreeMap;
+use std::iter::FromIterator;
+
+/// `MacTable` is an ordered map from MAC addresses to values, such
+/// as a forwarding table from addresses to ports.
+///
+/// Unlike a hash map, it keeps its addresses in numeric order, so
+/// it can list every entry under a prefix (for example, an OUI)
+/// and find the entry closest to an address that it does not hold.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct MacTable<V> {
+    entries: BTreeMap<[u8; 6], V>,
+}
+
+impl<V> Default for MacTable<V> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Converts a key back into a MAC address.
+fn to_address(octets: &[u8; 6]) -> MediaAccessControlAddress {
+    MediaAccessControlAddress::from(*octets)
+}
+
+impl<V> MacTable<V> {
+    /// Instantiates an empty `MacTable`.
+    pub fn new() -> Self {
+        Self {
+            entries: BTreeMap::new(),
+        }
+    }
+
+    /// Returns the number of entries.
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Whether the table has no entries.
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Inserts a value for the MAC address and returns the value
+    /// that it replaced, if any.
+    pub fn insert(&mut self, address: &MediaAccessControlAddress, value: V) -> Option<V> {
+        self.entries.insert(address.to_octets(), value)
+    }
+
+    /// Returns the value for the MAC address, if any.
+    pub fn get(&self, address: &MediaAccessControlAddress) -> Option<&V> {
+        self.entries.get(&address.to_octets())
+    }
+
+    /// Returns a mutable reference to the value for the MAC
+    /// address, if any.
+    pub fn get_mut(&mut self, address: &MediaAccessControlAddress) -> Option<&mut V> {
+        self.entries.get_mut(&address.to_octets())
+    }
+
+    /// Whether the table has an entry for the MAC address.
+    pub fn contains_key(&self, address: &MediaAccessControlAddress) -> bool {
+        self.entries.contains_key(&address.to_octets())
+    }
+
+    /// Removes the entry for the MAC address and returns its
+    /// value, if any.
+    pub fn remove(&mut self, address: &MediaAccessControlAddress) -> Option<V> {
+        self.entries.remove(&address.to_octets())
+    }
+
+    /// Removes every entry.
+    pub fn clear(&mut self) {
+        self.entries.clear();
+    }
+
+    /// Returns an iterator over the entries, in address order.
+    pub fn iter(&self) -> impl Iterator<Item = (MediaAccessControlAddress, &V)> {
+        self.entries
+            .iter()
+            .map(|(octets, value)| (to_address(octets), value))
+    }
+
+    /// Returns an iterator over the entries whose addresses fall
+    /// under `prefix`, in address order.
+    pub fn range(
+        &self,
+        prefix: &MacPrefix,
+    ) -> impl Iterator<Item = (MediaAccessControlAddress, &V)> {
+        let (first, last) = prefix.bounds();
+
+        self.entries
+            .range(first..=last)
+            .map(|(octets, value)| (to_address(octets), value))
+    }
+
+    /// Returns the entry whose address is numerically closest to
+    /// the MAC address (the entry for the address itself, if
+    /// there is one), preferring the lower address on a tie.
+    pub fn nearest(
+        &self,
+        address: &MediaAccessControlAddress,
+    ) -> Option<(MediaAccessControlAddress, &V)> {
+        let key = address.to_octets();
+        let target = to_value(&key);
+        let below = self.entries.range(..=key).next_back();
+        let above = self.entries.range(key..).next();
+
+        let closest = match (below, above) {
+            (Some(below), Some(above)) => {
+                if target - to_value(below.0) <= to_value(above.0) - target {
+                    below
+                } else {
+                    above
+                }
+            }
+            (Some(entry), None) | (None, Some(entry)) => entry,
+            (None, None) => return None,
+        };
+
+        Some((to_address(closest.0), closest.1))
+    }
+}
+
+impl<V> FromIterator<(MediaAccessControlAddress, V)> for MacTable<V> {
+    fn from_iter<I: IntoIterator<Item = (MediaAccessControlAddress, V)>>(iter: I) -> Self {
+        let mut table = Self::new();
+        table.extend(iter);
+        table
+    }
+}
+
+impl<V> Extend<(MediaAccessControlAddress, V)> for MacTable<V> {
+    fn extend<I: IntoIterator<Item = (MediaAccessControlAddress, V)>>(&mut self, iter: I) {
+        self.entries.extend(
+            iter.into_iter()
+                .map(|(address, value)| (address.to_octets(), value)),
+        );
+    }
+}
@@ -0,0 +1,5516 @@
+use super::bloom::MacBloom;
+use super::classification::{Cast, Kind};
+use super::datalink::DataLinkAddress;
+use super::error::Error;
+use super::eui64::Eui64;
+use super::evpn::EthernetSegmentId;
+use super::export::{escape_json, Encoding, Exporter};
+use super::fingerprint::{fingerprint, platform, role, Origin, Role};
+use super::format::{self, BufferTooSmall, Notation, MAX_LEN};
+use super::frame;
+use super::hash::{ether_crc, multicast_filter_index, sha256, shard_key};
+use super::lacp::LacpSystemId;
+use super::macaddress::MediaAccessControlAddress;
+use super::prefix::{MacPrefix, MacPrefixMap};
+use super::random::{random_laa_with, MacRng};
+use super::range::{self, MacRange};
+use super::scheme::LaaScheme;
+use super::set::MacSet;
+use super::stp::BridgeId;
+use super::table::MacTable;
+use super::utils::{diagnose, diagnose_eui64, validate, MacParseError};
+use super::vendor::{
+    codegen, CachedResolver, Enrich, Record, VendorFilter, VendorResolver, VendorTable,
+};
+use super::wifi::{multiple_bssids, nontransmitted_bssid, transmitted_bssid};
+use super::MacAddress;
+use super::{PTP_PEER_DELAY, PTP_PRIMARY};
+use std::cell::{Cell, RefCell};
+use std::collections::HashMap;
+use std::convert::TryFrom;
+use std::net::{Ipv4Addr, Ipv6Addr};
+
+#[test]
+#[should_panic]
+fn test_invalid_addresses() {
+    let addresses = [
+        "0a",                 // Too few digits
+        "0a1b2c3d4e5f6",      // Too many digits
+        "0a1b2c3d4e5g",       // Invalid digit
+        "-0a-1b-2c-3d-4e-5f", // Leading hyphen
+        "0a-1b-2c-3d-4e-5f-", // Trailing hyphen
+        "0a-1b-2c-3d-4e5f",   // Missing hyphen
+        ":0a:1b:2c:3d:4e:5f", // Leading colon
+        "0a:1b:2c:3d:4e:5f:", // Trailing colon
+        "0a:1b:2c:3d:4e5f",   // Missing colon
+        ".0a1b.2c3d.4e5f",    // Leading dot
+        "0a1b.2c3d.4e5f.",    // Trailing dot
+        "0a1b.2c3d4e5f",      // Missing dot
+    ];
+
+    for element in addresses.iter() {
+        let digits = element.to_string();
+        MediaAccessControlAddress::new(&digits).unwrap();
+    }
+}
+
+// An EUI is a unicast address.
+#[test]
+fn test_unicast_eui_addresses() {
+    let addresses = [
+        (
+            "a0b1c2d3e4f5", // Plain notation (lowercase)
+            "101000001011000111000010110100111110010011110101",
+            176685338322165,
+            "a0b1c2d3e4f5",
+            "a0-b1-c2-d3-e4-f5",
+            "a0:b1:c2:d3:e4:f5",
+            "a0b1.c2d3.e4f5",
+            ("a0b1c2", "d3e4f5"),
+            "unique",
+            true,
+            false,
+            false,
+            false,
+            true,
+            true,
+            false,
+        ),
+        (
+            "A0B1C2D3E4F5", // Plain notation (uppercase)
+            "101000001011000111000010110100111110010011110101",
+            176685338322165,
+            "a0b1c2d3e4f5",
+            "a0-b1-c2-d3-e4-f5",
+            "a0:b1:c2:d3:e4:f5",
+            "a0b1.c2d3.e4f5",
+            ("a0b1c2", "d3e4f5"),
+            "unique",
+            true,
+            false,
+            false,
+            false,
+            true,
+            true,
+            false,
+        ),
+        (
+            "a0-b1-c2-d3-e4-f5", // Hyphen notation (lowercase)
+            "101000001011000111000010110100111110010011110101",
+            176685338322165,
+            "a0b1c2d3e4f5",
+            "a0-b1-c2-d3-e4-f5",
+            "a0:b1:c2:d3:e4:f5",
+            "a0b1.c2d3.e4f5",
+            ("a0b1c2", "d3e4f5"),
+            "unique",
+            true,
+            false,
+            false,
+            false,
+            true,
+            true,
+            false,
+        ),
+        (
+            "A0-B1-C2-D3-E4-F5", // Hyphen notation (uppercase)
+            "101000001011000111000010110100111110010011110101",
+            176685338322165,
+            "a0b1c2d3e4f5",
+            "a0-b1-c2-d3-e4-f5",
+            "a0:b1:c2:d3:e4:f5",
+            "a0b1.c2d3.e4f5",
+            ("a0b1c2", "d3e4f5"),
+            "unique",
+            true,
+            false,
+            false,
+            false,
+            true,
+            true,
+            false,
+        ),
+        (
+            "a0:b1:c2:d3:e4:f5", // Colon notation (lowercase)
+            "101000001011000111000010110100111110010011110101",
+            176685338322165,
+            "a0b1c2d3e4f5",
+            "a0-b1-c2-d3-e4-f5",
+            "a0:b1:c2:d3:e4:f5",
+            "a0b1.c2d3.e4f5",
+            ("a0b1c2", "d3e4f5"),
+            "unique",
+            true,
+            false,
+            false,
+            false,
+            true,
+            true,
+            false,
+        ),
+        (
+            "A0:B1:C2:D3:E4:F5", // Colon notation (uppercase)
+            "101000001011000111000010110100111110010011110101",
+            176685338322165,
+            "a0b1c2d3e4f5",
+            "a0-b1-c2-d3-e4-f5",
+            "a0:b1:c2:d3:e4:f5",
+            "a0b1.c2d3.e4f5",
+            ("a0b1c2", "d3e4f5"),
+            "unique",
+            true,
+            false,
+            false,
+            false,
+            true,
+            true,
+            false,
+        ),
+        (
+            "a0b1.c2d3.e4f5", // Dot notation (lowercase)
+            "101000001011000111000010110100111110010011110101",
+            176685338322165,
+            "a0b1c2d3e4f5",
+            "a0-b1-c2-d3-e4-f5",
+            "a0:b1:c2:d3:e4:f5",
+            "a0b1.c2d3.e4f5",
+            ("a0b1c2", "d3e4f5"),
+            "unique",
+            true,
+            false,
+            false,
+            false,
+            true,
+            true,
+            false,
+        ),
+        (
+            "A0B1.C2D3.E4F5", // Dot notation (uppercase)
+            "101000001011000111000010110100111110010011110101",
+            176685338322165,
+            "a0b1c2d3e4f5",
+            "a0-b1-c2-d3-e4-f5",
+            "a0:b1:c2:d3:e4:f5",
+            "a0b1.c2d3.e4f5",
+            ("a0b1c2", "d3e4f5"),
+            "unique",
+            true,
+            false,
+            false,
+            false,
+            true,
+            true,
+            false,
+        ),
+    ];
+
+    for element in addresses.iter() {
+        let digits = element.0.to_string();
+        let mac = MediaAccessControlAddress::new(&digits).unwrap();
+
+        assert_eq!(mac.to_binary_representation(), element.1);
+        assert_eq!(mac.to_decimal_representation(), element.2);
+        assert_eq!(mac.to_plain_notation(), element.3);
+        assert_eq!(mac.to_hyphen_notation(), element.4);
+        assert_eq!(mac.to_colon_notation(), element.5);
+        assert_eq!(mac.to_dot_notation(), element.6);
+
+        let (first, second) = mac.to_fragments();
+        assert_eq!((first.as_str(), second.as_str()), element.7);
+        assert_eq!(mac.kind(), element.8);
+        assert_eq!(mac.has_oui(), element.9);
+        assert_eq!(mac.has_cid(), element.10);
+
+        assert_eq!(mac.is_broadcast(), element.11);
+        assert_eq!(mac.is_multicast(), element.12);
+        assert_eq!(mac.is_unicast(), element.13);
+        assert_eq!(mac.is_uaa(), element.14);
+        assert_eq!(mac.is_laa(), element.15);
+    }
+}
+
+// An ELI is a unicast address.
+#[test]
+fn test_unicast_eli_address() {
+    let address = (
+        "0a1b2c3d4e5f",
+        "000010100001101100101100001111010100111001011111",
+        11111822610015,
+        "0a1b2c3d4e5f",
+        "0a-1b-2c-3d-4e-5f",
+        "0a:1b:2c:3d:4e:5f",
+        "0a1b.2c3d.4e5f",
+        ("0a1b2c", "3d4e5f"),
+        "local",
+        false,
+        true,
+        false,
+        false,
+        true,
+        false,
+        true,
+    );
+
+    let digits = address.0.to_string();
+    let mac = MediaAccessControlAddress::new(&digits).unwrap();
+
+    assert_eq!(mac.to_binary_representation(), address.1);
+    assert_eq!(mac.to_decimal_representation(), address.2);
+    assert_eq!(mac.to_plain_notation(), address.3);
+    assert_eq!(mac.to_hyphen_notation(), address.4);
+    assert_eq!(mac.to_colon_notation(), address.5);
+    assert_eq!(mac.to_dot_notation(), address.6);
+
+    let (first, second) = mac.to_fragments();
+    assert_eq!((first.as_str(), second.as_str()), address.7);
+    assert_eq!(mac.kind(), address.8);
+    assert_eq!(mac.has_oui(), address.9);
+    assert_eq!(mac.has_cid(), address.10);
+
+    assert_eq!(mac.is_broadcast(), address.11);
+    assert_eq!(mac.is_multicast(), address.12);
+    assert_eq!(mac.is_unicast(), address.13);
+    assert_eq!(mac.is_uaa(), address.14);
+    assert_eq!(mac.is_laa(), address.15);
+}
+
+#[test]
+fn test_broadcast_address() {
+    let address = (
+        "ffffffffffff",
+        "111111111111111111111111111111111111111111111111",
+        281474976710655,
+        "ffffffffffff",
+        "ff-ff-ff-ff-ff-ff",
+        "ff:ff:ff:ff:ff:ff",
+        "ffff.ffff.ffff",
+        ("ffffff", "ffffff"),
+        "unknown",
+        false,
+        false,
+        true,
+        true,
+        false,
+        false,
+        false,
+    );
+
+    let digits = address.0.to_string();
+    let mac = MediaAccessControlAddress::new(&digits).unwrap();
+
+    assert_eq!(mac.to_binary_representation(), address.1);
+    assert_eq!(mac.to_decimal_representation(), address.2);
+    assert_eq!(mac.to_plain_notation(), address.3);
+    assert_eq!(mac.to_hyphen_notation(), address.4);
+    assert_eq!(mac.to_colon_notation(), address.5);
+    assert_eq!(mac.to_dot_notation(), address.6);
+
+    // These tests make little sense in the context
+    // of a broadcast address, but we run them for the
+    // sake of completeness.
+    let (first, second) = mac.to_fragments();
+    assert_eq!((first.as_str(), second.as_str()), address.7);
+    assert_eq!(mac.kind(), address.8);
+    assert_eq!(mac.has_oui(), address.9);
+    assert_eq!(mac.has_cid(), address.10);
+
+    assert_eq!(mac.is_broadcast(), address.11);
+    assert_eq!(mac.is_multicast(), address.12);
+    assert_eq!(mac.is_unicast(), address.13);
+    assert_eq!(mac.is_uaa(), address.14);
+    assert_eq!(mac.is_laa(), address.15);
+}
+
+#[test]
+fn test_multicast_address() {
+    let address = (
+        "0180c2000000", // Link-Layer Discovery Protocol
+        "000000011000000011000010000000000000000000000000",
+        1652522221568,
+        "0180c2000000",
+        "01-80-c2-00-00-00",
+        "01:80:c2:00:00:00",
+        "0180.c200.0000",
+        ("0180c2", "000000"),
+        "unknown",
+        false,
+        false,
+        false,
+        true,
+        false,
+        false,
+        false,
+    );
+
+    let digits = address.0.to_string();
+    let mac = MediaAccessControlAddress::new(&digits).unwrap();
+
+    assert_eq!(mac.to_binary_representation(), address.1);
+    assert_eq!(mac.to_decimal_representation(), address.2);
+    assert_eq!(mac.to_plain_notation(), address.3);
+    assert_eq!(mac.to_hyphen_notation(), address.4);
+    assert_eq!(mac.to_colon_notation(), address.5);
+    assert_eq!(mac.to_dot_notation(), address.6);
+
+    // These tests make little sense in the context
+    // of a multicast address, but we run them for the
+    // sake of completeness.
+    let (first, second) = mac.to_fragments();
+    assert_eq!((first.as_str(), second.as_str()), address.7);
+    assert_eq!(mac.kind(), address.8);
+    assert_eq!(mac.has_oui(), address.9);
+    assert_eq!(mac.has_cid(), address.10);
+
+    assert_eq!(mac.is_broadcast(), address.11);
+    assert_eq!(mac.is_multicast(), address.12);
+    assert_eq!(mac.is_unicast(), address.13);
+    assert_eq!(mac.is_uaa(), address.14);
+    assert_eq!(mac.is_laa(), address.15);
+}
+
+#[test]
+fn test_short_name() {
+    let digits = String::from("a0:b1:c2:d3:e4:f5");
+    let mac: MediaAccessControlAddress = MacAddress::new(&digits).unwrap();
+
+    assert_eq!(mac.to_plain_notation(), "a0b1c2d3e4f5");
+}
+
+#[test]
+fn test_bridge_filtered_addresses() {
+    let addresses = [
+        ("01-80-c2-00-00-00", true),  // Spanning Tree Protocol
+        ("01-80-c2-00-00-02", true),  // Slow Protocols (LACP)
+        ("01-80-c2-00-00-0e", true),  // Link-Layer Discovery Protocol
+        ("01-80-c2-00-00-0f", true),  // Last reserved address
+        ("01-80-c2-00-00-10", false), // All bridges
+        ("01-80-c2-00-00-20", false), // GARP/MRP
+        ("00-80-c2-00-00-00", false), // Unicast
+        ("ff-ff-ff-ff-ff-ff", false), // Broadcast
+    ];
+
+    for element in addresses.iter() {
+        let digits = element.0.to_string();
+        let mac = MediaAccessControlAddress::new(&digits).unwrap();
+
+        assert_eq!(mac.is_bridge_filtered(), element.1);
+    }
+}
+
+#[test]
+fn test_ptp_addresses() {
+    assert!(PTP_PRIMARY.is_ptp());
+    assert!(PTP_PEER_DELAY.is_ptp());
+    assert_eq!(PTP_PRIMARY.to_hyphen_notation(), "01-1b-19-00-00-00");
+    assert_eq!(PTP_PEER_DELAY.to_hyphen_notation(), "01-80-c2-00-00-0e");
+
+    let addresses = [
+        ("01-1b-19-00-00-00", true),
+        ("01-80-c2-00-00-0e", true),
+        ("01-1b-19-00-00-01", false),
+        ("01-80-c2-00-00-00", false),
+        ("a0-b1-c2-d3-e4-f5", false),
+    ];
+
+    for element in addresses.iter() {
+        let digits = element.0.to_string();
+        let mac = MediaAccessControlAddress::new(&digits).unwrap();
+
+        assert_eq!(mac.is_ptp(), element.1);
+    }
+}
+
+#[test]
+fn test_documentation_addresses() {
+    let unicast = MediaAccessControlAddress::documentation_unicast(0x2a);
+    assert_eq!(unicast.to_hyphen_notation(), "00-00-5e-00-53-2a");
+    assert!(unicast.is_unicast());
+    assert!(unicast.is_documentation_unicast());
+    assert!(!unicast.is_documentation_multicast());
+    assert!(unicast.is_documentation());
+
+    let multicast = MediaAccessControlAddress::documentation_multicast(0xff);
+    assert_eq!(multicast.to_hyphen_notation(), "01-00-5e-90-10-ff");
+    assert!(multicast.is_multicast());
+    assert!(!multicast.is_documentation_unicast());
+    assert!(multicast.is_documentation_multicast());
+    assert!(multicast.is_documentation());
+
+    let digits = String::from("00-00-5e-00-54-00");
+    let mac = MediaAccessControlAddress::new(&digits).unwrap();
+    assert!(!mac.is_documentation());
+}
+
+#[test]
+fn test_iana_addresses() {
+    let addresses = [
+        // (address, unicast, multicast, managed, vrrp, ipv4, mpls, ipv6)
+        (
+            "00-00-5e-00-01-07",
+            true,
+            false,
+            true,
+            true,
+            false,
+            false,
+            false,
+        ),
+        (
+            "00-00-5e-00-02-07",
+            true,
+            false,
+            true,
+            true,
+            false,
+            false,
+            false,
+        ),
+        (
+            "00-00-5e-00-53-01",
+            true,
+            false,
+            true,
+            false,
+            false,
+            false,
+            false,
+        ),
+        (
+            "01-00-5e-00-00-fb",
+            false,
+            true,
+            true,
+            false,
+            true,
+            false,
+            false,
+        ),
+        (
+            "01-00-5e-7f-ff-ff",
+            false,
+            true,
+            true,
+            false,
+            true,
+            false,
+            false,
+        ),
+        (
+            "01-00-5e-80-00-01",
+            false,
+            true,
+            true,
+            false,
+            false,
+            true,
+            false,
+        ),
+        (
+            "33-33-00-00-00-01",
+            false,
+            false,
+            true,
+            false,
+            false,
+            false,
+            true,
+        ),
+        (
+            "a0-b1-c2-d3-e4-f5",
+            false,
+            false,
+            false,
+            false,
+            false,
+            false,
+            false,
+        ),
+    ];
+
+    for element in addresses.iter() {
+        let digits = element.0.to_string();
+        let mac = MediaAccessControlAddress::new(&digits).unwrap();
+
+        assert_eq!(mac.is_iana_unicast(), element.1);
+        assert_eq!(mac.is_iana_multicast(), element.2);
+        assert_eq!(mac.is_iana_managed(), element.3);
+        assert_eq!(mac.is_vrrp(), element.4);
+        assert_eq!(mac.is_ipv4_multicast(), element.5);
+        assert_eq!(mac.is_mpls_multicast(), element.6);
+        assert_eq!(mac.is_ipv6_multicast(), element.7);
+    }
+}
+
+#[test]
+fn test_iana_constructors() {
+    let vrrp = MediaAccessControlAddress::vrrp_ipv4(7);
+    assert_eq!(vrrp.to_hyphen_notation(), "00-00-5e-00-01-07");
+
+    let vrrp = MediaAccessControlAddress::vrrp_ipv6(255);
+    assert_eq!(vrrp.to_hyphen_notation(), "00-00-5e-00-02-ff");
+
+    let group = Ipv4Addr::new(239, 129, 2, 3);
+    let mac = MediaAccessControlAddress::from_ipv4_multicast(group).unwrap();
+    assert_eq!(mac.to_hyphen_notation(), "01-00-5e-01-02-03");
+    assert!(MediaAccessControlAddress::from_ipv4_multicast(Ipv4Addr::new(10, 0, 0, 1)).is_err());
+
+    let groups = mac.to_ipv4_multicast_groups().unwrap();
+    assert_eq!(groups.len(), 32);
+    assert_eq!(groups[0], Ipv4Addr::new(224, 1, 2, 3));
+    assert_eq!(groups[1], Ipv4Addr::new(224, 129, 2, 3));
+    assert_eq!(groups[31], Ipv4Addr::new(239, 129, 2, 3));
+    assert!(groups.contains(&group));
+
+    for candidate in groups.iter() {
+        let mapped = MediaAccessControlAddress::from_ipv4_multicast(*candidate).unwrap();
+        assert_eq!(mapped.to_plain_notation(), mac.to_plain_notation());
+    }
+
+    let mpls = MediaAccessControlAddress::new("01-00-5e-8a-bc-de").unwrap();
+    assert!(mpls.to_ipv4_multicast_groups().is_err());
+    assert!(vrrp.to_ipv4_multicast_groups().is_err());
+
+    let group = "ff02::1:ff00:1".parse::<Ipv6Addr>().unwrap();
+    let mac = MediaAccessControlAddress::from_ipv6_multicast(group).unwrap();
+    assert_eq!(mac.to_hyphen_notation(), "33-33-ff-00-00-01");
+    assert!(MediaAccessControlAddress::from_ipv6_multicast(Ipv6Addr::LOCALHOST).is_err());
+
+    let address = "fe80::21b:63ff:fe84:45e6".parse::<Ipv6Addr>().unwrap();
+    let mac = MediaAccessControlAddress::solicited_node_mac(address).unwrap();
+    assert_eq!(mac.to_hyphen_notation(), "33-33-ff-84-45-e6");
+    assert!(mac.is_ipv6_multicast());
+    let mac = MediaAccessControlAddress::solicited_node_mac(Ipv6Addr::LOCALHOST).unwrap();
+    assert_eq!(mac.to_hyphen_notation(), "33-33-ff-00-00-01");
+    assert!(MediaAccessControlAddress::solicited_node_mac(group).is_err());
+    assert!(MediaAccessControlAddress::solicited_node_mac(Ipv6Addr::UNSPECIFIED).is_err());
+
+    let mac = MediaAccessControlAddress::from_mpls_multicast(0xabcde).unwrap();
+    assert_eq!(mac.to_hyphen_notation(), "01-00-5e-8a-bc-de");
+    assert!(MediaAccessControlAddress::from_mpls_multicast(0x100000).is_err());
+}
+
+#[test]
+fn test_docker_addresses() {
+    let ip = Ipv4Addr::new(172, 17, 0, 2);
+    let mac = MediaAccessControlAddress::from_docker_ipv4(ip);
+    assert_eq!(mac.to_colon_notation(), "02:42:ac:11:00:02");
+    assert!(mac.is_docker_derived());
+    assert!(mac.is_laa());
+    assert_eq!(mac.to_docker_ipv4().unwrap(), ip);
+
+    let mac = MediaAccessControlAddress::new("02:42:0a:00:00:ff").unwrap();
+    assert_eq!(mac.to_docker_ipv4().unwrap(), Ipv4Addr::new(10, 0, 0, 255));
+
+    let mac = MediaAccessControlAddress::new("02:43:ac:11:00:02").unwrap();
+    assert!(!mac.is_docker_derived());
+    assert!(mac.to_docker_ipv4().is_err());
+}
+
+#[test]
+fn test_octets() {
+    let digits = String::from("a0:b1:c2:d3:e4:f5");
+    let mac = MediaAccessControlAddress::new(&digits).unwrap();
+
+    assert_eq!(mac.to_octets(), [0xa0, 0xb1, 0xc2, 0xd3, 0xe4, 0xf5]);
+}
+
+#[test]
+fn test_multicast_filter_index() {
+    let addresses = [
+        ("01-00-5e-00-00-01", 0x7fa32d9b, 31, 255),
+        ("33-33-00-00-00-01", 0xf99baaba, 62, 499),
+        ("ff-ff-ff-ff-ff-ff", 0xff48647d, 63, 510),
+        ("01-80-c2-00-00-0e", 0x876cdef0, 33, 270),
+    ];
+
+    for element in addresses.iter() {
+        let digits = element.0.to_string();
+        let mac = MediaAccessControlAddress::new(&digits).unwrap();
+
+        assert_eq!(ether_crc(&mac), element.1);
+        assert_eq!(multicast_filter_index(&mac, 6).unwrap(), element.2);
+        assert_eq!(multicast_filter_index(&mac, 9).unwrap(), element.3);
+        assert_eq!(multicast_filter_index(&mac, 32).unwrap(), element.1);
+    }
+
+    let digits = String::from("01-00-5e-00-00-01");
+    let mac = MediaAccessControlAddress::new(&digits).unwrap();
+    assert!(multicast_filter_index(&mac, 0).is_err());
+    assert!(multicast_filter_index(&mac, 33).is_err());
+}
+
+#[test]
+fn test_shard_key() {
+    // These values are part of the crate's stable interface and
+    // must never change.
+    let addresses = [
+        ("00-00-00-00-00-00", 14, 883, 3793791032),
+        ("01-00-5e-00-00-01", 7, 454, 1953650009),
+        ("a0-b1-c2-d3-e4-f5", 1, 79, 341393612),
+        ("ff-ff-ff-ff-ff-ff", 12, 772, 3317913771),
+    ];
+
+    for element in addresses.iter() {
+        let digits = element.0.to_string();
+        let mac = MediaAccessControlAddress::new(&digits).unwrap();
+
+        assert_eq!(shard_key(&mac, 1).unwrap(), 0);
+        assert_eq!(shard_key(&mac, 16).unwrap(), element.1);
+        assert_eq!(shard_key(&mac, 1000).unwrap(), element.2);
+        assert_eq!(shard_key(&mac, u32::MAX).unwrap(), element.3);
+    }
+
+    // Sequential addresses spread evenly across the buckets.
+    let mut counts = [0; 8];
+
+    for value in 0..8000u64 {
+        let mac = MediaAccessControlAddress::from_octets(&value.to_be_bytes()[2..]).unwrap();
+        counts[shard_key(&mac, 8).unwrap() as usize] += 1;
+    }
+
+    assert!(counts.iter().all(|&count| count > 900 && count < 1100));
+
+    let digits = String::from("01-00-5e-00-00-01");
+    let mac = MediaAccessControlAddress::new(&digits).unwrap();
+    assert!(shard_key(&mac, 0).is_err());
+}
+
+#[test]
+fn test_hmac_sha256() {
+    use super::hash::hmac_sha256;
+
+    let hex = |digest: [u8; 32]| -> String {
+        digest
+            .iter()
+            .map(|octet| format!("{:02x}", octet))
+            .collect()
+    };
+
+    // Test cases 1, 2, and 6 from RFC 4231.
+    let vectors: [(Vec<u8>, &[u8], &str); 3] = [
+        (
+            vec![0x0b; 20],
+            b"Hi There",
+            "b0344c61d8db38535ca8afceaf0bf12b881dc200c9833da726e9376c2e32cff7",
+        ),
+        (
+            b"Jefe".to_vec(),
+            b"what do ya want for nothing?",
+            "5bdcc146bf60754e6a042426089575c75a003f089d2739839dec58b964ec3843",
+        ),
+        (
+            vec![0xaa; 131],
+            b"Test Using Larger Than Block-Size Key - Hash Key First",
+            "60e431591ee0b67f0d8a26aacbf5b77f8e0bc6213728c5140546040f0ee37f54",
+        ),
+    ];
+
+    for (key, message, digest) in vectors.iter() {
+        assert_eq!(hex(hmac_sha256(key, message)), *digest);
+    }
+}
+
+#[test]
+fn test_sha256() {
+    let hex = |digest: [u8; 32]| -> String {
+        digest
+            .iter()
+            .map(|octet| format!("{:02x}", octet))
+            .collect()
+    };
+
+    // The first five are the NIST examples from FIPS 180-2; the
+    // rest straddle the lengths at which padding spills into
+    // another block.
+    let vectors = [
+        (
+            String::new(),
+            "e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855",
+        ),
+        (
+            String::from("abc"),
+            "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad",
+        ),
+        (
+            String::from("abcdbcdecdefdefgefghfghighijhijkijkljklmklmnlmnomnopnopq"),
+            "248d6a61d20638b8e5c026930c3e6039a33ce45964ff2167f6ecedd419db06c1",
+        ),
+        (
+            String::from(
+                "abcdefghbcdefghicdefghijdefghijkefghijklfghijklmghijklmnhijklmnoijklmnopjklmnopqklmnopqrlmnopqrsmnopqrstnopqrstu",
+            ),
+            "cf5b16a778af8380036ce59e7b0492370b249b11e8f07a51afac45037afee9d1",
+        ),
+        (
+            "a".repeat(1_000_000),
+            "cdc76e5c9914fb9281a1c7e284d73e67f1809a48a497200e046d39ccc7112cd0",
+        ),
+        (
+            "a".repeat(55),
+            "9f4390f8d30c2dd92ec9f095b65e2b9ae9b0a925a5258e241c9f1e910f734318",
+        ),
+        (
+            "a".repeat(56),
+            "b35439a4ac6f0948b6d6f9e3c6af0f5f590ce20f1bde7090ef7970686ec6738a",
+        ),
+        (
+            "a".repeat(63),
+            "7d3e74a05d7db15bce4ad9ec0658ea98e3f06eeecf16b4c6fff2da457ddc2f34",
+        ),
+        (
+            "a".repeat(64),
+            "ffe054fe7ae0cb6dc65c3af9b61d5209f439851db43d0ba5997337df154668eb",
+        ),
+        (
+            "a".repeat(1000),
+            "41edece42d63e8d9bf515a9ba6932e1c20cbc9f5a5d134645adb5db1b9737ea3",
+        ),
+    ];
+
+    for (message, digest) in vectors.iter() {
+        assert_eq!(hex(sha256(message.as_bytes())), *digest);
+    }
+}
+
+#[test]
+fn test_snmp_addresses() {
+    let digits = String::from("A0 B1 C2 D3 E4 F5");
+    let mac = MediaAccessControlAddress::new(&digits).unwrap();
+    assert_eq!(mac.to_plain_notation(), "a0b1c2d3e4f5");
+    assert_eq!(mac.to_snmp_notation(), "A0 B1 C2 D3 E4 F5");
+
+    let octets = [0xa0, 0xb1, 0xc2, 0xd3, 0xe4, 0xf5];
+    let mac = MediaAccessControlAddress::from_octets(&octets).unwrap();
+    assert_eq!(mac.to_plain_notation(), "a0b1c2d3e4f5");
+    assert_eq!(mac.to_octets(), octets);
+
+    assert!(MediaAccessControlAddress::from_octets(&octets[..5]).is_err());
+
+    // Arrays of the right length need no check.
+    const GATEWAY: MediaAccessControlAddress =
+        MediaAccessControlAddress::from_array([0xa0, 0xb1, 0xc2, 0xd3, 0xe4, 0xf5]);
+    assert_eq!(GATEWAY, mac);
+    assert_eq!(MediaAccessControlAddress::from(octets), mac);
+
+    assert!(MediaAccessControlAddress::new("A0 B1 C2 D3 E4F5").is_err());
+    assert!(MediaAccessControlAddress::new(" A0 B1 C2 D3 E4 F5").is_err());
+}
+
+#[test]
+fn test_solaris_addresses() {
+    let addresses = [
+        ("0:3:ba:12:4:5", "0003ba120405", "0:3:ba:12:4:5"),
+        ("0:3:BA:12:4:5", "0003ba120405", "0:3:ba:12:4:5"),
+        ("00:03:ba:12:04:05", "0003ba120405", "0:3:ba:12:4:5"),
+        ("a0:b1:c2:d3:e4:f5", "a0b1c2d3e4f5", "a0:b1:c2:d3:e4:f5"),
+        ("0:0:0:0:0:0", "000000000000", "0:0:0:0:0:0"),
+    ];
+
+    for element in addresses.iter() {
+        let digits = element.0.to_string();
+        let mac = MediaAccessControlAddress::new(&digits).unwrap();
+
+        assert_eq!(mac.to_plain_notation(), element.1);
+        assert_eq!(mac.to_solaris_notation(), element.2);
+    }
+
+    assert!(MediaAccessControlAddress::new("0:3:ba:12:4:").is_err());
+    assert!(MediaAccessControlAddress::new("0:3:ba:12:4:005").is_err());
+    assert!(MediaAccessControlAddress::new("0-3-ba-12-4-5").is_err());
+}
+
+#[test]
+fn test_format_into() {
+    let addresses = [
+        ("a0b1c2d3e4f5", Notation::Plain, "a0b1c2d3e4f5"),
+        ("a0b1c2d3e4f5", Notation::Hyphen, "a0-b1-c2-d3-e4-f5"),
+        ("a0b1c2d3e4f5", Notation::Colon, "a0:b1:c2:d3:e4:f5"),
+        ("a0b1c2d3e4f5", Notation::Dot, "a0b1.c2d3.e4f5"),
+        ("a0b1c2d3e4f5", Notation::Space, "A0 B1 C2 D3 E4 F5"),
+        ("a0b1c2d3e4f5", Notation::Solaris, "a0:b1:c2:d3:e4:f5"),
+        ("0003ba120405", Notation::Solaris, "0:3:ba:12:4:5"),
+    ];
+
+    for element in addresses.iter() {
+        let digits = element.0.to_string();
+        let mac = MediaAccessControlAddress::new(&digits).unwrap();
+
+        let mut buffer = [0; MAX_LEN];
+        assert_eq!(mac.format_into(&mut buffer, element.1).unwrap(), element.2);
+        assert!(element.2.len() <= element.1.max_len());
+    }
+
+    let digits = String::from("a0b1c2d3e4f5");
+    let mac = MediaAccessControlAddress::new(&digits).unwrap();
+
+    let mut buffer = [0; 12];
+    assert_eq!(
+        mac.format_into(&mut buffer, Notation::Colon),
+        Err(BufferTooSmall { required: 17 })
+    );
+    assert_eq!(
+        mac.format_into(&mut buffer, Notation::Plain).unwrap(),
+        "a0b1c2d3e4f5"
+    );
+}
+
+#[cfg(feature = "heapless")]
+#[test]
+fn test_heapless_string() {
+    let digits = String::from("a0b1c2d3e4f5");
+    let mac = MediaAccessControlAddress::new(&digits).unwrap();
+
+    assert_eq!(
+        mac.to_heapless_string(Notation::Hyphen),
+        "a0-b1-c2-d3-e4-f5"
+    );
+}
+
+#[cfg(feature = "defmt")]
+#[test]
+fn test_defmt_format() {
+    fn assert_format<T: defmt::Format>(_: &T) {}
+
+    let mac = MediaAccessControlAddress::new("a0:b1:c2:d3:e4:f5").unwrap();
+    assert_format(&mac);
+    assert_format(&Eui64::new("a0:b1:c2:d3:e4:f5:06:07").unwrap());
+}
+
+#[cfg(feature = "rand")]
+#[test]
+fn test_random_laa() {
+    for _ in 0..32 {
+        let mac = super::random::random_laa().unwrap();
+
+        assert!(mac.is_unicast());
+        assert!(mac.is_laa());
+    }
+
+    for _ in 0..32 {
+        let mac = super::random::random_safe().unwrap();
+
+        assert!(mac.is_unicast());
+        assert!(mac.is_laa());
+    }
+
+    let mac = super::random::random_hypervisor(super::random::Hypervisor::Xen).unwrap();
+    assert!(mac.to_colon_notation().starts_with("00:16:3e:"));
+}
+
+#[test]
+fn test_random_laa_with() {
+    struct Constant(u8);
+
+    impl MacRng for Constant {
+        fn fill_octets(&mut self, octets: &mut [u8; 6]) {
+            *octets = [self.0; 6];
+        }
+    }
+
+    let mac = random_laa_with(&mut Constant(0xff));
+    assert_eq!(mac.to_plain_notation(), "feffffffffff");
+    assert!(mac.is_laa());
+
+    let mac = random_laa_with(&mut Constant(0x00));
+    assert_eq!(mac.to_plain_notation(), "020000000000");
+    assert!(mac.is_laa());
+
+    let mut rng = |octets: &mut [u8; 6]| *octets = [0xa1, 0xb1, 0xc2, 0xd3, 0xe4, 0xf5];
+    let mac = random_laa_with(&mut rng);
+    assert_eq!(mac.to_plain_notation(), "a2b1c2d3e4f5");
+}
+
+#[test]
+fn test_random_safe_with() {
+    use super::fingerprint;
+    use super::random::random_safe_with;
+
+    let mut ones = |octets: &mut [u8; 6]| *octets = [0xff; 6];
+    let mac = random_safe_with(&mut ones);
+    assert_eq!(mac.to_colon_notation(), "f2:ff:ff:ff:ff:ff");
+
+    let mut zeros = |octets: &mut [u8; 6]| *octets = [0x00; 6];
+    let mac = random_safe_with(&mut zeros);
+    assert_eq!(mac.to_colon_notation(), "02:00:00:00:00:00");
+
+    // Virtual prefixes in the quadrant are never drawn.
+    let mut qemu = |octets: &mut [u8; 6]| *octets = [0x53, 0x54, 0x00, 0x12, 0x34, 0x56];
+    let mac = random_safe_with(&mut qemu);
+    assert_eq!(mac.to_colon_notation(), "52:d4:00:12:34:56");
+
+    let mut docker = |octets: &mut [u8; 6]| *octets = [0x0b, 0x42, 0xac, 0x11, 0x00, 0x02];
+    let mac = random_safe_with(&mut docker);
+    assert_eq!(mac.to_colon_notation(), "02:c2:ac:11:00:02");
+
+    let mut state: u8 = 0;
+    let mut rng = |octets: &mut [u8; 6]| {
+        for octet in octets.iter_mut() {
+            state = state.wrapping_mul(31).wrapping_add(7);
+            *octet = state;
+        }
+    };
+
+    for _ in 0..256 {
+        let mac = random_safe_with(&mut rng);
+
+        assert!(mac.is_unicast());
+        assert!(mac.is_laa());
+        assert_eq!(mac.to_octets()[0] & 0x0f, 0x02);
+        assert_eq!(fingerprint::platform(&mac), None);
+        assert_eq!(fingerprint::role(&mac), None);
+    }
+}
+
+#[test]
+fn test_random_hypervisor_with() {
+    use super::random::{random_hypervisor_with, Hypervisor};
+
+    let mut ones = |octets: &mut [u8; 6]| *octets = [0xff; 6];
+    let mut zeros = |octets: &mut [u8; 6]| *octets = [0x00; 6];
+
+    let cases = [
+        (Hypervisor::Vmware, "00:50:56:3f:ff:ff", "00:50:56:00:00:00"),
+        (
+            Hypervisor::VmwareVcenter(5),
+            "00:50:56:85:ff:ff",
+            "00:50:56:85:00:00",
+        ),
+        (Hypervisor::Kvm, "52:54:00:ff:ff:ff", "52:54:00:00:00:00"),
+        (Hypervisor::Xen, "00:16:3e:7f:ff:ff", "00:16:3e:00:00:00"),
+    ];
+
+    for (hypervisor, highest, lowest) in cases {
+        let mac = random_hypervisor_with(hypervisor, &mut ones).unwrap();
+        assert_eq!(mac.to_colon_notation(), highest);
+        assert!(hypervisor.prefix().unwrap().contains(&mac));
+
+        let mac = random_hypervisor_with(hypervisor, &mut zeros).unwrap();
+        assert_eq!(mac.to_colon_notation(), lowest);
+    }
+
+    // QEMU's defaults are never drawn.
+    let mut default = |octets: &mut [u8; 6]| *octets = [0, 0, 0, 0x12, 0x34, 0x57];
+    let mac = random_hypervisor_with(Hypervisor::Kvm, &mut default).unwrap();
+    assert_eq!(mac.to_colon_notation(), "52:54:00:92:34:57");
+
+    assert_eq!(
+        Hypervisor::VmwareVcenter(63).prefix().unwrap().to_string(),
+        "00:50:56:bf:00:00/32"
+    );
+    assert!(Hypervisor::VmwareVcenter(64).prefix().is_err());
+    assert!(random_hypervisor_with(Hypervisor::VmwareVcenter(64), &mut ones).is_err());
+}
+
+#[test]
+fn test_vendor_codegen() {
+    let csv = "Registry,Assignment,Organization Name,Organization Address\r
+MA-L,00000C,\"Cisco Systems, Inc\",\"170 West Tasman Drive San Jose CA US 95134 \"\r
+MA-M,70B3D51,\"The \"\"Example\"\" Company\",\"Somewhere\"\r
+MA-L,000000,XEROX CORPORATION,\r
+";
+
+    let source = codegen(csv).unwrap();
+    assert_eq!(
+        source,
+        "&[
+    ::macaddress::vendor::Record { prefix: 0x000000000000, length: 24, organization: \"XEROX CORPORATION\", address: \"\" },
+    ::macaddress::vendor::Record { prefix: 0x00000c000000, length: 24, organization: \"Cisco Systems, Inc\", address: \"170 West Tasman Drive San Jose CA US 95134\" },
+    ::macaddress::vendor::Record { prefix: 0x70b3d5100000, length: 28, organization: \"The \\\"Example\\\" Company\", address: \"Somewhere\" },
+]"
+    );
+
+    assert!(codegen("MA-L,00000,Too Short").is_err());
+    assert!(codegen("MA-L,00000G,Not Hexadecimal").is_err());
+    assert!(codegen("MA-L,00000C").is_err());
+}
+
+#[test]
+fn test_vendor_filter() {
+    let csv = "Registry,Assignment,Organization Name,Organization Address
+# A comment
+MA-L,00000C,\"Cisco Systems, Inc\",San Jose
+MA-L,000000,XEROX CORPORATION,
+MA-L,70B3D5,IEEE Registration Authority,
+MA-M,70B3D51,Example MA-M,
+MA-S,70B3D5123,Example MA-S,
+";
+
+    let filter: VendorFilter = " cisco systems, inc ; 70:b3:d5:10:00:00/28;"
+        .parse()
+        .unwrap();
+    assert_eq!(
+        filter,
+        VendorFilter::new()
+            .with_organization("Cisco Systems, Inc")
+            .with_prefix("70:b3:d5:10:00:00/28".parse().unwrap())
+    );
+
+    // The prefix overlaps the MA-L block that encloses it, the MA-M
+    // block that it matches, and the MA-S block within it.
+    assert_eq!(
+        filter.filter(csv).unwrap(),
+        "Registry,Assignment,Organization Name,Organization Address
+# A comment
+MA-L,00000C,\"Cisco Systems, Inc\",San Jose
+MA-L,70B3D5,IEEE Registration Authority,
+MA-M,70B3D51,Example MA-M,
+MA-S,70B3D5123,Example MA-S,
+"
+    );
+
+    let filter = VendorFilter::new().with_organization("XEROX");
+    let source = codegen(&filter.filter(csv).unwrap()).unwrap();
+    assert_eq!(source.matches("Record {").count(), 1);
+    assert!(source.contains("XEROX CORPORATION"));
+
+    let filter = VendorFilter::new().with_prefix("70:b3:d5:12:40:00/36".parse().unwrap());
+    assert_eq!(
+        codegen(&filter.filter(csv).unwrap())
+            .unwrap()
+            .matches("Record {")
+            .count(),
+        2
+    );
+
+    assert!(VendorFilter::new().is_empty());
+    assert_eq!(VendorFilter::new().filter(csv).unwrap(), csv);
+    assert!("".parse::<VendorFilter>().unwrap().is_empty());
+    assert!("a0:b1:c2:00:00:00/49".parse::<VendorFilter>().is_err());
+    assert!(VendorFilter::new().filter("MA-L,00000G,Bad").is_err());
+
+    let name = format!("MACADDRESS_TEST_VENDORS_{}", std::process::id());
+    assert!(VendorFilter::from_env(&name).unwrap().is_empty());
+    std::env::set_var(&name, "cisco");
+    assert_eq!(
+        VendorFilter::from_env(&name).unwrap(),
+        VendorFilter::new().with_organization("cisco")
+    );
+    std::env::remove_var(&name);
+}
+
+#[test]
+fn test_vendor_table() {
+    static VENDORS: VendorTable = VendorTable::new(&[
+        Record {
+            prefix: 0x00000c000000,
+            length: 24,
+            organization: "Cisco Systems, Inc",
+            address: "170 West Tasman Drive San Jose CA US 95134",
+        },
+        Record {
+            prefix: 0x70b3d5000000,
+            length: 24,
+            organization: "IEEE Registration Authority",
+            address: "",
+        },
+        Record {
+            prefix: 0x70b3d5100000,
+            length: 28,
+            organization: "Example MA-M",
+            address: "",
+        },
+        Record {
+            prefix: 0x70b3d5123000,
+            length: 36,
+            organization: "Example MA-S",
+            address: "",
+        },
+    ]);
+
+    let addresses = [
+        ("00-00-0c-07-ac-01", Some("Cisco Systems, Inc")),
+        ("70-b3-d5-12-34-56", Some("Example MA-S")),
+        ("70-b3-d5-1f-ff-ff", Some("Example MA-M")),
+        ("70-b3-d5-20-00-00", Some("IEEE Registration Authority")),
+        ("a0-b1-c2-d3-e4-f5", None),
+    ];
+
+    assert_eq!(VENDORS.len(), 4);
+
+    for element in addresses.iter() {
+        let digits = element.0.to_string();
+        let mac = MediaAccessControlAddress::new(&digits).unwrap();
+        let organization = VENDORS.lookup(&mac).map(|record| record.organization);
+
+        assert_eq!(organization, element.1);
+        assert_eq!(VENDORS.is_registered(&mac), element.1.is_some());
+    }
+
+    let digits = String::from("00-00-0c-07-ac-01");
+    let mac = MediaAccessControlAddress::new(&digits).unwrap();
+    let record = VENDORS.lookup(&mac).unwrap();
+
+    assert_eq!(record.address, "170 West Tasman Drive San Jose CA US 95134");
+    assert_eq!(record.registry(), Some("MA-L"));
+    assert_eq!(record.block_size(), 1 << 24);
+    assert!(record.contains(&mac));
+
+    let digits = String::from("70-b3-d5-12-34-56");
+    let mac = MediaAccessControlAddress::new(&digits).unwrap();
+    let record = VENDORS.lookup(&mac).unwrap();
+
+    assert_eq!(record.prefix, 0x70b3d5123000);
+    assert_eq!(record.registry(), Some("MA-S"));
+    assert_eq!(record.block_size(), 4096);
+}
+
+#[cfg(feature = "phf")]
+#[test]
+fn test_phf_vendor_table() {
+    use super::vendor::{codegen_phf, PhfVendorTable};
+
+    // Each key is the block's prefix shifted left by 8 bits, with its
+    // length in the low 8 bits, as `codegen_phf` generates them.
+    static RECORDS: phf::Map<u64, Record> = phf::phf_map! {
+        0x00000c00000018u64 => Record {
+            prefix: 0x00000c000000,
+            length: 24,
+            organization: "Cisco Systems, Inc",
+            address: "170 West Tasman Drive San Jose CA US 95134",
+        },
+        0x70b3d500000018u64 => Record {
+            prefix: 0x70b3d5000000,
+            length: 24,
+            organization: "IEEE Registration Authority",
+            address: "",
+        },
+        0x70b3d51000001cu64 => Record {
+            prefix: 0x70b3d5100000,
+            length: 28,
+            organization: "Example MA-M",
+            address: "",
+        },
+        0x70b3d512300024u64 => Record {
+            prefix: 0x70b3d5123000,
+            length: 36,
+            organization: "Example MA-S",
+            address: "",
+        },
+    };
+
+    static VENDORS: PhfVendorTable = PhfVendorTable::new(&RECORDS);
+
+    let addresses = [
+        ("00-00-0c-07-ac-01", Some("Cisco Systems, Inc")),
+        ("70-b3-d5-12-34-56", Some("Example MA-S")),
+        ("70-b3-d5-1f-ff-ff", Some("Example MA-M")),
+        ("70-b3-d5-20-00-00", Some("IEEE Registration Authority")),
+        ("a0-b1-c2-d3-e4-f5", None),
+    ];
+
+    assert_eq!(VENDORS.len(), 4);
+
+    for element in addresses.iter() {
+        let digits = element.0.to_string();
+        let mac = MediaAccessControlAddress::new(&digits).unwrap();
+        let organization = VENDORS.lookup(&mac).map(|record| record.organization);
+
+        assert_eq!(organization, element.1);
+        assert_eq!(mac.vendor_with(&VENDORS).unwrap().as_deref(), element.1);
+        assert_eq!(VENDORS.is_registered(&mac), element.1.is_some());
+    }
+
+    let csv = "MA-L,00000C,\"Cisco Systems, Inc\",\"170 West Tasman Drive San Jose CA US 95134\"
+MA-S,70B3D5123,Example MA-S,
+";
+
+    let source = codegen_phf(csv).unwrap();
+    assert!(source.starts_with("::macaddress::vendor::phf::Map {"));
+    assert!(source.contains("(51539607576, ::macaddress::vendor::Record { prefix: 0x00000c000000, length: 24, organization: \"Cisco Systems, Inc\""));
+    assert!(source.contains("(31722925106135076, ::macaddress::vendor::Record { prefix: 0x70b3d5123000, length: 36, organization: \"Example MA-S\", address: \"\" })"));
+    assert!(codegen_phf("MA-L,00000G,Not Hexadecimal").is_err());
+}
+
+#[test]
+fn test_vendor_resolvers() {
+    static VENDORS: VendorTable = VendorTable::new(&[Record {
+        prefix: 0x00000c000000,
+        length: 24,
+        organization: "Cisco Systems, Inc",
+        address: "170 West Tasman Drive San Jose CA US 95134",
+    }]);
+
+    let mut map = HashMap::new();
+    map.insert(0x00000c, String::from("Cisco"));
+
+    struct Failing;
+
+    impl VendorResolver for Failing {
+        fn resolve(&self, _: &MediaAccessControlAddress) -> Result<Option<String>, Error> {
+            Err(Error::Network(String::from("Backend is unavailable.")))
+        }
+    }
+
+    let digits = String::from("00:00:0c:07:ac:01");
+    let mac = MediaAccessControlAddress::new(&digits).unwrap();
+
+    assert_eq!(
+        mac.vendor_with(&VENDORS).unwrap(),
+        Some(String::from("Cisco Systems, Inc"))
+    );
+    assert_eq!(mac.vendor_with(&map).unwrap(), Some(String::from("Cisco")));
+    assert!(mac.vendor_with(&Failing).is_err());
+
+    let resolvers: Vec<Box<dyn VendorResolver>> = vec![Box::new(VENDORS), Box::new(map)];
+
+    let digits = String::from("a0:b1:c2:d3:e4:f5");
+    let mac = MediaAccessControlAddress::new(&digits).unwrap();
+
+    for resolver in resolvers.iter() {
+        assert_eq!(mac.vendor_with(resolver).unwrap(), None);
+    }
+}
+
+#[cfg(feature = "net")]
+#[test]
+fn test_online_resolver() {
+    use super::vendor::OnlineResolver;
+    use std::io::{BufRead, BufReader, Write};
+    use std::net::TcpListener;
+    use std::thread;
+
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let port = listener.local_addr().unwrap().port();
+
+    let server = thread::spawn(move || {
+        let mut requests = Vec::new();
+
+        for _ in 0..2 {
+            let (mut stream, _) = listener.accept().unwrap();
+            let mut reader = BufReader::new(stream.try_clone().unwrap());
+            let mut lines = Vec::new();
+
+            loop {
+                let mut line = String::new();
+                reader.read_line(&mut line).unwrap();
+
+                if line.trim().is_empty() {
+                    break;
+                }
+
+                lines.push(line.trim().to_string());
+            }
+
+            let response = if lines[0].contains("00:00:0c") {
+                "HTTP/1.1 200 OK\r\nContent-Length: 18\r\nConnection: close\r\n\r\nCisco Systems, Inc"
+            } else {
+                "HTTP/1.1 404 Not Found\r\nContent-Length: 0\r\nConnection: close\r\n\r\n"
+            };
+
+            stream.write_all(response.as_bytes()).unwrap();
+            requests.push(lines);
+        }
+
+        requests
+    });
+
+    let endpoint = format!("http://127.0.0.1:{}/{{mac}}", port);
+    let resolver = OnlineResolver::new(&endpoint)
+        .unwrap()
+        .with_api_key("secret");
+
+    let digits = String::from("00-00-0c-07-ac-01");
+    let mac = MediaAccessControlAddress::new(&digits).unwrap();
+    assert_eq!(
+        mac.vendor_with(&resolver).unwrap(),
+        Some(String::from("Cisco Systems, Inc"))
+    );
+
+    let digits = String::from("a0-b1-c2-d3-e4-f5");
+    let mac = MediaAccessControlAddress::new(&digits).unwrap();
+    assert_eq!(mac.vendor_with(&resolver).unwrap(), None);
+
+    let requests = server.join().unwrap();
+    assert_eq!(requests[0][0], "GET /00:00:0c:07:ac:01 HTTP/1.1");
+    assert!(requests[0]
+        .iter()
+        .any(|line| line.eq_ignore_ascii_case("authorization: Bearer secret")));
+
+    assert!(OnlineResolver::new("https://example.com/").is_err());
+}
+
+#[cfg(feature = "net")]
+#[test]
+fn test_online_resolver_batch() {
+    use super::vendor::{Enrich, OnlineResolver};
+    use std::io::{BufRead, BufReader, Write};
+    use std::net::TcpListener;
+    use std::thread;
+    use std::time::{Duration, Instant};
+
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let port = listener.local_addr().unwrap().port();
+
+    let server = thread::spawn(move || {
+        let mut requests = Vec::new();
+
+        for attempt in 0..3 {
+            let (mut stream, _) = listener.accept().unwrap();
+            let mut reader = BufReader::new(stream.try_clone().unwrap());
+            let mut request = String::new();
+            reader.read_line(&mut request).unwrap();
+
+            loop {
+                let mut line = String::new();
+                reader.read_line(&mut line).unwrap();
+
+                if line.trim().is_empty() {
+                    break;
+                }
+            }
+
+            let response = match attempt {
+                0 => "HTTP/1.1 429 Too Many Requests\r\nRetry-After: 0\r\nContent-Length: 0\r\nConnection: close\r\n\r\n",
+                1 => "HTTP/1.1 200 OK\r\nContent-Length: 7\r\nConnection: close\r\n\r\nCisco\n\n",
+                _ => "HTTP/1.1 200 OK\r\nContent-Length: 8\r\nConnection: close\r\n\r\nExample\n",
+            };
+
+            stream.write_all(response.as_bytes()).unwrap();
+            requests.push(request.trim().to_string());
+        }
+
+        requests
+    });
+
+    let resolver = OnlineResolver::new(&format!("http://127.0.0.1:{}/{{mac}}", port))
+        .unwrap()
+        .with_batch_endpoint(&format!("http://127.0.0.1:{}/batch?q={{macs}}", port), 2)
+        .unwrap()
+        .with_rate_limit(10, Duration::from_secs(1))
+        .unwrap()
+        .with_retries(1, Duration::from_secs(60));
+
+    let addresses: Vec<MediaAccessControlAddress> = [
+        "a0:b1:c2:d3:e4:f5",
+        "00:00:0c:07:ac:01",
+        "0a:1b:2c:3d:4e:5f",
+    ]
+    .iter()
+    .map(|digits| MediaAccessControlAddress::new(digits).unwrap())
+    .collect();
+
+    let start = Instant::now();
+    let vendors: Vec<Option<String>> = addresses
+        .iter()
+        .enrich_with(&resolver)
+        .map(|result| result.unwrap().1)
+        .collect();
+
+    assert_eq!(
+        vendors,
+        vec![
+            Some(String::from("Example")),
+            Some(String::from("Cisco")),
+            None
+        ]
+    );
+
+    // Three requests, 100 milliseconds apart.
+    assert!(start.elapsed() >= Duration::from_millis(200));
+    assert_eq!(
+        server.join().unwrap(),
+        vec![
+            "GET /batch?q=00:00:0c:07:ac:01,0a:1b:2c:3d:4e:5f HTTP/1.1",
+            "GET /batch?q=00:00:0c:07:ac:01,0a:1b:2c:3d:4e:5f HTTP/1.1",
+            "GET /batch?q=a0:b1:c2:d3:e4:f5 HTTP/1.1",
+        ]
+    );
+
+    assert!(resolver.with_rate_limit(0, Duration::from_secs(1)).is_err());
+    assert!(OnlineResolver::new(OnlineResolver::MACVENDORS)
+        .unwrap()
+        .with_batch_endpoint("https://example.com/", 10)
+        .is_err());
+}
+
+#[cfg(feature = "net")]
+#[test]
+fn test_online_resolver_batch_unknown_last() {
+    use super::vendor::{OnlineResolver, VendorResolver};
+    use std::io::{BufRead, BufReader, Write};
+    use std::net::TcpListener;
+    use std::thread;
+
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let port = listener.local_addr().unwrap().port();
+
+    let server = thread::spawn(move || {
+        for response in [
+            "HTTP/1.1 200 OK\r\nContent-Length: 9\r\nConnection: close\r\n\r\nCisco\r\n\r\n",
+            "HTTP/1.1 200 OK\r\nContent-Length: 2\r\nConnection: close\r\n\r\n\n\n",
+            "HTTP/1.1 200 OK\r\nContent-Length: 6\r\nConnection: close\r\n\r\nCisco\n",
+        ]
+        .iter()
+        {
+            let (mut stream, _) = listener.accept().unwrap();
+            let mut reader = BufReader::new(stream.try_clone().unwrap());
+
+            loop {
+                let mut line = String::new();
+                reader.read_line(&mut line).unwrap();
+
+                if line.trim().is_empty() {
+                    break;
+                }
+            }
+
+            stream.write_all(response.as_bytes()).unwrap();
+        }
+    });
+
+    let resolver = OnlineResolver::new(&format!("http://127.0.0.1:{}/{{mac}}", port))
+        .unwrap()
+        .with_batch_endpoint(&format!("http://127.0.0.1:{}/batch?q={{macs}}", port), 2)
+        .unwrap();
+
+    let first = MediaAccessControlAddress::new("00:00:0c:07:ac:01").unwrap();
+    let last = MediaAccessControlAddress::new("a0:b1:c2:d3:e4:f5").unwrap();
+    let vendors = |resolver: &OnlineResolver| {
+        resolver
+            .resolve_batch(&[&first, &last])
+            .into_iter()
+            .map(|vendor| vendor.map_err(|error| error.to_string()))
+            .collect::<Vec<_>>()
+    };
+
+    // The last address is unknown, so the answer ends in an empty
+    // line, with either line ending.
+    assert_eq!(
+        vendors(&resolver),
+        vec![Ok(Some(String::from("Cisco"))), Ok(None)]
+    );
+    assert_eq!(vendors(&resolver), vec![Ok(None), Ok(None)]);
+
+    // Without the empty line, one address is unaccounted for.
+    assert!(resolver
+        .resolve_batch(&[&first, &last])
+        .iter()
+        .all(|vendor| matches!(vendor, Err(Error::Network(_)))));
+
+    server.join().unwrap();
+}
+
+#[cfg(feature = "net")]
+#[test]
+fn test_registry_client() {
+    use super::vendor::RegistryClient;
+    use std::io::{BufRead, BufReader, Write};
+    use std::net::TcpListener;
+    use std::thread;
+    use std::time::Duration;
+
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let port = listener.local_addr().unwrap().port();
+
+    let server = thread::spawn(move || {
+        let mut requests = Vec::new();
+
+        for attempt in 0..3 {
+            let (mut stream, _) = listener.accept().unwrap();
+            let mut reader = BufReader::new(stream.try_clone().unwrap());
+            let mut request = String::new();
+            reader.read_line(&mut request).unwrap();
+
+            loop {
+                let mut line = String::new();
+                reader.read_line(&mut line).unwrap();
+
+                if line.trim().is_empty() {
+                    break;
+                }
+            }
+
+            let response = match attempt {
+                0 => "HTTP/1.1 404 Not Found\r\nContent-Length: 0\r\nConnection: close\r\n\r\n",
+                1 => "HTTP/1.1 503 Service Unavailable\r\nContent-Length: 0\r\nConnection: close\r\n\r\n",
+                _ => "HTTP/1.1 200 OK\r\nContent-Length: 21\r\nConnection: close\r\n\r\nMA-L,A0B1C2,Example,\n",
+            };
+
+            stream.write_all(response.as_bytes()).unwrap();
+            requests.push(request.trim().to_string());
+        }
+
+        requests
+    });
+
+    let client = RegistryClient::new()
+        .without_proxy()
+        .with_mirror(&format!("http://127.0.0.1:{}/gone/", port))
+        .unwrap()
+        .with_mirror(&format!("http://127.0.0.1:{}/busy", port))
+        .unwrap()
+        .with_timeout(Duration::from_secs(10))
+        .with_retries(1, Duration::from_millis(10));
+
+    assert_eq!(
+        client.registry_urls("MA-S").unwrap(),
+        vec![
+            format!("http://127.0.0.1:{}/gone/oui36/oui36.csv", port),
+            format!("http://127.0.0.1:{}/busy/oui36/oui36.csv", port),
+            String::from("https://standards-oui.ieee.org/oui36/oui36.csv"),
+        ]
+    );
+
+    // The first mirror is missing the registry, and the second
+    // succeeds on its retry.
+    assert_eq!(
+        client.fetch_registry("MA-L").unwrap(),
+        "MA-L,A0B1C2,Example,\n"
+    );
+    assert_eq!(
+        server.join().unwrap(),
+        vec![
+            "GET /gone/oui/oui.csv HTTP/1.1",
+            "GET /busy/oui/oui.csv HTTP/1.1",
+            "GET /busy/oui/oui.csv HTTP/1.1",
+        ]
+    );
+
+    // Nothing listens here any more, so every attempt fails.
+    let url = format!("http://127.0.0.1:{}/oui/oui.csv", port);
+    assert!(matches!(client.fetch(&url), Err(Error::Network(_))));
+
+    assert!(matches!(
+        client.registry_urls("MA-X"),
+        Err(Error::Registry(_))
+    ));
+    assert!(RegistryClient::new()
+        .with_mirror("ftp://example.com")
+        .is_err());
+    assert!(RegistryClient::new().with_proxy("not a proxy").is_err());
+}
+
+#[test]
+fn test_cached_resolver() {
+    struct Counting {
+        calls: Cell<usize>,
+    }
+
+    impl VendorResolver for Counting {
+        fn resolve(&self, mac: &MediaAccessControlAddress) -> Result<Option<String>, Error> {
+            self.calls.set(self.calls.get() + 1);
+            Ok(Some(mac.to_fragments().0.to_string()))
+        }
+    }
+
+    let resolver = CachedResolver::new(
+        Counting {
+            calls: Cell::new(0),
+        },
+        2,
+    );
+
+    let addresses = [
+        ("a0b1c2d3e4f5", "a0b1c2", 1), // Miss
+        ("a0b1c2d3e000", "a0b1c2", 1), // Hit (same 36-bit prefix)
+        ("a0b1c2d3f000", "a0b1c2", 2), // Miss (different 36-bit prefix)
+        ("a0b1c2d3e4f5", "a0b1c2", 2), // Hit
+        ("0a1b2c3d4e5f", "0a1b2c", 3), // Miss, which evicts a0b1c2d3f
+        ("a0b1c2d3e4f5", "a0b1c2", 3), // Hit
+        ("a0b1c2d3f000", "a0b1c2", 4), // Miss
+    ];
+
+    for element in addresses.iter() {
+        let digits = element.0.to_string();
+        let mac = MediaAccessControlAddress::new(&digits).unwrap();
+
+        assert_eq!(
+            mac.vendor_with(&resolver).unwrap(),
+            Some(element.1.to_string())
+        );
+        assert_eq!(resolver.inner().calls.get(), element.2);
+        assert!(resolver.len() <= 2);
+    }
+
+    resolver.clear();
+    assert!(resolver.is_empty());
+
+    let resolver = CachedResolver::new(
+        Counting {
+            calls: Cell::new(0),
+        },
+        2,
+    )
+    .with_ttl(std::time::Duration::from_secs(0));
+
+    let digits = String::from("a0b1c2d3e4f5");
+    let mac = MediaAccessControlAddress::new(&digits).unwrap();
+    mac.vendor_with(&resolver).unwrap();
+    mac.vendor_with(&resolver).unwrap();
+    assert_eq!(resolver.inner().calls.get(), 2);
+}
+
+#[test]
+fn test_enrich_with() {
+    struct Logging {
+        calls: RefCell<Vec<String>>,
+    }
+
+    impl VendorResolver for Logging {
+        fn resolve(&self, mac: &MediaAccessControlAddress) -> Result<Option<String>, Error> {
+            let digits = mac.to_plain_notation();
+            self.calls.borrow_mut().push(digits.clone());
+
+            match &digits[..2] {
+                "ff" => Err(Error::Network(String::from("Backend is unavailable."))),
+                "00" => Ok(None),
+                _ => Ok(Some(digits[..6].to_string())),
+            }
+        }
+    }
+
+    let resolver = Logging {
+        calls: RefCell::new(Vec::new()),
+    };
+
+    let addresses: Vec<MediaAccessControlAddress> = [
+        "a0b1c2d3e4f5",
+        "0a1b2c3d4e5f",
+        "a0b1c2d3e000", // Same 36-bit prefix as the first
+        "000000000001",
+        "a0b1c2d3f000",
+        "ffffffffffff",
+        "0a1b2c3d4e00", // Same 36-bit prefix as the second, but in the next batch
+    ]
+    .iter()
+    .map(|digits| MediaAccessControlAddress::new(digits).unwrap())
+    .collect();
+
+    let mut enriched = addresses.iter().enrich_with(&resolver).with_batch_size(6);
+
+    // Nothing is looked up until the first address is needed.
+    assert!(resolver.calls.borrow().is_empty());
+
+    let (mac, vendor) = enriched.next().unwrap().unwrap();
+    assert_eq!(mac.to_plain_notation(), "a0b1c2d3e4f5");
+    assert_eq!(vendor.as_deref(), Some("a0b1c2"));
+
+    // The first batch looked up each block once, in ascending order.
+    assert_eq!(
+        *resolver.calls.borrow(),
+        vec![
+            "000000000001",
+            "0a1b2c3d4e5f",
+            "a0b1c2d3e4f5",
+            "a0b1c2d3f000",
+            "ffffffffffff"
+        ]
+    );
+
+    let rest: Vec<_> = enriched.collect();
+    let vendors: Vec<Option<&str>> = rest
+        .iter()
+        .take(4)
+        .map(|result| result.as_ref().unwrap().1.as_deref())
+        .collect();
+
+    assert_eq!(
+        vendors,
+        vec![Some("0a1b2c"), Some("a0b1c2"), None, Some("a0b1c2")]
+    );
+    assert!(rest[4].is_err());
+    assert_eq!(
+        rest[5].as_ref().unwrap().0.to_plain_notation(),
+        "0a1b2c3d4e00"
+    );
+    assert_eq!(resolver.calls.borrow().len(), 6);
+
+    // Owned addresses work, too, and come back out.
+    let owned: Result<Vec<_>, Error> = addresses[..2]
+        .iter()
+        .map(|mac| MediaAccessControlAddress::from_octets(&mac.to_octets()).unwrap())
+        .enrich_with(&resolver)
+        .collect();
+
+    assert_eq!(owned.unwrap()[1].0.to_plain_notation(), "0a1b2c3d4e5f");
+}
+
+#[test]
+fn test_multiple_bssids() {
+    let digits = String::from("a0:b1:c2:d3:e4:f5");
+    let transmitted = MediaAccessControlAddress::new(&digits).unwrap();
+
+    let bssids: Vec<String> = multiple_bssids(&transmitted, 3)
+        .unwrap()
+        .iter()
+        .map(|bssid| bssid.to_colon_notation())
+        .collect();
+
+    assert_eq!(
+        bssids,
+        [
+            "a0:b1:c2:d3:e4:f5",
+            "a0:b1:c2:d3:e4:f6",
+            "a0:b1:c2:d3:e4:f7",
+            "a0:b1:c2:d3:e4:f0",
+            "a0:b1:c2:d3:e4:f1",
+            "a0:b1:c2:d3:e4:f2",
+            "a0:b1:c2:d3:e4:f3",
+            "a0:b1:c2:d3:e4:f4",
+        ]
+    );
+
+    for index in 0..8 {
+        let nontransmitted = nontransmitted_bssid(&transmitted, 3, index).unwrap();
+        assert_eq!(
+            nontransmitted.to_colon_notation(),
+            bssids[usize::from(index)]
+        );
+
+        let recovered = transmitted_bssid(&nontransmitted, 3, index).unwrap();
+        assert_eq!(
+            recovered.to_plain_notation(),
+            transmitted.to_plain_notation()
+        );
+    }
+
+    assert_eq!(multiple_bssids(&transmitted, 8).unwrap().len(), 256);
+    assert!(multiple_bssids(&transmitted, 0).is_err());
+    assert!(multiple_bssids(&transmitted, 9).is_err());
+    assert!(nontransmitted_bssid(&transmitted, 3, 8).is_err());
+    assert!(transmitted_bssid(&transmitted, 3, 8).is_err());
+}
+
+#[test]
+fn test_lacp_system_id() {
+    let id: LacpSystemId = "32768,a0-b1-c2-d3-e4-f5".parse().unwrap();
+    assert_eq!(id.priority(), 32768);
+    assert_eq!(id.system().to_plain_notation(), "a0b1c2d3e4f5");
+    assert_eq!(id.to_string(), "32768,a0-b1-c2-d3-e4-f5");
+    assert_eq!(
+        id.to_bytes(),
+        [0x80, 0x00, 0xa0, 0xb1, 0xc2, 0xd3, 0xe4, 0xf5]
+    );
+    assert_eq!(LacpSystemId::from_bytes(&id.to_bytes()).unwrap(), id);
+
+    let parenthesized: LacpSystemId = "(32768, A0:B1:C2:D3:E4:F5)".parse().unwrap();
+    assert_eq!(parenthesized, id);
+
+    let ids = [
+        "32768,a0-b1-c2-d3-e4-f5",
+        "32768,0a-1b-2c-3d-4e-5f",
+        "100,ff-ff-ff-ff-ff-fe",
+        "65535,00-00-00-00-00-01",
+    ];
+
+    let mut ids: Vec<LacpSystemId> = ids.iter().map(|id| id.parse().unwrap()).collect();
+    ids.sort();
+
+    let ids: Vec<String> = ids.iter().map(|id| id.to_string()).collect();
+    assert_eq!(
+        ids,
+        [
+            "100,ff-ff-ff-ff-ff-fe",
+            "32768,0a-1b-2c-3d-4e-5f",
+            "32768,a0-b1-c2-d3-e4-f5",
+            "65535,00-00-00-00-00-01",
+        ]
+    );
+
+    assert!("a0-b1-c2-d3-e4-f5".parse::<LacpSystemId>().is_err());
+    assert!("65536,a0-b1-c2-d3-e4-f5".parse::<LacpSystemId>().is_err());
+    assert!("32768,a0-b1-c2-d3-e4".parse::<LacpSystemId>().is_err());
+    assert!(LacpSystemId::from_bytes(&[0; 7]).is_err());
+}
+
+#[test]
+fn test_bridge_id() {
+    let digits = String::from("a0:b1:c2:d3:e4:f5");
+    let mac = MediaAccessControlAddress::new(&digits).unwrap();
+
+    let bridge = BridgeId::new(32768, 1, &mac).unwrap();
+    assert_eq!(bridge.priority(), 32768);
+    assert_eq!(bridge.system_id_extension(), 1);
+    assert_eq!(bridge.bridge_priority(), 32769);
+    assert_eq!(bridge.address().to_plain_notation(), "a0b1c2d3e4f5");
+    assert_eq!(bridge.to_string(), "8001.a0b1c2d3e4f5");
+    assert_eq!(
+        bridge.to_bytes(),
+        [0x80, 0x01, 0xa0, 0xb1, 0xc2, 0xd3, 0xe4, 0xf5]
+    );
+    assert_eq!(BridgeId::from_bytes(&bridge.to_bytes()).unwrap(), bridge);
+    assert_eq!("8001.a0b1.c2d3.e4f5".parse::<BridgeId>().unwrap(), bridge);
+
+    let bridges = [
+        "8001.a0b1c2d3e4f5",
+        "8001.0a1b2c3d4e5f",
+        "8000.ffffffffffff",
+        "1064.ffffffffffff",
+    ];
+
+    let mut bridges: Vec<BridgeId> = bridges.iter().map(|id| id.parse().unwrap()).collect();
+    bridges.sort();
+
+    let bridges: Vec<String> = bridges.iter().map(|id| id.to_string()).collect();
+    assert_eq!(
+        bridges,
+        [
+            "1064.ffffffffffff",
+            "8000.ffffffffffff",
+            "8001.0a1b2c3d4e5f",
+            "8001.a0b1c2d3e4f5",
+        ]
+    );
+
+    assert!(BridgeId::new(100, 1, &mac).is_err());
+    assert!(BridgeId::new(4096, 4096, &mac).is_err());
+    assert!(BridgeId::from_bytes(&[0; 9]).is_err());
+    assert!("801.a0b1c2d3e4f5".parse::<BridgeId>().is_err());
+    assert!("800g.a0b1c2d3e4f5".parse::<BridgeId>().is_err());
+    assert!("+001.a0b1c2d3e4f5".parse::<BridgeId>().is_err());
+    assert!("8001a0b1c2d3e4f5".parse::<BridgeId>().is_err());
+}
+
+#[cfg(feature = "snmp")]
+#[test]
+fn test_snmp_client() {
+    use super::snmp::{FdbStatus, Message, SnmpClient, Value};
+    use std::net::UdpSocket;
+    use std::thread;
+    use std::time::Duration;
+
+    // Messages survive a round trip, including long lengths and
+    // multi-octet arcs.
+    let message = Message {
+        community: b"public".to_vec(),
+        tag: 0xa2,
+        request_id: 300,
+        first: 0,
+        second: -1,
+        bindings: vec![
+            (
+                vec![1, 3, 6, 1, 4, 1, 2636, 1],
+                Value::Bytes(vec![0x5a; 200]),
+            ),
+            (vec![2, 999], Value::Unsigned(u64::from(u32::MAX))),
+            (vec![1, 3, 6, 1], Value::Integer(-129)),
+            (vec![1, 3, 6, 1], Value::EndOfMibView),
+        ],
+    };
+
+    assert_eq!(Message::decode(&message.encode()), Some(message));
+    assert_eq!(Message::decode(&[0x30, 0x05, 0x02]), None);
+
+    // A fake agent serves one switch with Q-BRIDGE-MIB and a second
+    // one without it.
+    fn agent(bindings: Vec<(Vec<u32>, Value)>) -> (u16, thread::JoinHandle<()>) {
+        let socket = UdpSocket::bind("127.0.0.1:0").unwrap();
+        let port = socket.local_addr().unwrap().port();
+        socket
+            .set_read_timeout(Some(Duration::from_secs(1)))
+            .unwrap();
+
+        let handle = thread::spawn(move || {
+            let mut buffer = [0; 1500];
+            let mut dropped = false;
+
+            while let Ok((length, peer)) = socket.recv_from(&mut buffer) {
+                let request = Message::decode(&buffer[..length]).unwrap();
+                assert_eq!(request.tag, 0xa5);
+
+                if request.community != b"public" {
+                    continue;
+                }
+
+                // Drop the first request, so that the client retries.
+                if !dropped {
+                    dropped = true;
+                    continue;
+                }
+
+                let start = &request.bindings[0].0;
+                let mut found: Vec<(Vec<u32>, Value)> = bindings
+                    .iter()
+                    .filter(|(name, _)| name > start)
+                    .take(request.second as usize)
+                    .cloned()
+                    .collect();
+
+                if found.is_empty() {
+                    found.push((start.clone(), Value::EndOfMibView));
+                }
+
+                let response = Message {
+                    community: request.community,
+                    tag: 0xa2,
+                    request_id: request.request_id,
+                    first: 0,
+                    second: 0,
+                    bindings: found,
+                };
+
+                socket.send_to(&response.encode(), peer).unwrap();
+            }
+        });
+
+        (port, handle)
+    }
+
+    let q_fdb = |column: u32, fdb: u32, last: u32| {
+        vec![
+            1, 3, 6, 1, 2, 1, 17, 7, 1, 2, 2, 1, column, fdb, 160, 177, 194, 211, 228, last,
+        ]
+    };
+    let d_fdb = |column: u32, last: u32| {
+        vec![
+            1, 3, 6, 1, 2, 1, 17, 4, 3, 1, column, 160, 177, 194, 211, 228, last,
+        ]
+    };
+    let if_index = |port: u32| vec![1, 3, 6, 1, 2, 1, 17, 1, 4, 1, 2, port];
+
+    let mut bindings = vec![
+        (if_index(1), Value::Integer(1001)),
+        (if_index(2), Value::Integer(1002)),
+        (
+            d_fdb(1, 245),
+            Value::Bytes(vec![160, 177, 194, 211, 228, 245]),
+        ),
+        (d_fdb(2, 245), Value::Integer(2)),
+        (d_fdb(3, 245), Value::Integer(3)),
+        (vec![1, 3, 6, 1, 2, 1, 17, 4, 4, 1, 1, 1], Value::Integer(1)),
+    ];
+
+    for (fdb, last, port, status) in [(10, 245, 1, 3), (20, 245, 2, 3), (10, 1, 0, 4)] {
+        bindings.push((q_fdb(2, fdb, last), Value::Integer(port)));
+        bindings.push((q_fdb(3, fdb, last), Value::Integer(status)));
+    }
+
+    bindings.sort_by(|a, b| a.0.cmp(&b.0));
+
+    let (port, handle) = agent(bindings.clone());
+    let mut client = SnmpClient::new(("127.0.0.1", port), "public")
+        .unwrap()
+        .with_timeout(Duration::from_millis(200))
+        .unwrap()
+        .with_retries(2);
+
+    let entries = client.fdb().unwrap();
+    let summary: Vec<_> = entries
+        .iter()
+        .map(|entry| {
+            (
+                entry.address.to_colon_notation(),
+                entry.port,
+                entry.if_index,
+                entry.vlan,
+                entry.status,
+            )
+        })
+        .collect();
+
+    assert_eq!(
+        summary,
+        vec![
+            (
+                String::from("a0:b1:c2:d3:e4:01"),
+                0,
+                None,
+                Some(10),
+                FdbStatus::SelfAddress
+            ),
+            (
+                String::from("a0:b1:c2:d3:e4:f5"),
+                1,
+                Some(1001),
+                Some(10),
+                FdbStatus::Learned
+            ),
+            (
+                String::from("a0:b1:c2:d3:e4:f5"),
+                2,
+                Some(1002),
+                Some(20),
+                FdbStatus::Learned
+            ),
+        ]
+    );
+
+    let entries = client.dot1d_fdb().unwrap();
+    assert_eq!(entries.len(), 1);
+    assert_eq!(entries[0].port, 2);
+    assert_eq!(entries[0].if_index, Some(1002));
+    assert_eq!(entries[0].vlan, None);
+
+    drop(client);
+    handle.join().unwrap();
+
+    // Without Q-BRIDGE-MIB, `fdb` falls back to BRIDGE-MIB.
+    bindings.retain(|(name, _)| !name.starts_with(&[1, 3, 6, 1, 2, 1, 17, 7]));
+
+    let (port, handle) = agent(bindings);
+    let mut client = SnmpClient::new(("127.0.0.1", port), "public")
+        .unwrap()
+        .with_timeout(Duration::from_millis(200))
+        .unwrap();
+
+    let entries = client.fdb().unwrap();
+    assert_eq!(entries.len(), 1);
+    assert_eq!(entries[0].vlan, None);
+
+    // An agent that ignores the community never answers.
+    let mut client = SnmpClient::new(("127.0.0.1", port), "private")
+        .unwrap()
+        .with_timeout(Duration::from_millis(50))
+        .unwrap()
+        .with_retries(1);
+
+    assert!(client.fdb().is_err());
+    handle.join().unwrap();
+}
+
+#[test]
+fn test_cli_parse() {
+    use super::cli_parse::{self, EntryKind, Platform};
+
+    let ios = "switch#show mac address-table
+          Mac Address Table
+-------------------------------------------
+
+Vlan    Mac Address       Type        Ports
+----    -----------       --------    -----
+ All    0100.0ccc.cccc    STATIC      CPU
+  10    a0b1.c2d3.e4f5    DYNAMIC     Gi1/0/1
+ --More-- \u{8}\u{8}\u{8}\u{8}\u{8}\u{8}\u{8}\u{8}\u{8}  20    a0b1.c2d3.e4f6    DYNAMIC     Po1
+Total Mac Addresses for this criterion: 3
+switch#";
+
+    let nxos = "Legend:
+        * - primary entry, G - Gateway MAC, (R) - Routed MAC, O - Overlay MAC
+   VLAN     MAC Address      Type      age     Secure NTFY Ports
+---------+-----------------+--------+---------+------+----+------------------
+*   10     a0b1.c2d3.e4f5   dynamic  0         F      F    Eth1/1
+*   20     a0b1.c2d3.e4f6   dynamic  0         F      F    vPC Peer-Link
+G    -     a0b1.c2d3.e4f7   static   -         F      F    sup-eth1(R)";
+
+    let eos = "          Mac Address Table
+------------------------------------------------------------------
+
+Vlan    Mac Address       Type        Ports      Moves   Last Move
+----    -----------       ----        -----      -----   ---------
+  10    a0b1.c2d3.e4f5    DYNAMIC     Et1        1       0:00:12 ago
+  20    a0b1.c2d3.e4f6    STATIC      Et2
+Total Mac Addresses for this criterion: 2";
+
+    let junos =
+        "MAC flags (S - static MAC, D - dynamic MAC, L - locally learned, P - Persistent static)
+
+Ethernet switching table : 2 entries, 1 learned
+Routing instance : default-switch
+   Vlan                MAC                 MAC         Age    Logical                NH        RTR
+   name                address             flags              interface              Index     ID
+   default             a0:b1:c2:d3:e4:f5   D             -   ge-0/0/1.0             0         0
+   v20                 a0:b1:c2:d3:e4:f6   S             -   ge-0/0/2.0             0         0";
+
+    let legacy = "Ethernet-switching table: 2 entries, 1 learned
+  VLAN              MAC address       Type         Age Interfaces
+  default           *                 Flood          - All-members
+  default           a0:b1:c2:d3:e4:f5 Learn          0 ge-0/0/1.0";
+
+    assert_eq!(Platform::detect(ios), Some(Platform::Ios));
+    assert_eq!(Platform::detect(nxos), Some(Platform::NxOs));
+    assert_eq!(Platform::detect(eos), Some(Platform::Eos));
+    assert_eq!(Platform::detect(junos), Some(Platform::Junos));
+    assert_eq!(Platform::detect(legacy), Some(Platform::Junos));
+    assert_eq!(Platform::detect("% Invalid input"), None);
+
+    let summarize = |text: &str| -> Vec<(String, String, String, EntryKind)> {
+        cli_parse::parse(text)
+            .into_iter()
+            .map(|entry| {
+                (
+                    entry.vlan,
+                    entry.address.to_colon_notation(),
+                    entry.interface,
+                    entry.kind,
+                )
+            })
+            .collect()
+    };
+    let entry = |vlan: &str, mac: &str, interface: &str, kind: EntryKind| {
+        (
+            vlan.to_string(),
+            mac.to_string(),
+            interface.to_string(),
+            kind,
+        )
+    };
+
+    assert_eq!(
+        summarize(ios),
+        vec![
+            entry("All", "01:00:0c:cc:cc:cc", "CPU", EntryKind::Static),
+            entry("10", "a0:b1:c2:d3:e4:f5", "Gi1/0/1", EntryKind::Dynamic),
+            entry("20", "a0:b1:c2:d3:e4:f6", "Po1", EntryKind::Dynamic),
+        ]
+    );
+    assert_eq!(
+        summarize(nxos),
+        vec![
+            entry("10", "a0:b1:c2:d3:e4:f5", "Eth1/1", EntryKind::Dynamic),
+            entry(
+                "20",
+                "a0:b1:c2:d3:e4:f6",
+                "vPC Peer-Link",
+                EntryKind::Dynamic
+            ),
+            entry("-", "a0:b1:c2:d3:e4:f7", "sup-eth1(R)", EntryKind::Static),
+        ]
+    );
+    assert_eq!(
+        summarize(eos),
+        vec![
+            entry("10", "a0:b1:c2:d3:e4:f5", "Et1", EntryKind::Dynamic),
+            entry("20", "a0:b1:c2:d3:e4:f6", "Et2", EntryKind::Static),
+        ]
+    );
+    assert_eq!(
+        summarize(junos),
+        vec![
+            entry(
+                "default",
+                "a0:b1:c2:d3:e4:f5",
+                "ge-0/0/1.0",
+                EntryKind::Dynamic
+            ),
+            entry("v20", "a0:b1:c2:d3:e4:f6", "ge-0/0/2.0", EntryKind::Static),
+        ]
+    );
+    assert_eq!(
+        summarize(legacy),
+        vec![entry(
+            "default",
+            "a0:b1:c2:d3:e4:f5",
+            "ge-0/0/1.0",
+            EntryKind::Dynamic
+        )]
+    );
+
+    let entries = cli_parse::parse(ios);
+    assert_eq!(entries[0].vlan_id(), None);
+    assert_eq!(entries[1].vlan_id(), Some(10));
+
+    // Without headers, the platform must be named.
+    let bare = "10 a0b1.c2d3.e4f5 secure Gi1/0/3 0 -";
+    assert_eq!(cli_parse::parse(bare)[0].interface, "-");
+    assert_eq!(
+        cli_parse::parse_as(bare, Platform::Eos)[0].kind,
+        EntryKind::Other(String::from("secure"))
+    );
+    assert_eq!(
+        cli_parse::parse_as(bare, Platform::Eos)[0].interface,
+        "Gi1/0/3"
+    );
+    assert!(cli_parse::parse("").is_empty());
+}
+
+#[test]
+fn test_dhcp_leases() {
+    use super::dhcp;
+    use std::net::IpAddr;
+    use std::time::{Duration, UNIX_EPOCH};
+
+    let dnsmasq = "1760000000 a0:b1:c2:d3:e4:f5 192.168.1.10 laptop 01:a0:b1:c2:d3:e4:f5
+0 a0:b1:c2:d3:e4:f6 192.168.1.11 * *
+duid 00:01:00:01:2c:5f:3a:1b:a0:b1:c2:d3:e4:f5
+1760000000 305419896 fd00::10 laptop 00:01:00:01:2c:5f:3a:1b:a0:b1:c2:d3:e4:f5
+";
+
+    let leases = dhcp::parse_dnsmasq(dnsmasq).unwrap();
+    assert_eq!(leases.len(), 2);
+    assert_eq!(leases[0].address.to_colon_notation(), "a0:b1:c2:d3:e4:f5");
+    assert_eq!(leases[0].ip, "192.168.1.10".parse::<IpAddr>().unwrap());
+    assert_eq!(leases[0].hostname.as_deref(), Some("laptop"));
+    assert_eq!(
+        leases[0].expiry,
+        Some(UNIX_EPOCH + Duration::from_secs(1_760_000_000))
+    );
+    assert_eq!(leases[1].hostname, None);
+    assert_eq!(leases[1].expiry, None);
+
+    assert!(dhcp::parse_dnsmasq("1760000000 a0:b1:c2:d3:e4:f5 192.168.1.10").is_err());
+    assert!(dhcp::parse_dnsmasq("soon a0:b1:c2:d3:e4:f5 192.168.1.10 laptop *").is_err());
+    assert!(dhcp::parse_dnsmasq("0 a0:b1:c2:d3:e4:f5 192.168.1.300 laptop *").is_err());
+    assert!(dhcp::parse_dnsmasq("0 a0:b1:c2:d3:e4 192.168.1.10 laptop *").is_err());
+
+    let dhcpd = r#"# The format of this file is documented in the dhcpd.leases(5) manual page.
+# This lease file was written by isc-dhcp-4.4.3
+
+# authoring-byte-order entry is generated, DO NOT DELETE
+authoring-byte-order little-endian;
+
+lease 192.168.1.10 {
+  starts 4 2025/10/09 08:53:20;
+  ends 4 2025/10/09 09:53:20;
+  binding state active;
+  hardware ethernet a0:b1:c2:d3:e4:f5;
+  client-hostname "old-name";
+}
+lease 192.168.1.11 {
+  starts 4 2025/10/09 08:53:20;
+  ends never;
+  hardware ethernet a0:b1:c2:d3:e4:f6;
+  uid "\001\240\261\302\323\344\366";
+}
+lease 192.168.1.12 {
+  binding state free;
+}
+ia-na "\000\000\000\001" {
+  iaaddr fd00::10 {
+    ends 4 2025/10/09 09:53:20;
+  }
+}
+lease 192.168.1.10 {
+  starts 4 2025/10/09 09:00:00;
+  ends epoch 1760000000; # Thu Oct 09 08:53:20 2025
+  hardware ethernet a0:b1:c2:d3:e4:f5;
+  client-hostname "laptop; \"new\"";
+}
+lease 192.168.1.13 {
+  ends 6 2024/03/02 00:00:01;
+  hardware ethernet a0:b1:c2:d3:e4:f7;
+}
+"#;
+
+    let leases = dhcp::parse_dhcpd(dhcpd).unwrap();
+    let ips: Vec<String> = leases.iter().map(|lease| lease.ip.to_string()).collect();
+    assert_eq!(ips, vec!["192.168.1.10", "192.168.1.11", "192.168.1.13"]);
+    assert_eq!(leases[0].hostname.as_deref(), Some("laptop; \"new\""));
+    assert_eq!(
+        leases[0].expiry,
+        Some(UNIX_EPOCH + Duration::from_secs(1_760_000_000))
+    );
+    assert_eq!(leases[1].address.to_colon_notation(), "a0:b1:c2:d3:e4:f6");
+    assert_eq!(leases[1].hostname, None);
+    assert_eq!(leases[1].expiry, None);
+    assert_eq!(
+        leases[2].expiry,
+        Some(UNIX_EPOCH + Duration::from_secs(1_709_337_601))
+    );
+
+    // The UTC format and the epoch format agree.
+    let utc = dhcp::parse_dhcpd(
+        "lease 10.0.0.1 { ends 4 2025/10/09 08:53:20; hardware ethernet a0:b1:c2:d3:e4:f5; }",
+    )
+    .unwrap();
+    assert_eq!(utc[0].expiry, leases[0].expiry);
+
+    assert!(dhcp::parse_dhcpd("lease 10.0.0.1 { hardware ethernet a0:b1:c2:d3:e4:f5;").is_err());
+    assert!(dhcp::parse_dhcpd("lease 10.0.0.300 { }").is_err());
+    assert!(dhcp::parse_dhcpd("lease 10.0.0.1 { ends 4 2025/13/09 08:53:20; }").is_err());
+    assert!(dhcp::parse_dhcpd("lease 10.0.0.1 { client-hostname \"laptop; }").is_err());
+}
+
+#[test]
+fn test_scan() {
+    use super::format::Notation;
+    use super::scan::{self, Scanner};
+    use std::io::Cursor;
+
+    let text = "a0:b1:c2:d3:e4:f5 went to A0-B1-C2-D3-E4-F6, then a0b1.c2d3.e4f7 \
+                (also a0b1-c2d3-e4f8, A0 B1 C2 D3 E4 F9 at noon, 0:3:ba:12:4:5, and a0b1c2d3e4fa).";
+    let found = scan::find_all(text);
+
+    let summary: Vec<(&str, Notation, String)> = found
+        .iter()
+        .map(|found| {
+            (
+                &text[found.start..found.end],
+                found.notation,
+                found.address.to_colon_notation(),
+            )
+        })
+        .collect();
+
+    assert_eq!(
+        summary,
+        vec![
+            (
+                "a0:b1:c2:d3:e4:f5",
+                Notation::Colon,
+                String::from("a0:b1:c2:d3:e4:f5")
+            ),
+            (
+                "A0-B1-C2-D3-E4-F6",
+                Notation::Hyphen,
+                String::from("a0:b1:c2:d3:e4:f6")
+            ),
+            (
+                "a0b1.c2d3.e4f7",
+                Notation::Dot,
+                String::from("a0:b1:c2:d3:e4:f7")
+            ),
+            (
+                "a0b1-c2d3-e4f8",
+                Notation::FourDigitHyphen,
+                String::from("a0:b1:c2:d3:e4:f8")
+            ),
+            (
+                "A0 B1 C2 D3 E4 F9",
+                Notation::Space,
+                String::from("a0:b1:c2:d3:e4:f9")
+            ),
+            (
+                "0:3:ba:12:4:5",
+                Notation::Solaris,
+                String::from("00:03:ba:12:04:05")
+            ),
+            (
+                "a0b1c2d3e4fa",
+                Notation::Plain,
+                String::from("a0:b1:c2:d3:e4:fa")
+            ),
+        ]
+    );
+
+    // Longer runs of digits and groups are not addresses.
+    assert!(scan::find_all("sha1 a0b1c2d3e4f5a0b1c2d3e4f5a0b1c2d3e4f5a0b1").is_empty());
+    assert!(
+        scan::find_all("eui64 a0:b1:c2:ff:fe:d3:e4:f5 and fe80::a0b1:c2ff:fed3:e4f5").is_empty()
+    );
+    assert!(scan::find_all("dump: 00 11 a0 b1 c2 d3 e4 f5 66 77").is_empty());
+    assert!(scan::find_all("id=xa0:b1:c2:d3:e4:f5").is_empty());
+    assert!(scan::find_all("").is_empty());
+
+    // Plain notation can be left out.
+    let numbers = "order 123456789012 shipped to a0:b1:c2:d3:e4:f5";
+    assert_eq!(scan::find_all(numbers).len(), 2);
+    assert_eq!(
+        scan::find_all_with(numbers, &[Notation::Colon])[0].start,
+        30
+    );
+
+    // The scanner agrees with `find_all`, across lines and bytes that
+    // are not UTF-8.
+    let mut bytes = b"first \xff\xfe a0:b1:c2:d3:e4:f5\n".to_vec();
+    bytes.extend_from_slice(b"second a0b1.c2d3.e4f6 and a0b1c2d3e4f7\n");
+    bytes.extend_from_slice(b"third a0-b1-c2-d3-e4-f8");
+
+    let found: Vec<(usize, String)> = Scanner::new(Cursor::new(&bytes))
+        .map(|found| {
+            let found = found.unwrap();
+            (found.start, found.address.to_colon_notation())
+        })
+        .collect();
+
+    assert_eq!(
+        found,
+        vec![
+            (9, String::from("a0:b1:c2:d3:e4:f5")),
+            (34, String::from("a0:b1:c2:d3:e4:f6")),
+            (53, String::from("a0:b1:c2:d3:e4:f7")),
+            (72, String::from("a0:b1:c2:d3:e4:f8")),
+        ]
+    );
+
+    let found: Vec<_> = Scanner::new(Cursor::new(&bytes))
+        .with_notations(&[Notation::Dot])
+        .collect();
+    assert_eq!(found.len(), 1);
+}
+
+#[test]
+fn test_policy() {
+    use super::classification::Cast;
+    use super::fingerprint::Origin;
+    use super::policy::{Action, Decision, Matcher, Policy, Rule};
+
+    let mac = |digits: &str| MediaAccessControlAddress::new(digits).unwrap();
+
+    let text = "
+        # Lab policy.
+        default deny
+        deny randomized
+        deny a0:b1:c2:d3:e4:f5   # Stolen.
+        allow oui A0-B1-C2
+        allow 00:1b:63:00:00:00 00:00:00:00:ff:ff
+        allow 00:50:56:00:00:00/24
+        allow multicast
+    ";
+
+    let policy: Policy = text.parse().unwrap();
+    assert_eq!(policy.rules().len(), 6);
+    assert_eq!(policy.default_action(), Action::Deny);
+
+    let decide = |digits: &str| {
+        let decision = policy.decide(&mac(digits));
+        (
+            decision.is_allowed(),
+            decision
+                .rule()
+                .map(|rule| (rule.line.unwrap(), rule.to_string())),
+        )
+    };
+
+    assert_eq!(
+        decide("a2:b1:c2:d3:e4:f5"),
+        (false, Some((4, String::from("deny randomized"))))
+    );
+    assert_eq!(
+        decide("a0:b1:c2:d3:e4:f5"),
+        (false, Some((5, String::from("deny a0:b1:c2:d3:e4:f5"))))
+    );
+    assert_eq!(
+        decide("a0:b1:c2:d3:e4:f6"),
+        (true, Some((6, String::from("allow a0:b1:c2:00:00:00/24"))))
+    );
+    assert_eq!(
+        decide("00:1b:63:00:12:34"),
+        (
+            true,
+            Some((7, String::from("allow 00:1b:63:00:00:00 00:00:00:00:ff:ff")))
+        )
+    );
+    assert_eq!(
+        decide("00:50:56:01:02:03"),
+        (true, Some((8, String::from("allow 00:50:56:00:00:00/24"))))
+    );
+    assert_eq!(
+        decide("01:00:5e:00:00:01"),
+        (true, Some((9, String::from("allow multicast"))))
+    );
+    assert_eq!(decide("00:1b:63:01:12:34"), (false, None));
+    assert_eq!(decide("00:1c:63:00:12:34"), (false, None));
+
+    // Rules can be built, and they print the way they parse.
+    let policy = Policy::new()
+        .with_rule(Rule::new(Action::Deny, Matcher::Cast(Cast::Broadcast)))
+        .with_rule("deny laa".parse().unwrap());
+
+    assert!(policy.decide(&mac("00:1b:63:01:02:03")).is_allowed());
+    assert_eq!(
+        policy.decide(&mac("ff:ff:ff:ff:ff:ff")),
+        Decision::Deny {
+            rule: Some(&policy.rules()[0])
+        }
+    );
+    assert!(!policy.decide(&mac("02:00:00:00:00:01")).is_allowed());
+
+    for rule in [
+        "allow any",
+        "deny uaa",
+        "deny laa",
+        "deny physical",
+        "deny virtual",
+        "allow link-local-control",
+        "allow a0:b1:c2:d0:00:00/28",
+    ] {
+        assert_eq!(rule.parse::<Rule>().unwrap().to_string(), rule);
+    }
+
+    assert_eq!(
+        "deny Randomized".parse::<Rule>().unwrap().matcher,
+        Matcher::Origin(Origin::Randomized)
+    );
+    assert!("permit any".parse::<Rule>().is_err());
+    assert!("deny".parse::<Rule>().is_err());
+    assert!("deny oui a0:b1".parse::<Rule>().is_err());
+    assert!("deny oui a0:b1:zz".parse::<Rule>().is_err());
+    assert!("deny everything".parse::<Rule>().is_err());
+    assert!("deny a0:b1:c2:d3:e4:f5 a0:b1:c2:d3:e4:f5 any"
+        .parse::<Rule>()
+        .is_err());
+    assert_eq!(
+        "allow any\npermit any"
+            .parse::<Policy>()
+            .unwrap_err()
+            .to_string(),
+        "Pass in allow or deny (not permit) on line 2."
+    );
+    assert!(matches!(
+        "allow any\npermit any".parse::<Policy>(),
+        Err(Error::Invalid(_))
+    ));
+    assert!(matches!(
+        "deny a0:b1:c2:d3:e4:zz 00:00:00:00:00:ff".parse::<Rule>(),
+        Err(Error::Parse(MacParseError::InvalidCharacter { .. }))
+    ));
+    assert!("default maybe".parse::<Policy>().is_err());
+    assert!(matches!(
+        Policy::load("no/such/policy.txt"),
+        Err(Error::Io(_))
+    ));
+}
+
+#[test]
+fn test_fpma() {
+    use super::fcoe::Fpma;
+
+    let fpma = Fpma::new(0x0efc2a, 0x0a0b0c).unwrap();
+    assert_eq!(fpma.address().to_colon_notation(), "0e:fc:2a:0a:0b:0c");
+    assert_eq!(fpma.fc_map(), 0x0efc2a);
+    assert_eq!(fpma.fc_id(), 0x0a0b0c);
+    assert_eq!(fpma.fc_id_parts(), (0x0a, 0x0b, 0x0c));
+    assert!(fpma.address().is_laa());
+    assert!(fpma.address().is_unicast());
+
+    let mac = MediaAccessControlAddress::new("0e:fc:00:ff:ff:ff").unwrap();
+    let fpma = Fpma::from_address(&mac).unwrap();
+    assert_eq!(fpma, Fpma::with_default_fc_map(0xffffff).unwrap());
+    assert_eq!(fpma.address().to_plain_notation(), mac.to_plain_notation());
+
+    assert!(Fpma::new(0x0efd00, 0).is_err());
+    assert!(Fpma::new(0x0efc00, 0x1000000).is_err());
+    assert!(
+        Fpma::from_address(&MediaAccessControlAddress::new("0e:fd:00:01:02:03").unwrap()).is_err()
+    );
+}
+
+#[test]
+fn test_parse_any() {
+    use crate::datalink::{self, DataLinkAddress};
+
+    match datalink::parse_any("A0-B1-C2-D3-E4-F5").unwrap() {
+        DataLinkAddress::Mac48(mac) => assert_eq!(mac.to_plain_notation(), "a0b1c2d3e4f5"),
+        other => panic!("expected a MAC address, not {:?}", other),
+    }
+
+    match datalink::parse_any("0:3:ba:12:4:5").unwrap() {
+        DataLinkAddress::Mac48(mac) => assert_eq!(mac.to_plain_notation(), "0003ba120405"),
+        other => panic!("expected a MAC address, not {:?}", other),
+    }
+
+    match datalink::parse_any("a0b1.c2ff.fed3.e4f5").unwrap() {
+        DataLinkAddress::Eui64(eui) => assert_eq!(eui.to_plain_notation(), "a0b1c2fffed3e4f5"),
+        other => panic!("expected an EUI-64, not {:?}", other),
+    }
+
+    assert!(datalink::parse_any("a0:b1:c2:d3:e4").is_err());
+    assert!(datalink::parse_any("a0:b1:c2:d3:e4:f5:06").is_err());
+    assert!(datalink::parse_any("").is_err());
+}
+
+#[test]
+fn test_radix_sort() {
+    use crate::sort;
+
+    // A linear congruential generator, so that the test needs no
+    // random number generator.
+    let mut state: u64 = 0x2545_f491_4f6c_dd1d;
+    let mut octets: Vec<[u8; 6]> = (0..5_000)
+        .map(|_| {
+            state = state
+                .wrapping_mul(6_364_136_223_846_793_005)
+                .wrapping_add(1_442_695_040_888_963_407);
+            let bytes = (state >> 16).to_be_bytes();
+            // Repeat some OUIs, so that some passes are skipped.
+            [0xa0, 0xb1, bytes[4] & 0x03, bytes[5], bytes[6], bytes[7]]
+        })
+        .collect();
+    octets.push([0xff; 6]);
+    octets.push([0x00; 6]);
+    octets.push([0x00; 6]);
+
+    let mut expected = octets.clone();
+    expected.sort_unstable();
+
+    let mut addresses: Vec<MediaAccessControlAddress> = octets
+        .iter()
+        .map(|octets| MediaAccessControlAddress::from_octets(octets).unwrap())
+        .collect();
+
+    sort::sort_octets(&mut octets);
+    assert_eq!(octets, expected);
+
+    sort::sort(&mut addresses);
+    let sorted: Vec<[u8; 6]> = addresses
+        .iter()
+        .map(|address| address.to_octets())
+        .collect();
+    assert_eq!(sorted, expected);
+
+    let mut empty: Vec<[u8; 6]> = Vec::new();
+    sort::sort_octets(&mut empty);
+    assert!(empty.is_empty());
+
+    let mut same = vec![[0xa0, 0xb1, 0xc2, 0xd3, 0xe4, 0xf5]; 3];
+    sort::sort_octets(&mut same);
+    assert_eq!(same, vec![[0xa0, 0xb1, 0xc2, 0xd3, 0xe4, 0xf5]; 3]);
+}
+
+#[test]
+fn test_compiled_mac_set() {
+    use crate::error::Error;
+    use crate::prefix::MacPrefix;
+    use crate::set::{CompiledMacSet, MacSet};
+
+    let set: MacSet = [
+        "a0:b1:c2:d3:e4:f5",
+        "00:00:0c:07:ac:01",
+        "a0:b1:c2:00:00:01",
+        "ff:ff:ff:ff:ff:ff",
+    ]
+    .iter()
+    .map(|digits| MediaAccessControlAddress::new(digits).unwrap())
+    .collect();
+
+    let mut bytes = Vec::new();
+    set.write_compiled(&mut bytes).unwrap();
+    assert_eq!(bytes.len(), 16 + 4 * 6);
+    assert_eq!(&bytes[..8], b"MACSET\x00\x01");
+
+    let compiled = CompiledMacSet::from_bytes(&bytes[..]).unwrap();
+    assert_eq!(compiled.len(), 4);
+    assert!(!compiled.is_empty());
+    assert!(compiled.contains(&MediaAccessControlAddress::new("a0:b1:c2:d3:e4:f5").unwrap()));
+    assert!(!compiled.contains(&MediaAccessControlAddress::new("a0:b1:c2:d3:e4:f6").unwrap()));
+    assert_eq!(
+        compiled.as_octets()[0],
+        [0x00, 0x00, 0x0c, 0x07, 0xac, 0x01]
+    );
+    assert_eq!(compiled.to_set(), set);
+
+    let oui: MacPrefix = "a0:b1:c2:00:00:00/24".parse().unwrap();
+    let under: Vec<String> = compiled
+        .range(&oui)
+        .map(|address| address.to_colon_notation())
+        .collect();
+    assert_eq!(under, vec!["a0:b1:c2:00:00:01", "a0:b1:c2:d3:e4:f5"]);
+
+    let mut empty = Vec::new();
+    MacSet::new().write_compiled(&mut empty).unwrap();
+    assert!(CompiledMacSet::from_bytes(&empty[..]).unwrap().is_empty());
+    assert!(CompiledMacSet::from_bytes(&empty[..8]).is_err());
+
+    let mut wrong = bytes.clone();
+    wrong[7] = 2;
+    assert!(matches!(
+        CompiledMacSet::from_bytes(&wrong[..]),
+        Err(Error::Storage(_))
+    ));
+
+    let mut wrong = bytes.clone();
+    wrong[0] = b'X';
+    assert!(CompiledMacSet::from_bytes(&wrong[..]).is_err());
+
+    assert!(CompiledMacSet::from_bytes(&bytes[..bytes.len() - 1]).is_err());
+
+    let path = std::env::temp_dir().join(format!("macaddress-set-{}", std::process::id()));
+    set.save_compiled(&path).unwrap();
+    assert_eq!(CompiledMacSet::load(&path).unwrap().to_set(), set);
+
+    #[cfg(feature = "mmap")]
+    {
+        let mapped = CompiledMacSet::open(&path).unwrap();
+        assert_eq!(mapped.len(), 4);
+        assert!(mapped.contains(&MediaAccessControlAddress::new("ff-ff-ff-ff-ff-ff").unwrap()));
+    }
+
+    std::fs::remove_file(&path).unwrap();
+}
+
+#[cfg(feature = "mac_address")]
+#[test]
+fn test_mac_address_interop() {
+    let address = mac_address::MacAddress::new([0xa0, 0xb1, 0xc2, 0xd3, 0xe4, 0xf5]);
+
+    let mac = MediaAccessControlAddress::from(address);
+    assert_eq!(mac.to_colon_notation(), "a0:b1:c2:d3:e4:f5");
+    assert!(mac.is_unicast());
+
+    let back = mac_address::MacAddress::from(&mac);
+    assert_eq!(back, address);
+
+    let back: mac_address::MacAddress = mac.into();
+    assert_eq!(back.bytes(), [0xa0, 0xb1, 0xc2, 0xd3, 0xe4, 0xf5]);
+}
+
+#[test]
+fn test_csv_column() {
+    use crate::csv::MacColumn;
+    use crate::format::Notation;
+
+    let input = "Name,MAC Address,Notes\r\n\
+                 printer, A0-B1-C2-D3-E4-F5 ,\"lobby, 2nd floor\"\r\n\
+                 switch,a0b1.c2d3.e4f6,\"says \"\"hi\"\"\nto everyone\"\r\n\
+                 spare,,\r\n\
+                 camera,A0:B1:C2:D3:E4:F7\r\n";
+
+    let column = MacColumn::named("mac address");
+    let addresses: Vec<String> = column
+        .read(input.as_bytes())
+        .unwrap()
+        .iter()
+        .map(|address| address.to_colon_notation())
+        .collect();
+    assert_eq!(
+        addresses,
+        vec![
+            "a0:b1:c2:d3:e4:f5",
+            "a0:b1:c2:d3:e4:f6",
+            "a0:b1:c2:d3:e4:f7"
+        ]
+    );
+
+    let mut output = Vec::new();
+    let count = column
+        .with_notation(Notation::Hyphen)
+        .normalize(input.as_bytes(), &mut output)
+        .unwrap();
+    assert_eq!(count, 3);
+    assert_eq!(
+        String::from_utf8(output).unwrap(),
+        "Name,MAC Address,Notes\r\n\
+         printer,a0-b1-c2-d3-e4-f5,\"lobby, 2nd floor\"\r\n\
+         switch,a0-b1-c2-d3-e4-f6,\"says \"\"hi\"\"\nto everyone\"\r\n\
+         spare,,\r\n\
+         camera,a0-b1-c2-d3-e4-f7\r\n"
+    );
+
+    let semicolons = "host;mac\nprinter;A0B1C2D3E4F5";
+    let mut output = Vec::new();
+    MacColumn::at(1)
+        .with_delimiter(';')
+        .with_notation(Notation::Dot)
+        .normalize(semicolons.as_bytes(), &mut output)
+        .unwrap();
+    assert_eq!(
+        String::from_utf8(output).unwrap(),
+        "host;mac\nprinter;a0b1.c2d3.e4f5"
+    );
+
+    let error = MacColumn::named("mac")
+        .read("host,mac\nprinter,a0:b1:c2:d3:e4:f5\n\"quoted\nhost\",a0:b1\n".as_bytes())
+        .unwrap_err();
+    assert!(error.to_string().ends_with("on line 3."));
+
+    assert!(MacColumn::named("ethernet")
+        .read("host,mac\n".as_bytes())
+        .is_err());
+}
+
+#[cfg(feature = "clap")]
+#[test]
+fn test_value_parser() {
+    use clap::error::ErrorKind;
+    use clap::{value_parser, Arg, Command};
+
+    let command = Command::new("wake")
+        .arg(
+            Arg::new("mac")
+                .long("mac")
+                .value_parser(value_parser!(MediaAccessControlAddress)),
+        )
+        .arg(
+            Arg::new("eui")
+                .long("eui")
+                .value_parser(value_parser!(Eui64)),
+        );
+
+    let matches = command
+        .clone()
+        .try_get_matches_from([
+            "wake",
+            "--mac",
+            "A0-B1-C2-D3-E4-F5",
+            "--eui",
+            "a0b1c2fffed3e4f5",
+        ])
+        .unwrap();
+    let mac = matches.get_one::<MediaAccessControlAddress>("mac").unwrap();
+    assert_eq!(mac.to_colon_notation(), "a0:b1:c2:d3:e4:f5");
+    let eui = matches.get_one::<Eui64>("eui").unwrap();
+    assert_eq!(eui.to_hyphen_notation(), "a0-b1-c2-ff-fe-d3-e4-f5");
+
+    let error = command
+        .clone()
+        .try_get_matches_from(["wake", "--mac", "a0:b1:c2:d3:e4:g5"])
+        .unwrap_err();
+    assert_eq!(error.kind(), ErrorKind::ValueValidation);
+    let message = error.to_string();
+    assert!(message.contains("invalid value 'a0:b1:c2:d3:e4:g5' for '--mac <mac>'"));
+    assert!(message.contains("colon notation"));
+
+    assert!(command
+        .try_get_matches_from(["wake", "--eui", "a0:b1:c2:d3:e4:f5"])
+        .is_err());
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn test_serde() {
+    use crate::prefix::MacPrefix;
+    use crate::range::MacRange;
+    use crate::set::MacSet;
+    use serde_test::{assert_de_tokens_error, assert_ser_tokens, assert_tokens, Configure, Token};
+
+    let mac = MediaAccessControlAddress::new("a0:b1:c2:d3:e4:f5").unwrap();
+    assert_ser_tokens(&mac.readable(), &[Token::Str("a0:b1:c2:d3:e4:f5")]);
+    assert_ser_tokens(
+        &mac.compact(),
+        &[Token::Bytes(&[0xa0, 0xb1, 0xc2, 0xd3, 0xe4, 0xf5])],
+    );
+
+    let eui = Eui64::new("a0-b1-c2-ff-fe-d3-e4-f5").unwrap();
+    assert_ser_tokens(&eui.readable(), &[Token::Str("a0:b1:c2:ff:fe:d3:e4:f5")]);
+
+    let prefix: MacPrefix = "a0:b1:c2:00:00:00/24".parse().unwrap();
+    assert_tokens(&prefix.readable(), &[Token::Str("a0:b1:c2:00:00:00/24")]);
+    assert_tokens(
+        &prefix.compact(),
+        &[Token::Bytes(&[0xa0, 0xb1, 0xc2, 0x00, 0x00, 0x00, 24])],
+    );
+
+    let range: MacRange = "a0:b1:c2:00:00:01-a0:b1:c2:00:00:06".parse().unwrap();
+    assert_tokens(
+        &range.readable(),
+        &[Token::Str("a0:b1:c2:00:00:01-a0:b1:c2:00:00:06")],
+    );
+    assert_tokens(
+        &range.compact(),
+        &[Token::Bytes(&[
+            0xa0, 0xb1, 0xc2, 0x00, 0x00, 0x01, 0xa0, 0xb1, 0xc2, 0x00, 0x00, 0x06,
+        ])],
+    );
+
+    let set: MacSet = vec![
+        mac,
+        MediaAccessControlAddress::new("00:00:0c:07:ac:01").unwrap(),
+    ]
+    .into_iter()
+    .collect();
+    assert_tokens(
+        &set.clone().readable(),
+        &[
+            Token::Seq { len: Some(2) },
+            Token::Str("00:00:0c:07:ac:01"),
+            Token::Str("a0:b1:c2:d3:e4:f5"),
+            Token::SeqEnd,
+        ],
+    );
+    assert_tokens(
+        &set.compact(),
+        &[Token::Bytes(&[
+            0x00, 0x00, 0x0c, 0x07, 0xac, 0x01, 0xa0, 0xb1, 0xc2, 0xd3, 0xe4, 0xf5,
+        ])],
+    );
+
+    assert_de_tokens_error::<serde_test::Compact<MacPrefix>>(
+        &[Token::Bytes(&[0xa0, 0xb1, 0xc2, 0x00, 0x00, 0x00])],
+        "Pass in 7 bytes: six octets and a length.",
+    );
+    assert_de_tokens_error::<serde_test::Compact<MacSet>>(
+        &[Token::Bytes(&[0xa0, 0xb1, 0xc2])],
+        "Pass in six octets for each address.",
+    );
+}
+
+#[cfg(feature = "metrics")]
+#[test]
+fn test_metrics() {
+    use crate::inventory::Inventory;
+    use crate::metrics::{MeteredResolver, Metrics};
+    use crate::vendor::VendorResolver;
+    use std::collections::HashMap;
+    use std::sync::Arc;
+    use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+    let metrics = Arc::new(Metrics::new());
+    let first = MediaAccessControlAddress::new("a0:b1:c2:d3:e4:f5").unwrap();
+    let second = MediaAccessControlAddress::new("00:1b:63:84:45:e6").unwrap();
+    let now = SystemTime::now();
+
+    let mut inventory = Inventory::new();
+    inventory.observe(&first, now);
+    inventory.set_metrics(Arc::clone(&metrics));
+    assert_eq!(metrics.devices(), 1);
+
+    inventory.observe(&first, now);
+    inventory.observe(&second, now);
+    inventory.observe(&second, now);
+    assert_eq!(metrics.observations(), 3);
+    assert_eq!(metrics.new_devices(), 1);
+    assert_eq!(metrics.devices(), 2);
+
+    // A clone does not count twice.
+    let mut clone = inventory.clone();
+    clone.observe(
+        &MediaAccessControlAddress::new("02:00:00:00:00:01").unwrap(),
+        now,
+    );
+    assert_eq!(metrics.observations(), 3);
+
+    inventory.remove(&first);
+    assert_eq!(metrics.devices(), 1);
+
+    let mut vendors: HashMap<u32, String> = HashMap::new();
+    vendors.insert(0x001b63, String::from("Apple, Inc."));
+    let resolver = MeteredResolver::new(vendors, Arc::clone(&metrics));
+    assert!(resolver.resolve(&second).unwrap().is_some());
+    assert!(resolver.resolve(&first).unwrap().is_none());
+    assert_eq!(resolver.resolve_batch(&[&first, &second]).len(), 2);
+    assert_eq!(metrics.vendor_hits(), 2);
+    assert_eq!(metrics.vendor_misses(), 2);
+    assert_eq!(metrics.vendor_errors(), 0);
+
+    assert_eq!(metrics.database_age(), None);
+    let text = metrics.to_prometheus();
+    assert!(text.contains("# TYPE macaddress_observations_total counter\n"));
+    assert!(text.contains("macaddress_observations_total 3\n"));
+    assert!(text.contains("macaddress_new_devices_total 1\n"));
+    assert!(text.contains("macaddress_devices 1\n"));
+    assert!(text.contains("macaddress_vendor_lookups_total{result=\"hit\"} 2\n"));
+    assert!(text.contains("macaddress_vendor_lookups_total{result=\"error\"} 0\n"));
+    assert!(!text.contains("vendor_database_age_seconds"));
+
+    metrics.set_database_built(UNIX_EPOCH + Duration::from_secs(1_700_000_000));
+    assert!(metrics.database_age().unwrap() > 0);
+    assert!(metrics
+        .to_prometheus()
+        .contains("# TYPE macaddress_vendor_database_age_seconds gauge\n"));
+}
+
+#[test]
+fn test_format_many() {
+    use crate::format::{self, Notation};
+
+    let addresses: Vec<MediaAccessControlAddress> = ["a0:b1:c2:d3:e4:f5", "00:03:ba:12:04:05"]
+        .iter()
+        .map(|digits| MediaAccessControlAddress::new(digits).unwrap())
+        .collect();
+
+    let mut buffer = String::from("addresses: ");
+    format::format_many(&addresses, Notation::Solaris, ", ", &mut buffer);
+    assert_eq!(buffer, "addresses: a0:b1:c2:d3:e4:f5, 0:3:ba:12:4:5");
+
+    let mut bytes = Vec::new();
+    format::format_many_bytes(&addresses, Notation::Dot, b"\n", &mut bytes);
+    assert_eq!(bytes, b"a0b1.c2d3.e4f5\n0003.ba12.0405");
+
+    let mut empty = String::new();
+    format::format_many(&[], Notation::Colon, "\n", &mut empty);
+    assert!(empty.is_empty());
+}
+
+#[test]
+fn test_ethernet_segment_id() {
+    let digits = String::from("a0:b1:c2:d3:e4:f5");
+    let mac = MediaAccessControlAddress::new(&digits).unwrap();
+
+    let esi = EthernetSegmentId::type3(&mac, 0x0a0b0c).unwrap();
+    assert_eq!(esi.esi_type(), 3);
+    assert_eq!(esi.mac().unwrap().to_plain_notation(), "a0b1c2d3e4f5");
+    assert_eq!(esi.local_discriminator(), Some(0x0a0b0c));
+    assert_eq!(esi.to_string(), "03:a0:b1:c2:d3:e4:f5:0a:0b:0c");
+    assert_eq!(
+        esi.to_bytes(),
+        [0x03, 0xa0, 0xb1, 0xc2, 0xd3, 0xe4, 0xf5, 0x0a, 0x0b, 0x0c]
+    );
+    assert_eq!(EthernetSegmentId::from_bytes(&esi.to_bytes()).unwrap(), esi);
+    assert_eq!(
+        "03:A0:B1:C2:D3:E4:F5:0A:0B:0C"
+            .parse::<EthernetSegmentId>()
+            .unwrap(),
+        esi
+    );
+
+    let esi: EthernetSegmentId = "00:11:22:33:44:55:66:77:88:99".parse().unwrap();
+    assert_eq!(esi.esi_type(), 0);
+    assert!(esi.mac().is_none());
+    assert!(esi.local_discriminator().is_none());
+
+    assert!(EthernetSegmentId::type3(&mac, 0x0100_0000).is_err());
+    assert!(EthernetSegmentId::from_bytes(&[0; 9]).is_err());
+    assert!("03:a0:b1:c2:d3:e4:f5:0a:0b"
+        .parse::<EthernetSegmentId>()
+        .is_err());
+    assert!("03:a0:b1:c2:d3:e4:f5:0a:0b:0g"
+        .parse::<EthernetSegmentId>()
+        .is_err());
+    assert!("03:a0:b1:c2:d3:e4:f5:0a:0b:+1"
+        .parse::<EthernetSegmentId>()
+        .is_err());
+}
+
+#[test]
+fn test_eq_ignoring_ul_bit() {
+    let addresses = [
+        ("a0:b1:c2:d3:e4:f5", "a2:b1:c2:d3:e4:f5", true),
+        ("a0:b1:c2:d3:e4:f5", "a0:b1:c2:d3:e4:f5", true),
+        ("a0:b1:c2:d3:e4:f5", "a1:b1:c2:d3:e4:f5", false),
+        ("a0:b1:c2:d3:e4:f5", "a0:b1:c2:d3:e4:f6", false),
+    ];
+
+    for element in addresses.iter() {
+        let first = MediaAccessControlAddress::new(element.0).unwrap();
+        let second = MediaAccessControlAddress::new(element.1).unwrap();
+
+        assert_eq!(first.eq_ignoring_ul_bit(&second), element.2);
+        assert_eq!(second.eq_ignoring_ul_bit(&first), element.2);
+        assert_eq!(
+            first.ul_normalized_key() == second.ul_normalized_key(),
+            element.2
+        );
+    }
+
+    let mac = MediaAccessControlAddress::new("a2:b1:c2:d3:e4:f5").unwrap();
+    assert_eq!(
+        mac.ul_normalized_key(),
+        [0xa0, 0xb1, 0xc2, 0xd3, 0xe4, 0xf5]
+    );
+}
+
+#[test]
+fn test_hamming_distance() {
+    let addresses = [
+        ("a0:b1:c2:d3:e4:f5", "a0:b1:c2:d3:e4:f5", 0),
+        ("a0:b1:c2:d3:e4:f5", "a0:b1:c2:d3:e4:f4", 1),
+        ("a0:b1:c2:d3:e4:f5", "a0:b1:c2:d3:e4:5f", 4),
+        ("00:00:00:00:00:00", "ff:ff:ff:ff:ff:ff", 48),
+    ];
+
+    for element in addresses.iter() {
+        let first = MediaAccessControlAddress::new(element.0).unwrap();
+        let second = MediaAccessControlAddress::new(element.1).unwrap();
+
+        assert_eq!(first.hamming_distance(&second), element.2);
+        assert_eq!(second.hamming_distance(&first), element.2);
+    }
+}
+
+#[test]
+fn test_bits_range() {
+    let mac = MediaAccessControlAddress::new("a0:b1:c2:d3:e4:f5").unwrap();
+
+    let ranges = [
+        (0, 48, 0xa0b1_c2d3_e4f5),
+        (0, 24, 0xa0b1c2),
+        (24, 24, 0xd3e4f5),
+        (0, 1, 1),
+        (6, 1, 0),
+        (7, 1, 0),
+        (4, 8, 0x0b),
+        (44, 4, 0x5),
+        (47, 1, 1),
+    ];
+
+    for element in ranges.iter() {
+        assert_eq!(mac.bits_range(element.0, element.1).unwrap(), element.2);
+
+        let cleared = mac.with_bits_range(element.0, element.1, 0).unwrap();
+        assert_eq!(cleared.bits_range(element.0, element.1).unwrap(), 0);
+
+        let restored = cleared
+            .with_bits_range(element.0, element.1, element.2)
+            .unwrap();
+        assert_eq!(restored.to_octets(), mac.to_octets());
+    }
+
+    let local = mac.with_bits_range(6, 1, 1).unwrap();
+    assert_eq!(local.to_octets(), mac.as_local().to_octets());
+
+    let mac = mac.with_bits_range(24, 24, 0x000001).unwrap();
+    assert_eq!(mac.to_colon_notation(), "a0:b1:c2:00:00:01");
+
+    assert!(mac.bits_range(0, 0).is_err());
+    assert!(mac.bits_range(40, 9).is_err());
+    assert!(mac.bits_range(48, 1).is_err());
+    assert!(mac.with_bits_range(44, 4, 0x10).is_err());
+
+    let eui = Eui64::new("a0:b1:c2:d3:e4:f5:06:07").unwrap();
+    assert_eq!(eui.bits_range(0, 64).unwrap(), 0xa0b1_c2d3_e4f5_0607);
+    assert_eq!(eui.bits_range(56, 8).unwrap(), 0x07);
+
+    let eui = eui.with_bits_range(0, 64, u64::MAX).unwrap();
+    assert_eq!(eui.to_colon_notation(), "ff:ff:ff:ff:ff:ff:ff:ff");
+}
+
+#[test]
+fn test_canonicalization() {
+    let addresses = [
+        // (address, unicast, multicast, universal, local)
+        (
+            "a0:b1:c2:d3:e4:f5",
+            "a0b1c2d3e4f5",
+            "a1b1c2d3e4f5",
+            "a0b1c2d3e4f5",
+            "a2b1c2d3e4f5",
+        ),
+        (
+            "a3:b1:c2:d3:e4:f5",
+            "a2b1c2d3e4f5",
+            "a3b1c2d3e4f5",
+            "a1b1c2d3e4f5",
+            "a3b1c2d3e4f5",
+        ),
+    ];
+
+    for element in addresses.iter() {
+        let mac = MediaAccessControlAddress::new(element.0).unwrap();
+
+        assert_eq!(mac.as_unicast().to_plain_notation(), element.1);
+        assert_eq!(mac.as_multicast().to_plain_notation(), element.2);
+        assert_eq!(mac.as_universal().to_plain_notation(), element.3);
+        assert_eq!(mac.as_local().to_plain_notation(), element.4);
+    }
+
+    let mac = MediaAccessControlAddress::new("a1:b1:c2:d3:e4:f5").unwrap();
+    assert!(mac.as_unicast().as_local().is_laa());
+    assert!(mac.as_unicast().as_universal().is_uaa());
+    assert!(mac.as_unicast().is_unicast());
+    assert!(mac.as_multicast().is_multicast());
+}
+
+#[test]
+fn test_successors_in_oui() {
+    let addresses = [
+        ("a0:b1:c2:d3:e4:f5", Some("a0b1c2d3e4f6")),
+        ("a0:b1:c2:d3:e4:ff", Some("a0b1c2d3e500")),
+        ("a0:b1:c2:00:ff:ff", Some("a0b1c2010000")),
+        ("a0:b1:c2:ff:ff:ff", None),
+    ];
+
+    for element in addresses.iter() {
+        let mac = MediaAccessControlAddress::new(element.0).unwrap();
+        let next = mac.next_in_oui().map(|next| next.to_plain_notation());
+
+        assert_eq!(next.as_deref(), element.1);
+    }
+
+    let mac = MediaAccessControlAddress::new("a0:b1:c2:ff:ff:fc").unwrap();
+    let successors: Vec<String> = mac
+        .successors_in_oui()
+        .map(|next| next.to_plain_notation())
+        .collect();
+
+    assert_eq!(successors, ["a0b1c2fffffd", "a0b1c2fffffe", "a0b1c2ffffff"]);
+}
+
+#[test]
+fn test_parse_errors() {
+    let addresses = [
+        ("", None, None, None, "Pass in 12 hexadecimal digits; the string is empty."),
+        (
+            "0a",
+            None,
+            None,
+            Some(Notation::Plain),
+            "Looks like plain notation, but it has 2 digits instead of 12.",
+        ),
+        (
+            "0a1b2c3d4e5f6",
+            Some(12),
+            Some('6'),
+            Some(Notation::Plain),
+            "Looks like plain notation, but it has 13 digits instead of 12 (found '6' at byte 12).",
+        ),
+        (
+            "0a1b2c3d4e5g",
+            Some(11),
+            Some('g'),
+            Some(Notation::Plain),
+            "Looks like plain notation, but it contains a character that is neither a hexadecimal digit nor a separator (found 'g' at byte 11).",
+        ),
+        (
+            "-0a-1b-2c-3d-4e-5f",
+            Some(0),
+            Some('-'),
+            Some(Notation::Hyphen),
+            "Looks like hyphen notation, but group 1 is empty (found '-' at byte 0).",
+        ),
+        (
+            "0a-1b-2c-3d-4e-5f-",
+            Some(17),
+            Some('-'),
+            Some(Notation::Hyphen),
+            "Looks like hyphen notation, but it has more than 6 groups (found '-' at byte 17).",
+        ),
+        (
+            "0a-1b-2c-3d-4e5f",
+            Some(14),
+            Some('5'),
+            Some(Notation::Hyphen),
+            "Looks like hyphen notation, but group 5 has 4 digits (found '5' at byte 14).",
+        ),
+        (
+            "0a:1b:2c:3d4:4e:5f",
+            Some(11),
+            Some('4'),
+            Some(Notation::Colon),
+            "Looks like colon notation, but group 4 has 3 digits (found '4' at byte 11).",
+        ),
+        (
+            "0a-1b-2c:3d-4e-5f",
+            Some(8),
+            Some(':'),
+            Some(Notation::Hyphen),
+            "Looks like hyphen notation, but it mixes separators (found ':' at byte 8).",
+        ),
+        (
+            "0a1b.2c3d.4e5",
+            None,
+            None,
+            Some(Notation::Dot),
+            "Looks like dot notation, but group 3 has 3 digits.",
+        ),
+        (
+            "0a1b.2c.3d4e5f",
+            Some(7),
+            Some('.'),
+            Some(Notation::Dot),
+            "Looks like dot notation, but group 2 has 2 digits (found '.' at byte 7).",
+        ),
+        (
+            "0A 1B 2C 3D 4E",
+            None,
+            None,
+            Some(Notation::Space),
+            "Looks like space notation, but it has 5 groups instead of 6.",
+        ),
+    ];
+
+    for element in addresses.iter() {
+        let error = MediaAccessControlAddress::new_with_diagnostics(element.0).unwrap_err();
+        assert_eq!(diagnose(element.0).unwrap_err(), error);
+        assert_eq!(
+            MediaAccessControlAddress::new(element.0).unwrap_err(),
+            error.kind()
+        );
+
+        assert_eq!(error.position(), element.1);
+        assert_eq!(error.character(), element.2);
+        assert_eq!(error.notation(), element.3);
+        assert_eq!(error.to_string(), element.4);
+    }
+}
+
+#[test]
+fn test_parse_error_kind() {
+    let cases = [
+        ("", MacParseError::InvalidLength),
+        ("a0b1c2d3e4", MacParseError::InvalidLength),
+        ("a0:b1:c2:d3:e4", MacParseError::InvalidLength),
+        ("a0:b1:c2:d3:e4:f5:06", MacParseError::InvalidLength),
+        ("0a:1b:2c:3d4:4e:5f", MacParseError::InvalidLength),
+        (
+            "a0:b1:c2:d3:e4:g5",
+            MacParseError::InvalidCharacter { index: 15 },
+        ),
+        ("a0::b1:c2:d3:e4f5", MacParseError::InvalidSeparator),
+        ("a0b-1c2-d3e-4f5", MacParseError::InvalidSeparator),
+        ("a0-b1:c2-d3:e4-f5", MacParseError::MixedNotation),
+        ("a0b1c2d3e4f5a6", MacParseError::InvalidLength),
+        ("a0:b1:c2:d3:e4:", MacParseError::InvalidSeparator),
+        ("a0 b1 c2 d3 e4 f5 ", MacParseError::InvalidLength),
+        ("a0b1.c2d3.e4f", MacParseError::InvalidLength),
+        ("a0b1-c2d3-e4f5-a6b7", MacParseError::InvalidLength),
+        (
+            "a0:b1:c2:d3:e4:f5\n",
+            MacParseError::InvalidCharacter { index: 17 },
+        ),
+    ];
+
+    // Every way of parsing a string fails with the same kind, and
+    // the diagnosis agrees with it.
+    for (digits, kind) in cases.iter() {
+        assert_eq!(
+            MediaAccessControlAddress::new(digits),
+            Err(*kind),
+            "{:?}",
+            digits
+        );
+        assert_eq!(digits.parse::<MediaAccessControlAddress>(), Err(*kind));
+        assert_eq!(MediaAccessControlAddress::try_from(*digits), Err(*kind));
+        assert_eq!(
+            MediaAccessControlAddress::try_from(digits.to_string()),
+            Err(*kind)
+        );
+        assert_eq!(validate(digits), Err(*kind));
+        assert_eq!(diagnose(digits).unwrap_err().kind(), *kind);
+        assert_eq!(
+            MediaAccessControlAddress::new_with_diagnostics(digits)
+                .unwrap_err()
+                .kind(),
+            *kind
+        );
+    }
+
+    match MediaAccessControlAddress::new("a0:b1:c2:d3:e4:g5") {
+        Err(MacParseError::InvalidCharacter { index }) => assert_eq!(index, 15),
+        other => panic!("{:?}", other),
+    }
+
+    assert_eq!(
+        MediaAccessControlAddress::from_notation("a0:b1:c2:d3:e4:f5", Notation::Dot),
+        Err(MacParseError::InvalidSeparator)
+    );
+
+    let cases = [
+        ("", MacParseError::InvalidLength),
+        ("a0-b1-c2-d3-e4-f5", MacParseError::InvalidLength),
+        (
+            "a0-b1-c2-ff-fe-d3-e4-z5",
+            MacParseError::InvalidCharacter { index: 21 },
+        ),
+        ("a0-b1-c2-ff:fe-d3-e4-f5", MacParseError::MixedNotation),
+        ("a0-b1-c2-ff--fe-d3-e4", MacParseError::InvalidSeparator),
+    ];
+
+    for (digits, kind) in cases.iter() {
+        assert_eq!(Eui64::new(digits), Err(*kind), "{:?}", digits);
+        assert_eq!(digits.parse::<Eui64>(), Err(*kind));
+        assert_eq!(diagnose_eui64(digits).unwrap_err().kind(), *kind);
+    }
+
+    assert_eq!(
+        MacParseError::InvalidCharacter { index: 15 }.to_string(),
+        "invalid character at byte 15"
+    );
+    assert_eq!(MacParseError::MixedNotation.to_string(), "mixed notation");
+}
+
+#[test]
+fn test_validate() {
+    let addresses = [
+        ("a0b1c2d3e4f5", Notation::Plain),
+        ("A0-B1-C2-D3-E4-F5", Notation::Hyphen),
+        ("a0:b1:c2:d3:e4:f5", Notation::Colon),
+        ("00:03:ba:12:04:05", Notation::Colon),
+        ("0:3:ba:12:4:5", Notation::Solaris),
+        ("a0b1.c2d3.e4f5", Notation::Dot),
+        ("A0 B1 C2 D3 E4 F5", Notation::Space),
+    ];
+
+    for element in addresses.iter() {
+        assert_eq!(validate(element.0).unwrap(), element.1);
+    }
+
+    assert_eq!(
+        validate("a0:b1:c2:d3:e4"),
+        Err(MacParseError::InvalidLength)
+    );
+
+    let error = diagnose("a0:b1:c2:d3:e4").unwrap_err();
+    assert_eq!(error.notation(), Some(Notation::Colon));
+
+    for element in addresses.iter() {
+        assert_eq!(diagnose(element.0), Ok(element.1));
+    }
+
+    let invalid = [
+        "",
+        "a0b1c2d3e4f",
+        "a0b1c2d3e4f5a",
+        "a0:b1:c2:d3:e4:f5:",
+        ":a0:b1:c2:d3:e4:f5",
+        "a0::b1:c2:d3:e4:f5",
+        "a0:b1:c2:d3:e4:f5:06",
+        "a0-b1:c2-d3:e4-f5",
+        "a0b1-c2d3.e4f5",
+        "a0 b1 c2 d3 e4  f5",
+        "a0:b1:c2:d3:e4:g5",
+        "a0:b1:c2:d3:e4:f\u{e9}",
+        "a0b1c.2d3.e4f5",
+        "000:3:ba:12:4:5",
+    ];
+
+    for digits in invalid.iter() {
+        assert!(validate(digits).is_err(), "{:?}", digits);
+    }
+
+    let mac = MediaAccessControlAddress::new("A:B1:c:D3:0:f").unwrap();
+    assert_eq!(mac.to_octets(), [0x0a, 0xb1, 0x0c, 0xd3, 0x00, 0x0f]);
+    assert_eq!(crate::utils::clean("A:B1:c:D3:0:f"), "0ab10cd3000f");
+}
+
+#[test]
+fn test_notations() {
+    let octets = [0x00, 0x03, 0xba, 0x12, 0x04, 0x05];
+    let addresses = [
+        ("0003ba120405", Notation::Plain),
+        ("00-03-ba-12-04-05", Notation::Hyphen),
+        ("00:03:ba:12:04:05", Notation::Colon),
+        ("0003.ba12.0405", Notation::Dot),
+        ("0003-ba12-0405", Notation::FourDigitHyphen),
+        ("00 03 BA 12 04 05", Notation::Space),
+        ("0:3:ba:12:4:5", Notation::Solaris),
+    ];
+
+    for element in addresses.iter() {
+        assert_eq!(format::format(&octets, element.1), element.0);
+        assert_eq!(format::parse(element.0, element.1).unwrap(), octets);
+
+        let mac = MediaAccessControlAddress::from_notation(element.0, element.1).unwrap();
+        assert_eq!(mac.to_notation(element.1), element.0);
+    }
+
+    // Solaris notation merely allows omitting leading zeros.
+    assert_eq!(
+        format::parse("00:03:ba:12:04:05", Notation::Solaris).unwrap(),
+        octets
+    );
+
+    let error = format::parse("00:03:ba:12:04:05", Notation::Hyphen).unwrap_err();
+    assert_eq!(error.notation(), Some(Notation::Colon));
+    assert_eq!(
+        error.to_string(),
+        "Looks like colon notation, but it is not in hyphen notation."
+    );
+
+    let error = format::parse("00:03:ba", Notation::Colon).unwrap_err();
+    assert_eq!(error.reason(), "it has 3 groups instead of 6");
+    assert_eq!(
+        MediaAccessControlAddress::from_notation("00:03:ba", Notation::Colon),
+        Err(error.kind())
+    );
+
+    let mac = MediaAccessControlAddress::new("A0B1-C2D3-E4F5").unwrap();
+    assert_eq!(mac.to_plain_notation(), "a0b1c2d3e4f5");
+    assert_eq!(mac.to_four_digit_hyphen_notation(), "a0b1-c2d3-e4f5");
+
+    let error = MediaAccessControlAddress::new_with_diagnostics("a0b1-c2d3-e4f").unwrap_err();
+    assert_eq!(error.notation(), Some(Notation::FourDigitHyphen));
+    assert_eq!(error.reason(), "group 3 has 3 digits");
+}
+
+#[test]
+fn test_universal_and_local_bits() {
+    let addresses = [
+        // (address, universal, local, uaa, laa)
+        ("a0:b1:c2:d3:e4:f5", true, false, true, false),
+        ("a2:b1:c2:d3:e4:f5", false, true, false, true),
+        ("01:80:c2:00:00:00", true, false, false, false),
+        ("03:00:00:00:00:01", false, true, false, false),
+        ("ff:ff:ff:ff:ff:ff", false, true, false, false),
+    ];
+
+    for element in addresses.iter() {
+        let mac = MediaAccessControlAddress::new(element.0).unwrap();
+
+        assert_eq!(mac.is_universal(), element.1);
+        assert_eq!(mac.is_local(), element.2);
+        assert_eq!(mac.is_uaa(), element.3);
+        assert_eq!(mac.is_laa(), element.4);
+    }
+}
+
+#[test]
+fn test_eui64() {
+    let addresses = [
+        "a0b1c2fffed3e4f5",
+        "A0-B1-C2-FF-FE-D3-E4-F5",
+        "a0:b1:c2:ff:fe:d3:e4:f5",
+        "a0b1.c2ff.fed3.e4f5",
+        "a0b1-c2ff-fed3-e4f5",
+        "A0 B1 C2 FF FE D3 E4 F5",
+    ];
+
+    for digits in addresses.iter() {
+        let eui = Eui64::new(digits).unwrap();
+
+        assert_eq!(eui.to_plain_notation(), "a0b1c2fffed3e4f5");
+        assert_eq!(eui.to_hyphen_notation(), "a0-b1-c2-ff-fe-d3-e4-f5");
+        assert_eq!(eui.to_colon_notation(), "a0:b1:c2:ff:fe:d3:e4:f5");
+        assert_eq!(eui.to_dot_notation(), "a0b1.c2ff.fed3.e4f5");
+        assert_eq!(eui.to_snmp_notation(), "A0 B1 C2 FF FE D3 E4 F5");
+        assert_eq!(
+            eui.to_octets(),
+            [0xa0, 0xb1, 0xc2, 0xff, 0xfe, 0xd3, 0xe4, 0xf5]
+        );
+        assert_eq!(eui.to_decimal_representation(), 0xa0b1c2fffed3e4f5);
+        let (first, second) = eui.to_fragments();
+        assert_eq!((first.as_str(), second.as_str()), ("a0b1c2", "fffed3e4f5"));
+        assert!(eui.is_uaa());
+        assert!(eui.has_oui());
+        assert!(!eui.is_broadcast());
+    }
+
+    let eui = Eui64::new("2:0:5e:10:0:0:0:1").unwrap();
+    assert_eq!(eui.to_solaris_notation(), "2:0:5e:10:0:0:0:1");
+    assert!(eui.is_local());
+    assert!(eui.eq_ignoring_ul_bit(&eui.as_universal()));
+
+    let eui = Eui64::from_octets(&[0xff; 8]).unwrap();
+    assert!(eui.is_broadcast());
+    assert_eq!(Eui64::from([0xff; 8]), eui);
+    assert!(Eui64::from_octets(&[0xff; 6]).is_err());
+
+    let error = Eui64::new_with_diagnostics("a0b1c2d3e4f5").unwrap_err();
+    assert_eq!(
+        error.to_string(),
+        "Looks like plain notation, but it has 12 digits instead of 16."
+    );
+
+    let error = Eui64::new_with_diagnostics("a0:b1:c2:d3:e4:f5").unwrap_err();
+    assert_eq!(error.reason(), "it has 6 groups instead of 8");
+    assert_eq!(Eui64::new("a0:b1:c2:d3:e4:f5"), Err(error.kind()));
+
+    let error = Eui64::new_with_diagnostics("").unwrap_err();
+    assert_eq!(
+        error.to_string(),
+        "Pass in 16 hexadecimal digits; the string is empty."
+    );
+}
+
+#[test]
+fn test_classifications() {
+    let kinds = [
+        ("unique", Kind::Unique),
+        ("local", Kind::Local),
+        ("unknown", Kind::Unknown),
+    ];
+
+    for element in kinds.iter() {
+        assert_eq!(element.1.to_string(), element.0);
+        assert_eq!(element.0.parse::<Kind>().unwrap(), element.1);
+        assert_eq!(element.0.to_uppercase().parse::<Kind>().unwrap(), element.1);
+    }
+
+    let casts = [
+        ("unicast", Cast::Unicast, "a0:b1:c2:d3:e4:f5"),
+        ("multicast", Cast::Multicast, "01:00:5e:00:00:01"),
+        ("broadcast", Cast::Broadcast, "ff:ff:ff:ff:ff:ff"),
+    ];
+
+    for element in casts.iter() {
+        let mac = MediaAccessControlAddress::new(element.2).unwrap();
+
+        assert_eq!(mac.cast(), element.1);
+        assert_eq!(element.1.to_string(), element.0);
+        assert_eq!(element.0.parse::<Cast>().unwrap(), element.1);
+    }
+
+    for notation in Notation::ALL.iter() {
+        assert_eq!(notation.to_string().parse::<Notation>().unwrap(), *notation);
+    }
+
+    assert_eq!(Notation::FourDigitHyphen.to_string(), "four-digit-hyphen");
+    assert_eq!("Solaris".parse::<Notation>().unwrap(), Notation::Solaris);
+    assert_eq!(format!("{:>8}|", Kind::Local), "   local|");
+
+    assert!("eui".parse::<Kind>().is_err());
+    assert!("anycast".parse::<Cast>().is_err());
+    assert!("dash".parse::<Notation>().is_err());
+}
+
+#[test]
+fn test_data_link_address() {
+    let mac = MediaAccessControlAddress::new("a0b1c2d3e4f5").unwrap();
+    let eui = Eui64::new("a0b1c2fffed3e4f5").unwrap();
+    let guid = Eui64::new("0002c90300a1b2c3").unwrap();
+    let bluetooth = MediaAccessControlAddress::new("00:1a:7d:da:71:13").unwrap();
+    let multicast = MediaAccessControlAddress::new("01:00:5e:00:00:01").unwrap();
+
+    let addresses = [
+        // (address, display, bytes, kind, cast)
+        (
+            DataLinkAddress::from(mac),
+            "a0:b1:c2:d3:e4:f5",
+            vec![0xa0, 0xb1, 0xc2, 0xd3, 0xe4, 0xf5],
+            Kind::Unique,
+            Cast::Unicast,
+        ),
+        (
+            DataLinkAddress::from(eui),
+            "a0:b1:c2:ff:fe:d3:e4:f5",
+            vec![0xa0, 0xb1, 0xc2, 0xff, 0xfe, 0xd3, 0xe4, 0xf5],
+            Kind::Unique,
+            Cast::Unicast,
+        ),
+        (
+            DataLinkAddress::InfinibandGuid(guid),
+            "0002:c903:00a1:b2c3",
+            vec![0x00, 0x02, 0xc9, 0x03, 0x00, 0xa1, 0xb2, 0xc3],
+            Kind::Unique,
+            Cast::Unicast,
+        ),
+        (
+            DataLinkAddress::Bluetooth(bluetooth),
+            "00:1A:7D:DA:71:13",
+            vec![0x00, 0x1a, 0x7d, 0xda, 0x71, 0x13],
+            Kind::Unique,
+            Cast::Unicast,
+        ),
+        (
+            DataLinkAddress::Mac48(multicast),
+            "01:00:5e:00:00:01",
+            vec![0x01, 0x00, 0x5e, 0x00, 0x00, 0x01],
+            Kind::Unknown,
+            Cast::Multicast,
+        ),
+    ];
+
+    for element in addresses.iter() {
+        assert_eq!(element.0.to_string(), element.1);
+        assert_eq!(element.0.to_bytes(), element.2);
+        assert_eq!(element.0.kind(), element.3);
+        assert_eq!(element.0.cast(), element.4);
+    }
+}
+
+#[test]
+fn test_frame_addresses() {
+    let bytes = [
+        0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xa0, 0xb1, 0xc2, 0xd3, 0xe4, 0xf5, 0x08, 0x06,
+    ];
+
+    let (destination, source) = frame::addresses(&bytes).unwrap();
+    assert!(destination.is_broadcast());
+    assert_eq!(source.to_plain_notation(), "a0b1c2d3e4f5");
+
+    assert!(frame::addresses(&bytes[..13]).is_err());
+}
+
+#[test]
+fn test_inventory() {
+    use super::inventory::Inventory;
+    use std::time::{Duration, SystemTime};
+
+    let start = SystemTime::UNIX_EPOCH + Duration::from_secs(1_700_000_000);
+    let minutes = |count: u64| start + Duration::from_secs(count * 60);
+    let first = MediaAccessControlAddress::new("a0:b1:c2:d3:e4:f5").unwrap();
+    let second = MediaAccessControlAddress::new("00:00:0c:07:ac:01").unwrap();
+
+    let mut inventory = Inventory::new();
+    assert!(inventory.is_empty());
+
+    assert_eq!(inventory.observe(&first, minutes(5)).observations(), 1);
+    inventory.observe(&first, minutes(10));
+    inventory.observe(&first, minutes(1)); // Out of order
+    inventory.observe(&second, minutes(20));
+
+    let device = inventory.get(&first).unwrap();
+    assert_eq!(device.address().to_octets(), first.to_octets());
+    assert_eq!(device.first_seen(), minutes(1));
+    assert_eq!(device.last_seen(), minutes(10));
+    assert_eq!(device.observations(), 3);
+    assert_eq!(inventory.len(), 2);
+
+    assert_eq!(inventory.tag(&first, "owner", "lab").unwrap(), None);
+    assert_eq!(
+        inventory.tag(&first, "owner", "ops").unwrap().as_deref(),
+        Some("lab")
+    );
+    assert_eq!(inventory.get(&first).unwrap().tag("owner"), Some("ops"));
+    assert_eq!(inventory.untag(&first, "owner").as_deref(), Some("ops"));
+    assert!(inventory.get(&first).unwrap().tags().is_empty());
+
+    let unseen = MediaAccessControlAddress::new("0a:1b:2c:3d:4e:5f").unwrap();
+    assert!(inventory.tag(&unseen, "owner", "lab").is_err());
+    assert!(inventory.get(&unseen).is_none());
+
+    let addresses: Vec<String> = inventory
+        .iter()
+        .map(|device| device.address().to_colon_notation())
+        .collect();
+    assert_eq!(addresses, vec!["00:00:0c:07:ac:01", "a0:b1:c2:d3:e4:f5"]);
+
+    let recent: Vec<String> = inventory
+        .seen_between(minutes(15), minutes(30))
+        .map(|device| device.address().to_colon_notation())
+        .collect();
+    assert_eq!(recent, vec!["00:00:0c:07:ac:01"]);
+    assert_eq!(inventory.seen_between(minutes(10), minutes(20)).count(), 1);
+
+    assert!(inventory.remove(&second).is_some());
+    assert_eq!(inventory.len(), 1);
+
+    let mut watched = Inventory::new();
+    watched.observe(&first, minutes(1));
+    let sightings = watched.watch_channel(Some(Duration::from_secs(600)));
+    let news = std::sync::Arc::new(std::sync::Mutex::new(Vec::new()));
+    let seen = news.clone();
+    watched.watch(None, move |sighting| {
+        seen.lock()
+            .unwrap()
+            .push(sighting.address.to_colon_notation())
+    });
+
+    watched.observe(&first, minutes(10)); // Too soon to count as a return
+    watched.observe(&first, minutes(2)); // Out of order
+    watched.observe(&first, minutes(25));
+    watched.observe(&unseen, minutes(25));
+
+    let sightings: Vec<_> = sightings.try_iter().collect();
+    assert_eq!(sightings.len(), 2);
+    assert_eq!(sightings[0].address.to_octets(), first.to_octets());
+    assert_eq!(sightings[0].at, minutes(25));
+    assert_eq!(sightings[0].last_seen, Some(minutes(10)));
+    assert_eq!(sightings[1].last_seen, None);
+    assert_eq!(*news.lock().unwrap(), vec!["0a:1b:2c:3d:4e:5f"]);
+    assert!(watched.clone() == watched);
+
+    let mut vendors = HashMap::new();
+    vendors.insert(0x00000c, String::from("Cisco Systems"));
+    vendors.insert(0xa0b1c2, String::from("Example"));
+
+    let mut before = Inventory::new();
+    before.observe(&first, minutes(1));
+    before.observe(&second, minutes(1));
+    assert_eq!(before.tag_vendors(&vendors).unwrap(), 2);
+
+    let mut after = Inventory::new();
+    after.observe(&second, minutes(60));
+    after.observe(&unseen, minutes(60));
+    assert_eq!(after.tag_vendors(&vendors).unwrap(), 1);
+    assert!(before.diff(&after).changed_vendor.is_empty());
+
+    vendors.insert(0x00000c, String::from("Cisco"));
+    after.tag_vendors(&vendors).unwrap();
+    assert_eq!(after.get(&second).unwrap().vendor(), Some("Cisco"));
+
+    let diff = before.diff(&after);
+    assert!(diff.appeared.contains(&unseen));
+    assert!(diff.disappeared.contains(&first));
+    assert!(diff.changed_vendor.contains(&second));
+    assert_eq!(
+        (
+            diff.appeared.len(),
+            diff.disappeared.len(),
+            diff.changed_vendor.len()
+        ),
+        (1, 1, 1)
+    );
+    assert!(after.diff(&after).is_empty());
+
+    #[cfg(feature = "pcap")]
+    {
+        use super::capture::{Frame, LINKTYPE_ETHERNET};
+
+        let data = vec![
+            0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0x00, 0x00, 0x0c, 0x07, 0xac, 0x01, 0x08, 0x06,
+        ];
+        let frame = Frame {
+            link_type: LINKTYPE_ETHERNET,
+            length: data.len(),
+            data,
+        };
+
+        inventory.observe_frame(&frame, minutes(30));
+        assert_eq!(inventory.get(&second).unwrap().last_seen(), minutes(30));
+        assert!(inventory
+            .get(&MediaAccessControlAddress::new("ff:ff:ff:ff:ff:ff").unwrap())
+            .is_none());
+
+        inventory.observe_frame(
+            &Frame {
+                link_type: 0,
+                ..frame
+            },
+            minutes(40),
+        );
+        assert_eq!(inventory.get(&second).unwrap().observations(), 1);
+    }
+
+    #[cfg(feature = "system")]
+    {
+        use super::system::parse_ip_neigh;
+
+        let neighbors = parse_ip_neigh(
+            "192.168.1.1 dev eth0 lladdr a0:b1:c2:d3:e4:f5 REACHABLE
+fe80::1 dev eth1 lladdr a0:b1:c2:d3:e4:f5 STALE
+",
+        )
+        .unwrap();
+
+        inventory.observe_neighbors(&neighbors, minutes(50));
+        let device = inventory.get(&first).unwrap();
+        assert_eq!(device.observations(), 5);
+        assert_eq!(device.tag("ip"), Some("fe80::1"));
+        assert_eq!(device.tag("interface"), Some("eth1"));
+
+        // Older entries do not overwrite newer tags.
+        inventory.observe_neighbors(&neighbors[..1], minutes(45));
+        assert_eq!(inventory.get(&first).unwrap().tag("ip"), Some("fe80::1"));
+    }
+}
+
+#[test]
+fn test_persistent_inventory() {
+    use super::error::Error;
+    use super::inventory::{Device, InventoryStore, PersistentInventory};
+    use std::time::{Duration, SystemTime};
+
+    #[derive(Default)]
+    struct VecStore {
+        devices: Vec<Device>,
+        writes: usize,
+    }
+
+    impl InventoryStore for VecStore {
+        fn upsert(&mut self, device: &Device) -> Result<(), Error> {
+            self.remove(&device.address())?;
+            self.devices.push(device.clone());
+            self.writes += 1;
+            Ok(())
+        }
+
+        fn remove(&mut self, address: &MediaAccessControlAddress) -> Result<(), Error> {
+            let octets = address.to_octets();
+            self.devices
+                .retain(|device| device.address().to_octets() != octets);
+            Ok(())
+        }
+
+        fn load(&self) -> Result<Vec<Device>, Error> {
+            Ok(self.devices.clone())
+        }
+
+        fn seen_between(&self, start: SystemTime, end: SystemTime) -> Result<Vec<Device>, Error> {
+            Ok(self
+                .devices
+                .iter()
+                .filter(|device| start <= device.last_seen() && device.last_seen() < end)
+                .cloned()
+                .collect())
+        }
+    }
+
+    let start = SystemTime::UNIX_EPOCH + Duration::from_secs(1_700_000_000);
+    let minutes = |count: u64| start + Duration::from_secs(count * 60);
+    let first = MediaAccessControlAddress::new("a0:b1:c2:d3:e4:f5").unwrap();
+    let second = MediaAccessControlAddress::new("00:00:0c:07:ac:01").unwrap();
+
+    let mut inventory = PersistentInventory::open(VecStore::default()).unwrap();
+    inventory.observe(&first, minutes(5)).unwrap();
+    inventory.observe(&first, minutes(10)).unwrap();
+    inventory.observe(&second, minutes(20)).unwrap();
+    inventory.tag(&first, "owner", "lab").unwrap();
+    assert!(inventory
+        .tag(
+            &MediaAccessControlAddress::new("0a:1b:2c:3d:4e:5f").unwrap(),
+            "owner",
+            "lab"
+        )
+        .is_err());
+    assert_eq!(inventory.store().writes, 4);
+
+    // Reopening the store restores every device.
+    let store = VecStore {
+        devices: inventory.store().devices.clone(),
+        writes: 0,
+    };
+    let mut reopened = PersistentInventory::open(store).unwrap();
+    assert_eq!(reopened.inventory(), inventory.inventory());
+
+    let device = reopened.inventory().get(&first).unwrap();
+    assert_eq!(device.first_seen(), minutes(5));
+    assert_eq!(device.observations(), 2);
+    assert_eq!(device.tag("owner"), Some("lab"));
+
+    let recent = reopened.seen_between(minutes(15), minutes(30)).unwrap();
+    assert_eq!(recent.len(), 1);
+    assert_eq!(recent[0].address().to_octets(), second.to_octets());
+
+    assert_eq!(
+        reopened.untag(&first, "owner").unwrap().as_deref(),
+        Some("lab")
+    );
+    assert!(reopened
+        .store()
+        .devices
+        .iter()
+        .all(|device| device.tags().is_empty()));
+    assert!(reopened.remove(&second).unwrap().is_some());
+    assert_eq!(reopened.store().devices.len(), 1);
+
+    assert!(Device::from_parts(&first, minutes(2), minutes(1), 1, Default::default()).is_err());
+
+    #[cfg(feature = "sqlite")]
+    {
+        use super::inventory::SqliteStore;
+
+        let path = std::env::temp_dir().join(format!("macaddress-{}.sqlite", std::process::id()));
+        let _ = std::fs::remove_file(&path);
+
+        let mut inventory = PersistentInventory::open(SqliteStore::open(&path).unwrap()).unwrap();
+        inventory.observe(&first, minutes(5)).unwrap();
+        inventory.observe(&first, minutes(10)).unwrap();
+        inventory.observe(&second, minutes(20)).unwrap();
+        inventory.tag(&first, "owner", "lab").unwrap();
+        inventory.tag(&first, "ip", "192.168.1.10").unwrap();
+        inventory.untag(&first, "ip").unwrap();
+        let before = inventory.inventory().clone();
+        drop(inventory);
+
+        let mut reopened = PersistentInventory::open(SqliteStore::open(&path).unwrap()).unwrap();
+        assert_eq!(reopened.inventory(), &before);
+
+        let recent = reopened.seen_between(minutes(10), minutes(20)).unwrap();
+        assert_eq!(recent.len(), 1);
+        assert_eq!(recent[0].address().to_octets(), first.to_octets());
+        assert_eq!(recent[0].tag("owner"), Some("lab"));
+
+        reopened.remove(&first).unwrap();
+        assert_eq!(reopened.store().load().unwrap().len(), 1);
+
+        let store = SqliteStore::open_in_memory().unwrap();
+        assert!(store.load().unwrap().is_empty());
+
+        let _ = std::fs::remove_file(&path);
+    }
+}
+
+#[cfg(feature = "pcap")]
+#[test]
+fn test_capture() {
+    use super::capture::{self, Capture, LINKTYPE_ETHERNET};
+
+    let first = [
+        0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xa0, 0xb1, 0xc2, 0xd3, 0xe4, 0xf5, 0x08, 0x06,
+    ];
+    let second = [
+        0xa0, 0xb1, 0xc2, 0xd3, 0xe4, 0xf5, 0x00, 0x00, 0x0c, 0x07, 0xac, 0x01, 0x08, 0x00, 0x45,
+    ];
+
+    // A little-endian classic pcap file.
+    let mut pcap = vec![0xd4, 0xc3, 0xb2, 0xa1, 2, 0, 4, 0];
+    pcap.extend_from_slice(&[0; 8]);
+    pcap.extend_from_slice(&65535u32.to_le_bytes());
+    pcap.extend_from_slice(&1u32.to_le_bytes());
+
+    for frame in [&first[..], &second[..], &first[..6]].iter() {
+        pcap.extend_from_slice(&[0; 8]);
+        pcap.extend_from_slice(&(frame.len() as u32).to_le_bytes());
+        pcap.extend_from_slice(&(frame.len() as u32).to_le_bytes());
+        pcap.extend_from_slice(frame);
+    }
+
+    // A big-endian pcapng file with an Ethernet interface and a
+    // loopback interface.
+    let block = |block_type: u32, body: &[u8]| {
+        let mut body = body.to_vec();
+        body.resize((body.len() + 3) & !3, 0);
+
+        let length = (body.len() + 12) as u32;
+        let mut block = block_type.to_be_bytes().to_vec();
+        block.extend_from_slice(&length.to_be_bytes());
+        block.extend_from_slice(&body);
+        block.extend_from_slice(&length.to_be_bytes());
+        block
+    };
+    let packet = |interface: u32, frame: &[u8]| {
+        let mut body = interface.to_be_bytes().to_vec();
+        body.extend_from_slice(&[0; 8]);
+        body.extend_from_slice(&(frame.len() as u32).to_be_bytes());
+        body.extend_from_slice(&(frame.len() as u32).to_be_bytes());
+        body.extend_from_slice(frame);
+        block(6, &body)
+    };
+
+    let mut pcapng = block(
+        0x0a0d0d0a,
+        &[
+            0x1a, 0x2b, 0x3c, 0x4d, 0, 1, 0, 0, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff,
+        ],
+    );
+    pcapng.extend(block(1, &[0, 1, 0, 0, 0, 0, 0xff, 0xff]));
+    pcapng.extend(block(1, &[0, 0, 0, 0, 0, 0, 0xff, 0xff]));
+    pcapng.extend(packet(0, &first));
+    pcapng.extend(block(5, &[0; 4]));
+    pcapng.extend(packet(1, &second));
+    pcapng.extend(packet(0, &second));
+
+    let frames: Vec<_> = Capture::new(&pcapng[..])
+        .unwrap()
+        .collect::<Result<_, _>>()
+        .unwrap();
+    assert_eq!(frames.len(), 3);
+    assert_eq!(frames[0].link_type, LINKTYPE_ETHERNET);
+    assert_eq!(frames[0].data, first.to_vec());
+    assert_eq!(frames[0].length, first.len());
+    assert_eq!(frames[1].link_type, 0);
+
+    for file in [&pcap, &pcapng].iter() {
+        let observations: Vec<_> = capture::observe(&file[..])
+            .unwrap()
+            .iter()
+            .map(|observation| {
+                (
+                    observation.address.to_plain_notation(),
+                    observation.source_frames,
+                    observation.source_bytes,
+                    observation.destination_frames,
+                    observation.destination_bytes,
+                )
+            })
+            .collect();
+
+        assert_eq!(
+            observations,
+            vec![
+                (String::from("00000c07ac01"), 1, 15, 0, 0),
+                (String::from("a0b1c2d3e4f5"), 1, 14, 1, 15),
+                (String::from("ffffffffffff"), 0, 0, 1, 14),
+            ]
+        );
+    }
+
+    assert!(matches!(
+        capture::observe(&b"not a capture"[..]),
+        Err(Error::Invalid(_))
+    ));
+    assert!(matches!(
+        capture::observe(&pcap[..pcap.len() - 1]),
+        Err(Error::Invalid(_))
+    ));
+    assert!(Capture::new(&pcapng[..]).unwrap().nth(5).is_none());
+
+    // A second, little-endian section, which starts over with its
+    // own interfaces.
+    let little_block = |block_type: u32, body: &[u8]| {
+        let length = (body.len() + 12) as u32;
+        let mut block = block_type.to_le_bytes().to_vec();
+        block.extend_from_slice(&length.to_le_bytes());
+        block.extend_from_slice(body);
+        block.extend_from_slice(&length.to_le_bytes());
+        block
+    };
+    let mut packet = 0u32.to_le_bytes().to_vec();
+    packet.extend_from_slice(&[0; 8]);
+    packet.extend_from_slice(&(first.len() as u32).to_le_bytes());
+    packet.extend_from_slice(&(first.len() as u32).to_le_bytes());
+    packet.extend_from_slice(&first);
+    packet.resize((packet.len() + 3) & !3, 0);
+
+    let mut sections = pcapng.clone();
+    sections.extend(little_block(
+        0x0a0d0d0a,
+        &[
+            0x4d, 0x3c, 0x2b, 0x1a, 1, 0, 0, 0, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff,
+        ],
+    ));
+    sections.extend(little_block(1, &[1, 0, 0, 0, 0xff, 0xff, 0, 0]));
+    sections.extend(little_block(6, &packet));
+
+    let frames: Vec<_> = Capture::new(&sections[..])
+        .unwrap()
+        .collect::<Result<_, _>>()
+        .unwrap();
+    assert_eq!(frames.len(), 4);
+    assert_eq!(frames[3].link_type, LINKTYPE_ETHERNET);
+    assert_eq!(frames[3].data, first.to_vec());
+    assert_eq!(frames[3].length, first.len());
+}
+
+#[cfg(feature = "pcap")]
+#[test]
+fn test_monitor() {
+    use super::capture::{Frame, Monitor, LINKTYPE_ETHERNET};
+
+    let monitor = Monitor::new();
+    let frame = Frame {
+        link_type: LINKTYPE_ETHERNET,
+        data: vec![
+            0xa0, 0xb1, 0xc2, 0xd3, 0xe4, 0xf5, 0x00, 0x00, 0x0c, 0x07, 0xac, 0x01, 0x08, 0x00,
+        ],
+        length: 1514,
+    };
+
+    assert!(monitor.is_empty());
+
+    monitor.record(&frame);
+    monitor.record(&frame);
+    monitor.record(&Frame {
+        link_type: 0,
+        ..frame.clone()
+    });
+
+    let snapshot = monitor.snapshot();
+    assert_eq!(monitor.len(), 2);
+    assert_eq!(snapshot[0].address.to_plain_notation(), "00000c07ac01");
+    assert_eq!(snapshot[0].source_frames, 2);
+    assert_eq!(snapshot[0].source_bytes, 3028);
+    assert_eq!(snapshot[1].destination_frames, 2);
+    assert_eq!(snapshot[1].destination_bytes, 3028);
+
+    monitor.clear();
+    assert!(monitor.is_empty());
+}
+
+#[cfg(all(feature = "pcap", target_os = "linux"))]
+#[test]
+fn test_live_capture() {
+    use super::capture::{LiveCapture, Monitor};
+    use std::net::UdpSocket;
+    use std::sync::atomic::{AtomicBool, Ordering};
+    use std::sync::Arc;
+    use std::thread;
+    use std::time::Duration;
+
+    // Without CAP_NET_RAW the socket fails; with it, the lookup does.
+    assert!(matches!(
+        LiveCapture::open("no-such-interface"),
+        Err(Error::System(_))
+    ));
+
+    // Capturing requires CAP_NET_RAW, which tests usually lack.
+    let mut capture = match LiveCapture::open("lo") {
+        Ok(capture) => capture,
+        Err(_) => return,
+    };
+
+    // A timeout that would round to zero still times out.
+    capture.set_timeout(Some(Duration::from_nanos(1))).unwrap();
+    while capture.next_frame().unwrap().is_some() {}
+
+    let monitor = Arc::new(Monitor::new());
+    let stop = Arc::new(AtomicBool::new(false));
+    let handle = {
+        let monitor = Arc::clone(&monitor);
+        let stop = Arc::clone(&stop);
+        thread::spawn(move || capture.run(&monitor, &stop, Duration::from_millis(10)))
+    };
+
+    let socket = UdpSocket::bind("127.0.0.1:0").unwrap();
+    let address = socket.local_addr().unwrap();
+
+    for _ in 0..50 {
+        socket.send_to(b"macaddress", address).unwrap();
+        thread::sleep(Duration::from_millis(10));
+
+        if !monitor.is_empty() {
+            break;
+        }
+    }
+
+    stop.store(true, Ordering::Relaxed);
+    handle.join().unwrap().unwrap();
+
+    let snapshot = monitor.snapshot();
+    assert_eq!(snapshot[0].address.to_plain_notation(), "000000000000");
+    assert!(snapshot[0].source_bytes >= 14);
+}
+
+#[test]
+fn test_export() {
+    let mut vendors = HashMap::new();
+    vendors.insert(0x00000c, String::from("Cisco Systems, Inc"));
+    vendors.insert(0x0180c2, String::from("The \"Bridge\" Group"));
+
+    let inventory = vec![
+        MediaAccessControlAddress::new("00:00:0c:07:ac:01").unwrap(),
+        MediaAccessControlAddress::new("0a:b1:c2:d3:e4:f5").unwrap(),
+        MediaAccessControlAddress::new("01:80:c2:00:00:0e").unwrap(),
+    ];
+
+    let mut csv = Vec::new();
+    let rows = Exporter::new(&vendors, Encoding::Csv)
+        .write(&inventory, &mut csv)
+        .unwrap();
+
+    assert_eq!(rows, 3);
+    assert_eq!(
+        String::from_utf8(csv).unwrap(),
+        "address,oui,kind,cast,uaa,laa,vendor
+00:00:0c:07:ac:01,00000c,unique,unicast,true,false,\"Cisco Systems, Inc\"
+0a:b1:c2:d3:e4:f5,0ab1c2,local,unicast,false,true,
+01:80:c2:00:00:0e,0180c2,unknown,multicast,false,false,\"The \"\"Bridge\"\" Group\"
+"
+    );
+
+    let mut json = Vec::new();
+    Exporter::new(&vendors, Encoding::Json)
+        .with_notation(Notation::Hyphen)
+        .write(&inventory[..2], &mut json)
+        .unwrap();
+
+    assert_eq!(
+        String::from_utf8(json).unwrap(),
+        "[
+{\"address\":\"00-00-0c-07-ac-01\",\"oui\":\"00000c\",\"kind\":\"unique\",\"cast\":\"unicast\",\"uaa\":true,\"laa\":false,\"vendor\":\"Cisco Systems, Inc\"},
+{\"address\":\"0a-b1-c2-d3-e4-f5\",\"oui\":\"0ab1c2\",\"kind\":\"local\",\"cast\":\"unicast\",\"uaa\":false,\"laa\":true,\"vendor\":null}
+]
+"
+    );
+
+    let mut lines = Vec::new();
+    Exporter::new(&vendors, Encoding::JsonLines)
+        .write(&inventory[2..], &mut lines)
+        .unwrap();
+
+    assert_eq!(
+        String::from_utf8(lines).unwrap(),
+        "{\"address\":\"01:80:c2:00:00:0e\",\"oui\":\"0180c2\",\"kind\":\"unknown\",\"cast\":\"multicast\",\"uaa\":false,\"laa\":false,\"vendor\":\"The \\\"Bridge\\\" Group\"}\n"
+    );
+
+    let mut empty = Vec::new();
+    let rows = Exporter::new(&vendors, Encoding::Json)
+        .write(Vec::<MediaAccessControlAddress>::new(), &mut empty)
+        .unwrap();
+
+    assert_eq!(rows, 0);
+    assert_eq!(String::from_utf8(empty).unwrap(), "[]\n");
+
+    let mut yaml = Vec::new();
+    Exporter::new(&vendors, Encoding::Yaml)
+        .write(&inventory[1..], &mut yaml)
+        .unwrap();
+
+    assert_eq!(
+        String::from_utf8(yaml).unwrap(),
+        "- address: \"0a:b1:c2:d3:e4:f5\"
+  oui: \"0ab1c2\"
+  kind: \"local\"
+  cast: \"unicast\"
+  uaa: false
+  laa: true
+  vendor: null
+- address: \"01:80:c2:00:00:0e\"
+  oui: \"0180c2\"
+  kind: \"unknown\"
+  cast: \"multicast\"
+  uaa: false
+  laa: false
+  vendor: \"The \\\"Bridge\\\" Group\"
+"
+    );
+
+    let mut toml = Vec::new();
+    Exporter::new(&vendors, Encoding::Toml)
+        .write(&inventory[..2], &mut toml)
+        .unwrap();
+
+    assert_eq!(
+        String::from_utf8(toml).unwrap(),
+        "[[addresses]]
+address = \"00:00:0c:07:ac:01\"
+oui = \"00000c\"
+kind = \"unique\"
+cast = \"unicast\"
+uaa = true
+laa = false
+vendor = \"Cisco Systems, Inc\"
+
+[[addresses]]
+address = \"0a:b1:c2:d3:e4:f5\"
+oui = \"0ab1c2\"
+kind = \"local\"
+cast = \"unicast\"
+uaa = false
+laa = true
+"
+    );
+
+    let mut empty = Vec::new();
+    Exporter::new(&vendors, Encoding::Yaml)
+        .write(Vec::<MediaAccessControlAddress>::new(), &mut empty)
+        .unwrap();
+    assert_eq!(String::from_utf8(empty).unwrap(), "[]\n");
+}
+
+#[test]
+fn test_escape_json() {
+    assert_eq!(escape_json(""), "\"\"");
+    assert_eq!(
+        escape_json("a\"b\\c\nd\re\tf\u{0}g\u{1f}h\u{7f}i\u{80}j"),
+        "\"a\\\"b\\\\c\\nd\\re\\tf\\u0000g\\u001fh\\u007fi\u{80}j\""
+    );
+}
+
+#[test]
+fn test_fingerprint() {
+    let mut vendors = HashMap::new();
+    vendors.insert(0x00000c, String::from("Cisco Systems, Inc"));
+    vendors.insert(0x005056, String::from("VMware, Inc."));
+
+    let physical = MediaAccessControlAddress::new("00:00:0c:07:ac:01").unwrap();
+    let report = fingerprint(&physical, &vendors).unwrap();
+    assert_eq!(report.kind, Kind::Unique);
+    assert_eq!(report.cast, Cast::Unicast);
+    assert_eq!(report.origin, Some(Origin::Physical));
+    assert_eq!(report.platform, None);
+    assert_eq!(report.vendor.as_deref(), Some("Cisco Systems, Inc"));
+    assert_eq!(report.role, None);
+
+    let virtual_machine = MediaAccessControlAddress::new("00:50:56:a1:b2:c3").unwrap();
+    let report = fingerprint(&virtual_machine, &vendors).unwrap();
+    assert_eq!(report.origin, Some(Origin::Virtual));
+    assert_eq!(report.platform, Some("VMware"));
+    assert_eq!(report.vendor.as_deref(), Some("VMware, Inc."));
+
+    let container = MediaAccessControlAddress::new("02:42:ac:11:00:02").unwrap();
+    let report = fingerprint(&container, &vendors).unwrap();
+    assert_eq!(report.origin, Some(Origin::Virtual));
+    assert_eq!(report.platform, Some("Docker"));
+    assert_eq!(report.vendor, None);
+
+    let randomized = MediaAccessControlAddress::new("a6:b1:c2:d3:e4:f5").unwrap();
+    let report = fingerprint(&randomized, &vendors).unwrap();
+    assert_eq!(report.origin, Some(Origin::Randomized));
+    assert_eq!(report.platform, None);
+
+    let report = fingerprint(&PTP_PEER_DELAY, &vendors).unwrap();
+    assert_eq!(report.origin, None);
+    assert_eq!(report.role, Some(Role::Ptp));
+
+    let roles = [
+        ("ff:ff:ff:ff:ff:ff", Some(Role::Broadcast)),
+        ("01:80:c2:00:00:00", Some(Role::LinkLocalControl)),
+        ("00:00:5e:00:01:01", Some(Role::Vrrp)),
+        ("00:00:5e:00:53:01", Some(Role::Documentation)),
+        ("01:00:5e:00:00:fb", Some(Role::Ipv4Multicast)),
+        ("01:00:5e:80:00:01", Some(Role::MplsMulticast)),
+        ("33:33:00:00:00:01", Some(Role::Ipv6Multicast)),
+        ("01:00:0c:cc:cc:cc", None),
+    ];
+
+    for (digits, expected) in roles.iter() {
+        let mac = MediaAccessControlAddress::new(digits).unwrap();
+        assert_eq!(role(&mac), *expected);
+    }
+
+    assert_eq!(Role::Ipv4Multicast.to_string(), "ipv4-multicast");
+    assert_eq!(Origin::Randomized.to_string(), "randomized");
+    assert_eq!(platform(&physical), None);
+}
+
+#[test]
+fn test_report() {
+    use super::report::report;
+
+    let mut vendors = HashMap::new();
+    vendors.insert(0x00000c, String::from("Cisco \"Systems\""));
+
+    let inventory: Vec<MediaAccessControlAddress> = [
+        "00:00:0c:07:ac:01",
+        "00:00:0c:9f:f0:01",
+        "00:50:56:a1:b2:c3",
+        "a2:b1:c2:d3:e4:f5",
+        "01:00:5e:00:00:01",
+        "ff:ff:ff:ff:ff:ff",
+        "00:00:0c:07:ac:01",
+        "01:00:5e:00:00:fb",
+    ]
+    .iter()
+    .map(|digits| MediaAccessControlAddress::new(digits).unwrap())
+    .collect();
+
+    let summary = report(&inventory, &vendors).unwrap();
+
+    assert_eq!(summary.observations, 8);
+    assert_eq!(summary.addresses, 7);
+    assert_eq!((summary.uaa, summary.laa, summary.multicast), (3, 1, 3));
+    assert_eq!(
+        summary.origins,
+        vec![
+            (Origin::Physical, 2),
+            (Origin::Virtual, 1),
+            (Origin::Randomized, 1)
+        ]
+    );
+    assert_eq!(summary.origin(Origin::Randomized), 1);
+    assert_eq!(summary.unknown_vendors, 5);
+    assert_eq!(
+        summary.prefixes[0],
+        ("00:00:0c:00:00:00/24".parse().unwrap(), 2)
+    );
+    assert_eq!(
+        summary.prefixes[1],
+        ("01:00:5e:00:00:00/24".parse().unwrap(), 2)
+    );
+    assert_eq!(summary.prefixes.len(), 5);
+    assert_eq!(
+        summary.protocols,
+        vec![(Role::Ipv4Multicast, 2), (Role::Broadcast, 1)]
+    );
+    assert!((summary.ratio(summary.uaa) - 3.0 / 7.0).abs() < 1e-12);
+
+    assert_eq!(
+        summary.to_json(),
+        concat!(
+            "{\"observations\":8,\"addresses\":7,\"uaa\":3,\"laa\":1,\"multicast\":3,",
+            "\"ratios\":{\"uaa\":0.42857142857142855,\"laa\":0.14285714285714285,\"randomized\":0.14285714285714285},",
+            "\"origins\":{\"physical\":2,\"virtual\":1,\"randomized\":1},",
+            "\"vendors\":[{\"vendor\":\"Cisco \\\"Systems\\\"\",\"count\":2}],\"unknown_vendors\":5,",
+            "\"prefixes\":[{\"prefix\":\"00:00:0c:00:00:00/24\",\"count\":2},",
+            "{\"prefix\":\"01:00:5e:00:00:00/24\",\"count\":2},",
+            "{\"prefix\":\"00:50:56:00:00:00/24\",\"count\":1},",
+            "{\"prefix\":\"a2:b1:c2:00:00:00/24\",\"count\":1},",
+            "{\"prefix\":\"ff:ff:ff:00:00:00/24\",\"count\":1}],",
+            "\"protocols\":[{\"protocol\":\"ipv4-multicast\",\"count\":2},",
+            "{\"protocol\":\"broadcast\",\"count\":1}]}"
+        )
+    );
+
+    let empty = report(Vec::<MediaAccessControlAddress>::new(), &vendors).unwrap();
+    assert_eq!(empty.addresses, 0);
+    assert_eq!(empty.ratio(empty.laa), 0.0);
+    assert!(empty
+        .to_json()
+        .contains("\"ratios\":{\"uaa\":0,\"laa\":0,\"randomized\":0}"));
+
+    let yaml = summary.to_yaml();
+    assert!(yaml.starts_with("observations: 8\naddresses: 7\n"));
+    assert!(yaml.contains("ratios:\n  uaa: 0.42857142857142855\n"));
+    assert!(yaml.contains("origins:\n  physical: 2\n  virtual: 1\n  randomized: 1\n"));
+    assert!(yaml.contains("vendors:\n  - vendor: \"Cisco \\\"Systems\\\"\"\n    count: 2\n"));
+    assert!(yaml.ends_with("  - protocol: \"broadcast\"\n    count: 1\n"));
+    assert!(empty.to_yaml().contains("\nvendors: []\n"));
+
+    let toml = summary.to_toml();
+    assert!(toml.starts_with("observations = 8\naddresses = 7\n"));
+    assert!(toml.contains("\n[ratios]\nuaa = 0.42857142857142855\n"));
+    assert!(toml.contains("\n[[prefixes]]\nprefix = \"00:00:0c:00:00:00/24\"\ncount = 2\n"));
+    assert!(empty
+        .to_toml()
+        .contains("\nprotocols = []\n\n[ratios]\nuaa = 0.0\n"));
+}
+
+#[test]
+fn test_mac_prefix() {
+    let mac = MediaAccessControlAddress::new("a0:b1:c2:d3:e4:f5").unwrap();
+
+    let oui = MacPrefix::oui(&mac);
+    assert_eq!(oui.to_string(), "a0:b1:c2:00:00:00/24");
+    assert_eq!(oui.length(), 24);
+    assert_eq!(oui.size(), 1 << 24);
+    assert_eq!(oui.first().to_colon_notation(), "a0:b1:c2:00:00:00");
+    assert_eq!(oui.last().to_colon_notation(), "a0:b1:c2:ff:ff:ff");
+    assert!(oui.contains(&mac));
+    assert!(!oui.contains(&MediaAccessControlAddress::new("a0:b1:c3:00:00:00").unwrap()));
+
+    let prefix: MacPrefix = "a0-b1-c2-d3-e4-f5/28".parse().unwrap();
+    assert_eq!(prefix.to_string(), "a0:b1:c2:d0:00:00/28");
+    assert_eq!(prefix.last().to_colon_notation(), "a0:b1:c2:df:ff:ff");
+
+    let everything = MacPrefix::new(&mac, 0).unwrap();
+    assert_eq!(everything.to_string(), "00:00:00:00:00:00/0");
+    assert_eq!(everything.last().to_colon_notation(), "ff:ff:ff:ff:ff:ff");
+
+    let host = MacPrefix::new(&mac, 48).unwrap();
+    assert_eq!(host.size(), 1);
+    assert_eq!(
+        host.first().to_colon_notation(),
+        host.last().to_colon_notation()
+    );
+
+    assert!(MacPrefix::new(&mac, 49).is_err());
+    assert!("a0:b1:c2:d3:e4:f5".parse::<MacPrefix>().is_err());
+    assert!(matches!(
+        "a0:b1:c2:d3:e4:f5/x".parse::<MacPrefix>(),
+        Err(Error::Invalid(_))
+    ));
+    assert!(matches!(
+        "a0:b1:c2:d3:e4/24".parse::<MacPrefix>(),
+        Err(Error::Parse(MacParseError::InvalidLength))
+    ));
+}
+
+#[test]
+fn test_mac_range() {
+    let range: MacRange = "a0:b1:c2:00:00:01-a0:b1:c2:00:00:06".parse().unwrap();
+    assert_eq!(range.to_string(), "a0:b1:c2:00:00:01-a0:b1:c2:00:00:06");
+    assert_eq!(range.size(), 6);
+    assert!(range.contains(&MediaAccessControlAddress::new("a0:b1:c2:00:00:06").unwrap()));
+    assert!(!range.contains(&MediaAccessControlAddress::new("a0:b1:c2:00:00:07").unwrap()));
+
+    let addresses: Vec<String> = range.iter().map(|mac| mac.to_colon_notation()).collect();
+    assert_eq!(addresses.len(), 6);
+    assert_eq!(addresses[5], "a0:b1:c2:00:00:06");
+
+    let prefixes: Vec<String> = range.to_prefixes().iter().map(|p| p.to_string()).collect();
+    assert_eq!(
+        prefixes,
+        [
+            "a0:b1:c2:00:00:01/48",
+            "a0:b1:c2:00:00:02/47",
+            "a0:b1:c2:00:00:04/47",
+            "a0:b1:c2:00:00:06/48",
+        ]
+    );
+
+    // Hyphen notation, with hyphens inside each address.
+    let oui: MacRange = "a0-b1-c2-00-00-00-a0-b1-c2-ff-ff-ff".parse().unwrap();
+    assert_eq!(
+        oui,
+        MacRange::from("a0:b1:c2:00:00:00/24".parse::<MacPrefix>().unwrap())
+    );
+    assert_eq!(oui.to_prefixes().len(), 1);
+
+    let everything: MacRange = "00:00:00:00:00:00-ff:ff:ff:ff:ff:ff".parse().unwrap();
+    assert_eq!(
+        everything.to_prefixes()[0].to_string(),
+        "00:00:00:00:00:00/0"
+    );
+
+    assert!(matches!(
+        "a0:b1:c2:00:00:06-a0:b1:c2:00:00:01".parse::<MacRange>(),
+        Err(Error::Invalid(_))
+    ));
+    assert!(matches!(
+        "a0:b1:c2:00:00:01-a0:b1:c2:00:00:zz".parse::<MacRange>(),
+        Err(Error::Parse(MacParseError::InvalidCharacter { .. }))
+    ));
+    assert!("a0:b1:c2:00:00:06".parse::<MacRange>().is_err());
+    assert!("a0-b1-c2-00-00-06-a0-b1-c2-00-00"
+        .parse::<MacRange>()
+        .is_err());
+
+    let addresses: Vec<MediaAccessControlAddress> = [
+        "a0:b1:c2:00:00:03",
+        "a0:b1:c2:00:00:01",
+        "a0:b1:c2:00:00:02",
+        "a0:b1:c2:00:00:02",
+        "a0:b1:c2:00:01:00",
+    ]
+    .iter()
+    .map(|digits| MediaAccessControlAddress::new(digits).unwrap())
+    .collect();
+
+    let ranges: Vec<String> = range::summarize(&addresses)
+        .iter()
+        .map(|range| range.to_string())
+        .collect();
+    assert_eq!(
+        ranges,
+        [
+            "a0:b1:c2:00:00:01-a0:b1:c2:00:00:03",
+            "a0:b1:c2:00:01:00-a0:b1:c2:00:01:00",
+        ]
+    );
+    assert!(range::summarize(Vec::<MediaAccessControlAddress>::new()).is_empty());
+}
+
+#[test]
+fn test_mac_table() {
+    let addresses = [
+        "00:00:0c:07:ac:01",
+        "00:00:0c:9f:f0:01",
+        "a0:b1:c2:d3:e4:f5",
+        "a0:b1:c2:d3:e5:00",
+        "ff:ff:ff:ff:ff:ff",
+    ];
+
+    let mut table = MacTable::new();
+    assert!(table.is_empty());
+    assert!(table
+        .nearest(&MediaAccessControlAddress::new(addresses[0]).unwrap())
+        .is_none());
+
+    for (port, digits) in addresses.iter().enumerate().rev() {
+        let mac = MediaAccessControlAddress::new(digits).unwrap();
+        assert_eq!(table.insert(&mac, port), None);
+    }
+
+    assert_eq!(table.len(), 5);
+
+    let listed: Vec<String> = table
+        .iter()
+        .map(|(mac, _)| mac.to_colon_notation())
+        .collect();
+    assert_eq!(listed, addresses);
+
+    let cisco = MacPrefix::oui(&MediaAccessControlAddress::new("00:00:0c:00:00:00").unwrap());
+    let ports: Vec<usize> = table.range(&cisco).map(|(_, port)| *port).collect();
+    assert_eq!(ports, [0, 1]);
+
+    let top: MacPrefix = "ff:ff:ff:ff:ff:ff/48".parse().unwrap();
+    assert_eq!(table.range(&top).count(), 1);
+
+    let nearest = |digits: &str| {
+        let mac = MediaAccessControlAddress::new(digits).unwrap();
+        table
+            .nearest(&mac)
+            .map(|(mac, port)| (mac.to_colon_notation(), *port))
+    };
+
+    assert_eq!(
+        nearest("a0:b1:c2:d3:e4:f5"),
+        Some((addresses[2].to_string(), 2))
+    );
+    assert_eq!(
+        nearest("a0:b1:c2:d3:e4:fa"),
+        Some((addresses[2].to_string(), 2))
+    );
+    assert_eq!(
+        nearest("a0:b1:c2:d3:e4:fb"),
+        Some((addresses[3].to_string(), 3))
+    );
+    assert_eq!(
+        nearest("00:00:00:00:00:00"),
+        Some((addresses[0].to_string(), 0))
+    );
+
+    let mac = MediaAccessControlAddress::new(addresses[4]).unwrap();
+    assert_eq!(table.insert(&mac, 9), Some(4));
+    *table.get_mut(&mac).unwrap() += 1;
+    assert_eq!(table.get(&mac), Some(&10));
+    assert!(table.contains_key(&mac));
+    assert_eq!(table.remove(&mac), Some(10));
+    assert!(!table.contains_key(&mac));
+
+    table.clear();
+    assert!(table.is_empty());
+}
+
+#[test]
+fn test_mac_bloom() {
+    let mut bloom = MacBloom::new(10_000, 0.01).unwrap();
+    assert!(bloom.is_empty());
+    assert_eq!(bloom.bits(), 95_851);
+    assert_eq!(bloom.hashes(), 7);
+
+    let first = MediaAccessControlAddress::new("a0:b1:c2:00:00:00").unwrap();
+    let inserted: Vec<MediaAccessControlAddress> = first.successors_in_oui().take(10_000).collect();
+
+    for mac in inserted.iter() {
+        bloom.insert(mac);
+    }
+
+    assert_eq!(bloom.len(), 10_000);
+    assert!(inserted.iter().all(|mac| bloom.contains(mac)));
+    assert!((bloom.false_positive_rate() - 0.01).abs() < 0.001);
+
+    let other = MediaAccessControlAddress::new("0a:b1:c2:00:00:00").unwrap();
+    let false_positives = other
+        .successors_in_oui()
+        .take(10_000)
+        .filter(|mac| bloom.contains(mac))
+        .count();
+    assert!(false_positives < 200);
+
+    let bytes = bloom.to_bytes();
+    assert_eq!(&bytes[..4], b"MBF1");
+    assert_eq!(bytes.len(), 24 + 1498 * 8);
+
+    let restored = MacBloom::from_bytes(&bytes).unwrap();
+    assert_eq!(restored, bloom);
+    assert!(inserted.iter().all(|mac| restored.contains(mac)));
+
+    assert!(MacBloom::from_bytes(&bytes[..bytes.len() - 1]).is_err());
+    assert!(MacBloom::from_bytes(&bytes[1..]).is_err());
+    assert!(MacBloom::from_bytes(b"MBF1").is_err());
+
+    bloom.clear();
+    assert!(bloom.is_empty());
+    assert!(!bloom.contains(&first));
+
+    assert!(MacBloom::new(0, 0.01).is_err());
+    assert!(MacBloom::new(10, 0.0).is_err());
+    assert!(MacBloom::new(10, 1.0).is_err());
+    assert!(MacBloom::new(10, f64::NAN).is_err());
+    assert!(MacBloom::with_parameters(0, 1).is_err());
+    assert!(MacBloom::with_parameters(64, 0).is_err());
+}
+
+#[cfg(feature = "db")]
+#[test]
+fn test_vendor_database() {
+    use super::vendor::{Assignment, VendorDatabase};
+
+    let csv = "Registry,Assignment,Organization Name,Organization Address
+MA-L,00000C,\"Cisco Systems, Inc\",170 West Tasman Drive San Jose CA US 95134
+MA-L,A0B1C2,Old Name,
+MA-M,A0B1C2D,Example Devices,
+";
+
+    let old = VendorDatabase::from_csv(csv).unwrap();
+    assert_eq!(old.len(), 3);
+    assert_eq!(old.to_csv(), csv);
+
+    let mac = MediaAccessControlAddress::new("a0:b1:c2:d3:e4:f5").unwrap();
+    assert_eq!(old.lookup(&mac).unwrap().registry(), "MA-M");
+    assert!(old.is_registered(&mac));
+    assert!(!old.is_registered(&MediaAccessControlAddress::new("a2:b1:c2:d3:e4:f5").unwrap()));
+    assert_eq!(
+        mac.vendor_with(&old).unwrap().as_deref(),
+        Some("Example Devices")
+    );
+
+    let record = Record {
+        prefix: 0x00000c000000,
+        length: 24,
+        organization: "Cisco Systems, Inc",
+        address: "170 West Tasman Drive San Jose CA US 95134",
+    };
+    assert_eq!(Assignment::from(&record), *old.iter().next().unwrap());
+    assert!(Assignment::from(&record)
+        .contains(&MediaAccessControlAddress::new("00:00:0c:07:ac:01").unwrap()));
+
+    let new = VendorDatabase::from_csv(
+        "MA-L,00000C,\"Cisco Systems, Inc\",170 West Tasman Drive San Jose CA US 95134
+MA-L,A0B1C2,New Name,
+MA-S,A0B1C2D3E,Smaller Devices,
+",
+    )
+    .unwrap();
+
+    let delta = old.diff(&new);
+    assert_eq!(
+        delta,
+        format!(
+            "base {:08x}
+target {:08x}
+-MA-M,A0B1C2D
+~MA-L,A0B1C2,New Name,
++MA-S,A0B1C2D3E,Smaller Devices,
+",
+            old.checksum(),
+            new.checksum()
+        )
+    );
+
+    let trusted = |message: &[u8], signature: &[u8]| {
+        if signature == &message[..4] {
+            Ok(())
+        } else {
+            Err(Error::Invalid(String::from("bad signature")))
+        }
+    };
+
+    let mut updated = old.clone();
+    assert_eq!(
+        updated
+            .apply_delta(&delta, b"oops", &trusted)
+            .unwrap_err()
+            .to_string(),
+        "bad signature"
+    );
+    assert_eq!(updated, old);
+
+    updated.apply_delta(&delta, b"base", &trusted).unwrap();
+    assert_eq!(updated, new);
+    assert_eq!(updated.checksum(), new.checksum());
+    assert_eq!(
+        mac.vendor_with(&updated).unwrap().as_deref(),
+        Some("Smaller Devices")
+    );
+
+    let other = MediaAccessControlAddress::new("a0:b1:c2:00:00:01").unwrap();
+    assert_eq!(
+        other.vendor_with(&updated).unwrap().as_deref(),
+        Some("New Name")
+    );
+
+    // The delta no longer applies, since the database moved on.
+    assert!(updated.apply_delta(&delta, b"base", &trusted).is_err());
+    assert_eq!(updated, new);
+
+    let truncated: String = delta
+        .lines()
+        .take(4)
+        .map(|line| format!("{}\n", line))
+        .collect();
+    let mut partial = old.clone();
+    assert!(matches!(
+        partial.apply_delta(&truncated, b"base", &trusted),
+        Err(Error::Registry(_))
+    ));
+    assert_eq!(partial, old);
+
+    let header = format!(
+        "base {:08x}\ntarget {:08x}\n",
+        old.checksum(),
+        old.checksum()
+    );
+    let invalid = [
+        String::from("target 00000000\n"),
+        format!("{}*MA-L,A0B1C2,Name,\n", header),
+        format!("{}+MA-L,A0B1C2,Name,\n", header),
+        format!("{}-MA-L,FFFFFF\n", header),
+        format!("{}~MA-L,A0B1C,Name,\n", header),
+    ];
+
+    for delta in invalid.iter() {
+        let mut database = old.clone();
+        assert!(database
+            .apply_delta(delta, b"", &|_: &[u8], _: &[u8]| Ok(()))
+            .is_err());
+        assert_eq!(database, old);
+    }
+
+    let mut unchanged = old.clone();
+    unchanged
+        .apply_delta(&old.diff(&old), b"", &|_: &[u8], _: &[u8]| Ok(()))
+        .unwrap();
+    assert_eq!(unchanged, old);
+    assert!(VendorDatabase::new().is_empty());
+
+    let csv = new.to_csv();
+    let digest: String = sha256(csv.as_bytes())
+        .iter()
+        .map(|octet| format!("{:02x}", octet))
+        .collect();
+    assert_eq!(
+        VendorDatabase::from_csv_with_sha256(&csv, &digest.to_uppercase()).unwrap(),
+        new
+    );
+    assert!(VendorDatabase::from_csv_with_sha256(&csv, &"0".repeat(64)).is_err());
+    assert!(VendorDatabase::from_csv_with_sha256(&csv.replace("New", "Old"), &digest).is_err());
+
+    let signature = csv.as_bytes()[..4].to_vec();
+    assert_eq!(
+        VendorDatabase::from_signed_csv(&csv, &signature, &trusted).unwrap(),
+        new
+    );
+    assert_eq!(
+        VendorDatabase::from_signed_csv(&csv, b"oops", &trusted)
+            .unwrap_err()
+            .to_string(),
+        "bad signature"
+    );
+}
+
+#[cfg(feature = "db")]
+#[test]
+fn test_vendor_database_load() {
+    use super::vendor::VendorDatabase;
+    use std::fs;
+
+    let directory = std::env::temp_dir().join(format!("macaddress-load-{}", std::process::id()));
+    fs::create_dir_all(&directory).unwrap();
+    let path = directory.join("vendors.csv");
+    fs::write(
+        &path,
+        "Registry,Assignment,Organization Name,Organization Address
+MA-L,00000C,\"Cisco Systems, Inc\",
+",
+    )
+    .unwrap();
+
+    let database = VendorDatabase::load(&path).unwrap();
+    assert_eq!(database.len(), 1);
+
+    fs::write(&path, "MA-L,00000C\n").unwrap();
+    let malformed = VendorDatabase::load(&path);
+    fs::remove_dir_all(&directory).unwrap();
+
+    match malformed {
+        Err(Error::Registry(message)) => {
+            assert!(message.starts_with(&format!("{}: ", path.display())))
+        }
+        other => panic!("{:?}", other),
+    }
+
+    match VendorDatabase::load(&path) {
+        Err(Error::Registry(message)) => {
+            assert!(message.starts_with(&format!("{}: ", path.display())))
+        }
+        other => panic!("{:?}", other),
+    }
+}
+
+#[cfg(feature = "db")]
+#[test]
+fn test_vendor_database_metadata() {
+    use super::vendor::VendorDatabase;
+    use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+    let csv = "# built 1760572800
+# source MA-L 2025-10-15
+# source MA-M 2025-10-14
+# A comment that holds no metadata
+Registry,Assignment,Organization Name,Organization Address
+MA-L,00000C,\"Cisco Systems, Inc\",
+MA-L,A0B1C2,Example Networks,
+MA-M,A0B1C2D,Example Devices,
+";
+
+    let database = VendorDatabase::from_csv(csv).unwrap();
+    let metadata = database.metadata();
+    assert_eq!(
+        metadata.built,
+        Some(UNIX_EPOCH + Duration::from_secs(1_760_572_800))
+    );
+    assert_eq!(metadata.sources.len(), 2);
+    assert_eq!(metadata.sources["MA-L"], "2025-10-15");
+    assert_eq!(database.len(), 3);
+    assert_eq!(database.count("MA-L"), 2);
+    assert_eq!(database.count("MA-M"), 1);
+    assert_eq!(database.count("MA-S"), 0);
+
+    assert!(database.is_stale(Duration::from_secs(86_400)));
+    assert!(!database.is_stale(Duration::from_secs(u64::MAX)));
+    assert!(VendorDatabase::new().is_stale(Duration::from_secs(u64::MAX)));
+
+    let mut fresh = database.clone();
+    fresh.set_built(SystemTime::now());
+    assert!(!fresh.is_stale(Duration::from_secs(86_400)));
+    assert_eq!(fresh.checksum(), database.checksum());
+
+    fresh.set_source("MA-S", "2025-10-16").unwrap();
+    assert!(fresh.set_source("MA-S", "16 October").is_err());
+    assert!(fresh.set_source("", "2025-10-16").is_err());
+
+    let reloaded = VendorDatabase::from_csv(&fresh.to_csv()).unwrap();
+    assert_eq!(reloaded, fresh);
+    assert!(!csv.contains("# built 0"));
+    assert_eq!(
+        database
+            .to_csv()
+            .replace("# A comment that holds no metadata\n", ""),
+        csv.replace("# A comment that holds no metadata\n", "")
+    );
+
+    let delta = database.diff(&fresh);
+    assert!(delta.contains("# source MA-S 2025-10-16\n"));
+
+    let mut merged = VendorDatabase::from_csv("MA-L,00000C,Replaced,\n").unwrap();
+    merged.merge(database.clone());
+    assert_eq!(merged.len(), 3);
+    assert_eq!(merged.metadata().sources, database.metadata().sources);
+    assert_eq!(
+        merged.iter().next().unwrap().organization,
+        "Cisco Systems, Inc"
+    );
+
+    let mut updated = database.clone();
+    updated
+        .apply_delta(&delta, b"", &|_: &[u8], _: &[u8]| Ok(()))
+        .unwrap();
+    assert_eq!(updated, fresh);
+}
+
+#[cfg(feature = "db")]
+#[test]
+fn test_vendor_overlapping() {
+    use super::vendor::VendorDatabase;
+
+    static RECORDS: [Record; 4] = [
+        Record {
+            prefix: 0x00005e000000,
+            length: 24,
+            organization: "ICANN, IANA Department",
+            address: "",
+        },
+        Record {
+            prefix: 0xa0b1c2000000,
+            length: 24,
+            organization: "Example Networks",
+            address: "",
+        },
+        Record {
+            prefix: 0xa0b1c2d00000,
+            length: 28,
+            organization: "Example Devices",
+            address: "",
+        },
+        Record {
+            prefix: 0xa0b1c2d3e000,
+            length: 36,
+            organization: "Example Sensors",
+            address: "",
+        },
+    ];
+
+    let table = VendorTable::new(&RECORDS);
+    let database = VendorDatabase::from_csv(
+        "MA-L,00005E,\"ICANN, IANA Department\",
+MA-L,A0B1C2,Example Networks,
+MA-M,A0B1C2D,Example Devices,
+MA-S,A0B1C2D3E,Example Sensors,
+",
+    )
+    .unwrap();
+
+    let cases = [
+        ("a0:b1:c2:00:00:00/24", vec![1, 2, 3]),
+        ("a0:b1:c2:d3:00:00/32", vec![1, 2, 3]),
+        ("a0:b1:c2:d3:e4:f5/48", vec![1, 2, 3]),
+        ("a0:b1:c2:d4:00:00/32", vec![1, 2]),
+        ("a0:b1:c2:e0:00:00/28", vec![1]),
+        ("a0:00:00:00:00:00/8", vec![1, 2, 3]),
+        ("00:00:5e:00:53:00/40", vec![0]),
+        ("02:00:00:00:00:00/8", vec![]),
+        ("00:00:00:00:00:00/0", vec![0, 1, 2, 3]),
+    ];
+
+    for (prefix, expected) in cases.iter() {
+        let prefix: MacPrefix = prefix.parse().unwrap();
+
+        let records: Vec<&str> = table
+            .overlapping(&prefix)
+            .iter()
+            .map(|record| record.organization)
+            .collect();
+        let assignments: Vec<&str> = database
+            .overlapping(&prefix)
+            .iter()
+            .map(|assignment| assignment.organization.as_str())
+            .collect();
+        let expected: Vec<&str> = expected
+            .iter()
+            .map(|index| RECORDS[*index].organization)
+            .collect();
+
+        assert_eq!(records, expected);
+        assert_eq!(assignments, expected);
+    }
+}
+
+#[cfg(feature = "system")]
+#[test]
+fn test_neighbors() {
+    use super::system;
+
+    let arp = "\
+IP address       HW type     Flags       HW address            Mask     Device
+192.0.2.1        0x1         0x2         02:fc:00:00:00:05     *        eth0
+192.0.2.7        0x1         0x0         00:00:00:00:00:00     *        eth0
+192.0.2.8        0x20        0x2         80:00:02:08:fe:80:00:00:00:00:00:00:00:02:c9:03:00:0a:bc:de     *        ib0
+";
+
+    let neighbors = system::parse_proc_arp(arp).unwrap();
+    assert_eq!(neighbors.len(), 1);
+    assert_eq!(neighbors[0].ip.to_string(), "192.0.2.1");
+    assert_eq!(
+        neighbors[0].address.to_colon_notation(),
+        "02:fc:00:00:00:05"
+    );
+    assert_eq!(neighbors[0].interface, "eth0");
+    assert_eq!(neighbors[0].state, None);
+
+    let arp = "\
+IP address       HW type     Flags       HW address            Mask     Device
+192.0.2.1        0x1         0x2         zz                    *        eth0
+";
+    assert!(system::parse_proc_arp(arp).is_err());
+
+    let neigh = "\
+192.0.2.1 dev eth0 lladdr 02:fc:00:00:00:05 STALE
+192.0.2.9 dev eth0 FAILED
+fe80::a2b1:c2ff:fed3:e4f5 dev wlan0 lladdr a0:b1:c2:d3:e4:f5 router REACHABLE
+";
+
+    let neighbors = system::parse_ip_neigh(neigh).unwrap();
+    assert_eq!(neighbors.len(), 2);
+    assert_eq!(neighbors[0].state.as_deref(), Some("STALE"));
+    assert!(neighbors[1].ip.is_ipv6());
+    assert_eq!(
+        neighbors[1].address.to_colon_notation(),
+        "a0:b1:c2:d3:e4:f5"
+    );
+    assert_eq!(neighbors[1].interface, "wlan0");
+    assert_eq!(neighbors[1].state.as_deref(), Some("REACHABLE"));
+
+    // IPoIB and tunnel neighbors have other link-layer addresses.
+    let neigh = "\
+192.0.2.2 dev ib0 lladdr 80:00:02:08:fe:80:00:00:00:00:00:00:00:02:c9:03:00:0a:bc:de REACHABLE
+192.0.2.3 dev gre1 lladdr 198.51.100.1 PERMANENT
+fe80::3 dev ip6tnl1 lladdr 2001:db8::1 PERMANENT
+192.0.2.1 dev eth0 lladdr 02:fc:00:00:00:05 STALE
+";
+
+    let neighbors = system::parse_ip_neigh(neigh).unwrap();
+    assert_eq!(neighbors.len(), 1);
+    assert_eq!(neighbors[0].interface, "eth0");
+
+    assert!(system::parse_ip_neigh("192.0.2.1 dev eth0 lladdr zz STALE").is_err());
+    assert!(system::parse_ip_neigh("x dev eth0 lladdr 02:fc:00:00:00:05").is_err());
+}
+
+#[cfg(feature = "system")]
+#[test]
+fn test_interfaces() {
+    use super::system::{self, AddressSource};
+    use std::fs;
+
+    let root = std::env::temp_dir().join(format!("macaddress-interfaces-{}", std::process::id()));
+    let interfaces = [
+        ("lo", "00:00:00:00:00:00", "0", "772"),
+        ("wlan0", "a2:b1:c2:d3:e4:f5", "3", "1"),
+        ("eth0", "a0:b1:c2:d3:e4:f5", "0", "1"),
+        ("tun0", "", "0", "65534"),
+    ];
+
+    for (name, address, source, link_type) in interfaces.iter() {
+        let directory = root.join(name);
+        fs::create_dir_all(&directory).unwrap();
+        fs::write(directory.join("address"), format!("{}\n", address)).unwrap();
+        fs::write(directory.join("addr_assign_type"), source).unwrap();
+        fs::write(directory.join("type"), link_type).unwrap();
+    }
+
+    let found = system::parse_sys_class_net(&root);
+    fs::remove_dir_all(&root).unwrap();
+    let found = found.unwrap();
+
+    assert_eq!(found.len(), 2);
+    assert_eq!(found[0].name, "eth0");
+    assert_eq!(found[0].address.to_colon_notation(), "a0:b1:c2:d3:e4:f5");
+    assert_eq!(found[0].source, Some(AddressSource::Permanent));
+    assert_eq!(found[1].name, "wlan0");
+    assert_eq!(found[1].source, Some(AddressSource::Set));
+    assert_eq!(AddressSource::Random.to_string(), "random");
+
+    assert!(system::parse_sys_class_net(&root).is_err());
+}
+
+#[cfg(feature = "system")]
+#[test]
+fn test_machine_id() {
+    use super::system::{machine_id_from, AddressSource, Interface};
+
+    fn interface(name: &str, digits: &str, source: Option<AddressSource>) -> Interface {
+        Interface {
+            name: name.to_string(),
+            address: MediaAccessControlAddress::new(digits).unwrap(),
+            source,
+        }
+    }
+
+    let salt = b"inventory-agent";
+    let interfaces = vec![
+        interface("eth0", "a0:b1:c2:d3:e4:f5", Some(AddressSource::Permanent)),
+        interface("eth1", "00:1b:21:00:00:01", None),
+        interface("bond0", "a0:b1:c2:d3:e4:f5", Some(AddressSource::Stolen)),
+        interface("wlan0", "a2:b1:c2:d3:e4:f5", Some(AddressSource::Set)),
+        interface("veth0", "00:50:56:00:00:01", Some(AddressSource::Permanent)),
+        interface("docker0", "02:42:ac:11:00:02", Some(AddressSource::Set)),
+        interface("usb0", "a4:b1:c2:d3:e4:f5", Some(AddressSource::Random)),
+    ];
+
+    let id = machine_id_from(&interfaces, salt).unwrap();
+
+    // This value is part of the crate's stable interface and must
+    // never change.
+    assert_eq!(id, 0xa2a7_854c_edc6_7ace);
+
+    // Names, order, and non-physical interfaces do not matter.
+    let physical = vec![
+        interface("enp1s0", "00:1b:21:00:00:01", None),
+        interface("enp2s0", "a0:b1:c2:d3:e4:f5", None),
+    ];
+
+    assert_eq!(machine_id_from(&physical, salt).unwrap(), id);
+    assert_ne!(machine_id_from(&physical, b"licensing").unwrap(), id);
+    assert_ne!(machine_id_from(&physical[..1], salt).unwrap(), id);
+    assert!(machine_id_from(&interfaces[3..], salt).is_err());
+}
+
+#[test]
+fn test_error() {
+    use std::error::Error as _;
+    use std::io;
+
+    fn parse(digits: &str) -> Result<MediaAccessControlAddress, Error> {
+        Ok(MediaAccessControlAddress::new(digits)?)
+    }
+
+    fn prefix(text: &str) -> Result<MacPrefix, Error> {
+        text.parse::<MacPrefix>()
+    }
+
+    assert!(parse("a0:b1:c2:d3:e4:f5").is_ok());
+
+    match parse("a0:b1:c2:d3:e4:g5") {
+        Err(Error::Parse(error)) => {
+            assert_eq!(error, MacParseError::InvalidCharacter { index: 15 })
+        }
+        other => panic!("{:?}", other),
+    }
+
+    let error = parse("a0:b1:c2:d3:e4").unwrap_err();
+    assert!(error.source().is_some());
+    assert_eq!(
+        error.to_string(),
+        MediaAccessControlAddress::new("a0:b1:c2:d3:e4")
+            .unwrap_err()
+            .to_string()
+    );
+
+    let error = prefix("a0:b1:c2:d3:e4:f5/49").unwrap_err();
+    assert!(matches!(error, Error::Invalid(_)));
+    assert!(error.source().is_none());
+    assert_eq!(String::from(error), "Pass in a length between 0 and 48.");
+
+    let error = Error::from(io::Error::new(io::ErrorKind::NotFound, "vendors.csv"));
+    assert!(matches!(error, Error::Io(_)));
+    assert_eq!(error.to_string(), "vendors.csv");
+    assert_eq!(
+        Error::System(String::from("unsupported")).to_string(),
+        "unsupported"
+    );
+}
+
+#[test]
+fn test_try_from() {
+    fn convert<T: TryFrom<&'static str>>(digits: &'static str) -> Option<T> {
+        T::try_from(digits).ok()
+    }
+
+    let mac = MediaAccessControlAddress::try_from("a0-b1-c2-d3-e4-f5").unwrap();
+    assert_eq!(mac.to_colon_notation(), "a0:b1:c2:d3:e4:f5");
+
+    let mac = MediaAccessControlAddress::try_from(String::from("A0B1.C2D3.E4F5")).unwrap();
+    assert_eq!(mac.to_plain_notation(), "a0b1c2d3e4f5");
+
+    let mac: MediaAccessControlAddress = convert("0:3:ba:12:4:5").unwrap();
+    assert_eq!(mac.to_colon_notation(), "00:03:ba:12:04:05");
+    assert!(convert::<MediaAccessControlAddress>("a0:b1:c2:d3:e4").is_none());
+
+    let error = MediaAccessControlAddress::try_from(String::from("a0:b1:c2:d3:e4:g5")).unwrap_err();
+    assert_eq!(
+        error,
+        MediaAccessControlAddress::new("a0:b1:c2:d3:e4:g5").unwrap_err()
+    );
+
+    let eui = Eui64::try_from(String::from("a0-b1-c2-ff-fe-d3-e4-f5")).unwrap();
+    assert_eq!(eui.to_plain_notation(), "a0b1c2fffed3e4f5");
+    assert!(Eui64::try_from("a0-b1-c2-d3-e4-f5").is_err());
+}
+
+#[test]
+fn test_comparison() {
+    use std::collections::{BTreeMap, HashMap};
+
+    let low = MediaAccessControlAddress::new("00:00:0c:07:ac:01").unwrap();
+    let middle = MediaAccessControlAddress::new("0a:00:00:00:00:00").unwrap();
+    let high = MediaAccessControlAddress::new("a0:b1:c2:d3:e4:f5").unwrap();
+
+    assert_eq!(
+        high,
+        MediaAccessControlAddress::new("A0-B1-C2-D3-E4-F5").unwrap()
+    );
+    assert_ne!(low, high);
+    assert!(low < middle && middle < high);
+
+    let mut addresses = vec![high, low, middle, high, low];
+    addresses.sort();
+    addresses.dedup();
+    assert_eq!(addresses, vec![low, middle, high]);
+
+    let mut ports = HashMap::new();
+    ports.insert(high, 7);
+    ports.insert(low, 3);
+    assert_eq!(
+        ports[&MediaAccessControlAddress::new("a0b1.c2d3.e4f5").unwrap()],
+        7
+    );
+
+    let ordered: BTreeMap<_, _> = ports.into_iter().collect();
+    assert_eq!(ordered.keys().copied().collect::<Vec<_>>(), vec![low, high]);
+
+    let eui = Eui64::new("a0-b1-c2-ff-fe-d3-e4-f5").unwrap();
+    assert!(Eui64::new("00-00-00-00-00-00-00-01").unwrap() < eui);
+    assert_eq!(
+        crate::datalink::DataLinkAddress::Mac48(high),
+        crate::datalink::DataLinkAddress::Mac48(high)
+    );
+}
+
+#[test]
+fn test_display() {
+    let mac = MediaAccessControlAddress::new("A0-B1-C2-D3-E4-F5").unwrap();
+
+    assert_eq!(format!("{}", mac), "a0:b1:c2:d3:e4:f5");
+    assert_eq!(format!("{:#}", mac), "A0:B1:C2:D3:E4:F5");
+    assert_eq!(format!("[{:>20}]", mac), "[   a0:b1:c2:d3:e4:f5]");
+    assert_eq!(format!("[{:<20}]", mac), "[a0:b1:c2:d3:e4:f5   ]");
+    assert_eq!(format!("[{:*^#21}]", mac), "[**A0:B1:C2:D3:E4:F5**]");
+    assert_eq!(format!("{:.8}", mac), "a0:b1:c2");
+    assert_eq!(mac.to_string(), mac.to_colon_notation());
+
+    let eui = Eui64::new("a0b1c2fffed3e4f5").unwrap();
+    assert_eq!(format!("{:#}", eui), "A0:B1:C2:FF:FE:D3:E4:F5");
+}
+
+#[test]
+fn test_from_str() {
+    fn parse<T: std::str::FromStr>(digits: &str) -> Option<T> {
+        digits.parse().ok()
+    }
+
+    let mac: MediaAccessControlAddress = "a0:b1:c2:d3:e4:f5".parse().unwrap();
+    assert_eq!(mac.to_plain_notation(), "a0b1c2d3e4f5");
+
+    for digits in [
+        "A0-B1-C2-D3-E4-F5",
+        "a0b1.c2d3.e4f5",
+        "a0b1c2d3e4f5",
+        "A0 B1 C2 D3 E4 F5",
+    ] {
+        let mac = parse::<MediaAccessControlAddress>(digits).unwrap();
+        assert_eq!(mac.to_colon_notation(), "a0:b1:c2:d3:e4:f5");
+    }
+
+    let error = "a0:b1:c2:d3:e4:g5"
+        .parse::<MediaAccessControlAddress>()
+        .unwrap_err();
+    assert_eq!(
+        error,
+        MediaAccessControlAddress::new("a0:b1:c2:d3:e4:g5").unwrap_err()
+    );
+    assert_eq!(error, MacParseError::InvalidCharacter { index: 15 });
+
+    let eui: Eui64 = "a0-b1-c2-ff-fe-d3-e4-f5".parse().unwrap();
+    assert_eq!(eui.to_plain_notation(), "a0b1c2fffed3e4f5");
+    assert!(parse::<Eui64>("a0-b1-c2-d3-e4-f5").is_none());
+}
+
+#[test]
+fn test_new_as_ref() {
+    use std::borrow::Cow;
+
+    let expected = "a0:b1:c2:d3:e4:f5";
+
+    let mac = MediaAccessControlAddress::new(String::from("a0-b1-c2-d3-e4-f5")).unwrap();
+    assert_eq!(mac.to_colon_notation(), expected);
+
+    let owned = String::from("a0b1.c2d3.e4f5");
+    let mac = MediaAccessControlAddress::new(&owned).unwrap();
+    assert_eq!(mac.to_colon_notation(), expected);
+
+    let borrowed: Cow<str> = Cow::Borrowed("a0b1c2d3e4f5");
+    let mac = MediaAccessControlAddress::new(borrowed).unwrap();
+    assert_eq!(mac.to_colon_notation(), expected);
+
+    let owned: Cow<str> = Cow::Owned(String::from("A0 B1 C2 D3 E4 F5"));
+    let mac = MediaAccessControlAddress::new(&owned).unwrap();
+    assert_eq!(mac.to_colon_notation(), expected);
+
+    assert!(MediaAccessControlAddress::new(String::from("a0:b1:c2:d3:e4")).is_err());
+
+    let eui = Eui64::new(String::from("a0-b1-c2-ff-fe-d3-e4-f5")).unwrap();
+    assert_eq!(eui.to_plain_notation(), "a0b1c2fffed3e4f5");
+}
+
+#[test]
+fn test_laa_scheme() {
+    let scheme = LaaScheme::new(&[("site", 8), ("vlan", 12), ("host", 16)]).unwrap();
+    assert_eq!(scheme.bits(), 36);
+    assert_eq!(scheme.to_string(), "site:8,vlan:12,host:16");
+    assert_eq!(scheme, " site : 8 , vlan:12,host:16".parse().unwrap());
+
+    let mac = scheme.encode(&[0x2a, 0x064, 0x0105]).unwrap();
+    assert_eq!(mac.to_colon_notation(), "02:02:a0:64:01:05");
+    assert!(mac.is_laa() && mac.is_unicast());
+    assert_eq!(scheme.decode(&mac).unwrap(), vec![0x2a, 0x064, 0x0105]);
+    assert_eq!(scheme.get(&mac, "host").unwrap(), 0x0105);
+    assert!(scheme.get(&mac, "rack").is_err());
+
+    // Fields that reach the first octet skip the multicast and local
+    // bits.
+    let wide = LaaScheme::new(&[("high", 6), ("low", 40)]).unwrap();
+    let mac = wide.encode(&[0b11_1111, 0xff_ffff_ffff]).unwrap();
+    assert_eq!(mac.to_colon_notation(), "fe:ff:ff:ff:ff:ff");
+    assert_eq!(wide.decode(&mac).unwrap(), vec![0b11_1111, 0xff_ffff_ffff]);
+
+    let mac = wide.encode(&[0b10_0001, 1]).unwrap();
+    assert_eq!(mac.to_colon_notation(), "86:00:00:00:00:01");
+    assert_eq!(wide.decode(&mac).unwrap(), vec![0b10_0001, 1]);
+
+    // Values must fit, and addresses must be ones the scheme encodes.
+    assert!(scheme.encode(&[0x100, 0, 0]).is_err());
+    assert!(scheme.encode(&[0, 0]).is_err());
+
+    for digits in [
+        "00:02:a0:64:01:05",
+        "03:02:a0:64:01:05",
+        "02:12:a0:64:01:05",
+    ]
+    .iter()
+    {
+        let mac = MediaAccessControlAddress::new(digits).unwrap();
+        assert!(scheme.decode(&mac).is_err());
+    }
+
+    assert!(LaaScheme::new(&[]).is_err());
+    assert!(LaaScheme::new(&[("site", 0)]).is_err());
+    assert!(LaaScheme::new(&[("site", 8), ("site", 8)]).is_err());
+    assert!(LaaScheme::new(&[("site", 40), ("host", 7)]).is_err());
+    assert!(LaaScheme::new(&[("site:8", 8)]).is_err());
+    assert!("site:8,vlan".parse::<LaaScheme>().is_err());
+    assert!("site:x".parse::<LaaScheme>().is_err());
+}
+
+#[test]
+fn test_mac_set() {
+    let addresses: Vec<MediaAccessControlAddress> = [
+        "a0:b1:c2:d3:e4:f5",
+        "00:00:0c:07:ac:01",
+        "a0:b1:c2:00:00:01",
+        "a0-b1-c2-d3-e4-f5",
+    ]
+    .iter()
+    .map(|digits| MediaAccessControlAddress::new(digits).unwrap())
+    .collect();
+
+    let mut set: MacSet = addresses.iter().collect();
+    assert_eq!(set.len(), 3);
+    assert!(set.contains(&addresses[1]));
+
+    let listed: Vec<String> = set.iter().map(|mac| mac.to_colon_notation()).collect();
+    assert_eq!(
+        listed,
+        [
+            "00:00:0c:07:ac:01",
+            "a0:b1:c2:00:00:01",
+            "a0:b1:c2:d3:e4:f5"
+        ]
+    );
+
+    let oui: MacPrefix = "a0:b1:c2:00:00:00/24".parse().unwrap();
+    assert_eq!(set.range(&oui).count(), 2);
+
+    set.extend(vec![
+        MediaAccessControlAddress::new("ff:ff:ff:ff:ff:ff").unwrap()
+    ]);
+    set.extend(addresses.iter());
+    assert_eq!(set.len(), 4);
+
+    assert!(set.remove(&addresses[0]));
+    assert!(!set.remove(&addresses[0]));
+    assert!(!set.insert(&addresses[1]));
+    assert!(set.insert(&addresses[0]));
+
+    let owned: MacSet = addresses.into_iter().collect();
+    assert_eq!(owned.len(), 3);
+
+    set.clear();
+    assert!(set.is_empty());
+    assert_eq!(set, MacSet::new());
+
+    let table: MacTable<u16> = vec![
+        (
+            MediaAccessControlAddress::new("a0:b1:c2:d3:e4:f5").unwrap(),
+            1,
+        ),
+        (
+            MediaAccessControlAddress::new("00:00:0c:07:ac:01").unwrap(),
+            2,
+        ),
+    ]
+    .into_iter()
+    .collect();
+    assert_eq!(table.len(), 2);
+    assert_eq!(table.iter().next().unwrap().1, &2);
+}
+
+#[test]
+fn test_mac_prefix_map() {
+    let entries = [
+        ("a0:b1:c2:00:00:00/24", "example"),
+        ("a0:b1:c2:d0:00:00/28", "devices"),
+        ("a0:b1:c2:d3:e0:00/36", "sensors"),
+        ("00:00:00:00:00:00/0", "anything"),
+    ];
+
+    let mut map: MacPrefixMap<&str> = entries
+        .iter()
+        .map(|(prefix, name)| (prefix.parse().unwrap(), *name))
+        .collect();
+    assert_eq!(map.len(), 4);
+
+    fn lookup(map: &MacPrefixMap<&'static str>, digits: &str) -> Option<(String, &'static str)> {
+        map.longest_match(&MediaAccessControlAddress::new(digits).unwrap())
+            .map(|(prefix, name)| (prefix.to_string(), *name))
+    }
+
+    assert_eq!(
+        lookup(&map, "a0:b1:c2:d3:e4:f5"),
+        Some((String::from("a0:b1:c2:d3:e0:00/36"), "sensors"))
+    );
+    assert_eq!(lookup(&map, "a0:b1:c2:d4:00:00").unwrap().1, "devices");
+    assert_eq!(lookup(&map, "a0:b1:c2:e0:00:00").unwrap().1, "example");
+    assert_eq!(lookup(&map, "02:00:00:00:00:00").unwrap().1, "anything");
+
+    let devices: MacPrefix = "a0:b1:c2:d0:00:00/28".parse().unwrap();
+    assert_eq!(map.get(&devices), Some(&"devices"));
+    assert_eq!(map.insert(devices, "replaced"), Some("devices"));
+    assert_eq!(map.len(), 4);
+
+    map.extend(vec![("a0:b1:c2:d3:e4:f5/48".parse().unwrap(), "host")]);
+    assert_eq!(lookup(&map, "a0:b1:c2:d3:e4:f5").unwrap().1, "host");
+
+    let everything: MacPrefix = "00:00:00:00:00:00/0".parse().unwrap();
+    assert_eq!(map.remove(&everything), Some("anything"));
+    assert_eq!(map.remove(&everything), None);
+    assert_eq!(lookup(&map, "02:00:00:00:00:00"), None);
+
+    let listed: Vec<String> = map.iter().map(|(prefix, _)| prefix.to_string()).collect();
+    assert_eq!(listed[0], "a0:b1:c2:00:00:00/24");
+
+    map.clear();
+    assert!(map.is_empty());
+}
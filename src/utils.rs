@@ -0,0 +1,485 @@
+use super::format::Notation;
+use std::error::Error;
+use std::fmt;
+use std::ops::RangeInclusive;
+
+/// Returns the separator, the number of groups, and the number of
+/// digits in each group of a MAC address in `notation`.
+fn layout(notation: Notation) -> (Option<u8>, usize, RangeInclusive<usize>) {
+    match notation {
+        Notation::Plain => (None, 1, 12..=12),
+        Notation::Hyphen => (Some(b'-'), 6, 2..=2),
+        Notation::Colon => (Some(b':'), 6, 2..=2),
+        Notation::Dot => (Some(b'.'), 3, 4..=4),
+        Notation::FourDigitHyphen => (Some(b'-'), 3, 4..=4),
+        Notation::Space => (Some(b' '), 6, 2..=2),
+        Notation::Solaris => (Some(b':'), 6, 1..=2),
+    }
+}
+
+/// Whether `digits` is a MAC address in exactly `notation`.
+///
+/// Colon notation is also Solaris notation, since Solaris notation
+/// merely allows omitting leading zeros.
+pub(crate) fn matches(digits: &str, notation: Notation) -> bool {
+    let (separator, groups, widths) = layout(notation);
+    let mut count = 1;
+    let mut width = 0;
+
+    for byte in digits.bytes() {
+        if byte.is_ascii_hexdigit() {
+            width += 1;
+        } else if Some(byte) == separator && widths.contains(&width) {
+            count += 1;
+            width = 0;
+        } else {
+            return false;
+        }
+    }
+
+    count == groups && widths.contains(&width)
+}
+
+/// Returns the first notation, in the order of `Notation::ALL`,
+/// in which `digits` is a MAC address, if any.
+pub(crate) fn notation(digits: &str) -> Option<Notation> {
+    Notation::ALL
+        .iter()
+        .copied()
+        .find(|notation| matches(digits, *notation))
+}
+
+/// Checks whether `digits` is a MAC address without building a
+/// `MediaAccessControlAddress`, and returns its notation.
+///
+/// Colon notation takes precedence over Solaris notation, so
+/// only addresses that actually omit leading zeros are
+/// classified as Solaris notation.
+///
+/// To explain a failure to a person, call `diagnose` instead.
+pub fn validate(digits: &str) -> Result<Notation, MacParseError> {
+    inspect(digits, 6).map_err(|error| error.kind())
+}
+
+/// "Cleans" a MAC address by converting uppercase to lowercase
+/// letters, restoring any leading zeros that Solaris notation
+/// omits, and removing all hyphens, colons, dots, and spaces.
+pub fn clean(digits: &str) -> String {
+    let mut clean = String::with_capacity(digits.len());
+
+    for group in digits.split(|c: char| !c.is_ascii_hexdigit()) {
+        for _ in group.len()..2 {
+            clean.push('0');
+        }
+
+        clean.extend(group.chars().map(|c| c.to_ascii_lowercase()));
+    }
+
+    clean
+}
+
+/// Converts a valid identifier of `N` octets in any notation into
+/// its octets, without allocating.
+pub(crate) fn to_octets<const N: usize>(digits: &str) -> [u8; N] {
+    let mut octets = [0; N];
+    let mut nibble = 0;
+
+    for group in digits.split(|c: char| !c.is_ascii_hexdigit()) {
+        // A group of one digit omits its leading zero.
+        nibble += group.len() % 2;
+
+        for digit in group.bytes() {
+            let value = (digit as char).to_digit(16).unwrap() as u8;
+            octets[nibble / 2] |= value << (4 * (1 - nibble % 2));
+            nibble += 1;
+        }
+    }
+
+    octets
+}
+
+/// Why a string is not an identifier, as `new`, `from_str`,
+/// `try_from`, and `validate` report it, for matching on the
+/// failure programmatically.
+///
+/// `diagnose` explains the same failure in more detail, and
+/// `ParseError::kind` returns it.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum MacParseError {
+    /// The string is empty or has too few or too many digits or
+    /// groups.
+    InvalidLength,
+    /// The string contains a character that is neither a
+    /// hexadecimal digit nor a separator, at byte `index`.
+    InvalidCharacter {
+        /// The byte offset of the character.
+        index: usize,
+    },
+    /// A separator is missing, doubled, or out of place, or the
+    /// string is in a notation other than the one required.
+    InvalidSeparator,
+    /// The string mixes the separators of different notations
+    /// (for example, `a0-b1:c2-d3:e4-f5`).
+    MixedNotation,
+}
+
+impl fmt::Display for MacParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            MacParseError::InvalidLength => write!(f, "invalid length"),
+            MacParseError::InvalidCharacter { index } => {
+                write!(f, "invalid character at byte {}", index)
+            }
+            MacParseError::InvalidSeparator => write!(f, "invalid separator"),
+            MacParseError::MixedNotation => write!(f, "mixed notation"),
+        }
+    }
+}
+
+impl Error for MacParseError {}
+
+/// The explanation that `diagnose` returns when a string is not a
+/// MAC address in any supported notation.
+///
+/// Besides a human-readable explanation, it reports the byte
+/// offset and character at which parsing went wrong and the
+/// notation that the string most closely resembles.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ParseError {
+    kind: MacParseError,
+    position: Option<usize>,
+    character: Option<char>,
+    notation: Option<Notation>,
+    reason: String,
+    digits: usize,
+}
+
+impl ParseError {
+    /// Returns why the string is not an identifier, for matching
+    /// on, which is the error that `new` returns for the same
+    /// string.
+    pub fn kind(&self) -> MacParseError {
+        self.kind
+    }
+
+    /// Returns the byte offset of the offending character, if
+    /// there is one (there is not if the string ends early).
+    pub fn position(&self) -> Option<usize> {
+        self.position
+    }
+
+    /// Returns the offending character, if there is one.
+    pub fn character(&self) -> Option<char> {
+        self.character
+    }
+
+    /// Returns the notation that the string most closely
+    /// resembles, if any.
+    pub fn notation(&self) -> Option<Notation> {
+        self.notation
+    }
+
+    /// Returns what is wrong with the string (for example,
+    /// `group 4 has 3 digits`).
+    pub fn reason(&self) -> &str {
+        &self.reason
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.notation {
+            Some(notation) => write!(
+                f,
+                "Looks like {} notation, but {}",
+                notation_name(notation),
+                self.reason
+            )?,
+            None => write!(
+                f,
+                "Pass in {} hexadecimal digits; {}",
+                self.digits, self.reason
+            )?,
+        }
+
+        if let (Some(position), Some(character)) = (self.position, self.character) {
+            write!(f, " (found {:?} at byte {})", character, position)?;
+        }
+
+        write!(f, ".")
+    }
+}
+
+impl Error for ParseError {}
+
+impl From<ParseError> for String {
+    fn from(error: ParseError) -> Self {
+        error.to_string()
+    }
+}
+
+impl ParseError {
+    /// Instantiates `ParseError` for a string that is a MAC address,
+    /// but in `found` rather than the `expected` notation.
+    pub(crate) fn wrong_notation(found: Notation, expected: Notation) -> Self {
+        Self {
+            kind: MacParseError::InvalidSeparator,
+            position: None,
+            character: None,
+            notation: Some(found),
+            reason: format!("it is not in {} notation", notation_name(expected)),
+            digits: 12,
+        }
+    }
+}
+
+/// Returns the name of a notation as it appears in messages.
+fn notation_name(notation: Notation) -> &'static str {
+    match notation {
+        Notation::Plain => "plain",
+        Notation::Hyphen => "hyphen",
+        Notation::Colon => "colon",
+        Notation::Dot => "dot",
+        Notation::FourDigitHyphen => "four-digit-hyphen",
+        Notation::Space => "space",
+        Notation::Solaris => "Solaris",
+    }
+}
+
+/// Returns the notation that a separator implies.
+fn separated_notation(separator: char) -> Option<Notation> {
+    match separator {
+        '-' => Some(Notation::Hyphen),
+        ':' => Some(Notation::Colon),
+        '.' => Some(Notation::Dot),
+        ' ' => Some(Notation::Space),
+        _ => None,
+    }
+}
+
+/// Checks whether `digits` is a MAC address, as `validate` does,
+/// but explains a failure for a person: why the string is not in
+/// any supported notation, the first offending character, and the
+/// closest notation.
+pub fn diagnose(digits: &str) -> Result<Notation, ParseError> {
+    inspect(digits, 6)
+}
+
+/// Checks whether `digits` is an EUI-64, as `Eui64::new` does, and
+/// explains a failure the way `diagnose` does.
+pub fn diagnose_eui64(digits: &str) -> Result<Notation, ParseError> {
+    inspect(digits, 8)
+}
+
+/// Checks whether `digits` is an identifier that is `octets` octets
+/// wide, group by group, and returns its notation or explains why
+/// it is not one.
+///
+/// Every parser of identifiers goes through it, so the kind that
+/// `new` reports always agrees with `diagnose`.
+pub(crate) fn inspect(digits: &str, octets: usize) -> Result<Notation, ParseError> {
+    let error = |kind: MacParseError,
+                 position: Option<usize>,
+                 notation: Option<Notation>,
+                 reason: String| ParseError {
+        kind,
+        position,
+        character: position.and_then(|position| digits[position..].chars().next()),
+        notation,
+        reason,
+        digits: octets * 2,
+    };
+
+    if digits.is_empty() {
+        return Err(error(
+            MacParseError::InvalidLength,
+            None,
+            None,
+            String::from("the string is empty"),
+        ));
+    }
+
+    let separator = digits.chars().find(|c| separated_notation(*c).is_some());
+    let notation = match separator.map_or(Some(Notation::Plain), separated_notation) {
+        // Half as many hyphen-separated groups as octets resemble
+        // four-digit-hyphen rather than hyphen notation.
+        Some(Notation::Hyphen) if digits.split('-').count() == octets / 2 => {
+            Some(Notation::FourDigitHyphen)
+        }
+        notation => notation,
+    };
+
+    for (position, character) in digits.char_indices() {
+        if !character.is_ascii_hexdigit() && separated_notation(character).is_none() {
+            return Err(error(
+                MacParseError::InvalidCharacter { index: position },
+                Some(position),
+                notation,
+                String::from(
+                    "it contains a character that is neither a hexadecimal digit nor a separator",
+                ),
+            ));
+        }
+
+        if separated_notation(character).is_some() && Some(character) != separator {
+            return Err(error(
+                MacParseError::MixedNotation,
+                Some(position),
+                notation,
+                String::from("it mixes separators"),
+            ));
+        }
+    }
+
+    let (separator, count, widths) = match notation {
+        Some(Notation::Hyphen) | Some(Notation::Space) => (separator.unwrap(), octets, 2..=2),
+        Some(Notation::Colon) => (':', octets, 1..=2),
+        Some(Notation::Dot) => ('.', octets / 2, 4..=4),
+        Some(Notation::FourDigitHyphen) => ('-', octets / 2, 4..=4),
+        _ => {
+            let length = digits.len();
+
+            if length == octets * 2 {
+                return Ok(Notation::Plain);
+            }
+
+            let position = if length > octets * 2 {
+                Some(octets * 2)
+            } else {
+                None
+            };
+
+            return Err(error(
+                MacParseError::InvalidLength,
+                position,
+                notation,
+                format!("it has {} digits instead of {}", length, octets * 2),
+            ));
+        }
+    };
+
+    let mut start = 0;
+    let mut solaris = false;
+
+    for (index, group) in digits.split(separator).enumerate() {
+        let number = index + 1;
+        let length = group.len();
+
+        if index >= count {
+            return Err(error(
+                MacParseError::InvalidLength,
+                Some(start - 1),
+                notation,
+                format!("it has more than {} groups", count),
+            ));
+        }
+
+        if length == 0 {
+            let position = if start < digits.len() {
+                start
+            } else {
+                start - 1
+            };
+            return Err(error(
+                MacParseError::InvalidSeparator,
+                Some(position),
+                notation,
+                format!("group {} is empty", number),
+            ));
+        }
+
+        if !widths.contains(&length) {
+            let position = if length > *widths.end() {
+                Some(start + widths.end())
+            } else if start + length < digits.len() {
+                Some(start + length)
+            } else {
+                None
+            };
+
+            // The right number of digits in the wrong groups means
+            // that the separators are out of place.
+            let kind = if digits.bytes().filter(u8::is_ascii_hexdigit).count() == octets * 2 {
+                MacParseError::InvalidSeparator
+            } else {
+                MacParseError::InvalidLength
+            };
+
+            return Err(error(
+                kind,
+                position,
+                notation,
+                format!("group {} has {} digits", number, length),
+            ));
+        }
+
+        solaris |= separator == ':' && length == 1;
+        start += length + 1;
+    }
+
+    let groups = digits.split(separator).count();
+
+    if groups == count {
+        // Colon notation that omits leading zeros is Solaris notation.
+        if solaris {
+            Ok(Notation::Solaris)
+        } else {
+            Ok(notation.unwrap())
+        }
+    } else {
+        Err(error(
+            MacParseError::InvalidLength,
+            None,
+            notation,
+            format!("it has {} groups instead of {}", groups, count),
+        ))
+    }
+}
+
+/// Deserializes a value that human-readable formats, such as JSON,
+/// hold as a string and other formats hold as bytes, parsing either
+/// with the given function.
+#[cfg(feature = "serde")]
+pub(crate) fn deserialize<'de, D, T>(
+    deserializer: D,
+    expecting: &'static str,
+    from_str: fn(&str) -> Result<T, String>,
+    from_bytes: fn(&[u8]) -> Result<T, String>,
+) -> Result<T, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    struct Visitor<T> {
+        expecting: &'static str,
+        from_str: fn(&str) -> Result<T, String>,
+        from_bytes: fn(&[u8]) -> Result<T, String>,
+    }
+
+    impl<'de, T> serde::de::Visitor<'de> for Visitor<T> {
+        type Value = T;
+
+        fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+            f.write_str(self.expecting)
+        }
+
+        fn visit_str<E: serde::de::Error>(self, value: &str) -> Result<T, E> {
+            (self.from_str)(value).map_err(E::custom)
+        }
+
+        fn visit_bytes<E: serde::de::Error>(self, value: &[u8]) -> Result<T, E> {
+            (self.from_bytes)(value).map_err(E::custom)
+        }
+    }
+
+    let visitor = Visitor {
+        expecting,
+        from_str,
+        from_bytes,
+    };
+
+    if deserializer.is_human_readable() {
+        deserializer.deserialize_str(visitor)
+    } else {
+        deserializer.deserialize_bytes(visitor)
+    }
+}
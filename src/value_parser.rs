@@ -0,0 +1,64 @@
+use super::identifier::Identifier;
+use super::utils::{self, MacParseError};
+use clap::builder::{TypedValueParser, ValueParserFactory};
+use clap::error::ErrorKind;
+use std::convert::TryFrom;
+use std::ffi::OsStr;
+
+/// `IdentifierParser` is a `clap` value parser for
+/// `MediaAccessControlAddress` (`N` = 6) and `Eui64` (`N` = 8),
+/// which accepts everything that their `new` methods accept.
+///
+/// `value_parser!` picks it for either type, so command-line tools
+/// can declare arguments such as `--mac <MAC>` with the address as
+/// their type.  Invalid values are reported with the explanation
+/// that `utils::diagnose` gives, such as the offending character
+/// and the closest notation.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct IdentifierParser<const N: usize>;
+
+impl<const N: usize> TypedValueParser for IdentifierParser<N>
+where
+    for<'a> Identifier<N>: TryFrom<&'a str, Error = MacParseError>,
+{
+    type Value = Identifier<N>;
+
+    fn parse_ref(
+        &self,
+        cmd: &clap::Command,
+        arg: Option<&clap::Arg>,
+        value: &OsStr,
+    ) -> Result<Self::Value, clap::Error> {
+        let digits = value
+            .to_str()
+            .ok_or_else(|| clap::Error::new(ErrorKind::InvalidUtf8).with_cmd(cmd))?;
+
+        // The same check as `new`, with the explanation of `diagnose`.
+        utils::inspect(digits, N).map_err(|error| {
+            let arg = arg.map_or_else(|| String::from("..."), ToString::to_string);
+            clap::Error::raw(
+                ErrorKind::ValueValidation,
+                format!("invalid value '{}' for '{}': {}\n", digits, arg, error),
+            )
+            .with_cmd(cmd)
+        })?;
+
+        Ok(Identifier::from_array(utils::to_octets(digits)))
+    }
+}
+
+impl ValueParserFactory for Identifier<6> {
+    type Parser = IdentifierParser<6>;
+
+    fn value_parser() -> Self::Parser {
+        IdentifierParser
+    }
+}
+
+impl ValueParserFactory for Identifier<8> {
+    type Parser = IdentifierParser<8>;
+
+    fn value_parser() -> Self::Parser {
+        IdentifierParser
+    }
+}
@@ -0,0 +1,531 @@
+use super::error::Error;
+use super::macaddress::MediaAccessControlAddress;
+use super::prefix::MacPrefix;
+use std::collections::HashMap;
+use std::hash::BuildHasher;
+use std::str::FromStr;
+
+mod cache;
+mod enrich;
+
+#[cfg(feature = "db")]
+mod database;
+
+#[cfg(feature = "net")]
+mod client;
+
+#[cfg(feature = "net")]
+mod online;
+
+#[cfg(feature = "phf")]
+mod perfect;
+
+pub use self::cache::CachedResolver;
+pub use self::enrich::{Enrich, Enriched};
+
+#[cfg(feature = "db")]
+pub use self::database::{Assignment, Metadata, SignatureVerifier, VendorDatabase};
+
+#[cfg(feature = "net")]
+pub use self::client::RegistryClient;
+
+#[cfg(feature = "net")]
+pub use self::online::OnlineResolver;
+
+#[cfg(feature = "phf")]
+pub use self::perfect::{codegen_phf, PhfVendorTable};
+
+// The tables that `codegen_phf` generates name `phf` through this
+// path, so that crates that embed them need not depend on it.
+#[cfg(feature = "phf")]
+#[doc(hidden)]
+pub use phf;
+
+/// A source of vendor information for MAC addresses.
+///
+/// Implement this trait to look vendors up in any backend, such as
+/// an embedded table, a memory-mapped file, a remote API, or your
+/// own map, and then call `MediaAccessControlAddress::vendor_with`.
+pub trait VendorResolver {
+    /// Returns the name of the organization that registered the
+    /// block containing the MAC address, `None` if no block
+    /// contains it, or an error if the backend failed.
+    fn resolve(&self, mac: &MediaAccessControlAddress) -> Result<Option<String>, Error>;
+
+    /// Returns the vendors of many MAC addresses at once, in the
+    /// same order, each with its own error if the backend failed
+    /// for it.
+    ///
+    /// By default, it resolves each address in turn.  Backends that
+    /// can answer many addresses at once, such as remote APIs with
+    /// batch endpoints, override it.
+    fn resolve_batch(
+        &self,
+        macs: &[&MediaAccessControlAddress],
+    ) -> Vec<Result<Option<String>, Error>> {
+        macs.iter().map(|mac| self.resolve(mac)).collect()
+    }
+}
+
+impl<R: VendorResolver + ?Sized> VendorResolver for &R {
+    fn resolve(&self, mac: &MediaAccessControlAddress) -> Result<Option<String>, Error> {
+        (**self).resolve(mac)
+    }
+
+    fn resolve_batch(
+        &self,
+        macs: &[&MediaAccessControlAddress],
+    ) -> Vec<Result<Option<String>, Error>> {
+        (**self).resolve_batch(macs)
+    }
+}
+
+impl<R: VendorResolver + ?Sized> VendorResolver for Box<R> {
+    fn resolve(&self, mac: &MediaAccessControlAddress) -> Result<Option<String>, Error> {
+        (**self).resolve(mac)
+    }
+
+    fn resolve_batch(
+        &self,
+        macs: &[&MediaAccessControlAddress],
+    ) -> Vec<Result<Option<String>, Error>> {
+        (**self).resolve_batch(macs)
+    }
+}
+
+/// Maps 24-bit OUIs (for example, `0x00000c`) to organization names.
+impl<S: BuildHasher> VendorResolver for HashMap<u32, String, S> {
+    fn resolve(&self, mac: &MediaAccessControlAddress) -> Result<Option<String>, Error> {
+        let oui = (mac.to_decimal_representation() >> 24) as u32;
+        Ok(self.get(&oui).cloned())
+    }
+}
+
+/// One block of addresses that the IEEE Registration Authority
+/// assigned to an organization.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Record {
+    /// The first address in the block, as a 48-bit value.
+    pub prefix: u64,
+    /// The number of leading bits that the block fixes
+    /// (24 for MA-L, 28 for MA-M, or 36 for MA-S).
+    pub length: u8,
+    /// The name of the organization.
+    pub organization: &'static str,
+    /// The postal address of the organization, as registered
+    /// (empty if the registry withheld it).
+    pub address: &'static str,
+}
+
+impl Record {
+    /// Whether the block contains the MAC address.
+    pub fn contains(&self, mac: &MediaAccessControlAddress) -> bool {
+        let shift = 48 - u32::from(self.length);
+        mac.to_decimal_representation() >> shift == self.prefix >> shift
+    }
+
+    /// Returns the number of addresses in the block
+    /// (2^24 for MA-L, 2^20 for MA-M, or 2^12 for MA-S).
+    pub fn block_size(&self) -> u64 {
+        1 << (48 - u32::from(self.length))
+    }
+
+    /// Returns the name of the registry that assigned the block
+    /// (`MA-L`, `MA-M`, or `MA-S`), or `None` for any other length.
+    pub fn registry(&self) -> Option<&'static str> {
+        match self.length {
+            24 => Some("MA-L"),
+            28 => Some("MA-M"),
+            36 => Some("MA-S"),
+            _ => None,
+        }
+    }
+}
+
+/// A block's prefix and length, which identify it and order it.
+type Key = (u64, u8);
+
+/// Returns the keys of the registered blocks
+/// that could overlap `prefix`: the keys of the blocks that would
+/// enclose it, and the first and last keys of the blocks that
+/// could start within it.
+fn overlap_keys(prefix: &MacPrefix) -> (Vec<Key>, Key, Key) {
+    let first = prefix.first().to_decimal_representation();
+    let last = prefix.last().to_decimal_representation();
+
+    let enclosing = [24, 28, 36]
+        .iter()
+        .filter(|length| **length < prefix.length())
+        .map(|length| {
+            let shift = 48 - u32::from(*length);
+            (first >> shift << shift, *length)
+        })
+        .filter(|(start, _)| *start < first)
+        .collect();
+
+    (enclosing, (first, 0), (last, u8::MAX))
+}
+
+/// A compact, read-only table of `Record`s that lives in static
+/// memory, such as one generated at build time by `codegen`.
+///
+/// The records must be sorted by prefix and then by length,
+/// which `codegen` guarantees.
+#[derive(Clone, Copy, Debug)]
+pub struct VendorTable {
+    records: &'static [Record],
+}
+
+impl VendorTable {
+    /// Instantiates `VendorTable` with sorted records.
+    pub const fn new(records: &'static [Record]) -> Self {
+        Self { records }
+    }
+
+    /// Returns the number of records in the table.
+    pub fn len(&self) -> usize {
+        self.records.len()
+    }
+
+    /// Whether the table has no records.
+    pub fn is_empty(&self) -> bool {
+        self.records.is_empty()
+    }
+
+    /// Returns the most specific record whose block contains the
+    /// MAC address (MA-S before MA-M before MA-L).
+    pub fn lookup(&self, mac: &MediaAccessControlAddress) -> Option<&'static Record> {
+        let value = mac.to_decimal_representation();
+        let records = self.records;
+
+        [36, 28, 24].iter().find_map(|length| {
+            let shift = 48 - u32::from(*length);
+            let prefix = value >> shift << shift;
+
+            records
+                .binary_search_by_key(&(prefix, *length), |record| (record.prefix, record.length))
+                .ok()
+                .map(|index| &records[index])
+        })
+    }
+
+    /// Whether any record's block contains the MAC address.
+    ///
+    /// It is cheaper than `lookup` for hot paths that only need to
+    /// tell registered addresses from unregistered or made-up ones.
+    pub fn is_registered(&self, mac: &MediaAccessControlAddress) -> bool {
+        let value = mac.to_decimal_representation();
+
+        // Most blocks are MA-L blocks, so they are checked first.
+        [24, 28, 36].iter().any(|length| {
+            let shift = 48 - u32::from(*length);
+            let key = (value >> shift << shift, *length);
+
+            self.records
+                .binary_search_by_key(&key, |record| (record.prefix, record.length))
+                .is_ok()
+        })
+    }
+}
+
+impl VendorTable {
+    /// Returns every record whose block overlaps `prefix`, either
+    /// because it encloses the prefix or because it lies within
+    /// it, sorted by prefix and then by length.
+    ///
+    /// Use it to check that a planned block of addresses (for
+    /// example, for a locally-administered scheme) does not collide
+    /// with registered ones.
+    pub fn overlapping(&self, prefix: &MacPrefix) -> Vec<&'static Record> {
+        let records = self.records;
+        let (enclosing, lower, upper) = overlap_keys(prefix);
+        let key = |record: &Record| (record.prefix, record.length);
+
+        let mut overlapping: Vec<&'static Record> = enclosing
+            .iter()
+            .filter_map(|wanted| {
+                records
+                    .binary_search_by_key(wanted, key)
+                    .ok()
+                    .map(|index| &records[index])
+            })
+            .collect();
+
+        let start = records.partition_point(|record| key(record) < lower);
+        let end = records.partition_point(|record| key(record) <= upper);
+        overlapping.extend(records[start..end].iter());
+        overlapping
+    }
+}
+
+impl VendorResolver for VendorTable {
+    fn resolve(&self, mac: &MediaAccessControlAddress) -> Result<Option<String>, Error> {
+        Ok(self
+            .lookup(mac)
+            .map(|record| record.organization.to_string()))
+    }
+}
+
+/// Splits one line of CSV into fields, honoring double quotes
+/// and doubled double quotes within them.
+fn split_csv_line(line: &str) -> Vec<String> {
+    let mut fields = vec![String::new()];
+    let mut quoted = false;
+    let mut characters = line.chars().peekable();
+
+    while let Some(character) = characters.next() {
+        match character {
+            '"' if quoted && characters.peek() == Some(&'"') => {
+                characters.next();
+                fields.last_mut().unwrap().push('"');
+            }
+            '"' => quoted = !quoted,
+            ',' if !quoted => fields.push(String::new()),
+            _ => fields.last_mut().unwrap().push(character),
+        }
+    }
+
+    fields
+}
+
+/// One entry of a registry CSV: its prefix, length, organization,
+/// and address.
+type Entry = (u64, u8, String, String);
+
+/// Parses one line of a registry CSV in the IEEE layout, numbered
+/// from 0, into an entry, or `None` for blank lines, comments
+/// (lines that begin with `#`), and a header row before any
+/// entries.
+fn parse_registry_line(line: &str, number: usize, first: bool) -> Result<Option<Entry>, Error> {
+    let line = line.trim_end_matches('\r');
+
+    if line.trim().is_empty() || line.starts_with('#') {
+        return Ok(None);
+    }
+
+    let fields = split_csv_line(line);
+
+    if first && fields[0].trim() == "Registry" {
+        return Ok(None);
+    }
+
+    if fields.len() < 3 {
+        return Err(Error::Registry(format!(
+            "Pass in at least 3 fields on line {}.",
+            number + 1
+        )));
+    }
+
+    let assignment = fields[1].trim();
+    let digits = assignment.len();
+
+    if ![6, 7, 9].contains(&digits) || !assignment.chars().all(|c| c.is_ascii_hexdigit()) {
+        return Err(Error::Registry(format!(
+            "Pass in an assignment of 6, 7, or 9 hexadecimal digits on line {}.",
+            number + 1
+        )));
+    }
+
+    let length = digits as u8 * 4;
+    let prefix = u64::from_str_radix(assignment, 16).unwrap() << (48 - u32::from(length));
+    let address = fields.get(3).map_or("", |address| address.trim());
+
+    Ok(Some((
+        prefix,
+        length,
+        fields[2].trim().to_string(),
+        address.to_string(),
+    )))
+}
+
+/// Parses a registry CSV in the IEEE layout into
+/// `(prefix, length, organization, address)` tuples, sorted by
+/// prefix and then by length, without duplicates.
+///
+/// Lines that begin with `#` are comments.
+fn parse_registry(csv: &str) -> Result<Vec<Entry>, Error> {
+    let mut records = Vec::new();
+
+    for (number, line) in csv.lines().enumerate() {
+        if let Some(entry) = parse_registry_line(line, number, records.is_empty())? {
+            records.push(entry);
+        }
+    }
+
+    records.sort();
+    records.dedup_by(|a, b| a.0 == b.0 && a.1 == b.1);
+    Ok(records)
+}
+
+/// Converts a registry CSV into Rust source for a slice of
+/// `Record`s that `VendorTable::new` accepts.
+///
+/// The CSV uses the IEEE layout (`Registry`, `Assignment`,
+/// `Organization Name`, and optionally `Organization Address`),
+/// with or without a header row.  Assignments have 6, 7, or 9
+/// hexadecimal digits (MA-L, MA-M, or MA-S).
+///
+/// Call it from `build.rs` and write the result into `OUT_DIR`,
+/// then `include!` that file to embed your own curated vendor
+/// list at compile time:
+///
+/// ```ignore
+/// // build.rs
+/// let csv = std::fs::read_to_string("oui.csv").unwrap();
+/// let source = macaddress::vendor::codegen(&csv).unwrap();
+/// let out = std::path::Path::new(&std::env::var("OUT_DIR").unwrap()).join("oui.rs");
+/// std::fs::write(out, source).unwrap();
+///
+/// // main.rs
+/// use macaddress::vendor::VendorTable;
+///
+/// static VENDORS: VendorTable = VendorTable::new(include!(concat!(env!("OUT_DIR"), "/oui.rs")));
+/// ```
+pub fn codegen(csv: &str) -> Result<String, Error> {
+    let records = parse_registry(csv)?;
+    let mut source = String::from("&[\n");
+
+    for (prefix, length, organization, address) in records.iter() {
+        source.push_str(&format!(
+            "    {},\n",
+            record_source(*prefix, *length, organization, address)
+        ));
+    }
+
+    source.push(']');
+    Ok(source)
+}
+
+/// Returns Rust source for one `Record`.
+fn record_source(prefix: u64, length: u8, organization: &str, address: &str) -> String {
+    format!(
+        "::macaddress::vendor::Record {{ prefix: {:#014x}, length: {}, organization: {:?}, address: {:?} }}",
+        prefix, length, organization, address
+    )
+}
+
+/// A selection of registry entries, by organization or by prefix,
+/// for embedding only the vendors that a build cares about (for
+/// example, in a firmware image that cannot carry the full
+/// registry).
+///
+/// An entry is selected if its organization's name contains any of
+/// the selected names, ignoring case, or if its block overlaps any
+/// of the selected prefixes.  A filter that selects nothing keeps
+/// every entry, so that builds without a selection embed the full
+/// registry.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct VendorFilter {
+    organizations: Vec<String>,
+    prefixes: Vec<MacPrefix>,
+}
+
+impl VendorFilter {
+    /// Instantiates a `VendorFilter` that selects nothing, and so
+    /// keeps every entry.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Also selects the organizations whose names contain `name`,
+    /// ignoring case.
+    pub fn with_organization(mut self, name: &str) -> Self {
+        self.organizations.push(name.to_lowercase());
+        self
+    }
+
+    /// Also selects the blocks that overlap `prefix`.
+    pub fn with_prefix(mut self, prefix: MacPrefix) -> Self {
+        self.prefixes.push(prefix);
+        self
+    }
+
+    /// Whether the filter selects nothing, and so keeps every entry.
+    pub fn is_empty(&self) -> bool {
+        self.organizations.is_empty() && self.prefixes.is_empty()
+    }
+
+    /// Instantiates `VendorFilter` from the environment variable
+    /// `name`, in the format that `from_str` parses, or selects
+    /// nothing if it is unset.
+    ///
+    /// Call it from `build.rs`, along with
+    /// `cargo:rerun-if-env-changed`, to choose the vendors when
+    /// building (for example, `MACADDRESS_VENDORS="cisco;juniper"`).
+    pub fn from_env(name: &str) -> Result<Self, Error> {
+        match std::env::var(name) {
+            Ok(value) => value.parse(),
+            Err(std::env::VarError::NotPresent) => Ok(Self::new()),
+            Err(error) => Err(Error::Invalid(format!("{}: {}", name, error))),
+        }
+    }
+
+    /// Whether the filter keeps the entry.
+    fn keeps(&self, (prefix, length, organization, _): &Entry) -> bool {
+        if self.is_empty() {
+            return true;
+        }
+
+        let first = *prefix;
+        let last = first + (1 << (48 - u32::from(*length))) - 1;
+        let organization = organization.to_lowercase();
+
+        self.organizations
+            .iter()
+            .any(|name| organization.contains(name.as_str()))
+            || self.prefixes.iter().any(|selected| {
+                selected.first().to_decimal_representation() <= last
+                    && first <= selected.last().to_decimal_representation()
+            })
+    }
+
+    /// Returns the lines of a registry CSV in the IEEE layout that
+    /// the filter keeps, along with its header row and comments, so
+    /// that the result can be passed on to `codegen` (or any other
+    /// consumer of the layout).
+    pub fn filter(&self, csv: &str) -> Result<String, Error> {
+        let mut filtered = String::new();
+        let mut first = true;
+
+        for (number, line) in csv.lines().enumerate() {
+            let keep = match parse_registry_line(line, number, first)? {
+                Some(entry) => {
+                    first = false;
+                    self.keeps(&entry)
+                }
+                None => true,
+            };
+
+            if keep {
+                filtered.push_str(line);
+                filtered.push('\n');
+            }
+        }
+
+        Ok(filtered)
+    }
+}
+
+/// Parses a semicolon-separated list of selections (for example,
+/// `cisco;juniper;70:b3:d5:00:00:00/24`).  Selections that contain
+/// a slash are prefixes, and the rest are organization names.
+/// Names may contain commas, as many organizations' do.
+impl FromStr for VendorFilter {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut filter = Self::new();
+
+        for selection in s.split(';').map(str::trim) {
+            if selection.is_empty() {
+                continue;
+            } else if selection.contains('/') {
+                filter = filter.with_prefix(selection.parse()?);
+            } else {
+                filter = filter.with_organization(selection);
+            }
+        }
+
+        Ok(filter)
+    }
+}
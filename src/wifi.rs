@@ -0,0 +1,85 @@
+use super::error::Error;
+use super::macaddress::MediaAccessControlAddress;
+
+/// Replaces the `max_bssid_indicator` least-significant bits of
+/// `bssid` by adding `offset` to them, modulo 2^n, as the
+/// IEEE 802.11 Multiple BSSID scheme does.
+fn offset_bssid(
+    bssid: &MediaAccessControlAddress,
+    max_bssid_indicator: u8,
+    offset: i16,
+) -> Result<MediaAccessControlAddress, Error> {
+    if !(1..=8).contains(&max_bssid_indicator) {
+        return Err(Error::Invalid(String::from(
+            "Pass in a MaxBSSID indicator between 1 and 8.",
+        )));
+    }
+
+    let modulus = 1 << max_bssid_indicator;
+    let mut octets = bssid.to_octets();
+    let last = i16::from(octets[5]);
+    let low = (last % modulus + offset).rem_euclid(modulus);
+
+    octets[5] = (last - last % modulus + low) as u8;
+    Ok(MediaAccessControlAddress::from(octets))
+}
+
+/// Returns the BSSID with index `bssid_index` in the
+/// IEEE 802.11 Multiple BSSID set whose transmitted BSSID is
+/// `transmitted` and whose MaxBSSID indicator is
+/// `max_bssid_indicator` (n, between 1 and 8).
+///
+/// The set holds 2^n BSSIDs.  The BSSID with index i has the
+/// same 48 - n most-significant bits as the transmitted BSSID,
+/// and its n least-significant bits are those of the transmitted
+/// BSSID plus i, modulo 2^n.  Index 0 is the transmitted BSSID.
+pub fn nontransmitted_bssid(
+    transmitted: &MediaAccessControlAddress,
+    max_bssid_indicator: u8,
+    bssid_index: u8,
+) -> Result<MediaAccessControlAddress, Error> {
+    if max_bssid_indicator < 8 && bssid_index >> max_bssid_indicator != 0 {
+        return Err(Error::Invalid(String::from(
+            "Pass in a BSSID index below 2^n.",
+        )));
+    }
+
+    offset_bssid(transmitted, max_bssid_indicator, i16::from(bssid_index))
+}
+
+/// Returns every BSSID in the IEEE 802.11 Multiple BSSID set
+/// whose transmitted BSSID is `transmitted`, in index order
+/// (see `nontransmitted_bssid`).
+pub fn multiple_bssids(
+    transmitted: &MediaAccessControlAddress,
+    max_bssid_indicator: u8,
+) -> Result<Vec<MediaAccessControlAddress>, Error> {
+    if !(1..=8).contains(&max_bssid_indicator) {
+        return Err(Error::Invalid(String::from(
+            "Pass in a MaxBSSID indicator between 1 and 8.",
+        )));
+    }
+
+    (0..1_u16 << max_bssid_indicator)
+        .map(|index| offset_bssid(transmitted, max_bssid_indicator, index as i16))
+        .collect()
+}
+
+/// Recovers the transmitted BSSID of an IEEE 802.11 Multiple
+/// BSSID set from one of its nontransmitted BSSIDs, given that
+/// BSSID's index and the set's MaxBSSID indicator.
+///
+/// This is the inverse of `nontransmitted_bssid`.
+pub fn transmitted_bssid(
+    nontransmitted: &MediaAccessControlAddress,
+    max_bssid_indicator: u8,
+    bssid_index: u8,
+) -> Result<MediaAccessControlAddress, Error> {
+    if max_bssid_indicator < 8 && bssid_index >> max_bssid_indicator != 0 {
+        return Err(Error::Invalid(String::from(
+            "Pass in a BSSID index below 2^n.",
+        )));
+    }
+
+    offset_bssid(nontransmitted, max_bssid_indicator, -i16::from(bssid_index))
+}
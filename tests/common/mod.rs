@@ -0,0 +1,61 @@
+//! Helpers for running the `macaddress` command-line tool in tests.
+
+#![allow(dead_code)]
+
+use std::fs;
+use std::io::Write;
+use std::path::PathBuf;
+use std::process::{Command, Output, Stdio};
+
+/// Returns a command that runs the `macaddress` tool.
+pub fn macaddress() -> Command {
+    Command::new(env!("CARGO_BIN_EXE_macaddress"))
+}
+
+/// Runs the `macaddress` tool with `args`, feeding it `input`, and
+/// returns its status, standard output, and standard error.
+pub fn run(args: &[&str], input: &str) -> (bool, String, String) {
+    let mut child = macaddress()
+        .args(args)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .unwrap();
+
+    child
+        .stdin
+        .take()
+        .unwrap()
+        .write_all(input.as_bytes())
+        .unwrap();
+    let Output {
+        status,
+        stdout,
+        stderr,
+    } = child.wait_with_output().unwrap();
+
+    (
+        status.success(),
+        String::from_utf8(stdout).unwrap(),
+        String::from_utf8(stderr).unwrap(),
+    )
+}
+
+/// Writes `contents` to a file named `name` in a directory of the
+/// test's own, and returns its path.
+pub fn file(test: &str, name: &str, contents: &str) -> PathBuf {
+    let directory =
+        std::env::temp_dir().join(format!("macaddress-{}-{}", test, std::process::id()));
+    fs::create_dir_all(&directory).unwrap();
+
+    let path = directory.join(name);
+    fs::write(&path, contents).unwrap();
+    path
+}
+
+/// A small vendor registry in the IEEE layout.
+pub const REGISTRY: &str = "Registry,Assignment,Organization Name,Organization Address\r
+MA-L,005056,\"VMware, Inc.\",3401 Hillview Avenue Palo Alto CA US 94304\r
+MA-L,00000C,\"Cisco Systems, Inc\",170 West Tasman Drive San Jose CA US 95134\r
+";
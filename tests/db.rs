@@ -0,0 +1,138 @@
+//! Tests for `macaddress db update`.
+
+#![cfg(feature = "cli")]
+
+mod common;
+
+use macaddress::hash::sha256;
+use macaddress::vendor::VendorDatabase;
+use std::fs;
+
+fn hex(digest: [u8; 32]) -> String {
+    digest
+        .iter()
+        .map(|octet| format!("{:02x}", octet))
+        .collect()
+}
+
+#[test]
+fn test_db_update() {
+    let source = common::file("db-update", "oui.csv", common::REGISTRY);
+    let out = source.with_file_name("vendors.csv");
+
+    let (success, stdout, stderr) = common::run(
+        &[
+            "db",
+            "update",
+            "--source",
+            source.to_str().unwrap(),
+            "--out",
+            out.to_str().unwrap(),
+        ],
+        "",
+    );
+    assert!(success, "{}", stderr);
+    assert!(stderr.contains("Parsed 2 assignments from"));
+    assert!(stderr.contains("Wrote 2 assignments (MA-L 2, MA-M 0, MA-S 0)"));
+    assert!(!stderr.contains("Warning"));
+
+    // It prints the checksum of the database that it wrote.
+    let database = VendorDatabase::from_csv(&fs::read_to_string(&out).unwrap()).unwrap();
+    assert_eq!(database.len(), 2);
+    assert_eq!(stdout, format!("{:08x}\n", database.checksum()));
+    assert!(!out.with_extension("tmp").exists());
+}
+
+#[test]
+fn test_db_update_sha256() {
+    let source = common::file("db-sha256", "oui.csv", common::REGISTRY);
+    let out = source.with_file_name("vendors.csv");
+    let digest = hex(sha256(common::REGISTRY.as_bytes()));
+    let update = |digest: &str| {
+        common::run(
+            &[
+                "db",
+                "update",
+                "--source",
+                source.to_str().unwrap(),
+                "--sha256",
+                digest,
+                "--out",
+                out.to_str().unwrap(),
+            ],
+            "",
+        )
+    };
+
+    let (success, _, stderr) = update(&digest);
+    assert!(success, "{}", stderr);
+
+    let wrong = "0".repeat(64);
+    let (success, _, stderr) = update(&wrong);
+    assert!(!success);
+    assert_eq!(
+        stderr.lines().last().unwrap(),
+        format!(
+            "macaddress: {}: Pass in a database whose SHA-256 digest is {} (not {}).",
+            source.display(),
+            wrong,
+            digest
+        )
+    );
+
+    let (success, _, stderr) = update("abc");
+    assert!(!success);
+    assert_eq!(
+        stderr,
+        "macaddress: Pass in a SHA-256 digest of 64 hexadecimal digits.\n"
+    );
+
+    let (success, _, stderr) = common::run(&["db", "update", "--sha256", &digest], "");
+    assert!(!success);
+    assert_eq!(
+        stderr,
+        "macaddress: Pass in --sha256 once, right after the --source that it checks.\n"
+    );
+}
+
+#[test]
+fn test_db_update_refusals() {
+    let source = common::file("db-refusals", "oui.csv", common::REGISTRY);
+    let out = source.with_file_name("vendors.csv");
+
+    // A database with another checksum is never written.
+    let (success, _, stderr) = common::run(
+        &[
+            "db",
+            "update",
+            "--source",
+            source.to_str().unwrap(),
+            "--out",
+            out.to_str().unwrap(),
+            "--expect",
+            "00000000",
+        ],
+        "",
+    );
+    assert!(!success);
+    assert!(stderr.contains("so it was not written."));
+    assert!(!out.exists());
+
+    // Nothing would stop tampering with plain HTTP.
+    let (success, _, stderr) = common::run(
+        &["db", "update", "--source", "http://example.com/oui.csv"],
+        "",
+    );
+    assert!(!success);
+    assert_eq!(
+        stderr,
+        "Fetching http://example.com/oui.csv...\nmacaddress: Pass in an https:// URL, or a --sha256 digest for http://example.com/oui.csv.\n"
+    );
+
+    let (success, _, stderr) = common::run(&["db", "update", "--verify"], "");
+    assert!(!success);
+    assert_eq!(
+        stderr,
+        "macaddress: Pass in a known option (not --verify).\n"
+    );
+}
@@ -0,0 +1,179 @@
+//! Golden tests for the machine-readable output modes.
+
+#![cfg(feature = "cli")]
+
+mod common;
+
+/// Two addresses and one that stands alone.
+const LIST: &str = "\
+a0:b1:c2:00:00:00
+a0:b1:c2:00:00:01
+a0:b1:c2:00:00:04
+";
+
+/// A registered address and a group address.
+const ADDRESSES: &str = "\
+01:00:5e:00:00:01
+00:50:56:a1:b2:c3
+";
+
+/// Runs `command` on `input`, with the small vendor registry, and
+/// returns its standard output.
+fn output(test: &str, command: &[&str], input: &str) -> String {
+    let db = common::file(test, "oui.csv", common::REGISTRY);
+    let mut args = command.to_vec();
+    args.extend(["--db", db.to_str().unwrap()].iter());
+
+    let (success, stdout, stderr) = common::run(&args, input);
+    assert!(success, "{}", stderr);
+    stdout
+}
+
+#[test]
+fn test_output_json() {
+    assert_eq!(
+        output("output-json", &["sort", "--json"], ADDRESSES),
+        r#"[
+{"address":"00:50:56:a1:b2:c3","oui":"005056","kind":"unique","cast":"unicast","uaa":true,"laa":false,"vendor":"VMware, Inc."},
+{"address":"01:00:5e:00:00:01","oui":"01005e","kind":"unknown","cast":"multicast","uaa":false,"laa":false,"vendor":null}
+]
+"#
+    );
+
+    assert_eq!(
+        common::run(&["range", "summarize", "--json"], LIST),
+        (
+            true,
+            String::from(
+                r#"[{"prefix":"a0:b1:c2:00:00:00/47","first":"a0:b1:c2:00:00:00","last":"a0:b1:c2:00:00:01","size":2},{"prefix":"a0:b1:c2:00:00:04/48","first":"a0:b1:c2:00:00:04","last":"a0:b1:c2:00:00:04","size":1}]
+"#
+            ),
+            String::new()
+        )
+    );
+}
+
+#[test]
+fn test_output_csv() {
+    assert_eq!(
+        output("output-csv", &["sort", "--csv"], ADDRESSES),
+        "address,oui,kind,cast,uaa,laa,vendor
+00:50:56:a1:b2:c3,005056,unique,unicast,true,false,\"VMware, Inc.\"
+01:00:5e:00:00:01,01005e,unknown,multicast,false,false,
+"
+    );
+
+    assert_eq!(
+        common::run(&["range", "summarize", "--ranges", "--csv"], LIST),
+        (
+            true,
+            String::from(
+                "range,first,last,size
+a0:b1:c2:00:00:00-a0:b1:c2:00:00:01,a0:b1:c2:00:00:00,a0:b1:c2:00:00:01,2
+a0:b1:c2:00:00:04-a0:b1:c2:00:00:04,a0:b1:c2:00:00:04,a0:b1:c2:00:00:04,1
+"
+            ),
+            String::new()
+        )
+    );
+}
+
+#[test]
+fn test_output_yaml() {
+    assert_eq!(
+        output("output-yaml", &["sort", "--yaml"], ADDRESSES),
+        r#"- address: "00:50:56:a1:b2:c3"
+  oui: "005056"
+  kind: "unique"
+  cast: "unicast"
+  uaa: true
+  laa: false
+  vendor: "VMware, Inc."
+- address: "01:00:5e:00:00:01"
+  oui: "01005e"
+  kind: "unknown"
+  cast: "multicast"
+  uaa: false
+  laa: false
+  vendor: null
+"#
+    );
+
+    assert_eq!(
+        common::run(&["range", "summarize", "--yaml"], LIST),
+        (
+            true,
+            String::from(
+                r#"- prefix: "a0:b1:c2:00:00:00/47"
+  first: "a0:b1:c2:00:00:00"
+  last: "a0:b1:c2:00:00:01"
+  size: 2
+- prefix: "a0:b1:c2:00:00:04/48"
+  first: "a0:b1:c2:00:00:04"
+  last: "a0:b1:c2:00:00:04"
+  size: 1
+"#
+            ),
+            String::new()
+        )
+    );
+}
+
+#[test]
+fn test_output_toml() {
+    // TOML has no null, so an unknown vendor is left out.
+    assert_eq!(
+        output("output-toml", &["sort", "--toml"], ADDRESSES),
+        r#"[[addresses]]
+address = "00:50:56:a1:b2:c3"
+oui = "005056"
+kind = "unique"
+cast = "unicast"
+uaa = true
+laa = false
+vendor = "VMware, Inc."
+
+[[addresses]]
+address = "01:00:5e:00:00:01"
+oui = "01005e"
+kind = "unknown"
+cast = "multicast"
+uaa = false
+laa = false
+"#
+    );
+
+    assert_eq!(
+        common::run(&["range", "summarize", "--toml"], LIST),
+        (
+            true,
+            String::from(
+                r#"[[ranges]]
+prefix = "a0:b1:c2:00:00:00/47"
+first = "a0:b1:c2:00:00:00"
+last = "a0:b1:c2:00:00:01"
+size = 2
+
+[[ranges]]
+prefix = "a0:b1:c2:00:00:04/48"
+first = "a0:b1:c2:00:00:04"
+last = "a0:b1:c2:00:00:04"
+size = 1
+"#
+            ),
+            String::new()
+        )
+    );
+}
+
+#[test]
+fn test_output_conflict() {
+    assert_eq!(
+        common::run(&["sort", "--json", "--csv"], ""),
+        (
+            false,
+            String::new(),
+            String::from("macaddress: Pass in only one of --json, --csv, --yaml, or --toml.\n")
+        )
+    );
+}
@@ -0,0 +1,145 @@
+//! Tests for `macaddress range expand` and `macaddress range
+//! summarize`.
+
+#![cfg(feature = "cli")]
+
+mod common;
+
+#[test]
+fn test_range_expand() {
+    assert_eq!(
+        common::run(
+            &[
+                "range",
+                "expand",
+                "a0:b1:c2:00:00:00/46",
+                "--notation",
+                "plain"
+            ],
+            ""
+        ),
+        (
+            true,
+            String::from("a0b1c2000000\na0b1c2000001\na0b1c2000002\na0b1c2000003\n"),
+            String::new()
+        )
+    );
+
+    assert_eq!(
+        common::run(
+            &["range", "expand", "a0:b1:c2:00:00:fe-a0:b1:c2:00:01:01"],
+            ""
+        ),
+        (
+            true,
+            String::from(
+                "a0:b1:c2:00:00:fe\na0:b1:c2:00:00:ff\na0:b1:c2:00:01:00\na0:b1:c2:00:01:01\n"
+            ),
+            String::new()
+        )
+    );
+}
+
+#[test]
+fn test_range_expand_limit() {
+    assert_eq!(
+        common::run(
+            &["range", "expand", "a0:b1:c2:00:00:00/24", "--limit", "2"],
+            ""
+        ),
+        (
+            true,
+            String::from("a0:b1:c2:00:00:00\na0:b1:c2:00:00:01\n"),
+            String::from(
+                "Stopped after 2 of 16777216 addresses; pass in a larger --limit for more.\n"
+            )
+        )
+    );
+
+    // A limit that covers the whole range says nothing.
+    let (success, stdout, stderr) = common::run(
+        &["range", "expand", "a0:b1:c2:00:00:00/46", "--limit", "4"],
+        "",
+    );
+    assert!(success);
+    assert_eq!(stdout.lines().count(), 4);
+    assert_eq!(stderr, "");
+
+    let (success, stdout, stderr) = common::run(
+        &["range", "expand", "a0:b1:c2:00:00:00/46", "--limit", "0"],
+        "",
+    );
+    assert!(success);
+    assert_eq!(stdout, "");
+    assert_eq!(
+        stderr,
+        "Stopped after 0 of 4 addresses; pass in a larger --limit for more.\n"
+    );
+
+    // Without a limit, it stops at 65536 addresses.
+    let (success, stdout, stderr) = common::run(&["range", "expand", "a0:b1:c2:00:00:00/24"], "");
+    assert!(success);
+    assert_eq!(stdout.lines().count(), 65536);
+    assert_eq!(stdout.lines().last(), Some("a0:b1:c2:00:ff:ff"));
+    assert!(stderr.starts_with("Stopped after 65536 of 16777216 addresses"));
+
+    let (success, _, stderr) = common::run(
+        &["range", "expand", "a0:b1:c2:00:00:00/24", "--limit", "-1"],
+        "",
+    );
+    assert!(!success);
+    assert_eq!(
+        stderr,
+        "macaddress: Pass in a limit of 0 or more addresses.\n"
+    );
+
+    let (success, _, stderr) = common::run(&["range", "expand"], "");
+    assert!(!success);
+    assert_eq!(stderr, "macaddress: Pass in a prefix or range to expand.\n");
+}
+
+#[test]
+fn test_range_summarize() {
+    let addresses = "\
+a0:b1:c2:00:00:03
+a0:b1:c2:00:00:00
+a0-b1-c2-00-00-01
+a0b1.c2d3.e4f5
+a0:b1:c2:00:00:02
+a0:b1:c2:00:00:04
+";
+
+    assert_eq!(
+        common::run(&["range", "summarize"], addresses),
+        (
+            true,
+            String::from("a0:b1:c2:00:00:00/46\na0:b1:c2:00:00:04/48\na0:b1:c2:d3:e4:f5/48\n"),
+            String::new()
+        )
+    );
+
+    assert_eq!(
+        common::run(&["range", "summarize", "--ranges"], addresses),
+        (
+            true,
+            String::from(
+                "a0:b1:c2:00:00:00-a0:b1:c2:00:00:04\na0:b1:c2:d3:e4:f5-a0:b1:c2:d3:e4:f5\n"
+            ),
+            String::new()
+        )
+    );
+
+    let path = common::file("range-summarize", "list.txt", addresses);
+    let (success, stdout, _) = common::run(&["range", "summarize", path.to_str().unwrap()], "");
+    assert!(success);
+    assert_eq!(stdout.lines().count(), 3);
+
+    let (success, stdout, stderr) =
+        common::run(&["range", "summarize"], "a0:b1:c2:00:00:00\nnope\n");
+    assert!(success);
+    assert_eq!(stdout, "a0:b1:c2:00:00:00/48\n");
+    assert!(stderr.ends_with("\nSkipped 1 invalid line.\n"));
+
+    let (_, _, stderr) = common::run(&["range", "summarize"], "x\ny\n");
+    assert!(stderr.ends_with("\nSkipped 2 invalid lines.\n"));
+}
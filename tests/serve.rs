@@ -0,0 +1,205 @@
+//! Tests for `macaddress serve`.
+
+#![cfg(feature = "serve")]
+
+mod common;
+
+use std::io::{BufRead, BufReader, Read, Write};
+use std::net::{SocketAddr, TcpStream};
+use std::process::{Child, Stdio};
+
+/// A running server, which is killed when dropped.
+struct Server {
+    child: Child,
+    address: SocketAddr,
+}
+
+impl Server {
+    fn start(test: &str) -> Self {
+        let db = common::file(test, "oui.csv", common::REGISTRY);
+        let mut child = common::macaddress()
+            .args(["serve", "--listen", "127.0.0.1:0", "--db"])
+            .arg(db)
+            .stderr(Stdio::piped())
+            .spawn()
+            .unwrap();
+
+        let mut line = String::new();
+        BufReader::new(child.stderr.take().unwrap())
+            .read_line(&mut line)
+            .unwrap();
+        let address = line
+            .trim_start_matches("Listening on http://")
+            .split(' ')
+            .next()
+            .unwrap()
+            .parse()
+            .unwrap();
+
+        Self { child, address }
+    }
+
+    /// Sends `request` as is, and returns the status line and body
+    /// of the response.
+    fn send(&self, request: &str) -> (String, String) {
+        let mut stream = TcpStream::connect(self.address).unwrap();
+        stream.write_all(request.as_bytes()).unwrap();
+
+        let mut response = String::new();
+        stream.read_to_string(&mut response).unwrap();
+
+        let (head, body) = response.split_once("\r\n\r\n").unwrap();
+        let status = head.lines().next().unwrap();
+        (status.to_string(), body.trim_end().to_string())
+    }
+
+    fn get(&self, target: &str) -> (String, String) {
+        self.send(&format!(
+            "GET {} HTTP/1.1\r\nHost: localhost\r\n\r\n",
+            target
+        ))
+    }
+}
+
+impl Drop for Server {
+    fn drop(&mut self) {
+        let _ = self.child.kill();
+        let _ = self.child.wait();
+    }
+}
+
+#[test]
+fn test_serve_routes() {
+    let server = Server::start("serve-routes");
+
+    assert_eq!(
+        server.get("/normalize?address=A0-B1-C2-D3-E4-F5&notation=hyphen"),
+        (
+            String::from("HTTP/1.1 200 OK"),
+            String::from(r#"{"address":"a0-b1-c2-d3-e4-f5"}"#)
+        )
+    );
+
+    assert_eq!(
+        server.get("/vendor?address=00:50:56:a1:b2:c3"),
+        (
+            String::from("HTTP/1.1 200 OK"),
+            String::from(
+                r#"{"address":"00:50:56:a1:b2:c3","oui":"005056","vendor":"VMware, Inc."}"#
+            )
+        )
+    );
+
+    let (status, body) = server.get("/classify?address=00:00:0c:a1:b2:c3");
+    assert_eq!(status, "HTTP/1.1 200 OK");
+    assert!(body.starts_with(r#"{"address":"00:00:0c:a1:b2:c3","#));
+    assert!(body.contains(r#""administration":"uaa""#));
+    assert!(body.contains(r#""vendor":"Cisco Systems, Inc""#));
+}
+
+#[test]
+fn test_serve_classify_administration() {
+    let server = Server::start("serve-administration");
+
+    let (_, body) = server.get("/classify?address=02:b1:c2:d3:e4:f5");
+    assert!(body.contains(r#""administration":"laa""#));
+
+    // Group addresses are neither universally nor locally
+    // administered.
+    let (status, body) = server.get("/classify?address=01:00:5e:00:00:01");
+    assert_eq!(status, "HTTP/1.1 200 OK");
+    assert!(body.contains(r#""cast":"multicast""#));
+    assert!(body.contains(r#""administration":"""#));
+}
+
+#[test]
+fn test_serve_errors() {
+    let server = Server::start("serve-errors");
+
+    let (status, body) = server.get("/lookup?address=a0:b1:c2:d3:e4:f5");
+    assert_eq!(status, "HTTP/1.1 404 Not Found");
+    assert_eq!(
+        body,
+        r#"{"error":"Pass in /normalize, /classify, or /vendor (not /lookup)."}"#
+    );
+
+    let (status, body) = server.get("/normalize");
+    assert_eq!(status, "HTTP/1.1 400 Bad Request");
+    assert_eq!(body, r#"{"error":"Pass in an address parameter."}"#);
+
+    let (status, _) = server.get("/normalize?address=a0:b1:c2:d3:e4");
+    assert_eq!(status, "HTTP/1.1 400 Bad Request");
+
+    let (status, _) = server.get("/normalize?address=a0:b1:c2:d3:e4:f5&notation=morse");
+    assert_eq!(status, "HTTP/1.1 400 Bad Request");
+
+    let (status, body) = server.send("POST /normalize?address=a0:b1:c2:d3:e4:f5 HTTP/1.1\r\n\r\n");
+    assert_eq!(status, "HTTP/1.1 405 Method Not Allowed");
+    assert_eq!(body, r#"{"error":"Pass in a GET request."}"#);
+
+    let (status, body) = server.send("HELLO\r\n\r\n");
+    assert_eq!(status, "HTTP/1.1 400 Bad Request");
+    assert_eq!(body, r#"{"error":"Pass in an HTTP request."}"#);
+}
+
+#[test]
+fn test_serve_percent_decoding() {
+    let server = Server::start("serve-decoding");
+
+    assert_eq!(
+        server.get("/normalize?address=a0%3Ab1%3ac2%3Ad3%3Ae4%3Af5"),
+        (
+            String::from("HTTP/1.1 200 OK"),
+            String::from(r#"{"address":"a0:b1:c2:d3:e4:f5"}"#)
+        )
+    );
+
+    assert_eq!(
+        server.get("/normalize?address=a0+b1+c2+d3+e4+f5&notation=dot"),
+        (
+            String::from("HTTP/1.1 200 OK"),
+            String::from(r#"{"address":"a0b1.c2d3.e4f5"}"#)
+        )
+    );
+
+    // A sign is not a hex digit, so `%+1` stays as it is, with the
+    // `+` decoded as a space.
+    let (status, body) = server.get("/normalize?address=%+1");
+    assert_eq!(status, "HTTP/1.1 400 Bad Request");
+    assert!(body.contains("'%'"), "{}", body);
+}
+
+#[test]
+fn test_serve_header_limits() {
+    let server = Server::start("serve-headers");
+    let headers = "X-Padding: 1\r\n".repeat(65);
+
+    let (status, body) = server.send(&format!(
+        "GET /normalize?address=a0:b1:c2:d3:e4:f5 HTTP/1.1\r\n{}\r\n",
+        headers
+    ));
+    assert_eq!(status, "HTTP/1.1 431 Request Header Fields Too Large");
+    assert_eq!(body, r#"{"error":"Pass in fewer or shorter headers."}"#);
+}
+
+#[test]
+fn test_serve_connection_limit() {
+    let server = Server::start("serve-connections");
+
+    // Each of these holds a connection open without sending a
+    // request.
+    let idle: Vec<TcpStream> = (0..64)
+        .map(|_| TcpStream::connect(server.address).unwrap())
+        .collect();
+
+    // The server turns the next client away before reading its
+    // request, so this one sends none.
+    let mut response = String::new();
+    TcpStream::connect(server.address)
+        .unwrap()
+        .read_to_string(&mut response)
+        .unwrap();
+    assert!(response.starts_with("HTTP/1.1 503 Service Unavailable\r\n"));
+
+    drop(idle);
+}
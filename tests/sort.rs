@@ -0,0 +1,124 @@
+//! Tests for `macaddress sort` and `macaddress dedup`.
+
+#![cfg(feature = "cli")]
+
+mod common;
+
+use std::fs;
+
+/// Addresses that are out of order and repeat, so that sorting them
+/// in chunks of two leaves duplicates in different chunks.
+const ADDRESSES: &str = "\
+a0:b1:c2:d3:e4:f5
+00-00-0C-07-AC-01
+a0b1.c2d3.e4f5
+ff:ff:ff:ff:ff:ff
+00:00:0c:07:ac:01
+02:00:00:00:00:01
+a0:b1:c2:d3:e4:f5
+00:00:00:00:00:00
+";
+
+const SORTED: &str = "\
+00:00:00:00:00:00
+00:00:0c:07:ac:01
+00:00:0c:07:ac:01
+02:00:00:00:00:01
+a0:b1:c2:d3:e4:f5
+a0:b1:c2:d3:e4:f5
+a0:b1:c2:d3:e4:f5
+ff:ff:ff:ff:ff:ff
+";
+
+const UNIQUE: &str = "\
+00:00:00:00:00:00
+00:00:0c:07:ac:01
+02:00:00:00:00:01
+a0:b1:c2:d3:e4:f5
+ff:ff:ff:ff:ff:ff
+";
+
+#[test]
+fn test_sort() {
+    assert_eq!(
+        common::run(&["sort"], ADDRESSES),
+        (true, String::from(SORTED), String::new())
+    );
+    assert_eq!(
+        common::run(&["dedup"], ADDRESSES),
+        (true, String::from(UNIQUE), String::new())
+    );
+    assert_eq!(
+        common::run(&["sort", "--unique"], ADDRESSES),
+        (true, String::from(UNIQUE), String::new())
+    );
+}
+
+#[test]
+fn test_sort_external_merge() {
+    // Runs spill into a directory of the test's own, which must be
+    // empty again afterward.
+    let directory = common::file("sort-merge", "input", "")
+        .parent()
+        .unwrap()
+        .join("runs");
+    fs::create_dir_all(&directory).unwrap();
+
+    for buffer in ["1", "2", "3"].iter() {
+        for (command, expected) in [("sort", SORTED), ("dedup", UNIQUE)].iter() {
+            let output = common::macaddress()
+                .args([command, "--buffer", buffer])
+                .env("TMPDIR", &directory)
+                .stdin(fs::File::open(common::file("sort-merge", "input", ADDRESSES)).unwrap())
+                .output()
+                .unwrap();
+
+            assert!(output.status.success());
+            assert_eq!(
+                String::from_utf8(output.stdout).unwrap(),
+                *expected,
+                "{} --buffer {}",
+                command,
+                buffer
+            );
+            assert_eq!(fs::read_dir(&directory).unwrap().count(), 0);
+        }
+    }
+
+    // Small buffers spill, so they fail where runs cannot be
+    // written, while the default buffer never touches the disk.
+    let missing = directory.join("missing");
+    let spill = |args: &[&str]| {
+        common::macaddress()
+            .args(args)
+            .env("TMPDIR", &missing)
+            .stdin(fs::File::open(common::file("sort-merge", "input", ADDRESSES)).unwrap())
+            .output()
+            .unwrap()
+            .status
+            .success()
+    };
+    assert!(!spill(&["sort", "--buffer", "2"]));
+    assert!(spill(&["sort"]));
+}
+
+#[test]
+fn test_sort_skipped() {
+    let (success, stdout, stderr) = common::run(&["sort"], "a0:b1:c2:d3:e4:f5\nnope\n\n");
+    assert!(success);
+    assert_eq!(stdout, "a0:b1:c2:d3:e4:f5\n");
+    assert_eq!(
+        stderr,
+        "-:2: Looks like plain notation, but it contains a character that is neither a hexadecimal digit nor a separator (found 'n' at byte 0).\nSkipped 1 invalid line.\n"
+    );
+
+    let (_, _, stderr) = common::run(&["dedup", "--notation", "hyphen"], "x\ny\n");
+    assert!(stderr.ends_with("Skipped 2 invalid lines.\n"));
+
+    let (success, _, stderr) = common::run(&["sort", "--buffer", "0"], "");
+    assert!(!success);
+    assert_eq!(
+        stderr,
+        "macaddress: Pass in a buffer of at least 1 address.\n"
+    );
+}